@@ -50,7 +50,7 @@ fn main() -> Result<()> {
     
     // Draw some text
     let text_pos = rx::Point::new(110.0, 180.0);
-    canvas.draw_text("Hello, RX Framework!", text_pos, Color::WHITE)?;
+    canvas.draw_text("Hello, RX Framework!", text_pos, &rx::Font::system(), 14.0, Color::WHITE)?;
     
     // Present the canvas
     canvas.present()?;
@@ -3,9 +3,11 @@
 //! This module provides the main Application struct that manages the framework
 //! lifecycle, event handling, and window management.
 
-use crate::{Error, Result, Event, EventLoop, Window, WindowBuilder};
+use crate::{Error, Result, Event, EventLoop, FrameRecorder, Window, WindowBuilder};
 use crate::platform::{create_platform_backend, PlatformBackend};
 use crate::platform::common::{Timer, PerformanceMonitor};
+use crate::drawing::{AtlasStats, GlyphAtlas};
+use crate::drawing::DrawStats;
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 
@@ -34,6 +36,8 @@ pub struct Application {
     windows: Arc<Mutex<HashMap<u64, Window>>>,
     event_loop: Arc<Mutex<EventLoop>>,
     is_running: Arc<Mutex<bool>>,
+    performance: Arc<Mutex<Option<PerformanceMonitor>>>,
+    recorders: Arc<Mutex<Vec<FrameRecorder>>>,
     config: ApplicationConfig,
 }
 
@@ -151,12 +155,17 @@ impl Application {
         }
         
         let event_loop = EventLoop::new(Arc::clone(&backend))?;
+        let performance = config
+            .enable_performance_monitoring
+            .then(|| PerformanceMonitor::new(config.performance_sample_count));
         
         Ok(Self {
             backend,
             windows: Arc::new(Mutex::new(HashMap::new())),
             event_loop: Arc::new(Mutex::new(event_loop)),
             is_running: Arc::new(Mutex::new(false)),
+            performance: Arc::new(Mutex::new(performance)),
+            recorders: Arc::new(Mutex::new(Vec::new())),
             config,
         })
     }
@@ -242,11 +251,6 @@ impl Application {
         }
         
         let mut timer = Timer::new(self.config.target_fps);
-        let mut performance_monitor = if self.config.enable_performance_monitoring {
-            Some(PerformanceMonitor::new(self.config.performance_sample_count))
-        } else {
-            None
-        };
         
         while self.is_running() {
             let frame_start = std::time::Instant::now();
@@ -283,9 +287,8 @@ impl Application {
             self.update_windows(timer.delta_time())?;
             
             // Record performance data
-            if let Some(ref mut monitor) = performance_monitor {
-                monitor.record_frame_time(frame_start.elapsed());
-            }
+            self.record_frame(frame_start.elapsed());
+            self.capture_recordings()?;
             
             // Frame rate limiting
             if timer.tick() {
@@ -308,6 +311,8 @@ impl Application {
             return Ok(false);
         }
         
+        let frame_start = std::time::Instant::now();
+        
         // Process events
         let events = {
             let mut event_loop = self.event_loop.lock().map_err(|_| Error::framework("Failed to lock event loop"))?;
@@ -339,9 +344,50 @@ impl Application {
         // Update and render all windows
         self.update_windows(std::time::Duration::from_millis(16))?; // Assume ~60 FPS
         
+        // Record performance data
+        self.record_frame(frame_start.elapsed());
+        self.capture_recordings()?;
+        
         Ok(true)
     }
     
+    /// Capture the next frame of the running recordings, dropping finished ones.
+    fn capture_recordings(&self) -> Result<()> {
+        let mut recorders = self.recorders.lock().map_err(|_| Error::framework("Failed to lock recorders"))?;
+        let mut result = Ok(());
+        recorders.retain(|recorder| match recorder.capture_frame() {
+            Ok(finished) => !finished,
+            Err(error) => {
+                result = Err(error);
+                false
+            }
+        });
+        result
+    }
+    
+    /// Record frames of a window after each frame of the application loop.
+    ///
+    /// The recorder stops capturing once it has all of its frames; keep a
+    /// clone to encode them yourself, or give it an output path.
+    pub fn record(&self, recorder: FrameRecorder) {
+        if let Ok(mut recorders) = self.recorders.lock() {
+            recorders.push(recorder);
+        }
+    }
+    
+    /// Record a finished frame, along with the draws made during it.
+    fn record_frame(&self, frame_time: std::time::Duration) {
+        let mut draws = DrawStats::default();
+        for window in self.windows() {
+            draws += window.take_draw_stats();
+        }
+        if let Ok(mut performance) = self.performance.lock() {
+            if let Some(monitor) = performance.as_mut() {
+                monitor.record_frame(frame_time, draws);
+            }
+        }
+    }
+    
     fn dispatch_event_to_windows(&self, event: &Event) -> Result<()> {
         let windows = self.windows.lock().map_err(|_| Error::framework("Failed to lock windows"))?;
         
@@ -364,9 +410,12 @@ impl Application {
     }
     
     fn cleanup(&self) -> Result<()> {
-        // Close all windows
+        // Close all windows, freeing their drawing resources while the backend is alive
         {
             let mut windows = self.windows.lock().map_err(|_| Error::framework("Failed to lock windows"))?;
+            for window in windows.values() {
+                window.release_surface();
+            }
             windows.clear();
         }
         
@@ -381,9 +430,21 @@ impl Application {
     
     /// Get performance statistics (if monitoring is enabled).
     pub fn performance_stats(&self) -> Option<PerformanceStats> {
-        // This would return performance data if monitoring is enabled
-        // For now, return None as we don't store the monitor in the Application
-        None::<PerformanceStats>
+        let performance = self.performance.lock().ok()?;
+        let monitor = performance.as_ref()?;
+        let milliseconds = |time: Option<std::time::Duration>| time.map_or(0.0, |time| time.as_secs_f64() * 1000.0);
+        let glyph_atlas = GlyphAtlas::shared().lock().map(|atlas| atlas.stats()).unwrap_or_default();
+        
+        Some(PerformanceStats {
+            average_fps: monitor.average_fps(),
+            average_frame_time_ms: monitor.average_frame_time().as_secs_f64() * 1000.0,
+            min_frame_time_ms: milliseconds(monitor.min_frame_time()),
+            max_frame_time_ms: milliseconds(monitor.max_frame_time()),
+            p95_frame_time_ms: milliseconds(monitor.percentile_frame_time(0.95)),
+            draw_calls_per_frame: monitor.average_draw_calls(),
+            submissions_per_frame: monitor.average_submissions(),
+            glyph_atlas,
+        })
     }
 }
 
@@ -394,6 +455,14 @@ pub struct PerformanceStats {
     pub average_frame_time_ms: f64,
     pub min_frame_time_ms: f64,
     pub max_frame_time_ms: f64,
+    /// Frame time that 95% of frames stayed within.
+    pub p95_frame_time_ms: f64,
+    /// Drawing calls made per frame, before batching.
+    pub draw_calls_per_frame: f64,
+    /// Calls that reached the platform per frame, after batching.
+    pub submissions_per_frame: f64,
+    /// Current glyph atlas counters.
+    pub glyph_atlas: AtlasStats,
 }
 
 #[cfg(test)]
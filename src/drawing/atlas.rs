@@ -0,0 +1,323 @@
+//! Glyph atlas.
+//!
+//! Rasterized glyphs of every loaded font are packed into shared 8-bit
+//! coverage pages, keyed by font, glyph, pixel size and subpixel offset, so
+//! text drawn again costs a lookup and a copy. Glyphs are placed on shelves
+//! of similar height; once every page is full the atlas starts afresh.
+
+use super::font::{Font, FontId, GlyphPosition};
+use super::{raster, Color, Image, Point};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Width and height of an atlas page in pixels.
+const PAGE_SIZE: usize = 1024;
+
+/// Most pages kept before the atlas is cleared.
+const MAX_PAGES: usize = 4;
+
+/// Horizontal subpixel positions rasterized per glyph.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+/// Largest text size drawn from the atlas; bigger text is filled from outlines.
+pub(crate) const MAX_GLYPH_SIZE: f32 = 256.0;
+
+/// Cache key for a glyph rasterized at a size and subpixel offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font: FontId,
+    glyph: u16,
+    size: u32,
+    subpixel: u8,
+}
+
+/// Where a glyph's coverage is stored, valid until the atlas is next changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AtlasGlyph {
+    page: usize,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+    /// Offset of the top-left pixel from the glyph origin.
+    pub left: i32,
+    pub top: i32,
+}
+
+/// A row of glyphs no taller than its height.
+struct Shelf {
+    y: usize,
+    height: usize,
+    right: usize,
+}
+
+struct Page {
+    coverage: Vec<u8>,
+    shelves: Vec<Shelf>,
+    bottom: usize,
+}
+
+impl Page {
+    fn new() -> Self {
+        Self {
+            coverage: vec![0; PAGE_SIZE * PAGE_SIZE],
+            shelves: Vec::new(),
+            bottom: 0,
+        }
+    }
+
+    /// Find room for a rectangle, returning its top-left corner.
+    fn allocate(&mut self, width: usize, height: usize) -> Option<(usize, usize)> {
+        // One pixel of padding keeps neighbours apart
+        let (width, height) = (width + 1, height + 1);
+        if width > PAGE_SIZE || height > PAGE_SIZE {
+            return None;
+        }
+        // Use the tightest shelf that fits, so short glyphs do not waste tall rows
+        let shelf = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= height && PAGE_SIZE - shelf.right >= width)
+            .min_by_key(|shelf| shelf.height);
+        if let Some(shelf) = shelf {
+            let position = (shelf.right, shelf.y);
+            shelf.right += width;
+            return Some(position);
+        }
+        if PAGE_SIZE - self.bottom < height {
+            return None;
+        }
+        let y = self.bottom;
+        self.bottom += height;
+        self.shelves.push(Shelf { y, height, right: width });
+        Some((0, y))
+    }
+}
+
+/// Counters describing how well the atlas is working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AtlasStats {
+    /// Glyphs currently stored, including empty ones.
+    pub glyphs: usize,
+    /// Coverage pages allocated.
+    pub pages: usize,
+    /// Lookups answered from the atlas.
+    pub hits: u64,
+    /// Lookups that rasterized a glyph.
+    pub misses: u64,
+}
+
+/// Cache of rasterized glyphs shared by all fonts.
+pub struct GlyphAtlas {
+    pages: Vec<Page>,
+    glyphs: HashMap<GlyphKey, Option<AtlasGlyph>>,
+    hits: u64,
+    misses: u64,
+    /// Number of times the atlas was cleared, which invalidates earlier lookups.
+    generation: u64,
+}
+
+impl GlyphAtlas {
+    /// Create an empty atlas.
+    pub fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            glyphs: HashMap::new(),
+            hits: 0,
+            misses: 0,
+            generation: 0,
+        }
+    }
+
+    /// The atlas used by every canvas.
+    pub fn shared() -> &'static Mutex<GlyphAtlas> {
+        static SHARED: OnceLock<Mutex<GlyphAtlas>> = OnceLock::new();
+        SHARED.get_or_init(|| Mutex::new(GlyphAtlas::new()))
+    }
+
+    /// Get the current counters.
+    pub fn stats(&self) -> AtlasStats {
+        AtlasStats {
+            glyphs: self.glyphs.len(),
+            pages: self.pages.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    /// Drop every stored glyph and page.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+        self.glyphs.clear();
+        self.generation += 1;
+    }
+
+    /// Look up the glyphs of a run whose baseline starts at device pixel
+    /// `origin`, pairing each with the whole pixel its origin is snapped to.
+    ///
+    /// Returns `None` if the atlas filled up and was cleared part way, which
+    /// invalidates the glyphs looked up before.
+    pub(crate) fn place_run(&mut self, font: &Font, glyphs: &[GlyphPosition], size: f32, origin: Point) -> Option<Vec<(AtlasGlyph, (i32, i32))>> {
+        let generation = self.generation;
+        let baseline = origin.y.round() as i32;
+        let mut placed = Vec::with_capacity(glyphs.len());
+        for glyph in glyphs {
+            let steps = ((origin.x + glyph.x) * SUBPIXEL_STEPS).round();
+            let pen = (steps / SUBPIXEL_STEPS).floor();
+            let source = font.fallback_font(glyph.font);
+            if let Some(entry) = self.glyph(&source, glyph.glyph, size, steps / SUBPIXEL_STEPS - pen) {
+                placed.push((entry, (pen as i32, baseline)));
+            }
+        }
+        (self.generation == generation).then_some(placed)
+    }
+
+    /// Look up a glyph of `font` at `size` pixels whose origin sits at a
+    /// fractional horizontal `offset`, rasterizing it on first use.
+    ///
+    /// Returns `None` for glyphs with no outline.
+    pub(crate) fn glyph(&mut self, font: &Font, glyph: u16, size: f32, offset: f32) -> Option<AtlasGlyph> {
+        let subpixel = (offset.clamp(0.0, 1.0) * SUBPIXEL_STEPS).round().min(SUBPIXEL_STEPS - 1.0);
+        let key = GlyphKey {
+            font: font.id(),
+            glyph,
+            size: size.to_bits(),
+            subpixel: subpixel as u8,
+        };
+        if let Some(entry) = self.glyphs.get(&key) {
+            self.hits += 1;
+            return *entry;
+        }
+        self.misses += 1;
+
+        let Some(mask) = font.glyph_mask(glyph, size, subpixel / SUBPIXEL_STEPS) else {
+            self.glyphs.insert(key, None);
+            return None;
+        };
+        if mask.width >= PAGE_SIZE || mask.height >= PAGE_SIZE {
+            return None;
+        }
+        let (x, y, page) = match self.allocate(mask.width, mask.height) {
+            Some(place) => place,
+            None => {
+                self.clear();
+                self.allocate(mask.width, mask.height)?
+            }
+        };
+        let coverage = &mut self.pages[page].coverage;
+        for row in 0..mask.height {
+            for column in 0..mask.width {
+                coverage[(y + row) * PAGE_SIZE + x + column] = (mask.get(column, row) * 255.0).round() as u8;
+            }
+        }
+        let entry = AtlasGlyph {
+            page,
+            x,
+            y,
+            width: mask.width,
+            height: mask.height,
+            left: mask.x,
+            top: mask.y,
+        };
+        self.glyphs.insert(key, Some(entry));
+        Some(entry)
+    }
+
+    /// Blend a glyph in a solid color into `target`, whose top-left pixel is
+    /// at `origin`, with the glyph origin at pixel `position`.
+    pub(crate) fn composite(&self, target: &mut Image, origin: (i32, i32), glyph: &AtlasGlyph, position: (i32, i32), color: Color) {
+        let Some(page) = self.pages.get(glyph.page) else {
+            return;
+        };
+        let (target_width, target_height) = (target.width() as i32, target.height() as i32);
+        let pixels = target.pixels_mut();
+        for row in 0..glyph.height {
+            let target_y = position.1 + glyph.top + row as i32 - origin.1;
+            if target_y < 0 || target_y >= target_height {
+                continue;
+            }
+            let start = (glyph.y + row) * PAGE_SIZE + glyph.x;
+            for (column, coverage) in page.coverage[start..start + glyph.width].iter().enumerate() {
+                let target_x = position.0 + glyph.left + column as i32 - origin.0;
+                if *coverage == 0 || target_x < 0 || target_x >= target_width {
+                    continue;
+                }
+                let offset = (target_y as usize * target_width as usize + target_x as usize) * 4;
+                raster::blend_pixel(&mut pixels[offset..offset + 4], color, *coverage as f32 / 255.0);
+            }
+        }
+    }
+
+    /// Find room on an existing page or a new one, returning the corner and page.
+    fn allocate(&mut self, width: usize, height: usize) -> Option<(usize, usize, usize)> {
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            if let Some((x, y)) = page.allocate(width, height) {
+                return Some((x, y, index));
+            }
+        }
+        if self.pages.len() >= MAX_PAGES {
+            return None;
+        }
+        let mut page = Page::new();
+        let (x, y) = page.allocate(width, height)?;
+        self.pages.push(page);
+        Some((x, y, self.pages.len() - 1))
+    }
+}
+
+impl Default for GlyphAtlas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::font::ttf;
+
+    #[test]
+    fn test_glyph_cache() {
+        let font = Font::from_bytes(ttf::testing::test_font()).unwrap();
+        let mut atlas = GlyphAtlas::new();
+
+        // The square covers 5×5 pixels above the baseline at 10px
+        let glyph = atlas.glyph(&font, 1, 10.0, 0.0).unwrap();
+        assert_eq!((glyph.left, glyph.top, glyph.width, glyph.height), (0, -5, 5, 5));
+        assert_eq!(atlas.glyph(&font, 1, 10.0, 0.0), Some(glyph));
+        assert!(atlas.glyph(&font, 0, 10.0, 0.0).is_none());
+        assert_eq!(atlas.stats(), AtlasStats { glyphs: 2, pages: 1, hits: 1, misses: 2 });
+
+        // Other sizes, offsets and fonts are separate entries
+        let other = Font::from_bytes(ttf::testing::test_font()).unwrap();
+        assert_ne!(atlas.glyph(&font, 1, 10.0, 0.5), Some(glyph));
+        assert_ne!(atlas.glyph(&other, 1, 10.0, 0.0), Some(glyph));
+        assert_eq!(atlas.stats().glyphs, 4);
+
+        // A run snaps each glyph to a quarter pixel
+        let at = |x: f32| GlyphPosition { index: 0, glyph: 1, font: 0, x, advance: 6.0 };
+        let run = [at(0.0), at(6.3)];
+        let placed = atlas.place_run(&font, &run, 10.0, Point::new(2.0, 9.6)).unwrap();
+        assert_eq!(placed[0], (glyph, (2, 10)));
+        assert_eq!(placed[1].1, (8, 10));
+
+        let mut image = Image::new(8, 8);
+        atlas.composite(&mut image, (0, 0), &glyph, (1, 6), Color::RED);
+        assert_eq!(image.pixel(3, 3), Color::RED);
+        assert_eq!(image.pixel(0, 3), Color::TRANSPARENT);
+        assert_eq!(image.pixel(3, 6), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_shelf_packing() {
+        let mut page = Page::new();
+        assert_eq!(page.allocate(10, 20), Some((0, 0)));
+        assert_eq!(page.allocate(10, 20), Some((11, 0)));
+        // Shorter glyphs reuse the shelf, taller ones open a new one
+        assert_eq!(page.allocate(10, 8), Some((22, 0)));
+        assert_eq!(page.allocate(10, 30), Some((0, 21)));
+        assert_eq!(page.allocate(PAGE_SIZE, 1), None);
+        assert_eq!(page.allocate(PAGE_SIZE - 1, PAGE_SIZE - 53), Some((0, 52)));
+        assert_eq!(page.allocate(1, 1), Some((33, 0)));
+    }
+}
@@ -0,0 +1,411 @@
+//! Draw-call batching.
+//!
+//! On backends where every call goes to a display server, a screen of text
+//! costs one submission per run. [`Canvas`](super::Canvas) wraps such
+//! contexts in a batching layer: text runs, which reach the context as small
+//! images, and the solid rectangles drawn among them are composited in
+//! software into one image. That image is submitted with a single call when
+//! anything else is drawn, the clip changes or the frame is presented.
+//! Source-over compositing is associative, so the result matches drawing
+//! each piece in turn.
+//!
+//! A batch lying on a solid rectangle the backend has just filled starts
+//! from that color, so it is submitted opaque and the backend can copy it
+//! without reading the surface back. Other batches are translucent and cost
+//! the backend a read-back of their bounds, on X11 one `XGetImage`. Text in
+//! the platform font is drawn by the backend itself and ends the batch.
+
+use super::{raster, BlendMode, Clip, Color, CornerRadii, FillRule, Font, GlyphPosition, Image, ImageFilter, Paint, Path, Point, Rect, StrokeStyle, TextMetrics, Transform};
+use crate::platform::DrawingContext;
+use crate::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Largest area in pixels a batch may cover before it is submitted.
+const MAX_BATCH_AREA: f32 = 2048.0 * 2048.0;
+
+/// Counts of drawing calls made and submitted to backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DrawStats {
+    /// Calls made by canvases.
+    pub draw_calls: u64,
+    /// Calls passed on to the backend after batching.
+    pub submissions: u64,
+}
+
+impl std::ops::AddAssign for DrawStats {
+    fn add_assign(&mut self, other: DrawStats) {
+        self.draw_calls += other.draw_calls;
+        self.submissions += other.submissions;
+    }
+}
+
+/// Running draw counts, shared by the canvases drawing to one surface.
+#[derive(Debug, Default)]
+pub(crate) struct DrawCounters {
+    draw_calls: AtomicU64,
+    submissions: AtomicU64,
+}
+
+impl DrawCounters {
+    /// Get the counts since the last call and reset them.
+    pub fn take(&self) -> DrawStats {
+        DrawStats {
+            draw_calls: self.draw_calls.swap(0, Ordering::Relaxed),
+            submissions: self.submissions.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    fn count_call(&self) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn count_submission(&self) {
+        self.submissions.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A piece of a pending batch, in device pixels.
+enum BatchItem {
+    Rect(Rect, Color),
+    Image(Image, (i32, i32)),
+}
+
+/// Wraps a context, merging consecutive text and rectangle draws.
+pub(crate) struct BatchingContext {
+    inner: Box<dyn DrawingContext>,
+    items: Vec<BatchItem>,
+    bounds: Option<Rect>,
+    counters: Arc<DrawCounters>,
+    /// Solid rectangle last filled by the backend, if the surface still shows it.
+    backdrop: Option<(Rect, Color)>,
+    clipped: bool,
+}
+
+impl BatchingContext {
+    /// Wrap `inner`, adding the calls made and submitted to `counters`.
+    pub fn new(inner: Box<dyn DrawingContext>, counters: Arc<DrawCounters>) -> Self {
+        Self {
+            inner,
+            items: Vec::new(),
+            bounds: None,
+            counters,
+            backdrop: None,
+            clipped: false,
+        }
+    }
+
+    /// Remember a solid fill made by the backend, so batches inside it can start opaque.
+    fn set_backdrop(&mut self, rect: Rect, color: Color) {
+        let whole = [rect.x, rect.y, rect.width, rect.height].iter().all(|value| value.fract() == 0.0);
+        self.backdrop = (whole && color.a >= 1.0 && !self.clipped).then_some((rect, color));
+    }
+
+    /// Add a piece to the batch, submitting the batch first if it would grow too large.
+    fn push(&mut self, item: BatchItem, area: Rect) -> Result<()> {
+        if let Some(bounds) = self.bounds {
+            let merged = bounds.union(area);
+            if merged.width * merged.height > MAX_BATCH_AREA {
+                self.flush()?;
+            }
+        }
+        self.bounds = Some(self.bounds.map_or(area, |bounds| bounds.union(area)));
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// Submit the pending batch as one image.
+    fn flush(&mut self) -> Result<()> {
+        let items = std::mem::take(&mut self.items);
+        let Some(bounds) = self.bounds.take() else {
+            return Ok(());
+        };
+        let (width, height) = self.inner.size();
+        let Some(area) = bounds.intersection(Rect::new(0.0, 0.0, width as f32, height as f32)) else {
+            return Ok(());
+        };
+        let (left, top) = (area.x.floor(), area.y.floor());
+        let (right, bottom) = (area.right().ceil(), area.bottom().ceil());
+        if right <= left || bottom <= top {
+            return Ok(());
+        }
+
+        let origin = (left as i32, top as i32);
+        let mut image = Image::new((right - left) as u32, (bottom - top) as u32);
+        let pixels = Rect::new(left, top, right - left, bottom - top);
+        if let Some((_, color)) = self.backdrop.take().filter(|(backdrop, _)| backdrop.intersection(pixels) == Some(pixels)) {
+            image.fill(color);
+        }
+        for item in &items {
+            match item {
+                BatchItem::Rect(rect, color) => {
+                    raster::fill_path(&mut image, origin, &Path::rect(*rect), &Paint::Solid(*color), FillRule::NonZero);
+                }
+                BatchItem::Image(piece, position) => {
+                    raster::composite_layer(&mut image, origin, piece, *position, 1.0, BlendMode::Normal);
+                }
+            }
+        }
+        self.counters.count_submission();
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        self.inner.draw_image(&image, src, Transform::translate(left, top), ImageFilter::Nearest)
+    }
+
+    /// Submit the pending batch and count a call that bypasses it.
+    fn submit(&mut self) -> Result<&mut dyn DrawingContext> {
+        self.flush()?;
+        self.backdrop = None;
+        self.counters.count_call();
+        self.counters.count_submission();
+        Ok(self.inner.as_mut())
+    }
+}
+
+impl Drop for BatchingContext {
+    fn drop(&mut self) {
+        // Canvases dropped without presenting still show what they drew
+        let _ = self.flush();
+    }
+}
+
+impl DrawingContext for BatchingContext {
+    fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+        // Everything pending is covered
+        self.items.clear();
+        self.bounds = None;
+        self.submit()?.clear(color)?;
+        let (width, height) = self.size();
+        self.set_backdrop(Rect::new(0.0, 0.0, width as f32, height as f32), Color::rgba(color.0, color.1, color.2, color.3));
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        // Rectangles only join a batch that is already open, so backgrounds
+        // drawn before any text stay native fills
+        if self.items.is_empty() {
+            self.submit()?.fill_rect(x, y, width, height, color)?;
+            self.set_backdrop(Rect::new(x, y, width, height), Color::rgba(color.0, color.1, color.2, color.3));
+            return Ok(());
+        }
+        self.counters.count_call();
+        let rect = Rect::new(x, y, width, height);
+        self.push(BatchItem::Rect(rect, Color::rgba(color.0, color.1, color.2, color.3)), rect)
+    }
+
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.submit()?.stroke_rect(x, y, width, height, color, stroke)
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.submit()?.draw_text(text, x, y, color)
+    }
+
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.submit()?.draw_text_transformed(text, transform, color)
+    }
+
+    fn measure_text(&self, text: &str) -> Option<TextMetrics> {
+        self.inner.measure_text(text)
+    }
+
+    fn draw_glyph_run(&mut self, _font: &Font, _glyphs: &[GlyphPosition], _size: f32, _transform: Transform, _color: (f32, f32, f32, f32)) -> Result<bool> {
+        // Text is rasterized by the canvas so that it can join the batch
+        Ok(false)
+    }
+
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        // Images placed at whole pixels, as text runs are, join the batch;
+        // opaque ones only when it is open, as the backend copies them directly
+        let whole = |value: f32| value.fract() == 0.0;
+        let aligned = transform.is_translation() && [src.x, src.y, src.width, src.height, transform.tx, transform.ty].iter().all(|v| whole(*v));
+        let covers_image = src == Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        if !aligned || !covers_image || (self.items.is_empty() && image.is_opaque()) {
+            return self.submit()?.draw_image(image, src, transform, filter);
+        }
+        self.counters.count_call();
+        let area = Rect::new(transform.tx, transform.ty, src.width, src.height);
+        let position = (transform.tx as i32, transform.ty as i32);
+        self.push(BatchItem::Image(image.clone(), position), area)
+    }
+
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        self.submit()?.draw_layer(layer, origin, opacity, blend_mode)
+    }
+
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        self.submit()?.blur_region(region, radius)
+    }
+
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        self.submit()?.fill_path(path, color, fill_rule)
+    }
+
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.submit()?.stroke_path(path, color, stroke)
+    }
+
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        self.submit()?.fill_path_paint(path, paint, fill_rule)
+    }
+
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        self.submit()?.stroke_path_paint(path, paint, stroke)
+    }
+
+    fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.submit()?.fill_rounded_rect(rect, radii, color)
+    }
+
+    fn stroke_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.submit()?.stroke_rounded_rect(rect, radii, color, stroke)
+    }
+
+    fn fill_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.submit()?.fill_ellipse(cx, cy, radius_x, radius_y, color)
+    }
+
+    fn stroke_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.submit()?.stroke_ellipse(cx, cy, radius_x, radius_y, color, stroke)
+    }
+
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        // Pending draws were made under the old clip
+        self.flush()?;
+        self.clipped = clip.is_some();
+        self.inner.set_clip(clip)
+    }
+
+    fn present(&mut self) -> Result<()> {
+        self.flush()?;
+        self.inner.present()
+    }
+
+    fn read_pixels(&mut self) -> Result<Image> {
+        self.flush()?;
+        self.inner.read_pixels()
+    }
+
+    fn present_rects(&mut self, rects: &[Rect]) -> Result<()> {
+        self.flush()?;
+        self.inner.present_rects(rects)
+    }
+
+    fn supports_batching(&self) -> bool {
+        false
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.inner.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::surface::ImageContext;
+    use std::sync::Mutex;
+
+    /// Software context that counts the calls reaching it.
+    struct Counting {
+        target: ImageContext,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl DrawingContext for Counting {
+        fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.target.clear(color)
+        }
+
+        fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.calls.lock().unwrap().push("fill_rect");
+            self.target.fill_rect(x, y, width, height, color)
+        }
+
+        fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.target.stroke_rect(x, y, width, height, color, stroke)
+        }
+
+        fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.target.draw_text(text, x, y, color)
+        }
+
+        fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+            self.calls.lock().unwrap().push(if image.is_opaque() { "draw_opaque_image" } else { "draw_image" });
+            self.target.draw_image(image, src, transform, filter)
+        }
+
+        fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+            self.calls.lock().unwrap().push("fill_path");
+            self.target.fill_path(path, color, fill_rule)
+        }
+
+        fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.target.stroke_path(path, color, stroke)
+        }
+
+        fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+            self.target.set_clip(clip)
+        }
+
+        fn present(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn supports_batching(&self) -> bool {
+            true
+        }
+
+        fn size(&self) -> (u32, u32) {
+            self.target.size()
+        }
+    }
+
+    #[test]
+    fn test_batches_text_and_rects() {
+        let font = Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap();
+        let image = Arc::new(Mutex::new(Image::new(40, 20)));
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let context = Counting {
+            target: ImageContext::new(image.clone(), (0, 0), (40, 20)),
+            calls: calls.clone(),
+        };
+        let mut canvas = crate::drawing::Canvas::new(Box::new(context));
+        canvas.fill_rect(Rect::new(0.0, 0.0, 40.0, 20.0), Color::WHITE).unwrap();
+        for row in 0..3 {
+            let y = 5.0 + row as f32 * 6.0;
+            canvas.draw_text("AB", Point::new(2.0, y), &font, 10.0, Color::BLACK).unwrap();
+            canvas.fill_rect(Rect::new(30.0, y - 4.0, 4.0, 4.0), Color::RED).unwrap();
+        }
+        // The background is drawn natively and the rest waits for the batch
+        assert_eq!(*calls.lock().unwrap(), ["fill_rect"]);
+        assert_eq!(canvas.take_draw_stats(), DrawStats { draw_calls: 7, submissions: 1 });
+        canvas.fill_path(&Path::rect(Rect::new(36.0, 0.0, 2.0, 2.0)), Color::BLUE, FillRule::NonZero).unwrap();
+        // The batch lies on the background, so it reaches the backend opaque
+        assert_eq!(*calls.lock().unwrap(), ["fill_rect", "draw_opaque_image", "fill_path"]);
+        assert_eq!(canvas.take_draw_stats(), DrawStats { draw_calls: 1, submissions: 2 });
+
+        let image = image.lock().unwrap();
+        assert_eq!(image.pixel(4, 3), Color::BLACK);
+        assert_eq!(image.pixel(31, 8), Color::RED);
+        assert_eq!(image.pixel(25, 8), Color::WHITE);
+        assert_eq!(image.pixel(36, 0), Color::BLUE);
+    }
+
+    #[test]
+    fn test_flushes_on_drop() {
+        let image = Arc::new(Mutex::new(Image::new(10, 10)));
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let context = Counting {
+            target: ImageContext::new(image.clone(), (0, 0), (10, 10)),
+            calls: calls.clone(),
+        };
+        let mut canvas = crate::drawing::Canvas::new(Box::new(context));
+        let mut text = Image::new(2, 2);
+        text.fill(Color::GREEN.with_alpha(0.5));
+        canvas.draw_image_at(&text, Point::new(3.0, 3.0)).unwrap();
+        assert!(calls.lock().unwrap().is_empty());
+        drop(canvas);
+        assert_eq!(*calls.lock().unwrap(), ["draw_image"]);
+        assert!((image.lock().unwrap().pixel(4, 4).a - 0.5).abs() < 0.01);
+    }
+}
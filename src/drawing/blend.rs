@@ -0,0 +1,152 @@
+//! Blend modes for compositing layers.
+//!
+//! Colors are straight (not premultiplied) alpha. Separable modes follow the
+//! W3C compositing rules: the blended color is mixed with the source by the
+//! backdrop's alpha and then composited source-over. The Porter-Duff modes
+//! weight source and backdrop by the usual coverage fractions.
+
+use super::Color;
+
+/// How a layer is combined with what is already drawn beneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// Source-over compositing.
+    #[default]
+    Normal,
+    /// Multiply the colors, which always darkens.
+    Multiply,
+    /// Invert, multiply and invert again, which always lightens.
+    Screen,
+    /// Multiply dark backdrop colors and screen light ones.
+    Overlay,
+    /// Keep the darker of the two colors.
+    Darken,
+    /// Keep the lighter of the two colors.
+    Lighten,
+    /// Erase the backdrop.
+    Clear,
+    /// Replace the backdrop with the source.
+    Source,
+    /// Keep the backdrop and drop the source.
+    Destination,
+    /// Draw the source behind the backdrop.
+    DestinationOver,
+    /// Keep the source where the backdrop is drawn.
+    SourceIn,
+    /// Keep the backdrop where the source is drawn.
+    DestinationIn,
+    /// Keep the source where the backdrop is empty.
+    SourceOut,
+    /// Keep the backdrop where the source is empty.
+    DestinationOut,
+    /// Draw the source only on top of the backdrop.
+    SourceAtop,
+    /// Draw the backdrop only on top of the source.
+    DestinationAtop,
+    /// Keep source and backdrop where they do not overlap.
+    Xor,
+    /// Add source and backdrop.
+    Plus,
+}
+
+impl BlendMode {
+    /// Combine a source color with a backdrop color.
+    pub fn blend(self, src: Color, dst: Color) -> Color {
+        let (sa, da) = (src.a.clamp(0.0, 1.0), dst.a.clamp(0.0, 1.0));
+        let (fa, fb) = match self {
+            BlendMode::Normal | BlendMode::Multiply | BlendMode::Screen | BlendMode::Overlay | BlendMode::Darken | BlendMode::Lighten => {
+                return self.blend_separable(src, dst, sa, da);
+            }
+            BlendMode::Clear => (0.0, 0.0),
+            BlendMode::Source => (1.0, 0.0),
+            BlendMode::Destination => (0.0, 1.0),
+            BlendMode::DestinationOver => (1.0 - da, 1.0),
+            BlendMode::SourceIn => (da, 0.0),
+            BlendMode::DestinationIn => (0.0, sa),
+            BlendMode::SourceOut => (1.0 - da, 0.0),
+            BlendMode::DestinationOut => (0.0, 1.0 - sa),
+            BlendMode::SourceAtop => (da, 1.0 - sa),
+            BlendMode::DestinationAtop => (1.0 - da, sa),
+            BlendMode::Xor => (1.0 - da, 1.0 - sa),
+            BlendMode::Plus => (1.0, 1.0),
+        };
+
+        let alpha = (sa * fa + da * fb).min(1.0);
+        if alpha <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        let channel = |s: f32, d: f32| ((s * sa * fa + d * da * fb) / alpha).clamp(0.0, 1.0);
+        Color::rgba(channel(src.r, dst.r), channel(src.g, dst.g), channel(src.b, dst.b), alpha)
+    }
+
+    /// Blend with a separable mode, then composite source-over.
+    fn blend_separable(self, src: Color, dst: Color, sa: f32, da: f32) -> Color {
+        let alpha = sa + da * (1.0 - sa);
+        if alpha <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        let mix = |s: f32, d: f32| {
+            let blended = match self {
+                BlendMode::Multiply => s * d,
+                BlendMode::Screen => s + d - s * d,
+                BlendMode::Overlay if d <= 0.5 => 2.0 * s * d,
+                BlendMode::Overlay => 1.0 - 2.0 * (1.0 - s) * (1.0 - d),
+                BlendMode::Darken => s.min(d),
+                BlendMode::Lighten => s.max(d),
+                _ => s,
+            };
+            let s = (1.0 - da) * s + da * blended;
+            ((s * sa + d * da * (1.0 - sa)) / alpha).clamp(0.0, 1.0)
+        };
+        Color::rgba(mix(src.r, dst.r), mix(src.g, dst.g), mix(src.b, dst.b), alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color(actual: Color, expected: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(
+            close(actual.r, expected.r) && close(actual.g, expected.g) && close(actual.b, expected.b) && close(actual.a, expected.a),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_separable_modes() {
+        let src = Color::rgb(0.5, 0.2, 1.0);
+        let dst = Color::rgb(0.4, 0.8, 0.0);
+        assert_color(BlendMode::Normal.blend(src, dst), src);
+        assert_color(BlendMode::Multiply.blend(src, dst), Color::rgb(0.2, 0.16, 0.0));
+        assert_color(BlendMode::Screen.blend(src, dst), Color::rgb(0.7, 0.84, 1.0));
+        assert_color(BlendMode::Overlay.blend(src, dst), Color::rgb(0.4, 0.68, 0.0));
+        assert_color(BlendMode::Darken.blend(src, dst), Color::rgb(0.4, 0.2, 0.0));
+
+        // Half-transparent source over an opaque backdrop
+        let half = BlendMode::Multiply.blend(src.with_alpha(0.5), dst);
+        assert_color(half, Color::rgb(0.3, 0.48, 0.0));
+
+        // Nothing to blend with, so the source is drawn as is
+        assert_color(BlendMode::Multiply.blend(src, Color::TRANSPARENT), src);
+    }
+
+    #[test]
+    fn test_porter_duff_modes() {
+        let src = Color::RED.with_alpha(0.5);
+        let dst = Color::BLUE;
+        assert_eq!(BlendMode::Clear.blend(src, dst), Color::TRANSPARENT);
+        assert_color(BlendMode::Source.blend(src, dst), src);
+        assert_color(BlendMode::Destination.blend(src, dst), dst);
+        assert_color(BlendMode::SourceIn.blend(src, dst), src);
+        assert_color(BlendMode::SourceIn.blend(src, Color::TRANSPARENT), Color::TRANSPARENT);
+        assert_color(BlendMode::DestinationOut.blend(src, dst), Color::BLUE.with_alpha(0.5));
+        assert_color(BlendMode::SourceAtop.blend(src, dst), Color::rgb(0.5, 0.0, 0.5));
+        assert_color(BlendMode::Xor.blend(Color::RED, Color::BLUE), Color::TRANSPARENT);
+        assert_color(BlendMode::DestinationOver.blend(src, dst), dst);
+        assert_color(BlendMode::Plus.blend(Color::RED, Color::BLUE), Color::MAGENTA);
+    }
+}
@@ -0,0 +1,108 @@
+//! Clip regions for the drawing canvas.
+//!
+//! A `Clip` lives in device coordinates and is the intersection of a
+//! rectangle with any number of paths. Axis-aligned rectangles are merged
+//! into the rectangle so the common case stays cheap for backends.
+
+use super::{FillRule, Path, Point, Rect};
+
+/// Device-space clip region.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip {
+    rect: Rect,
+    paths: Vec<(Path, FillRule)>,
+}
+
+impl Clip {
+    /// Create a clip limited to a rectangle.
+    pub fn rect(rect: Rect) -> Self {
+        Self {
+            rect: normalize(rect),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Create a clip limited to a path.
+    pub fn path(path: Path, fill_rule: FillRule) -> Self {
+        Self::rect(path.bounds()).intersect_path(path, fill_rule)
+    }
+
+    /// Get the bounding rectangle of the clip.
+    pub fn bounds(&self) -> Rect {
+        self.rect
+    }
+
+    /// Get the paths the clip is limited to, in addition to its bounds.
+    pub fn paths(&self) -> &[(Path, FillRule)] {
+        &self.paths
+    }
+
+    /// Check if the clip is a plain rectangle.
+    pub fn is_rect(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Check if nothing can be drawn through the clip.
+    pub fn is_empty(&self) -> bool {
+        self.rect.is_empty()
+    }
+
+    /// Intersect the clip with a rectangle.
+    pub fn intersect_rect(&self, rect: Rect) -> Clip {
+        Clip {
+            rect: self.rect.intersection(normalize(rect)).unwrap_or_else(Rect::zero),
+            paths: self.paths.clone(),
+        }
+    }
+
+    /// Intersect the clip with a path.
+    pub fn intersect_path(&self, path: Path, fill_rule: FillRule) -> Clip {
+        let mut clip = self.intersect_rect(path.bounds());
+        clip.paths.push((path, fill_rule));
+        clip
+    }
+
+    /// Check if a point is visible through the clip.
+    pub fn contains_point(&self, point: Point) -> bool {
+        point.x >= self.rect.x
+            && point.x < self.rect.right()
+            && point.y >= self.rect.y
+            && point.y < self.rect.bottom()
+            && self.paths.iter().all(|(path, fill_rule)| path.contains_point(point, *fill_rule))
+    }
+}
+
+/// Make a rectangle's width and height non-negative.
+fn normalize(rect: Rect) -> Rect {
+    let (x, width) = if rect.width < 0.0 { (rect.x + rect.width, -rect.width) } else { (rect.x, rect.width) };
+    let (y, height) = if rect.height < 0.0 { (rect.y + rect.height, -rect.height) } else { (rect.y, rect.height) };
+    Rect::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_intersection() {
+        let clip = Clip::rect(Rect::new(0.0, 0.0, 100.0, 100.0)).intersect_rect(Rect::new(50.0, 60.0, 100.0, 100.0));
+        assert!(clip.is_rect());
+        assert_eq!(clip.bounds(), Rect::new(50.0, 60.0, 50.0, 40.0));
+
+        let empty = clip.intersect_rect(Rect::new(200.0, 200.0, 10.0, 10.0));
+        assert!(empty.is_empty());
+        assert!(!empty.contains_point(Point::new(205.0, 205.0)));
+    }
+
+    #[test]
+    fn test_path_intersection() {
+        let circle = Path::circle(Point::new(50.0, 50.0), 10.0);
+        let clip = Clip::rect(Rect::new(0.0, 0.0, 100.0, 100.0)).intersect_path(circle, FillRule::NonZero);
+
+        assert!(!clip.is_rect());
+        let bounds = clip.bounds();
+        assert!((bounds.x - 40.0).abs() < 0.001 && (bounds.right() - 60.0).abs() < 0.001);
+        assert!(clip.contains_point(Point::new(50.0, 50.0)));
+        assert!(!clip.contains_point(Point::new(41.0, 41.0)));
+    }
+}
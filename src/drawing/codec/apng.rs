@@ -0,0 +1,95 @@
+//! Animated PNG encoding.
+//!
+//! Every frame covers the whole canvas and replaces the previous one, so
+//! viewers without APNG support still show the first frame.
+
+use super::deflate::zlib_compress;
+use super::png::{filter_rows, write_chunk, SIGNATURE};
+use super::{fit_canvas, AnimationFrame};
+
+/// Encode frames as an endlessly looping animated PNG the size of the first frame.
+pub fn encode(frames: &[AnimationFrame]) -> Vec<u8> {
+    let (width, height) = frames.first().map_or((0, 0), |frame| (frame.image.width(), frame.image.height()));
+    let mut file = SIGNATURE.to_vec();
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut file, b"IHDR", &header);
+
+    let mut control = Vec::with_capacity(8);
+    control.extend_from_slice(&(frames.len() as u32).to_be_bytes());
+    control.extend_from_slice(&0u32.to_be_bytes());
+    write_chunk(&mut file, b"acTL", &control);
+
+    // Frame controls and frame data share one sequence
+    let mut sequence = 0u32;
+    for (index, frame) in frames.iter().enumerate() {
+        let milliseconds = frame.delay.as_millis().min(u16::MAX as u128) as u16;
+        let mut control = Vec::with_capacity(26);
+        control.extend_from_slice(&sequence.to_be_bytes());
+        control.extend_from_slice(&width.to_be_bytes());
+        control.extend_from_slice(&height.to_be_bytes());
+        control.extend_from_slice(&[0; 8]);
+        control.extend_from_slice(&milliseconds.to_be_bytes());
+        control.extend_from_slice(&1000u16.to_be_bytes());
+        // Leave the frame in place and replace the pixels under it
+        control.extend_from_slice(&[0, 0]);
+        write_chunk(&mut file, b"fcTL", &control);
+        sequence += 1;
+
+        let data = zlib_compress(&filter_rows(&fit_canvas(&frame.image, width, height)));
+        if index == 0 {
+            write_chunk(&mut file, b"IDAT", &data);
+        } else {
+            let mut body = sequence.to_be_bytes().to_vec();
+            body.extend_from_slice(&data);
+            write_chunk(&mut file, b"fdAT", &body);
+            sequence += 1;
+        }
+    }
+    write_chunk(&mut file, b"IEND", &[]);
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::{Color, Image};
+    use std::time::Duration;
+
+    /// Find the bodies of every chunk of a kind.
+    fn chunks<'a>(file: &'a [u8], kind: &[u8; 4]) -> Vec<&'a [u8]> {
+        let mut found = Vec::new();
+        let mut offset = SIGNATURE.len();
+        while offset + 8 <= file.len() {
+            let length = u32::from_be_bytes(file[offset..offset + 4].try_into().unwrap()) as usize;
+            if &file[offset + 4..offset + 8] == kind {
+                found.push(&file[offset + 8..offset + 8 + length]);
+            }
+            offset += length + 12;
+        }
+        found
+    }
+
+    #[test]
+    fn test_encode_apng() {
+        let frames = [
+            AnimationFrame { image: Image::filled(3, 2, Color::RED), delay: Duration::from_millis(40) },
+            AnimationFrame { image: Image::filled(3, 2, Color::BLUE), delay: Duration::from_millis(60) },
+        ];
+        let file = encode(&frames);
+
+        assert_eq!(&chunks(&file, b"acTL")[0][..4], &2u32.to_be_bytes());
+        let controls = chunks(&file, b"fcTL");
+        assert_eq!(controls.len(), 2);
+        assert_eq!(&controls[1][..4], &1u32.to_be_bytes());
+        assert_eq!(&controls[1][20..24], &[0, 60, 0x03, 0xe8]);
+        assert_eq!(&chunks(&file, b"fdAT")[0][..4], &2u32.to_be_bytes());
+
+        // Plain decoders show the first frame
+        let image = Image::from_png(&file).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.pixel(2, 1), Color::RED);
+    }
+}
@@ -0,0 +1,223 @@
+//! BMP decoding.
+//!
+//! Supports uncompressed 1, 4, 8, 16, 24 and 32-bit bitmaps, including
+//! `BI_BITFIELDS` channel masks and both bottom-up and top-down row order.
+
+use super::pixel_count;
+use crate::drawing::Image;
+use crate::{Error, Result};
+
+/// Uncompressed pixels.
+const BI_RGB: u32 = 0;
+
+/// Uncompressed pixels with explicit channel masks.
+const BI_BITFIELDS: u32 = 3;
+
+/// Uncompressed pixels with explicit channel masks including alpha.
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// Decode a BMP file.
+pub fn decode(data: &[u8]) -> Result<Image> {
+    if !data.starts_with(b"BM") {
+        return Err(Error::resource("Not a BMP file"));
+    }
+    let pixel_offset = read_u32(data, 10)? as usize;
+    let header_size = read_u32(data, 14)? as usize;
+
+    let (width, height, bit_count, compression) = if header_size == 12 {
+        // OS/2 core header with 16-bit dimensions
+        (read_u16(data, 18)? as i32, read_u16(data, 20)? as i16 as i32, read_u16(data, 24)?, BI_RGB)
+    } else if header_size >= 40 {
+        (read_u32(data, 18)? as i32, read_u32(data, 22)? as i32, read_u16(data, 28)?, read_u32(data, 30)?)
+    } else {
+        return Err(Error::resource(format!("Unsupported BMP header size {}", header_size)));
+    };
+
+    if width <= 0 || height == 0 {
+        return Err(Error::resource("Invalid BMP dimensions"));
+    }
+    let top_down = height < 0;
+    let (width, height) = (width as usize, height.unsigned_abs() as usize);
+    pixel_count(width, height)?;
+
+    let masks = match (compression, bit_count) {
+        (BI_BITFIELDS, 16 | 32) | (BI_ALPHABITFIELDS, 16 | 32) => {
+            // Masks follow a 40-byte header, or live inside larger ones
+            let alpha = if compression == BI_ALPHABITFIELDS || header_size >= 56 { read_u32(data, 66)? } else { 0 };
+            [read_u32(data, 54)?, read_u32(data, 58)?, read_u32(data, 62)?, alpha]
+        }
+        (BI_RGB, 16) => [0x7c00, 0x03e0, 0x001f, 0],
+        (BI_RGB, 32) => [0x00ff_0000, 0x0000_ff00, 0x0000_00ff, 0xff00_0000],
+        (BI_RGB, 1 | 4 | 8 | 24) => [0; 4],
+        _ => {
+            return Err(Error::resource(format!(
+                "Unsupported BMP format: {} bits per pixel with compression {}",
+                bit_count, compression
+            )))
+        }
+    };
+
+    let palette: Vec<[u8; 4]> = if bit_count <= 8 {
+        let entry_size = if header_size == 12 { 3 } else { 4 };
+        let palette_start = 14 + header_size;
+        let count = if header_size >= 40 { read_u32(data, 46)? as usize } else { 0 };
+        let count = if count == 0 { 1 << bit_count } else { count.min(1 << bit_count) };
+        (0..count)
+            .map(|i| {
+                let entry = palette_start + i * entry_size;
+                data.get(entry..entry + 3)
+                    .map(|bgr| [bgr[2], bgr[1], bgr[0], 255])
+                    .ok_or_else(|| Error::resource("BMP palette is truncated"))
+            })
+            .collect::<Result<_>>()?
+    } else {
+        Vec::new()
+    };
+
+    let row_size = (width * bit_count as usize).div_ceil(32) * 4;
+    let pixels_end = row_size.checked_mul(height).and_then(|size| size.checked_add(pixel_offset));
+    if pixels_end.is_none_or(|end| end > data.len()) {
+        return Err(Error::resource("BMP pixel data is truncated"));
+    }
+    let mut image = Image::new(width as u32, height as u32);
+    let mut has_alpha = false;
+
+    for row in 0..height {
+        let start = pixel_offset + row * row_size;
+        let line = data
+            .get(start..start + row_size)
+            .ok_or_else(|| Error::resource("BMP pixel data is truncated"))?;
+        let y = if top_down { row } else { height - 1 - row };
+
+        for x in 0..width {
+            let rgba = match bit_count {
+                1 | 4 | 8 => {
+                    let bit = x * bit_count as usize;
+                    let shift = 8 - bit_count as usize - bit % 8;
+                    let index = (line[bit / 8] >> shift) as usize & ((1 << bit_count) - 1);
+                    palette.get(index).copied().unwrap_or([0, 0, 0, 255])
+                }
+                24 => [line[x * 3 + 2], line[x * 3 + 1], line[x * 3], 255],
+                16 => {
+                    let value = u32::from(u16::from_le_bytes([line[x * 2], line[x * 2 + 1]]));
+                    masked_pixel(value, masks)
+                }
+                _ => {
+                    let value = u32::from_le_bytes([line[x * 4], line[x * 4 + 1], line[x * 4 + 2], line[x * 4 + 3]]);
+                    masked_pixel(value, masks)
+                }
+            };
+            has_alpha |= rgba[3] != 0;
+            let index = (y * width + x) * 4;
+            image.pixels_mut()[index..index + 4].copy_from_slice(&rgba);
+        }
+    }
+
+    // Many writers leave the alpha byte of 32-bit images zeroed
+    if !has_alpha {
+        for pixel in image.pixels_mut().chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+    }
+
+    Ok(image)
+}
+
+/// Expand a pixel using red, green, blue and alpha masks.
+fn masked_pixel(value: u32, masks: [u32; 4]) -> [u8; 4] {
+    let channel = |mask: u32, missing: u8| {
+        if mask == 0 {
+            return missing;
+        }
+        let shift = mask.trailing_zeros();
+        let max = u64::from(mask >> shift);
+        (u64::from((value & mask) >> shift) * 255 / max) as u8
+    };
+    [channel(masks[0], 0), channel(masks[1], 0), channel(masks[2], 0), channel(masks[3], 255)]
+}
+
+/// Read a little-endian u16.
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| Error::resource("BMP file is truncated"))
+}
+
+/// Read a little-endian u32.
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| Error::resource("BMP file is truncated"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Color;
+
+    /// Build a BMP file with a 40-byte header.
+    fn build_bmp(width: i32, height: i32, bit_count: u16, palette: &[[u8; 4]], pixels: &[u8]) -> Vec<u8> {
+        let pixel_offset = 54 + palette.len() * 4;
+        let mut file = b"BM".to_vec();
+        file.extend_from_slice(&((pixel_offset + pixels.len()) as u32).to_le_bytes());
+        file.extend_from_slice(&[0; 4]);
+        file.extend_from_slice(&(pixel_offset as u32).to_le_bytes());
+        file.extend_from_slice(&40u32.to_le_bytes());
+        file.extend_from_slice(&width.to_le_bytes());
+        file.extend_from_slice(&height.to_le_bytes());
+        file.extend_from_slice(&1u16.to_le_bytes());
+        file.extend_from_slice(&bit_count.to_le_bytes());
+        file.extend_from_slice(&BI_RGB.to_le_bytes());
+        file.extend_from_slice(&[0; 12]);
+        file.extend_from_slice(&(palette.len() as u32).to_le_bytes());
+        file.extend_from_slice(&[0; 4]);
+        for entry in palette {
+            file.extend_from_slice(entry);
+        }
+        file.extend_from_slice(pixels);
+        file
+    }
+
+    #[test]
+    fn test_bottom_up_24_bit() {
+        // Rows are padded to four bytes and stored bottom row first
+        let pixels = [
+            0, 0, 255, 0, 255, 0, 0, 0,
+            255, 0, 0, 255, 255, 255, 0, 0,
+        ];
+        let image = decode(&build_bmp(2, 2, 24, &[], &pixels)).unwrap();
+
+        assert_eq!(image.pixel(0, 1), Color::RED);
+        assert_eq!(image.pixel(1, 1), Color::GREEN);
+        assert_eq!(image.pixel(0, 0), Color::BLUE);
+        assert_eq!(image.pixel(1, 0), Color::WHITE);
+    }
+
+    #[test]
+    fn test_top_down_palette() {
+        let palette = [[0, 0, 0, 0], [0, 0, 255, 0]];
+        let image = decode(&build_bmp(3, -1, 8, &palette, &[1, 0, 1, 0])).unwrap();
+
+        assert_eq!(image.pixel(0, 0), Color::RED);
+        assert_eq!(image.pixel(1, 0), Color::BLACK);
+        assert_eq!(image.pixel(2, 0), Color::RED);
+    }
+
+    #[test]
+    fn test_32_bit_alpha() {
+        let opaque = decode(&build_bmp(1, 1, 32, &[], &[255, 0, 0, 0])).unwrap();
+        assert_eq!(opaque.pixel(0, 0), Color::BLUE);
+
+        let translucent = decode(&build_bmp(1, 1, 32, &[], &[255, 0, 0, 51])).unwrap();
+        assert_eq!(translucent.pixels(), &[0, 0, 255, 51]);
+
+        assert!(decode(&build_bmp(1, 1, 24, &[], &[])).is_err());
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        // Tiny files claiming huge bitmaps fail before allocating
+        assert!(decode(&build_bmp(i32::MAX, i32::MAX, 24, &[], &[0; 4])).is_err());
+        assert!(decode(&build_bmp(16000, -16000, 32, &[], &[0; 4])).is_err());
+    }
+}
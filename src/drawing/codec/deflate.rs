@@ -0,0 +1,209 @@
+//! zlib and DEFLATE compression (RFC 1950 and RFC 1951).
+//!
+//! Repeated strings are found with hash chains over a 32 KiB window and the
+//! result is written as a single block with the fixed Huffman codes, which
+//! keeps the encoder small while still shrinking typical image rows and
+//! drawing commands several times over.
+
+use super::inflate::{adler32, DISTANCE_BASE, DISTANCE_EXTRA, LENGTH_BASE, LENGTH_EXTRA};
+
+/// Size of the window matches are searched in.
+const WINDOW_SIZE: usize = 32 * 1024;
+
+/// Shortest match DEFLATE can encode.
+const MIN_MATCH: usize = 3;
+
+/// Longest match DEFLATE can encode.
+const MAX_MATCH: usize = 258;
+
+/// Number of bits in the hash of the next three bytes.
+const HASH_BITS: u32 = 15;
+
+/// Most earlier positions tried when looking for a match.
+const MAX_CHAIN: usize = 64;
+
+/// Sentinel for an empty hash chain entry.
+const NONE: u32 = u32::MAX;
+
+/// Compress data into a zlib stream.
+pub fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32 KiB window at the default level
+    let mut output = vec![0x78, 0x9c];
+    output.extend_from_slice(&deflate(data));
+    output.extend_from_slice(&adler32(data).to_be_bytes());
+    output
+}
+
+/// Compress data into a raw DEFLATE stream.
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    // A single final block with fixed Huffman codes
+    writer.bits(1, 1);
+    writer.bits(1, 2);
+
+    let mut matcher = Matcher::new(data);
+    let mut position = 0;
+    while position < data.len() {
+        match matcher.find(position) {
+            Some((length, distance)) => {
+                write_match(&mut writer, length, distance);
+                for offset in 0..length {
+                    matcher.insert(position + offset);
+                }
+                position += length;
+            }
+            None => {
+                write_symbol(&mut writer, data[position] as u16);
+                matcher.insert(position);
+                position += 1;
+            }
+        }
+    }
+    write_symbol(&mut writer, 256);
+    writer.finish()
+}
+
+/// Finds earlier occurrences of the bytes at a position.
+struct Matcher<'a> {
+    data: &'a [u8],
+    /// Most recent position for each hash.
+    head: Vec<u32>,
+    /// Previous position with the same hash, indexed by position in the window.
+    previous: Vec<u32>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            head: vec![NONE; 1 << HASH_BITS],
+            previous: vec![NONE; WINDOW_SIZE],
+        }
+    }
+
+    fn hash(&self, position: usize) -> usize {
+        let bytes = &self.data[position..position + MIN_MATCH];
+        let value = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        (value.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, position: usize) {
+        if position + MIN_MATCH <= self.data.len() {
+            let hash = self.hash(position);
+            self.previous[position % WINDOW_SIZE] = self.head[hash];
+            self.head[hash] = position as u32;
+        }
+    }
+
+    /// Find the longest match for `position`, returning its length and distance.
+    fn find(&self, position: usize) -> Option<(usize, usize)> {
+        if position + MIN_MATCH > self.data.len() {
+            return None;
+        }
+        let limit = (self.data.len() - position).min(MAX_MATCH);
+        let mut best: Option<(usize, usize)> = None;
+        let mut candidate = self.head[self.hash(position)];
+        for _ in 0..MAX_CHAIN {
+            if candidate == NONE {
+                break;
+            }
+            let earlier = candidate as usize;
+            let distance = position - earlier;
+            if distance > WINDOW_SIZE {
+                break;
+            }
+            let length = self.data[earlier..].iter()
+                .zip(&self.data[position..position + limit])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length >= MIN_MATCH && best.is_none_or(|(best, _)| length > best) {
+                best = Some((length, distance));
+                if length == limit {
+                    break;
+                }
+            }
+            let next = self.previous[earlier % WINDOW_SIZE];
+            // Entries overwritten by newer positions point forward; stop there
+            if next != NONE && next as usize >= earlier {
+                break;
+            }
+            candidate = next;
+        }
+        best
+    }
+}
+
+/// Write a length/distance pair.
+fn write_match(writer: &mut BitWriter, length: usize, distance: usize) {
+    let code = LENGTH_BASE.iter().rposition(|base| *base as usize <= length).unwrap_or(0);
+    write_symbol(writer, 257 + code as u16);
+    writer.bits((length - LENGTH_BASE[code] as usize) as u32, LENGTH_EXTRA[code] as u32);
+
+    let code = DISTANCE_BASE.iter().rposition(|base| *base as usize <= distance).unwrap_or(0);
+    writer.huffman(code as u32, 5);
+    writer.bits((distance - DISTANCE_BASE[code] as usize) as u32, DISTANCE_EXTRA[code] as u32);
+}
+
+/// Write a literal or length symbol with the fixed Huffman code.
+fn write_symbol(writer: &mut BitWriter, symbol: u16) {
+    let symbol = u32::from(symbol);
+    match symbol {
+        0..=143 => writer.huffman(0x30 + symbol, 8),
+        144..=255 => writer.huffman(0x190 + symbol - 144, 9),
+        256..=279 => writer.huffman(symbol - 256, 7),
+        _ => writer.huffman(0xc0 + symbol - 280, 8),
+    }
+}
+
+/// Packs bits least significant first.
+#[derive(Default)]
+struct BitWriter {
+    output: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    /// Write the low `count` bits of `value`.
+    fn bits(&mut self, value: u32, count: u32) {
+        self.buffer |= u64::from(value) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.output.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Write a Huffman code, which is stored most significant bit first.
+    fn huffman(&mut self, code: u32, length: u32) {
+        self.bits(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.output.push(self.buffer as u8);
+        }
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::codec::inflate::zlib_decompress;
+
+    #[test]
+    fn test_round_trip() {
+        let mut data = b"a quick brown fox jumps over the lazy dog; ".repeat(40);
+        data.extend((0..5000u32).map(|i| (i * 7 % 251) as u8));
+        data.extend(std::iter::repeat_n(0u8, 1000));
+        let compressed = zlib_compress(&data);
+        assert!(compressed.len() < data.len() / 2);
+        assert_eq!(zlib_decompress(&compressed).unwrap(), data);
+
+        for data in [&b""[..], b"x", b"ab"] {
+            assert_eq!(zlib_decompress(&zlib_compress(data)).unwrap(), data);
+        }
+    }
+}
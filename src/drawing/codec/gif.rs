@@ -0,0 +1,283 @@
+//! Animated GIF encoding.
+//!
+//! Each frame gets its own 256-entry palette: frames with few colors, like
+//! most interface screenshots, keep them exactly, and others are mapped to a
+//! uniform color cube. Pixels less than half opaque become transparent.
+
+use super::{fit_canvas, AnimationFrame};
+use crate::drawing::Image;
+use std::collections::HashMap;
+
+/// Palette index reserved for transparent pixels.
+const TRANSPARENT: u8 = 255;
+
+/// Levels of red, green and blue in the fallback color cube.
+const CUBE: [usize; 3] = [6, 7, 6];
+
+/// Largest LZW code.
+const MAX_CODE: u16 = 4095;
+
+/// Encode frames as an endlessly looping GIF the size of the first frame.
+pub fn encode(frames: &[AnimationFrame]) -> Vec<u8> {
+    let (width, height) = frames.first().map_or((0, 0), |frame| (frame.image.width(), frame.image.height()));
+    let mut file = b"GIF89a".to_vec();
+    file.extend_from_slice(&(width as u16).to_le_bytes());
+    file.extend_from_slice(&(height as u16).to_le_bytes());
+    // No global palette
+    file.extend_from_slice(&[0, 0, 0]);
+    // Loop forever
+    file.extend_from_slice(&[0x21, 0xff, 0x0b]);
+    file.extend_from_slice(b"NETSCAPE2.0");
+    file.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for frame in frames {
+        let image = fit_canvas(&frame.image, width, height);
+        let (palette, indices) = quantize(&image);
+        let transparent = indices.contains(&TRANSPARENT);
+
+        // Viewers treat very short delays as slow, so keep at least 20ms
+        let centiseconds = (frame.delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        file.extend_from_slice(&[0x21, 0xf9, 0x04, 1 << 3 | transparent as u8]);
+        file.extend_from_slice(&centiseconds.to_le_bytes());
+        file.extend_from_slice(&[TRANSPARENT, 0x00]);
+
+        file.push(0x2c);
+        file.extend_from_slice(&[0, 0, 0, 0]);
+        file.extend_from_slice(&(width as u16).to_le_bytes());
+        file.extend_from_slice(&(height as u16).to_le_bytes());
+        file.push(0x87);
+        for color in &palette {
+            file.extend_from_slice(color);
+        }
+
+        file.push(8);
+        for block in lzw_compress(&indices, 8).chunks(255) {
+            file.push(block.len() as u8);
+            file.extend_from_slice(block);
+        }
+        file.push(0x00);
+    }
+    file.push(0x3b);
+    file
+}
+
+/// Build a palette for an image and map each pixel to it.
+fn quantize(image: &Image) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let pixels = image.pixels().chunks_exact(4);
+    let mut palette = Vec::new();
+    let mut lookup = HashMap::new();
+    let mut indices = Vec::with_capacity(pixels.len());
+    for pixel in pixels.clone() {
+        if pixel[3] < 128 {
+            indices.push(TRANSPARENT);
+            continue;
+        }
+        let color = [pixel[0], pixel[1], pixel[2]];
+        let index = *lookup.entry(color).or_insert_with(|| {
+            palette.push(color);
+            palette.len() - 1
+        });
+        if index >= TRANSPARENT as usize {
+            return cube_quantize(image);
+        }
+        indices.push(index as u8);
+    }
+    palette.resize(256, [0; 3]);
+    (palette, indices)
+}
+
+/// Map every pixel to the nearest color of a uniform color cube.
+fn cube_quantize(image: &Image) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let level = |value: usize, levels: usize| (value * 255 / (levels - 1)) as u8;
+    let mut palette = Vec::with_capacity(256);
+    for r in 0..CUBE[0] {
+        for g in 0..CUBE[1] {
+            for b in 0..CUBE[2] {
+                palette.push([level(r, CUBE[0]), level(g, CUBE[1]), level(b, CUBE[2])]);
+            }
+        }
+    }
+    palette.resize(256, [0; 3]);
+
+    let step = |value: u8, levels: usize| (value as usize * (levels - 1) + 127) / 255;
+    let indices = image
+        .pixels()
+        .chunks_exact(4)
+        .map(|pixel| {
+            if pixel[3] < 128 {
+                return TRANSPARENT;
+            }
+            let (r, g, b) = (step(pixel[0], CUBE[0]), step(pixel[1], CUBE[1]), step(pixel[2], CUBE[2]));
+            ((r * CUBE[1] + g) * CUBE[2] + b) as u8
+        })
+        .collect();
+    (palette, indices)
+}
+
+/// Packs variable-width codes least significant bit first.
+struct BitWriter {
+    output: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u32) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.output.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.output.push(self.buffer as u8);
+        }
+        self.output
+    }
+}
+
+/// Compress palette indices with GIF's variant of LZW.
+fn lzw_compress(indices: &[u8], min_code_size: u32) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter { output: Vec::new(), buffer: 0, bits: 0 };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+    writer.write(clear, width);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, width);
+        return writer.finish();
+    };
+    let mut prefix = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, width);
+        // Widen codes once the decoder's table, one entry behind ours, needs it
+        if next > (1 << width) - 1 && width < 12 {
+            width += 1;
+        }
+        if next <= MAX_CODE {
+            table.insert((prefix, index), next);
+            next += 1;
+        } else {
+            writer.write(clear, width);
+            table.clear();
+            next = end + 1;
+            width = min_code_size + 1;
+        }
+        prefix = u16::from(index);
+    }
+    writer.write(prefix, width);
+    if next > (1 << width) - 1 && width < 12 {
+        width += 1;
+    }
+    writer.write(end, width);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Color;
+    use std::time::Duration;
+
+    /// Decompress GIF LZW data.
+    fn lzw_decompress(data: &[u8], min_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut output = Vec::new();
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0u32, data.iter());
+        loop {
+            while bits < width {
+                buffer |= u32::from(*bytes.next().unwrap()) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << width) - 1)) as usize;
+            buffer >>= width;
+            bits -= width;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect();
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match (table.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = table[previous].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+                (None, None) => panic!("invalid first code"),
+            };
+            if let Some(previous) = previous {
+                if table.len() < 4096 {
+                    let mut added = table[previous].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let repetitive: Vec<u8> = (0..20000).map(|i| ((i / 7) % 5) as u8).collect();
+        let noisy: Vec<u8> = (0..20000u32).map(|i| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        for data in [vec![], vec![3], repetitive, noisy] {
+            assert_eq!(lzw_decompress(&lzw_compress(&data, 8), 8), data);
+        }
+    }
+
+    #[test]
+    fn test_encode_gif() {
+        let mut image = Image::filled(4, 2, Color::RED);
+        image.set_pixel(3, 1, Color::TRANSPARENT);
+        let frames = [AnimationFrame { image, delay: Duration::from_millis(50) }];
+        let file = encode(&frames);
+
+        assert!(file.starts_with(b"GIF89a"));
+        assert_eq!(&file[6..10], &[4, 0, 2, 0]);
+        assert_eq!(file.last(), Some(&0x3b));
+        // Graphic control: transparency on, 5 centiseconds
+        let control = file.windows(3).position(|bytes| bytes == [0x21, 0xf9, 0x04]).unwrap();
+        assert_eq!(&file[control + 3..control + 7], &[0x09, 5, 0, TRANSPARENT]);
+
+        let (palette, indices) = quantize(&frames[0].image);
+        assert_eq!(palette[0], [255, 0, 0]);
+        assert_eq!(indices, [0, 0, 0, 0, 0, 0, 0, TRANSPARENT]);
+    }
+
+    #[test]
+    fn test_cube_quantize() {
+        let mut image = Image::new(300, 1);
+        for x in 0..300 {
+            image.set_pixel(x, 0, Color::rgb(x as f32 / 299.0, (x % 17) as f32 / 16.0, 0.5));
+        }
+        let (palette, indices) = quantize(&image);
+        assert_eq!(palette.len(), 256);
+        // The least and most red pixels get the cube's lowest and highest red
+        assert_eq!(palette[indices[0] as usize][0], 0);
+        assert_eq!(palette[indices[299] as usize][0], 255);
+    }
+}
@@ -0,0 +1,335 @@
+//! zlib and DEFLATE decompression (RFC 1950 and RFC 1951).
+
+use crate::{Error, Result};
+
+/// Largest number of bits in a Huffman code.
+const MAX_BITS: usize = 15;
+
+/// Base lengths for length codes 257..285.
+pub(super) const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+
+/// Extra bits for length codes 257..285.
+pub(super) const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+/// Base distances for distance codes 0..29.
+pub(super) const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+
+/// Extra bits for distance codes 0..29.
+pub(super) const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+/// Order in which code length code lengths are stored.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decompress a zlib stream.
+pub fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < 6 {
+        return Err(Error::resource("zlib stream is truncated"));
+    }
+    let (cmf, flg) = (data[0], data[1]);
+    if cmf & 0x0f != 8 || cmf >> 4 > 7 {
+        return Err(Error::resource("zlib stream does not use deflate compression"));
+    }
+    if (u16::from(cmf) << 8 | u16::from(flg)) % 31 != 0 {
+        return Err(Error::resource("zlib header checksum mismatch"));
+    }
+    if flg & 0x20 != 0 {
+        return Err(Error::resource("zlib preset dictionaries are not supported"));
+    }
+
+    let (output, consumed) = inflate(&data[2..])?;
+    let trailer = data
+        .get(2 + consumed..2 + consumed + 4)
+        .ok_or_else(|| Error::resource("zlib stream is missing its checksum"))?;
+    let expected = u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    if adler32(&output) != expected {
+        return Err(Error::resource("zlib checksum mismatch"));
+    }
+    Ok(output)
+}
+
+/// Decompress a raw DEFLATE stream, returning the output and the number of input bytes used.
+pub fn inflate(data: &[u8]) -> Result<(Vec<u8>, usize)> {
+    let mut reader = BitReader::new(data);
+    let mut output = Vec::new();
+
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let len = reader.bytes(2)?;
+                let nlen = reader.bytes(2)?;
+                let len = u16::from_le_bytes([len[0], len[1]]);
+                if len != !u16::from_le_bytes([nlen[0], nlen[1]]) {
+                    return Err(Error::resource("deflate stored block length mismatch"));
+                }
+                output.extend_from_slice(reader.bytes(len as usize)?);
+            }
+            1 => {
+                let (literals, distances) = fixed_tables();
+                inflate_block(&mut reader, &mut output, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut output, &literals, &distances)?;
+            }
+            _ => return Err(Error::resource("invalid deflate block type")),
+        }
+        if last {
+            break;
+        }
+    }
+
+    reader.align();
+    Ok((output, reader.position))
+}
+
+/// Compute the Adler-32 checksum of some data.
+pub(super) fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    b << 16 | a
+}
+
+/// Decode the symbols of one compressed block.
+fn inflate_block(reader: &mut BitReader, output: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> Result<()> {
+    loop {
+        let symbol = literals.decode(reader)?;
+        match symbol {
+            0..=255 => output.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let index = symbol as usize - 257;
+                let length = LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+                let code = distances.decode(reader)? as usize;
+                if code >= DISTANCE_BASE.len() {
+                    return Err(Error::resource("invalid deflate distance code"));
+                }
+                let distance = DISTANCE_BASE[code] as usize + reader.bits(DISTANCE_EXTRA[code] as u32)? as usize;
+                if distance > output.len() {
+                    return Err(Error::resource("deflate distance reaches before the start of the output"));
+                }
+                // Copies may overlap their own output, so go byte by byte
+                let start = output.len() - distance;
+                for i in 0..length {
+                    let byte = output[start + i];
+                    output.push(byte);
+                }
+            }
+            _ => return Err(Error::resource("invalid deflate literal/length code")),
+        }
+    }
+}
+
+/// Huffman tables for fixed-code blocks.
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    // Fixed tables are always well-formed
+    let literals = Huffman::new(&lengths).unwrap_or_default();
+    let distances = Huffman::new(&[5; 30]).unwrap_or_default();
+    (literals, distances)
+}
+
+/// Read the Huffman tables of a dynamic-code block.
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman)> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_table = Huffman::new(&code_lengths)?;
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_table.decode(reader)?;
+        match symbol {
+            0..=15 => lengths.push(symbol as u8),
+            16 => {
+                let previous = *lengths
+                    .last()
+                    .ok_or_else(|| Error::resource("deflate repeat code without a previous length"))?;
+                let count = 3 + reader.bits(2)? as usize;
+                lengths.extend(std::iter::repeat_n(previous, count));
+            }
+            17 => {
+                let count = 3 + reader.bits(3)? as usize;
+                lengths.extend(std::iter::repeat_n(0, count));
+            }
+            18 => {
+                let count = 11 + reader.bits(7)? as usize;
+                lengths.extend(std::iter::repeat_n(0, count));
+            }
+            _ => return Err(Error::resource("invalid deflate code length symbol")),
+        }
+    }
+    if lengths.len() > literal_count + distance_count {
+        return Err(Error::resource("deflate code lengths overflow the table"));
+    }
+    if lengths[256] == 0 {
+        return Err(Error::resource("deflate block has no end-of-block code"));
+    }
+
+    let literals = Huffman::new(&lengths[..literal_count])?;
+    let distances = Huffman::new(&lengths[literal_count..])?;
+    Ok((literals, distances))
+}
+
+/// Canonical Huffman decoding table.
+#[derive(Debug, Default)]
+struct Huffman {
+    /// Number of codes of each bit length.
+    counts: [u16; MAX_BITS + 1],
+    /// Symbols ordered by code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        // Reject over-subscribed codes; incomplete codes are allowed
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(Error::resource("over-subscribed deflate Huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for bits in 1..=MAX_BITS {
+            offsets[bits + 1] = offsets[bits] + counts[bits];
+        }
+        let mut symbols = vec![0u16; offsets[MAX_BITS + 1] as usize];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16> {
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for &count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32;
+            let count = i32::from(count);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(Error::resource("invalid deflate Huffman code"))
+    }
+}
+
+/// Least-significant-bit-first reader over a byte slice.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    bit_buffer: u32,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0, bit_buffer: 0, bit_count: 0 }
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32> {
+        while self.bit_count < count {
+            let byte = *self
+                .data
+                .get(self.position)
+                .ok_or_else(|| Error::resource("deflate stream is truncated"))?;
+            self.position += 1;
+            self.bit_buffer |= u32::from(byte) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let value = self.bit_buffer & ((1u64 << count) - 1) as u32;
+        self.bit_buffer >>= count;
+        self.bit_count -= count;
+        Ok(value)
+    }
+
+    /// Discard the remaining bits of the current byte.
+    fn align(&mut self) {
+        // Whole buffered bytes were read ahead and must be given back
+        self.position -= (self.bit_count / 8) as usize;
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + count)
+            .ok_or_else(|| Error::resource("deflate stream is truncated"))?;
+        self.position += count;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_block() {
+        // zlib header, one final stored block holding "hello", Adler-32
+        let mut data = vec![0x78, 0x01, 0x01, 0x05, 0x00, 0xfa, 0xff];
+        data.extend_from_slice(b"hello");
+        data.extend_from_slice(&adler32(b"hello").to_be_bytes());
+        assert_eq!(zlib_decompress(&data).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_fixed_huffman_block() {
+        // "abcabcabc" compressed with a fixed-code block and a back-reference
+        let data = [0x78, 0xda, 0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00, 0x11, 0x3d, 0x03, 0x73];
+        assert_eq!(zlib_decompress(&data).unwrap(), b"abcabcabc");
+    }
+
+    #[test]
+    fn test_dynamic_huffman_block() {
+        let data = [
+            0x78, 0xda, 0x0d, 0xc5, 0x31, 0x01, 0x00, 0x00, 0x00, 0x82, 0xb0, 0xac, 0xa0, 0xfd, 0x33, 0xe8, 0xb3, 0x89,
+            0x24, 0x60, 0x6b, 0x7d, 0x1e, 0x61, 0x73, 0x18, 0x09, 0x33,
+        ];
+        assert_eq!(zlib_decompress(&data).unwrap(), b"babaccaabddbdbabdbbabbaa");
+    }
+
+    #[test]
+    fn test_corrupt_streams() {
+        assert!(zlib_decompress(&[0x78, 0x9c, 0x4b]).is_err());
+        assert!(zlib_decompress(&[0x78, 0x9d, 0x03, 0x00, 0x00, 0x00, 0x00, 0x01]).is_err());
+    }
+}
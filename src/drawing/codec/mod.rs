@@ -0,0 +1,64 @@
+//! Image file decoders and encoders.
+//!
+//! Formats are detected from their leading bytes, so callers never need to
+//! trust a file extension. Images are encoded as PNG, and animations as
+//! APNG or GIF.
+
+pub mod inflate;
+pub mod deflate;
+pub mod png;
+pub mod apng;
+pub mod gif;
+pub mod bmp;
+pub mod ppm;
+
+use super::Image;
+use crate::{Error, Result};
+use std::time::Duration;
+
+/// Most pixels a decoded image may have; 16384 × 16384 takes a gigabyte.
+pub(crate) const MAX_PIXELS: usize = 1 << 28;
+
+/// Check the dimensions declared by a file, returning the number of pixels.
+pub(crate) fn pixel_count(width: usize, height: usize) -> Result<usize> {
+    width
+        .checked_mul(height)
+        .filter(|count| *count <= MAX_PIXELS)
+        .ok_or_else(|| Error::resource(format!("Image dimensions {}x{} are too large", width, height)))
+}
+
+/// One frame of an animation.
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    pub image: Image,
+    /// How long the frame is shown.
+    pub delay: Duration,
+}
+
+/// Decode an image file, detecting its format from its contents.
+pub fn decode(data: &[u8]) -> Result<Image> {
+    if data.starts_with(&png::SIGNATURE) {
+        png::decode(data)
+    } else if data.starts_with(b"BM") {
+        bmp::decode(data)
+    } else if data.len() >= 2 && data[0] == b'P' && (b'1'..=b'6').contains(&data[1]) {
+        ppm::decode(data)
+    } else {
+        Err(Error::resource("Unrecognized image format"))
+    }
+}
+
+/// Crop or pad an image with transparency to the given size.
+pub(crate) fn fit_canvas(image: &Image, width: u32, height: u32) -> Image {
+    if (image.width(), image.height()) == (width, height) {
+        return image.clone();
+    }
+    let mut fitted = Image::new(width, height);
+    let columns = width.min(image.width()) as usize * 4;
+    for y in 0..height.min(image.height()) as usize {
+        let source = y * image.width() as usize * 4;
+        let target = y * width as usize * 4;
+        fitted.pixels_mut()[target..target + columns].copy_from_slice(&image.pixels()[source..source + columns]);
+    }
+    fitted
+}
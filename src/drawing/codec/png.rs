@@ -0,0 +1,466 @@
+//! PNG decoding and encoding.
+//!
+//! Supports every standard color type and bit depth, Adam7 interlacing and
+//! `tRNS` transparency. 16-bit samples are reduced to 8 bits. Images are
+//! encoded as 8-bit RGBA with a filter chosen per row.
+
+use super::deflate::zlib_compress;
+use super::inflate::zlib_decompress;
+use super::pixel_count;
+use crate::drawing::Image;
+use crate::{Error, Result};
+
+/// Bytes every PNG file starts with.
+pub const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Adam7 passes as (x start, y start, x step, y step).
+const ADAM7: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/// Image header fields.
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+    interlaced: bool,
+}
+
+impl Header {
+    /// Number of samples per pixel.
+    fn channels(&self) -> usize {
+        match self.color_type {
+            0 | 3 => 1,
+            4 => 2,
+            2 => 3,
+            _ => 4,
+        }
+    }
+
+    /// Bytes in one row of `width` pixels, excluding the filter byte.
+    fn row_bytes(&self, width: usize) -> usize {
+        (width * self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+
+    /// Distance in bytes to the corresponding byte of the previous pixel.
+    fn filter_stride(&self) -> usize {
+        (self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+}
+
+/// Decode a PNG file.
+pub fn decode(data: &[u8]) -> Result<Image> {
+    if !data.starts_with(&SIGNATURE) {
+        return Err(Error::resource("Not a PNG file"));
+    }
+
+    let mut header = None;
+    let mut palette: Vec<[u8; 4]> = Vec::new();
+    let mut transparent: Option<[u16; 3]> = None;
+    let mut compressed = Vec::new();
+    let mut position = SIGNATURE.len();
+
+    loop {
+        let length = read_u32(data, position)? as usize;
+        let kind = data
+            .get(position + 4..position + 8)
+            .ok_or_else(|| Error::resource("PNG chunk is truncated"))?;
+        let body = data
+            .get(position + 8..position + 8 + length)
+            .ok_or_else(|| Error::resource("PNG chunk is truncated"))?;
+        let crc = read_u32(data, position + 8 + length)?;
+        if crc32(&data[position + 4..position + 8 + length]) != crc {
+            return Err(Error::resource("PNG chunk checksum mismatch"));
+        }
+        position += 12 + length;
+
+        match kind {
+            b"IHDR" => header = Some(parse_header(body)?),
+            b"PLTE" => {
+                palette = body.chunks_exact(3).map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+            }
+            b"tRNS" => match header.as_ref().map(|h| h.color_type) {
+                Some(3) => {
+                    for (entry, &alpha) in palette.iter_mut().zip(body) {
+                        entry[3] = alpha;
+                    }
+                }
+                Some(0) if body.len() >= 2 => {
+                    let gray = u16::from_be_bytes([body[0], body[1]]);
+                    transparent = Some([gray; 3]);
+                }
+                Some(2) if body.len() >= 6 => {
+                    let sample = |i: usize| u16::from_be_bytes([body[i], body[i + 1]]);
+                    transparent = Some([sample(0), sample(2), sample(4)]);
+                }
+                _ => {}
+            },
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {
+                // Unknown critical chunks cannot be skipped safely
+                if kind[0] & 0x20 == 0 {
+                    return Err(Error::resource(format!(
+                        "Unsupported critical PNG chunk {}",
+                        String::from_utf8_lossy(kind)
+                    )));
+                }
+            }
+        }
+    }
+
+    let header = header.ok_or_else(|| Error::resource("PNG file has no IHDR chunk"))?;
+    if header.color_type == 3 && palette.is_empty() {
+        return Err(Error::resource("Palette PNG file has no PLTE chunk"));
+    }
+
+    let passes: Vec<(usize, usize, usize, usize)> = if header.interlaced {
+        ADAM7.to_vec()
+    } else {
+        vec![(0, 0, 1, 1)]
+    };
+    let raw = zlib_decompress(&compressed)?;
+    let expected: usize = passes
+        .iter()
+        .map(|&(x0, y0, dx, dy)| {
+            let pass_width = (header.width + dx - 1 - x0) / dx;
+            let pass_height = (header.height + dy - 1 - y0) / dy;
+            if pass_width == 0 || pass_height == 0 { 0 } else { (header.row_bytes(pass_width) + 1) * pass_height }
+        })
+        .sum();
+    if raw.len() < expected {
+        return Err(Error::resource("PNG image data is truncated"));
+    }
+    let mut image = Image::new(header.width as u32, header.height as u32);
+
+    let mut offset = 0;
+    for (x0, y0, dx, dy) in passes {
+        let pass_width = (header.width + dx - 1 - x0) / dx;
+        let pass_height = (header.height + dy - 1 - y0) / dy;
+        if pass_width == 0 || pass_height == 0 {
+            continue;
+        }
+
+        let row_bytes = header.row_bytes(pass_width);
+        let size = (row_bytes + 1) * pass_height;
+        let pass = raw
+            .get(offset..offset + size)
+            .ok_or_else(|| Error::resource("PNG image data is truncated"))?;
+        offset += size;
+
+        let rows = unfilter(pass, row_bytes, header.filter_stride())?;
+        for (row, line) in rows.chunks_exact(row_bytes).enumerate() {
+            for column in 0..pass_width {
+                let rgba = pixel(&header, line, column, &palette, transparent);
+                let (x, y) = (x0 + column * dx, y0 + row * dy);
+                let index = (y * header.width + x) * 4;
+                image.pixels_mut()[index..index + 4].copy_from_slice(&rgba);
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+/// Parse and validate the IHDR chunk.
+fn parse_header(body: &[u8]) -> Result<Header> {
+    if body.len() != 13 {
+        return Err(Error::resource("PNG IHDR chunk has the wrong size"));
+    }
+    let header = Header {
+        width: read_u32(body, 0)? as usize,
+        height: read_u32(body, 4)? as usize,
+        bit_depth: body[8],
+        color_type: body[9],
+        interlaced: body[12] == 1,
+    };
+
+    let valid_depth = match header.color_type {
+        0 => matches!(header.bit_depth, 1 | 2 | 4 | 8 | 16),
+        3 => matches!(header.bit_depth, 1 | 2 | 4 | 8),
+        2 | 4 | 6 => matches!(header.bit_depth, 8 | 16),
+        _ => false,
+    };
+    if !valid_depth {
+        return Err(Error::resource(format!(
+            "Unsupported PNG color type {} with bit depth {}",
+            header.color_type, header.bit_depth
+        )));
+    }
+    if header.width == 0 || header.height == 0 || body[10] != 0 || body[11] != 0 || body[12] > 1 {
+        return Err(Error::resource("Invalid PNG header"));
+    }
+    pixel_count(header.width, header.height)?;
+    Ok(header)
+}
+
+/// Undo the per-row filters of one (sub)image.
+fn unfilter(data: &[u8], row_bytes: usize, stride: usize) -> Result<Vec<u8>> {
+    let mut output = vec![0u8; row_bytes * (data.len() / (row_bytes + 1))];
+    let mut previous = vec![0u8; row_bytes];
+
+    for (row, line) in data.chunks_exact(row_bytes + 1).enumerate() {
+        let (filter, line) = (line[0], &line[1..]);
+        let current = &mut output[row * row_bytes..(row + 1) * row_bytes];
+        for i in 0..row_bytes {
+            let left = if i >= stride { current[i - stride] } else { 0 };
+            let up = previous[i];
+            let up_left = if i >= stride { previous[i - stride] } else { 0 };
+            let predictor = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(Error::resource(format!("Invalid PNG filter type {}", filter))),
+            };
+            current[i] = line[i].wrapping_add(predictor);
+        }
+        previous.copy_from_slice(current);
+    }
+
+    Ok(output)
+}
+
+/// The Paeth predictor.
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let (pa, pb, pc) = ((p - i16::from(a)).abs(), (p - i16::from(b)).abs(), (p - i16::from(c)).abs());
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Read the RGBA8 value of a pixel from an unfiltered row.
+fn pixel(header: &Header, line: &[u8], column: usize, palette: &[[u8; 4]], transparent: Option<[u16; 3]>) -> [u8; 4] {
+    let depth = header.bit_depth as usize;
+    let channels = header.channels();
+    let sample = |channel: usize| -> u16 {
+        let index = column * channels + channel;
+        match depth {
+            16 => u16::from_be_bytes([line[index * 2], line[index * 2 + 1]]),
+            8 => u16::from(line[index]),
+            _ => {
+                let bit = index * depth;
+                let byte = line[bit / 8];
+                let shift = 8 - depth - bit % 8;
+                u16::from(byte >> shift) & ((1 << depth) - 1)
+            }
+        }
+    };
+    let to_u8 = |value: u16| -> u8 {
+        match depth {
+            16 => (value >> 8) as u8,
+            _ => (u32::from(value) * 255 / ((1u32 << depth) - 1)) as u8,
+        }
+    };
+    let alpha_for = |samples: [u16; 3]| if transparent == Some(samples) { 0 } else { 255 };
+
+    match header.color_type {
+        0 => {
+            let gray = sample(0);
+            let value = to_u8(gray);
+            [value, value, value, alpha_for([gray; 3])]
+        }
+        2 => {
+            let rgb = [sample(0), sample(1), sample(2)];
+            [to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]), alpha_for(rgb)]
+        }
+        3 => palette.get(sample(0) as usize).copied().unwrap_or([0, 0, 0, 255]),
+        4 => {
+            let value = to_u8(sample(0));
+            [value, value, value, to_u8(sample(1))]
+        }
+        _ => [to_u8(sample(0)), to_u8(sample(1)), to_u8(sample(2)), to_u8(sample(3))],
+    }
+}
+
+/// Encode an image as an RGBA PNG file.
+pub fn encode(image: &Image) -> Vec<u8> {
+    let mut file = SIGNATURE.to_vec();
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&image.width().to_be_bytes());
+    header.extend_from_slice(&image.height().to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut file, b"IHDR", &header);
+    write_chunk(&mut file, b"IDAT", &zlib_compress(&filter_rows(image)));
+    write_chunk(&mut file, b"IEND", &[]);
+    file
+}
+
+/// Filter each row of an image with the filter that leaves the smallest
+/// sum of absolute differences, prefixing it with the filter type.
+pub(crate) fn filter_rows(image: &Image) -> Vec<u8> {
+    let row_bytes = image.width() as usize * 4;
+    let mut output = Vec::with_capacity((row_bytes + 1) * image.height() as usize);
+    let empty = vec![0u8; row_bytes];
+    let mut candidate = vec![0u8; row_bytes];
+    let mut best = vec![0u8; row_bytes];
+    for (y, row) in image.pixels().chunks_exact(row_bytes.max(1)).enumerate().take(image.height() as usize) {
+        let above = if y == 0 { &empty[..] } else { &image.pixels()[(y - 1) * row_bytes..y * row_bytes] };
+        let mut best_filter = 0;
+        let mut best_cost = u64::MAX;
+        for filter in 0..5u8 {
+            for i in 0..row_bytes {
+                let left = if i >= 4 { row[i - 4] } else { 0 };
+                let upper_left = if i >= 4 { above[i - 4] } else { 0 };
+                let predicted = match filter {
+                    0 => 0,
+                    1 => left,
+                    2 => above[i],
+                    3 => ((u16::from(left) + u16::from(above[i])) / 2) as u8,
+                    _ => paeth(left, above[i], upper_left),
+                };
+                candidate[i] = row[i].wrapping_sub(predicted);
+            }
+            let cost = candidate.iter().map(|value| u64::from((*value as i8).unsigned_abs())).sum();
+            if cost < best_cost {
+                best_cost = cost;
+                best_filter = filter;
+                std::mem::swap(&mut best, &mut candidate);
+            }
+        }
+        output.push(best_filter);
+        output.extend_from_slice(&best);
+    }
+    output
+}
+
+/// Append a chunk with its length and checksum.
+pub(crate) fn write_chunk(file: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    file.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    let start = file.len();
+    file.extend_from_slice(kind);
+    file.extend_from_slice(body);
+    let crc = crc32(&file[start..]);
+    file.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Read a big-endian u32.
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| Error::resource("PNG file is truncated"))
+}
+
+/// Compute the CRC-32 used by PNG chunks.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Color;
+
+    /// Build a PNG file from a header and uncompressed scanlines.
+    fn build_png(width: u32, height: u32, bit_depth: u8, color_type: u8, extra: &[(&[u8; 4], Vec<u8>)], scanlines: &[u8]) -> Vec<u8> {
+        let mut chunks: Vec<(&[u8; 4], Vec<u8>)> = Vec::new();
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[bit_depth, color_type, 0, 0, 0]);
+        chunks.push((b"IHDR", ihdr));
+        chunks.extend(extra.iter().cloned());
+
+        // Store the scanlines in a single uncompressed deflate block
+        let mut zlib = vec![0x78, 0x01, 0x01];
+        zlib.extend_from_slice(&(scanlines.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(scanlines.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(scanlines);
+        let (mut a, mut b) = (1u32, 0u32);
+        for &byte in scanlines {
+            a = (a + u32::from(byte)) % 65521;
+            b = (b + a) % 65521;
+        }
+        zlib.extend_from_slice(&(b << 16 | a).to_be_bytes());
+        chunks.push((b"IDAT", zlib));
+        chunks.push((b"IEND", Vec::new()));
+
+        let mut file = SIGNATURE.to_vec();
+        for (kind, body) in chunks {
+            file.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            let start = file.len();
+            file.extend_from_slice(kind);
+            file.extend_from_slice(&body);
+            let crc = crc32(&file[start..]);
+            file.extend_from_slice(&crc.to_be_bytes());
+        }
+        file
+    }
+
+    #[test]
+    fn test_rgba_with_filters() {
+        // Row 0 uses Sub, row 1 uses Up
+        let scanlines = [
+            1, 255, 0, 0, 255, 1, 0, 0, 0,
+            2, 0, 0, 255, 0, 0, 255, 0, 128,
+        ];
+        let image = decode(&build_png(2, 2, 8, 6, &[], &scanlines)).unwrap();
+
+        assert_eq!(image.pixel(0, 0), Color::RED);
+        assert_eq!(image.pixels()[4..8], [0, 0, 0, 255]);
+        assert_eq!(image.pixels()[8..12], [255, 0, 255, 255]);
+        assert_eq!(image.pixels()[12..16], [0, 255, 0, 127]);
+    }
+
+    #[test]
+    fn test_palette_with_transparency() {
+        let palette = vec![255, 0, 0, 0, 0, 255];
+        let trns = vec![255, 0];
+        // Two 1-bit pixels: index 0 then index 1
+        let scanlines = [0, 0b0100_0000];
+        let image = decode(&build_png(2, 1, 1, 3, &[(b"PLTE", palette), (b"tRNS", trns)], &scanlines)).unwrap();
+
+        assert_eq!(image.pixel(0, 0), Color::RED);
+        assert_eq!(image.pixels()[4..8], [0, 0, 255, 0]);
+    }
+
+    #[test]
+    fn test_rejects_corruption() {
+        let mut file = build_png(1, 1, 8, 0, &[], &[0, 7]);
+        assert_eq!(decode(&file).unwrap().pixels(), &[7, 7, 7, 255]);
+
+        let last = file.len() - 13;
+        file[last] ^= 1;
+        assert!(decode(&file).is_err());
+        assert!(decode(b"GIF89a").is_err());
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let mut image = Image::new(5, 3);
+        for y in 0..3 {
+            for x in 0..5 {
+                image.set_pixel(x, y, Color::rgba8((x * 50) as u8, (y * 90) as u8, 200, (255 - x * 30) as u8));
+            }
+        }
+        let decoded = decode(&encode(&image)).unwrap();
+        assert_eq!(decoded.pixels(), image.pixels());
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        // Tiny files claiming huge images fail before allocating
+        assert!(decode(&build_png(u32::MAX, u32::MAX, 8, 6, &[], &[0, 0, 0, 0, 0])).is_err());
+        assert!(decode(&build_png(16000, 16000, 8, 6, &[], &[0, 0, 0, 0, 0])).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! Netpbm decoding (PBM, PGM and PPM in both ASCII and binary form).
+
+use super::pixel_count;
+use crate::drawing::Image;
+use crate::{Error, Result};
+
+/// Decode a P1 to P6 Netpbm file.
+pub fn decode(data: &[u8]) -> Result<Image> {
+    if data.len() < 2 || data[0] != b'P' || !(b'1'..=b'6').contains(&data[1]) {
+        return Err(Error::resource("Not a PBM, PGM or PPM file"));
+    }
+    let kind = data[1] - b'0';
+    let bitmap = kind == 1 || kind == 4;
+    let channels = if kind == 3 || kind == 6 { 3 } else { 1 };
+
+    let mut reader = Reader { data, position: 2 };
+    let width = reader.number()? as usize;
+    let height = reader.number()? as usize;
+    let max = if bitmap { 1 } else { reader.number()? };
+    if width == 0 || height == 0 || max == 0 || max > 65535 {
+        return Err(Error::resource("Invalid Netpbm header"));
+    }
+
+    let count = pixel_count(width, height)?;
+    // Every sample takes at least one byte, or one bit in binary bitmaps
+    let least_bytes = match kind {
+        4 => width.div_ceil(8) * height,
+        _ => count * channels,
+    };
+    if data.len().saturating_sub(reader.position) < least_bytes {
+        return Err(Error::resource("Netpbm raster is truncated"));
+    }
+    let mut samples: Vec<u32> = Vec::with_capacity(count * channels);
+    if kind <= 3 {
+        for _ in 0..count * channels {
+            // ASCII bitmaps may pack digits without separators
+            let sample = if bitmap { reader.bit()? } else { reader.number()? };
+            samples.push(sample);
+        }
+    } else {
+        // A single whitespace byte separates the header from the raster
+        let raster = data.get(reader.position + 1..).unwrap_or(&[]);
+        if bitmap {
+            let row_bytes = width.div_ceil(8);
+            for y in 0..height {
+                for x in 0..width {
+                    let byte = raster
+                        .get(y * row_bytes + x / 8)
+                        .ok_or_else(|| Error::resource("Netpbm raster is truncated"))?;
+                    samples.push(u32::from(byte >> (7 - x % 8) & 1));
+                }
+            }
+        } else {
+            let wide = max > 255;
+            let sample_bytes = if wide { 2 } else { 1 };
+            let raster = raster
+                .get(..count * channels * sample_bytes)
+                .ok_or_else(|| Error::resource("Netpbm raster is truncated"))?;
+            if wide {
+                samples.extend(raster.chunks_exact(2).map(|b| u32::from(u16::from_be_bytes([b[0], b[1]]))));
+            } else {
+                samples.extend(raster.iter().map(|&b| u32::from(b)));
+            }
+        }
+    }
+
+    let mut image = Image::new(width as u32, height as u32);
+    let scale = |value: u32| (value.min(max) * 255 / max) as u8;
+    for (pixel, sample) in image.pixels_mut().chunks_exact_mut(4).zip(samples.chunks_exact(channels)) {
+        let rgb = if bitmap {
+            // In PBM files 1 means black
+            let value = if sample[0] == 1 { 0 } else { 255 };
+            [value; 3]
+        } else if channels == 1 {
+            [scale(sample[0]); 3]
+        } else {
+            [scale(sample[0]), scale(sample[1]), scale(sample[2])]
+        };
+        pixel.copy_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+    }
+    Ok(image)
+}
+
+/// Tokenizer for Netpbm headers and ASCII rasters.
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    /// Skip whitespace and `#` comments.
+    fn skip_space(&mut self) {
+        while let Some(&byte) = self.data.get(self.position) {
+            if byte == b'#' {
+                while self.data.get(self.position).is_some_and(|&b| b != b'\n') {
+                    self.position += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read a decimal number.
+    fn number(&mut self) -> Result<u32> {
+        self.skip_space();
+        let start = self.position;
+        while self.data.get(self.position).is_some_and(|b| b.is_ascii_digit()) {
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.position])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| Error::resource("Invalid or truncated Netpbm number"))
+    }
+
+    /// Read a single `0` or `1` digit.
+    fn bit(&mut self) -> Result<u32> {
+        self.skip_space();
+        match self.data.get(self.position) {
+            Some(&digit @ (b'0' | b'1')) => {
+                self.position += 1;
+                Ok(u32::from(digit - b'0'))
+            }
+            _ => Err(Error::resource("Invalid or truncated PBM data")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Color;
+
+    #[test]
+    fn test_ascii_formats() {
+        let ppm = decode(b"P3\n# a comment\n2 1\n255\n255 0 0  0 0 255\n").unwrap();
+        assert_eq!(ppm.pixel(0, 0), Color::RED);
+        assert_eq!(ppm.pixel(1, 0), Color::BLUE);
+
+        let pbm = decode(b"P1 3 1 101").unwrap();
+        assert_eq!(pbm.pixel(0, 0), Color::BLACK);
+        assert_eq!(pbm.pixel(1, 0), Color::WHITE);
+    }
+
+    #[test]
+    fn test_binary_formats() {
+        let mut ppm = b"P6 1 1 65535\n".to_vec();
+        ppm.extend_from_slice(&[0xff, 0xff, 0x80, 0x00, 0x00, 0x00]);
+        assert_eq!(decode(&ppm).unwrap().pixels(), &[255, 127, 0, 255]);
+
+        let mut pgm = b"P5 2 1 15\n".to_vec();
+        pgm.extend_from_slice(&[15, 0]);
+        assert_eq!(decode(&pgm).unwrap().pixels(), &[255, 255, 255, 255, 0, 0, 0, 255]);
+
+        assert!(decode(b"P6 2 2 255\n\x00\x00").is_err());
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        // Tiny files claiming huge images fail before allocating
+        assert!(decode(b"P6 4294967295 4294967295 255\n\x00").is_err());
+        assert!(decode(b"P3 16000 16000 255\n0 0 0").is_err());
+        assert!(decode(b"P4 16000 16000\n\x00").is_err());
+    }
+}
@@ -0,0 +1,634 @@
+//! Color spaces and contrast.
+//!
+//! [`Color`] channels are sRGB encoded. This module converts them to and
+//! from linear light, the cylindrical HSL and HSV models, and OKLab and its
+//! polar form OKLCH, in which equal steps look roughly equally large. Hues are
+//! in degrees. Contrast follows WCAG 2: the ratio of the relative luminances
+//! of two colors, from 1:1 up to 21:1 for black on white.
+//!
+//! [`Color::parse`] reads the CSS color syntax: hex notation, `rgb()`,
+//! `hsl()` and the named colors.
+
+use super::Color;
+use crate::{Error, Result};
+
+/// Minimum contrast ratio for body text at WCAG level AA.
+pub const CONTRAST_AA: f32 = 4.5;
+
+/// Minimum contrast ratio for large text at WCAG level AA.
+pub const CONTRAST_AA_LARGE: f32 = 3.0;
+
+/// Minimum contrast ratio for body text at WCAG level AAA.
+pub const CONTRAST_AAA: f32 = 7.0;
+
+/// A color as hue, saturation and lightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees, from 0 up to 360.
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+/// A color as hue, saturation and value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    /// Hue in degrees, from 0 up to 360.
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+/// A color in the OKLab perceptual color space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    /// Perceived lightness, from 0 for black to 1 for white.
+    pub l: f32,
+    /// Green (negative) to red (positive) axis.
+    pub a: f32,
+    /// Blue (negative) to yellow (positive) axis.
+    pub b: f32,
+    pub alpha: f32,
+}
+
+/// A color in OKLCH, the polar form of OKLab.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    /// Perceived lightness, from 0 for black to 1 for white.
+    pub l: f32,
+    /// Colorfulness, 0 for grays and about 0.37 at most within sRGB.
+    pub chroma: f32,
+    /// Hue in degrees, from 0 up to 360.
+    pub hue: f32,
+    pub alpha: f32,
+}
+
+/// Color space two colors are interpolated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorSpace {
+    /// The encoded channels, as CSS and most image editors do by default.
+    Srgb,
+    /// Linear light, which keeps mixtures as bright as physically mixed light.
+    LinearSrgb,
+    /// OKLab, which keeps mixtures perceptually even.
+    #[default]
+    Oklab,
+    /// OKLCH, which keeps mixtures saturated by taking the shorter way round the hue circle.
+    Oklch,
+}
+
+/// Convert an sRGB encoded channel to linear light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light channel to sRGB encoding.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl Color {
+    /// Parse a CSS color.
+    ///
+    /// Accepts `#RGB`, `#RGBA`, `#RRGGBB` and `#RRGGBBAA`, `rgb()`/`rgba()`
+    /// and `hsl()`/`hsla()` in both the comma and the space separated forms,
+    /// and the CSS named colors including `transparent`. Names and function
+    /// names are case insensitive.
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let parsed = if let Some(hex) = value.strip_prefix('#') {
+            parse_hex(hex)
+        } else {
+            let lower = value.to_ascii_lowercase();
+            match lower.split_once('(') {
+                Some((function, arguments)) => {
+                    let arguments = arguments.strip_suffix(')').and_then(color_arguments);
+                    match function.trim() {
+                        "rgb" | "rgba" => arguments.and_then(|arguments| rgb_function(&arguments)),
+                        "hsl" | "hsla" => arguments.and_then(|arguments| hsl_function(&arguments)),
+                        _ => None,
+                    }
+                }
+                None => named(&lower),
+            }
+        };
+        parsed.ok_or_else(|| Error::drawing(format!("Invalid color '{}'", value)))
+    }
+
+    /// Create a color from hue in degrees, saturation and lightness.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Self::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// Create a color from hue in degrees, saturation, lightness and alpha.
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        Hsl { hue, saturation, lightness, alpha }.into()
+    }
+
+    /// Create a color from hue in degrees, saturation and value.
+    pub fn hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        Hsv { hue, saturation, value, alpha: 1.0 }.into()
+    }
+
+    /// Create a color from OKLCH lightness, chroma and hue in degrees.
+    ///
+    /// Colors outside sRGB are clipped channel by channel.
+    pub fn oklch(l: f32, chroma: f32, hue: f32) -> Self {
+        Oklch { l, chroma, hue, alpha: 1.0 }.into()
+    }
+
+    /// Convert to hue, saturation and lightness.
+    pub fn to_hsl(self) -> Hsl {
+        self.into()
+    }
+
+    /// Convert to hue, saturation and value.
+    pub fn to_hsv(self) -> Hsv {
+        self.into()
+    }
+
+    /// Convert to OKLab.
+    pub fn to_oklab(self) -> Oklab {
+        self.into()
+    }
+
+    /// Convert to OKLCH.
+    pub fn to_oklch(self) -> Oklch {
+        self.into()
+    }
+
+    /// Get the color channels in linear light, with alpha unchanged.
+    pub fn to_linear(self) -> (f32, f32, f32, f32) {
+        (srgb_to_linear(self.r), srgb_to_linear(self.g), srgb_to_linear(self.b), self.a)
+    }
+
+    /// Create a color from linear light channels and alpha.
+    pub fn from_linear(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self::rgba(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a)
+    }
+
+    /// Interpolate towards `other` in the given color space.
+    ///
+    /// `t` of 0 gives this color and 1 gives `other`. Alpha is interpolated
+    /// linearly and the color channels are weighted by it, so mixing with a
+    /// transparent color does not pull in its hidden color.
+    pub fn interpolate(self, other: Color, t: f32, space: ColorSpace) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let alpha = lerp(self.a, other.a, t);
+        // Premultiplied interpolation; fully transparent ends contribute only their alpha
+        let (weight_self, weight_other) = match (self.a > 0.0, other.a > 0.0) {
+            (true, true) => (self.a * (1.0 - t) / alpha, other.a * t / alpha),
+            (true, false) => (1.0, 0.0),
+            (false, true) => (0.0, 1.0),
+            (false, false) => (1.0 - t, t),
+        };
+        let mix = |from: f32, to: f32| from * weight_self + to * weight_other;
+        match space {
+            ColorSpace::Srgb => Color::rgba(mix(self.r, other.r), mix(self.g, other.g), mix(self.b, other.b), alpha),
+            ColorSpace::LinearSrgb => {
+                let (r1, g1, b1, _) = self.to_linear();
+                let (r2, g2, b2, _) = other.to_linear();
+                Color::from_linear(mix(r1, r2), mix(g1, g2), mix(b1, b2), alpha)
+            }
+            ColorSpace::Oklab => {
+                let (from, to) = (self.to_oklab(), other.to_oklab());
+                Oklab { l: mix(from.l, to.l), a: mix(from.a, to.a), b: mix(from.b, to.b), alpha }.into()
+            }
+            ColorSpace::Oklch => {
+                let (mut from, mut to) = (self.to_oklch(), other.to_oklch());
+                // Grays have no hue of their own, so they take the other one
+                if from.chroma < ACHROMATIC {
+                    from.hue = to.hue;
+                }
+                if to.chroma < ACHROMATIC {
+                    to.hue = from.hue;
+                }
+                let mut delta = (to.hue - from.hue).rem_euclid(360.0);
+                if delta > 180.0 {
+                    delta -= 360.0;
+                }
+                Oklch {
+                    l: mix(from.l, to.l),
+                    chroma: mix(from.chroma, to.chroma),
+                    hue: from.hue + delta * weight_other,
+                    alpha,
+                }
+                .into()
+            }
+        }
+    }
+
+    /// Mix with another color in OKLab; `t` of 0.5 gives the perceptual midpoint.
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        self.interpolate(other, t, ColorSpace::Oklab)
+    }
+
+    /// Composite another color over this one in linear light.
+    ///
+    /// Unlike [`blend_with`](Color::blend_with), a half transparent white over
+    /// black comes out as bright as the two lights mixed.
+    pub fn blend_linear(self, other: Color) -> Color {
+        let alpha = other.a + self.a * (1.0 - other.a);
+        if alpha <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        let (r1, g1, b1, _) = self.to_linear();
+        let (r2, g2, b2, _) = other.to_linear();
+        let over = |back: f32, front: f32| (front * other.a + back * self.a * (1.0 - other.a)) / alpha;
+        Color::from_linear(over(r1, r2), over(g1, g2), over(b1, b2), alpha)
+    }
+
+    /// Change the OKLab lightness by `delta`, keeping hue and chroma.
+    ///
+    /// Themes use this to derive hover and pressed shades that look evenly
+    /// spaced whatever the base color.
+    pub fn adjust_lightness(self, delta: f32) -> Color {
+        let mut lch = self.to_oklch();
+        lch.l = (lch.l + delta).clamp(0.0, 1.0);
+        lch.into()
+    }
+
+    /// Get the WCAG relative luminance, from 0 for black to 1 for white.
+    pub fn relative_luminance(self) -> f32 {
+        let (r, g, b, _) = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Get the WCAG contrast ratio with another color, from 1 up to 21.
+    ///
+    /// Alpha is ignored; composite translucent colors over their background first.
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let (l1, l2) = (self.relative_luminance(), other.relative_luminance());
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Check whether text in this color on `background` reaches a contrast ratio.
+    pub fn meets_contrast(self, background: Color, ratio: f32) -> bool {
+        self.contrast_ratio(background) >= ratio
+    }
+
+    /// Pick black or white, whichever contrasts more with this color.
+    pub fn contrasting_text(self) -> Color {
+        if Color::BLACK.contrast_ratio(self) >= Color::WHITE.contrast_ratio(self) {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        }
+    }
+}
+
+/// Parse hex digits without the leading `#`.
+pub(crate) fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).ok().map(|value| value * 17);
+    let pair = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    let (r, g, b, a) = match hex.len() {
+        3 => (digit(0)?, digit(1)?, digit(2)?, 255),
+        4 => (digit(0)?, digit(1)?, digit(2)?, digit(3)?),
+        6 => (pair(0)?, pair(2)?, pair(4)?, 255),
+        8 => (pair(0)?, pair(2)?, pair(4)?, pair(6)?),
+        _ => return None,
+    };
+    Some(Color::rgba8(r, g, b, a))
+}
+
+/// Split the arguments of a color function into three components and an alpha.
+///
+/// Both `1, 2, 3, 0.5` and `1 2 3 / 0.5` are accepted.
+fn color_arguments(arguments: &str) -> Option<Vec<&str>> {
+    let (components, alpha) = match arguments.split_once('/') {
+        Some((components, alpha)) => (components, Some(alpha.trim())),
+        None => (arguments, None),
+    };
+    let mut parts: Vec<&str> = components.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()).collect();
+    if let Some(alpha) = alpha {
+        if parts.len() != 3 || alpha.is_empty() {
+            return None;
+        }
+        parts.push(alpha);
+    }
+    matches!(parts.len(), 3 | 4).then_some(parts)
+}
+
+/// Parse a number or percentage, where 100% equals `full`.
+///
+/// `none` counts as zero, as in CSS Color 4.
+fn component(value: &str, full: f32) -> Option<f32> {
+    if value == "none" {
+        return Some(0.0);
+    }
+    let number = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f32>().ok()? / 100.0 * full,
+        None => value.parse::<f32>().ok()?,
+    };
+    number.is_finite().then_some(number)
+}
+
+fn alpha_component(arguments: &[&str]) -> Option<f32> {
+    arguments.get(3).map_or(Some(1.0), |alpha| component(alpha, 1.0))
+}
+
+fn rgb_function(arguments: &[&str]) -> Option<Color> {
+    let channel = |index: usize| component(arguments[index], 255.0).map(|value| value / 255.0);
+    Some(Color::rgba(channel(0)?, channel(1)?, channel(2)?, alpha_component(arguments)?))
+}
+
+fn hsl_function(arguments: &[&str]) -> Option<Color> {
+    let hue = arguments[0];
+    let hue = if let Some(turns) = hue.strip_suffix("turn") {
+        turns.parse::<f32>().ok()? * 360.0
+    } else if let Some(gradians) = hue.strip_suffix("grad") {
+        gradians.parse::<f32>().ok()? * 0.9
+    } else if let Some(radians) = hue.strip_suffix("rad") {
+        radians.parse::<f32>().ok()?.to_degrees()
+    } else {
+        match hue.strip_suffix("deg").unwrap_or(hue) {
+            "none" => 0.0,
+            degrees => degrees.parse::<f32>().ok()?,
+        }
+    };
+    // Saturation and lightness are percentages; bare numbers count as percent
+    let percent = |index: usize| component(arguments[index].trim_end_matches('%'), 0.0).map(|value| value / 100.0);
+    Some(Color::hsla(hue, percent(1)?, percent(2)?, alpha_component(arguments)?))
+}
+
+/// Look up a lowercase CSS color name.
+fn named(name: &str) -> Option<Color> {
+    if name == "transparent" {
+        return Some(Color::TRANSPARENT);
+    }
+    let index = NAMED_COLORS.binary_search_by(|(candidate, _)| candidate.cmp(&name)).ok()?;
+    let value = NAMED_COLORS[index].1;
+    Some(Color::rgb8((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+/// The CSS named colors, sorted by name.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff), ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4), ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff), ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed), ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b), ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc), ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3), ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969), ("dimgrey", 0x696969), ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0), ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080), ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082), ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5), ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6), ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2), ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3), ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00), ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000), ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3), ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee), ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc), ("mediumvioletred", 0xc71585), ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1), ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead),
+    ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000), ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6), ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee), ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9), ("peru", 0xcd853f), ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd), ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xff0000), ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xfffafa), ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4), ("tan", 0xd2b48c), ("teal", 0x008080), ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347), ("turquoise", 0x40e0d0), ("violet", 0xee82ee), ("wheat", 0xf5deb3),
+    ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+/// Chroma below which an OKLCH color is treated as gray.
+const ACHROMATIC: f32 = 1e-4;
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Hue in degrees and the largest and smallest channel of an RGB color.
+fn hue_and_range(color: Color) -> (f32, f32, f32) {
+    let (r, g, b) = (color.r, color.g, color.b);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, max, min)
+}
+
+/// The RGB color with a hue, chroma and offset added to every channel.
+fn from_hue(hue: f32, chroma: f32, offset: f32, alpha: f32) -> Color {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    Color::rgba(r + offset, g + offset, b + offset, alpha)
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let (hue, max, min) = hue_and_range(color);
+        let lightness = (max + min) / 2.0;
+        let saturation = if max - min <= 0.0 {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Hsl { hue, saturation, lightness, alpha: color.a }
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let saturation = hsl.saturation.clamp(0.0, 1.0);
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        from_hue(hsl.hue, chroma, lightness - chroma / 2.0, hsl.alpha)
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let (hue, max, min) = hue_and_range(color);
+        let saturation = if max <= 0.0 { 0.0 } else { (max - min) / max };
+        Hsv { hue, saturation, value: max, alpha: color.a }
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let value = hsv.value.clamp(0.0, 1.0);
+        let chroma = value * hsv.saturation.clamp(0.0, 1.0);
+        from_hue(hsv.hue, chroma, value - chroma, hsv.alpha)
+    }
+}
+
+impl From<Color> for Oklab {
+    fn from(color: Color) -> Self {
+        let (r, g, b, alpha) = color.to_linear();
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        Oklab {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+            alpha,
+        }
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(lab: Oklab) -> Self {
+        let l = lab.l + 0.396_337_78 * lab.a + 0.215_803_76 * lab.b;
+        let m = lab.l - 0.105_561_346 * lab.a - 0.063_854_17 * lab.b;
+        let s = lab.l - 0.089_484_18 * lab.a - 1.291_485_5 * lab.b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+        Color::from_linear(
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+            lab.alpha,
+        )
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        let chroma = (lab.a * lab.a + lab.b * lab.b).sqrt();
+        let hue = if chroma < ACHROMATIC { 0.0 } else { lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0) };
+        Oklch { l: lab.l, chroma, hue, alpha: lab.alpha }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        let (sin, cos) = lch.hue.to_radians().sin_cos();
+        Oklab { l: lch.l, a: lch.chroma * cos, b: lch.chroma * sin, alpha: lch.alpha }
+    }
+}
+
+impl From<Color> for Oklch {
+    fn from(color: Color) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Color {
+    fn from(lch: Oklch) -> Self {
+        Oklab::from(lch).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Color, expected: Color) {
+        let channels = [(actual.r, expected.r), (actual.g, expected.g), (actual.b, expected.b), (actual.a, expected.a)];
+        assert!(channels.iter().all(|(a, e)| (a - e).abs() < 0.002), "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn test_hsl_and_hsv() {
+        assert_close(Color::hsl(0.0, 1.0, 0.5), Color::RED);
+        assert_close(Color::hsl(120.0, 1.0, 0.25), Color::rgb(0.0, 0.5, 0.0));
+        assert_close(Color::hsv(240.0, 1.0, 1.0), Color::BLUE);
+        let color = Color::rgba(0.2, 0.6, 0.9, 0.5);
+        let hsl = color.to_hsl();
+        assert!((hsl.hue - 205.714).abs() < 0.01);
+        assert_close(hsl.into(), color);
+        assert_close(color.to_hsv().into(), color);
+        assert_eq!(Color::GRAY.to_hsl().saturation, 0.0);
+    }
+
+    #[test]
+    fn test_oklab() {
+        let white = Color::WHITE.to_oklab();
+        assert!((white.l - 1.0).abs() < 0.001 && white.a.abs() < 0.001 && white.b.abs() < 0.001);
+        let red = Color::RED.to_oklch();
+        assert!((red.l - 0.628).abs() < 0.001 && (red.chroma - 0.2577).abs() < 0.001 && (red.hue - 29.23).abs() < 0.05);
+        for color in [Color::rgb(0.2, 0.6, 0.9), Color::YELLOW, Color::DARK_GRAY] {
+            assert_close(color.to_oklch().into(), color);
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        assert!((srgb_to_linear(linear_to_srgb(0.3)) - 0.3).abs() < 1e-5);
+        assert_close(Color::BLACK.interpolate(Color::WHITE, 0.5, ColorSpace::Srgb), Color::gray(0.5));
+        assert_close(Color::BLACK.interpolate(Color::WHITE, 0.5, ColorSpace::LinearSrgb), Color::gray(0.7354));
+        // Perceptual midpoint of black and white has OKLab lightness 0.5
+        assert!((Color::BLACK.mix(Color::WHITE, 0.5).to_oklab().l - 0.5).abs() < 0.002);
+        // Transparent ends do not tint the result
+        assert_close(Color::RED.interpolate(Color::TRANSPARENT, 0.5, ColorSpace::Srgb), Color::RED.with_alpha(0.5));
+        // OKLCH goes the short way from red to blue, through magenta rather than green
+        let middle = Color::RED.interpolate(Color::BLUE, 0.5, ColorSpace::Oklch);
+        assert!(middle.g < 0.1 && middle.r > 0.5 && middle.b > 0.5);
+        assert_close(Color::BLACK.blend_linear(Color::WHITE.with_alpha(0.5)), Color::gray(0.7354));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Color::parse("#f00").unwrap(), Color::RED);
+        assert_eq!(Color::parse("#00ff0080").unwrap(), Color::rgba8(0, 255, 0, 128));
+        assert_eq!(Color::parse("#0f08").unwrap(), Color::rgba8(0, 255, 0, 136));
+        assert_eq!(Color::parse("rgb(0, 128, 255)").unwrap(), Color::rgb8(0, 128, 255));
+        assert_eq!(Color::parse("RGBA(100%, 0%, 0%, 0.5)").unwrap(), Color::rgba(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(Color::parse("rgb(0 0 255 / 25%)").unwrap(), Color::BLUE.with_alpha(0.25));
+        assert_close(Color::parse("hsl(120, 100%, 25%)").unwrap(), Color::rgb(0.0, 0.5, 0.0));
+        assert_close(Color::parse("hsla(0.5turn 100% 50% / 0.5)").unwrap(), Color::CYAN.with_alpha(0.5));
+        assert_eq!(Color::parse(" RebeccaPurple ").unwrap(), Color::rgb8(0x66, 0x33, 0x99));
+        assert_eq!(Color::parse("transparent").unwrap(), Color::TRANSPARENT);
+        for invalid in ["", "#12", "#ggg", "rgb(1, 2)", "rgb(1 2 3 4 5)", "hsl(0, 0%, 0%", "hsl(50%, 0%, 0%)", "notacolor", "url(#a)"] {
+            assert!(Color::parse(invalid).is_err(), "{}", invalid);
+        }
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_contrast() {
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 0.01);
+        assert!((Color::RED.contrast_ratio(Color::RED) - 1.0).abs() < 1e-6);
+        assert!(Color::rgb8(0x76, 0x76, 0x76).meets_contrast(Color::WHITE, CONTRAST_AA));
+        assert!(!Color::rgb8(0x77, 0x77, 0x77).meets_contrast(Color::WHITE, CONTRAST_AA));
+        assert_eq!(Color::YELLOW.contrasting_text(), Color::BLACK);
+        assert_eq!(Color::BLUE.contrasting_text(), Color::WHITE);
+        let base = Color::rgb(0.2, 0.4, 0.6);
+        let hover = base.adjust_lightness(0.1);
+        assert!((hover.to_oklab().l - base.to_oklab().l - 0.1).abs() < 0.005);
+        assert!((hover.to_oklch().hue - base.to_oklch().hue).abs() < 1.0);
+    }
+}
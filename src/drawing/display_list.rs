@@ -0,0 +1,620 @@
+//! Retained drawing with dirty-rectangle repainting.
+//!
+//! A [`DisplayList`] records the device-space calls a canvas makes, each
+//! with the clip it was drawn under and the pixels it can touch. Comparing
+//! the lists of two frames gives the regions that changed, and
+//! [`RetainedRenderer`] replays and presents only those regions.
+
+use super::{BlendMode, Canvas, Clip, Color, CornerRadii, FillRule, Image, ImageFilter, LineJoin, Paint, Path, Point, Rect, StrokeStyle, TextMetrics, Transform};
+use crate::platform::DrawingContext;
+use crate::Result;
+use std::sync::{Arc, Mutex};
+
+/// Extent assumed above the baseline of platform text that cannot be measured.
+const TEXT_ASCENT: f32 = 16.0;
+
+/// Extent assumed below the baseline of platform text that cannot be measured.
+const TEXT_DESCENT: f32 = 6.0;
+
+/// Width assumed per character of platform text that cannot be measured.
+const TEXT_ADVANCE: f32 = 10.0;
+
+/// Margin added around every item for antialiased edges.
+const ANTIALIAS_MARGIN: f32 = 1.0;
+
+/// Number of damaged rectangles above which they are merged into one.
+const MAX_DAMAGE_RECTS: usize = 8;
+
+/// A recorded drawing call, in device coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayCommand {
+    Clear(Color),
+    FillRect(Rect, Color),
+    StrokeRect(Rect, Color, StrokeStyle),
+    Text(String, Point, Color),
+    TextTransformed(String, Transform, Color),
+    Image(Image, Rect, Transform, ImageFilter),
+    Layer(Image, Point, f32, BlendMode),
+    BlurRegion(Rect, f32),
+    FillPath(Path, Color, FillRule),
+    StrokePath(Path, Color, StrokeStyle),
+    FillPathPaint(Path, Paint, FillRule),
+    StrokePathPaint(Path, Paint, StrokeStyle),
+    FillRoundedRect(Rect, CornerRadii, Color),
+    StrokeRoundedRect(Rect, CornerRadii, Color, StrokeStyle),
+    FillEllipse(Point, f32, f32, Color),
+    StrokeEllipse(Point, f32, f32, Color, StrokeStyle),
+}
+
+/// A recorded call with the clip it was drawn under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayItem {
+    /// The drawing call.
+    pub command: DisplayCommand,
+    /// Clip active when the call was made.
+    pub clip: Option<Clip>,
+    /// Whole-pixel area the call can touch.
+    pub bounds: Rect,
+}
+
+/// Recorded drawing calls in paint order.
+///
+/// Clones share their items, so cached lists are cheap to hand out.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DisplayList {
+    items: Arc<Vec<DisplayItem>>,
+}
+
+impl DisplayList {
+    /// Create an empty display list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record what `draw` paints on a canvas of the given device size.
+    ///
+    /// Platform text is bounded by assumed metrics; use
+    /// [`DisplayList::record_for`] to measure it as the target draws it.
+    pub fn record(size: (u32, u32), draw: impl FnOnce(&mut Canvas) -> Result<()>) -> Result<DisplayList> {
+        Self::record_measured(size, |_| None, draw)
+    }
+
+    /// Record what `draw` paints for replay on `target`, bounding platform
+    /// text by the target's own text measurement.
+    pub fn record_for(target: &Canvas, draw: impl FnOnce(&mut Canvas) -> Result<()>) -> Result<DisplayList> {
+        let size = target.size();
+        let size = (size.width as u32, size.height as u32);
+        Self::record_measured(size, |text| target.measure_platform_text(text), draw)
+    }
+
+    fn record_measured(
+        size: (u32, u32),
+        measure: impl Fn(&str) -> Option<TextMetrics>,
+        draw: impl FnOnce(&mut Canvas) -> Result<()>,
+    ) -> Result<DisplayList> {
+        let items = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Recorder {
+            items: items.clone(),
+            clip: None,
+            size,
+        };
+        let mut canvas = Canvas::new(Box::new(recorder));
+        draw(&mut canvas)?;
+        drop(canvas);
+        let mut items = std::mem::take(&mut *items.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+        // Text is bounded here, where the recorder can ask the target to measure it
+        items.retain_mut(|item| {
+            let area = match item.command {
+                DisplayCommand::Text(ref text, position, _) => {
+                    Transform::translate(position.x, position.y).transform_rect(text_area(text, measure(text)))
+                }
+                DisplayCommand::TextTransformed(ref text, transform, _) => transform.transform_rect(text_area(text, measure(text))),
+                _ => return true,
+            };
+            match visible_bounds(size, item.clip.as_ref(), area) {
+                Some(bounds) => {
+                    item.bounds = bounds;
+                    true
+                }
+                None => false,
+            }
+        });
+        Ok(DisplayList { items: Arc::new(items) })
+    }
+
+    /// The recorded items.
+    pub fn items(&self) -> &[DisplayItem] {
+        &self.items
+    }
+
+    /// Check if nothing was drawn.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Area covered by all items, if any.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.items.iter().map(|item| item.bounds).reduce(Rect::union)
+    }
+
+    /// Area that differs between `previous` and this list.
+    ///
+    /// Items shared at the start and end of both lists are skipped, so a
+    /// single added, removed or changed item only damages its own bounds.
+    pub fn damage(&self, previous: &DisplayList) -> Option<Rect> {
+        if Arc::ptr_eq(&self.items, &previous.items) {
+            return None;
+        }
+        let (old, new) = (previous.items.as_slice(), self.items.as_slice());
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        old[prefix..old.len() - suffix]
+            .iter()
+            .chain(&new[prefix..new.len() - suffix])
+            .map(|item| item.bounds)
+            .reduce(Rect::union)
+    }
+
+    /// Replay the items that touch `region`, clipped to it.
+    pub(crate) fn replay(&self, context: &mut dyn DrawingContext, region: Rect) -> Result<()> {
+        let mut active: Option<Clip> = None;
+        for item in self.items.iter().filter(|item| item.bounds.intersects_with(region)) {
+            let clip = match item.clip {
+                Some(ref clip) => clip.intersect_rect(region),
+                None => Clip::rect(region),
+            };
+            if clip.is_empty() {
+                continue;
+            }
+            if active.as_ref() != Some(&clip) {
+                context.set_clip(Some(&clip))?;
+                active = Some(clip);
+            }
+            replay_command(context, &item.command, item.bounds)?;
+        }
+        if active.is_some() {
+            context.set_clip(None)?;
+        }
+        Ok(())
+    }
+}
+
+/// Send one recorded call to a context.
+fn replay_command(context: &mut dyn DrawingContext, command: &DisplayCommand, bounds: Rect) -> Result<()> {
+    match command {
+        // Clearing ignores the clip on most backends, so it is filled instead
+        DisplayCommand::Clear(color) => context.fill_rect(bounds.x, bounds.y, bounds.width, bounds.height, color.to_tuple()),
+        DisplayCommand::FillRect(rect, color) => context.fill_rect(rect.x, rect.y, rect.width, rect.height, color.to_tuple()),
+        DisplayCommand::StrokeRect(rect, color, stroke) => {
+            context.stroke_rect(rect.x, rect.y, rect.width, rect.height, color.to_tuple(), stroke)
+        }
+        DisplayCommand::Text(text, position, color) => context.draw_text(text, position.x, position.y, color.to_tuple()),
+        DisplayCommand::TextTransformed(text, transform, color) => context.draw_text_transformed(text, *transform, color.to_tuple()),
+        DisplayCommand::Image(image, src, transform, filter) => context.draw_image(image, *src, *transform, *filter),
+        DisplayCommand::Layer(layer, origin, opacity, blend_mode) => context.draw_layer(layer, *origin, *opacity, *blend_mode),
+        DisplayCommand::BlurRegion(region, radius) => context.blur_region(*region, *radius),
+        DisplayCommand::FillPath(path, color, fill_rule) => context.fill_path(path, color.to_tuple(), *fill_rule),
+        DisplayCommand::StrokePath(path, color, stroke) => context.stroke_path(path, color.to_tuple(), stroke),
+        DisplayCommand::FillPathPaint(path, paint, fill_rule) => context.fill_path_paint(path, paint, *fill_rule),
+        DisplayCommand::StrokePathPaint(path, paint, stroke) => context.stroke_path_paint(path, paint, stroke),
+        DisplayCommand::FillRoundedRect(rect, radii, color) => context.fill_rounded_rect(*rect, *radii, color.to_tuple()),
+        DisplayCommand::StrokeRoundedRect(rect, radii, color, stroke) => {
+            context.stroke_rounded_rect(*rect, *radii, color.to_tuple(), stroke)
+        }
+        DisplayCommand::FillEllipse(center, rx, ry, color) => context.fill_ellipse(center.x, center.y, *rx, *ry, color.to_tuple()),
+        DisplayCommand::StrokeEllipse(center, rx, ry, color, stroke) => {
+            context.stroke_ellipse(center.x, center.y, *rx, *ry, color.to_tuple(), stroke)
+        }
+    }
+}
+
+/// Drawing context that appends calls to a display list.
+struct Recorder {
+    items: Arc<Mutex<Vec<DisplayItem>>>,
+    clip: Option<Clip>,
+    size: (u32, u32),
+}
+
+impl Recorder {
+    /// Record a call touching `area`, unless the clip or surface hides it.
+    fn push(&mut self, command: DisplayCommand, area: Rect) -> Result<()> {
+        let Some(bounds) = visible_bounds(self.size, self.clip.as_ref(), area) else {
+            return Ok(());
+        };
+        self.push_item(command, bounds);
+        Ok(())
+    }
+
+    /// Record text, which is bounded once the list has been recorded.
+    fn push_text(&mut self, command: DisplayCommand) -> Result<()> {
+        self.push_item(command, Rect::default());
+        Ok(())
+    }
+
+    fn push_item(&mut self, command: DisplayCommand, bounds: Rect) {
+        let item = DisplayItem {
+            command,
+            clip: self.clip.clone(),
+            bounds,
+        };
+        self.items.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(item);
+    }
+}
+
+impl DrawingContext for Recorder {
+    fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+        let surface = Rect::new(0.0, 0.0, self.size.0 as f32, self.size.1 as f32);
+        self.push(DisplayCommand::Clear(color_of(color)), surface)
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        let rect = Rect::new(x, y, width, height);
+        self.push(DisplayCommand::FillRect(rect, color_of(color)), rect)
+    }
+
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let rect = Rect::new(x, y, width, height);
+        let area = rect.expand(stroke_extent(stroke));
+        self.push(DisplayCommand::StrokeRect(rect, color_of(color), stroke.clone()), area)
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.push_text(DisplayCommand::Text(text.to_string(), Point::new(x, y), color_of(color)))
+    }
+
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.push_text(DisplayCommand::TextTransformed(text.to_string(), transform, color_of(color)))
+    }
+
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        let area = transform.transform_rect(src);
+        self.push(DisplayCommand::Image(image.clone(), src, transform, filter), area)
+    }
+
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let area = Rect::new(origin.x, origin.y, layer.width() as f32, layer.height() as f32);
+        self.push(DisplayCommand::Layer(layer.clone(), origin, opacity, blend_mode), area)
+    }
+
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        self.push(DisplayCommand::BlurRegion(region, radius), region)
+    }
+
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        self.push(DisplayCommand::FillPath(path.clone(), color_of(color), fill_rule), path.bounds())
+    }
+
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let area = path.bounds().expand(stroke_extent(stroke));
+        self.push(DisplayCommand::StrokePath(path.clone(), color_of(color), stroke.clone()), area)
+    }
+
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        self.push(DisplayCommand::FillPathPaint(path.clone(), paint.clone(), fill_rule), path.bounds())
+    }
+
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        let area = path.bounds().expand(stroke_extent(stroke));
+        self.push(DisplayCommand::StrokePathPaint(path.clone(), paint.clone(), stroke.clone()), area)
+    }
+
+    fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.push(DisplayCommand::FillRoundedRect(rect, radii, color_of(color)), rect)
+    }
+
+    fn stroke_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let area = rect.expand(stroke_extent(stroke));
+        self.push(DisplayCommand::StrokeRoundedRect(rect, radii, color_of(color), stroke.clone()), area)
+    }
+
+    fn fill_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        let area = Rect::new(cx - radius_x, cy - radius_y, radius_x * 2.0, radius_y * 2.0);
+        self.push(DisplayCommand::FillEllipse(Point::new(cx, cy), radius_x, radius_y, color_of(color)), area)
+    }
+
+    fn stroke_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let area = Rect::new(cx - radius_x, cy - radius_y, radius_x * 2.0, radius_y * 2.0).expand(stroke_extent(stroke));
+        let command = DisplayCommand::StrokeEllipse(Point::new(cx, cy), radius_x, radius_y, color_of(color), stroke.clone());
+        self.push(command, area)
+    }
+
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        self.clip = clip.cloned();
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+/// Canvas of the given size that records into a throwaway list.
+#[cfg(test)]
+pub(crate) fn test_canvas(size: (u32, u32)) -> Canvas {
+    Canvas::new(Box::new(Recorder {
+        items: Arc::default(),
+        clip: None,
+        size,
+    }))
+}
+
+/// Convert a context color tuple back to a color.
+fn color_of(color: (f32, f32, f32, f32)) -> Color {
+    Color::rgba(color.0, color.1, color.2, color.3)
+}
+
+/// Distance a stroke can reach past the outline it follows.
+fn stroke_extent(stroke: &StrokeStyle) -> f32 {
+    let reach = match stroke.join {
+        LineJoin::Miter => stroke.miter_limit.max(std::f32::consts::SQRT_2),
+        _ => std::f32::consts::SQRT_2,
+    };
+    stroke.width / 2.0 * reach
+}
+
+/// Area of platform text relative to its baseline origin, as measured by
+/// the target or assumed when it cannot measure.
+fn text_area(text: &str, metrics: Option<TextMetrics>) -> Rect {
+    match metrics {
+        Some(metrics) => Rect::new(0.0, -metrics.ascent, metrics.width, metrics.height()),
+        None => {
+            let width = text.chars().count() as f32 * TEXT_ADVANCE;
+            Rect::new(0.0, -TEXT_ASCENT, width, TEXT_ASCENT + TEXT_DESCENT)
+        }
+    }
+}
+
+/// Pixels of `area` left visible by `clip` on a surface of `size`.
+fn visible_bounds(size: (u32, u32), clip: Option<&Clip>, area: Rect) -> Option<Rect> {
+    let surface = Rect::new(0.0, 0.0, size.0 as f32, size.1 as f32);
+    let visible = match clip {
+        Some(clip) => clip.bounds().intersection(surface),
+        None => Some(surface),
+    };
+    visible.and_then(|visible| pixel_bounds(area).intersection(visible))
+}
+
+/// Round an area out to whole pixels, with room for antialiasing.
+fn pixel_bounds(area: Rect) -> Rect {
+    let area = area.expand(ANTIALIAS_MARGIN);
+    let left = area.x.floor();
+    let top = area.y.floor();
+    Rect::new(left, top, area.right().ceil() - left, area.bottom().ceil() - top)
+}
+
+/// Regions of the surface that need repainting.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Damage {
+    rects: Vec<Rect>,
+}
+
+impl Damage {
+    /// Create empty damage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a region, merging it with the regions it overlaps.
+    pub fn add(&mut self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        let mut rect = rect;
+        while let Some(index) = self.rects.iter().position(|other| other.intersects_with(rect)) {
+            rect = rect.union(self.rects.swap_remove(index));
+        }
+        self.rects.push(rect);
+        if self.rects.len() > MAX_DAMAGE_RECTS {
+            let all = self.rects.drain(..).reduce(Rect::union);
+            self.rects.extend(all);
+        }
+    }
+
+    /// The damaged regions, which do not overlap.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Check if nothing needs repainting.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Total damaged area.
+    pub fn area(&self) -> f32 {
+        self.rects.iter().map(|rect| rect.area()).sum()
+    }
+}
+
+/// Paints frames made of keyed display lists, repainting only what changed.
+///
+/// Each layer is identified by a key, such as a widget ID, and is compared
+/// with the layer of the same key from the previous frame.
+#[derive(Debug, Clone)]
+pub struct RetainedRenderer {
+    background: Color,
+    layers: Vec<(u64, DisplayList)>,
+    size: Option<(u32, u32)>,
+}
+
+impl RetainedRenderer {
+    /// Create a renderer that clears damaged regions to `background`.
+    pub fn new(background: Color) -> Self {
+        Self {
+            background,
+            layers: Vec::new(),
+            size: None,
+        }
+    }
+
+    /// Get the background color.
+    pub fn background(&self) -> Color {
+        self.background
+    }
+
+    /// Set the background color, repainting everything on the next frame.
+    pub fn set_background(&mut self, background: Color) {
+        if background != self.background {
+            self.background = background;
+            self.invalidate();
+        }
+    }
+
+    /// Forget the previous frame so the next one is painted in full.
+    pub fn invalidate(&mut self) {
+        self.size = None;
+    }
+
+    /// The layers of the last painted frame.
+    pub fn layers(&self) -> &[(u64, DisplayList)] {
+        &self.layers
+    }
+
+    /// Compute the damage between the last painted frame and `layers`.
+    pub fn damage(&self, layers: &[(u64, DisplayList)], size: (u32, u32)) -> Damage {
+        let mut damage = Damage::new();
+        let same_keys = self.layers.len() == layers.len()
+            && self.layers.iter().zip(layers).all(|(old, new)| old.0 == new.0);
+        if self.size != Some(size) {
+            damage.add(Rect::new(0.0, 0.0, size.0 as f32, size.1 as f32));
+        } else if same_keys {
+            for ((_, old), (_, new)) in self.layers.iter().zip(layers) {
+                if let Some(rect) = new.damage(old) {
+                    damage.add(rect);
+                }
+            }
+        } else {
+            // Layers were added, removed or restacked, so everything they cover changed
+            for (_, list) in self.layers.iter().chain(layers) {
+                if let Some(rect) = list.bounds() {
+                    damage.add(rect);
+                }
+            }
+        }
+        damage
+    }
+
+    /// Paint a frame, replaying and presenting only its damaged regions.
+    pub fn paint(&mut self, canvas: &mut Canvas, layers: Vec<(u64, DisplayList)>) -> Result<Damage> {
+        let size = canvas.size();
+        let size = (size.width as u32, size.height as u32);
+        let damage = self.damage(&layers, size);
+        if !damage.is_empty() {
+            for &rect in damage.rects() {
+                canvas.fill_region(rect, self.background)?;
+                for (_, list) in &layers {
+                    canvas.draw_display_list(list, rect)?;
+                }
+            }
+            canvas.present_rects(damage.rects())?;
+        }
+        self.layers = layers;
+        self.size = Some(size);
+        Ok(damage)
+    }
+}
+
+impl Default for RetainedRenderer {
+    fn default() -> Self {
+        Self::new(Color::WHITE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Font;
+
+    fn record(draw: impl FnOnce(&mut Canvas) -> Result<()>) -> DisplayList {
+        DisplayList::record((200, 100), draw).unwrap()
+    }
+
+    #[test]
+    fn test_records_device_space_items() {
+        let list = record(|canvas| {
+            canvas.translate(10.0, 10.0);
+            canvas.fill_rect(Rect::new(0.0, 0.0, 20.0, 10.0), Color::RED)?;
+            canvas.clip_rect(Rect::new(0.0, 0.0, 5.0, 5.0));
+            canvas.fill_rect(Rect::new(0.0, 0.0, 20.0, 10.0), Color::BLUE)?;
+            canvas.fill_rect(Rect::new(500.0, 0.0, 20.0, 10.0), Color::BLUE)
+        });
+        assert_eq!(list.items().len(), 2);
+        assert_eq!(list.items()[0].command, DisplayCommand::FillRect(Rect::new(10.0, 10.0, 20.0, 10.0), Color::RED));
+        assert_eq!(list.items()[0].bounds, Rect::new(9.0, 9.0, 22.0, 12.0));
+        assert_eq!(list.items()[1].bounds, Rect::new(10.0, 10.0, 5.0, 5.0));
+        assert_eq!(list.bounds(), Some(Rect::new(9.0, 9.0, 22.0, 12.0)));
+    }
+
+    #[test]
+    fn test_damage_covers_only_changed_items() {
+        let frame = |caret: bool| {
+            record(move |canvas| {
+                canvas.fill_rect(Rect::new(0.0, 0.0, 200.0, 30.0), Color::WHITE)?;
+                if caret {
+                    canvas.fill_rect(Rect::new(50.0, 5.0, 1.0, 20.0), Color::BLACK)?;
+                }
+                canvas.stroke_rect(Rect::new(0.0, 0.0, 200.0, 30.0), Color::BLACK, 1.0)
+            })
+        };
+        assert_eq!(frame(true).damage(&frame(true)), None);
+        assert_eq!(frame(false).damage(&frame(true)), Some(Rect::new(49.0, 4.0, 3.0, 22.0)));
+        assert_eq!(frame(true).damage(&DisplayList::new()), frame(true).bounds());
+    }
+
+    #[test]
+    fn test_damage_merges_overlapping_rects() {
+        let mut damage = Damage::new();
+        damage.add(Rect::new(0.0, 0.0, 10.0, 10.0));
+        damage.add(Rect::new(20.0, 0.0, 10.0, 10.0));
+        damage.add(Rect::new(5.0, 5.0, 20.0, 2.0));
+        assert_eq!(damage.rects(), &[Rect::new(0.0, 0.0, 30.0, 10.0)]);
+        for i in 0..MAX_DAMAGE_RECTS {
+            damage.add(Rect::new(0.0, 20.0 + i as f32 * 10.0, 5.0, 5.0));
+        }
+        assert_eq!(damage.rects().len(), 1);
+    }
+
+    #[test]
+    fn test_renderer_repaints_damage_only() {
+        let mut renderer = RetainedRenderer::default();
+        let layers = |x: f32| {
+            vec![
+                (1, record(|canvas| canvas.fill_rect(Rect::new(0.0, 0.0, 100.0, 100.0), Color::BLUE))),
+                (2, record(move |canvas| canvas.fill_rect(Rect::new(x, 10.0, 2.0, 2.0), Color::RED))),
+            ]
+        };
+        assert_eq!(renderer.damage(&layers(10.0), (200, 100)).area(), 200.0 * 100.0);
+        renderer.layers = layers(10.0);
+        renderer.size = Some((200, 100));
+        assert!(renderer.damage(&layers(10.0), (200, 100)).is_empty());
+        let damage = renderer.damage(&layers(20.0), (200, 100));
+        assert_eq!(damage.rects(), &[Rect::new(9.0, 9.0, 14.0, 4.0)]);
+        let reordered = layers(10.0).into_iter().rev().collect::<Vec<_>>();
+        assert_eq!(renderer.damage(&reordered, (200, 100)).rects(), &[Rect::new(0.0, 0.0, 101.0, 100.0)]);
+    }
+
+    #[test]
+    fn test_measures_text_through_target() {
+        let mut target = crate::drawing::RenderTarget::new(crate::drawing::Size::new(200.0, 100.0));
+        let canvas = target.canvas();
+        let text = "Measured by the target";
+        let list = DisplayList::record_for(canvas, |canvas| {
+            canvas.draw_text(text, Point::new(10.0, 50.0), &Font::system(), 14.0, Color::BLACK)
+        })
+        .unwrap();
+        let area = Transform::translate(10.0, 50.0).transform_rect(text_area(text, canvas.measure_platform_text(text)));
+        assert_eq!(list.items()[0].bounds, visible_bounds((200, 100), None, area).unwrap());
+        let assumed = record(|canvas| canvas.draw_text(text, Point::new(10.0, 50.0), &Font::system(), 14.0, Color::BLACK));
+        let area = Transform::translate(10.0, 50.0).transform_rect(text_area(text, None));
+        assert_eq!(assumed.items()[0].bounds, visible_bounds((200, 100), None, area).unwrap());
+    }
+}
@@ -0,0 +1,294 @@
+//! Drop shadows and blur.
+//!
+//! Gaussian blur is approximated by three successive box blurs, each done
+//! as a horizontal and a vertical pass with a running sum, so the cost does
+//! not depend on the radius. As in CSS, a blur radius `r` blurs with a
+//! standard deviation of `r / 2`.
+
+use super::raster::Mask;
+use super::{Color, FillRule, Image, LineJoin, Path, Point, Rect, StrokeStyle};
+
+/// Box blurs used to approximate a Gaussian.
+const BOX_PASSES: usize = 3;
+
+/// Largest blur radius applied; larger ones look the same on any screen.
+pub(crate) const MAX_BLUR_RADIUS: f32 = 250.0;
+
+/// Shadow cast by a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    /// Offset of the shadow from the shape.
+    pub offset: Point,
+    /// How far the shadow edge is blurred.
+    pub blur_radius: f32,
+    /// How far the shadow grows (or, if negative, shrinks) before blurring.
+    pub spread: f32,
+    /// Shadow color.
+    pub color: Color,
+}
+
+impl Shadow {
+    /// Create a shadow without spread.
+    pub fn new(offset: Point, blur_radius: f32, color: Color) -> Self {
+        Self {
+            offset,
+            blur_radius: blur_radius.clamp(0.0, MAX_BLUR_RADIUS),
+            spread: 0.0,
+            color,
+        }
+    }
+
+    /// Set the spread.
+    pub fn spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Area covered by the shadow of a shape with the given bounds.
+    pub fn bounds(&self, shape: Rect) -> Rect {
+        let reach = self.spread.max(0.0) + blur_extent(self.blur_radius);
+        let grown = shape.expand(reach);
+        Rect::new(grown.x + self.offset.x, grown.y + self.offset.y, grown.width, grown.height)
+    }
+}
+
+/// Distance past an edge at which a blur of `radius` fades out.
+fn blur_extent(radius: f32) -> f32 {
+    // Three standard deviations
+    radius.clamp(0.0, MAX_BLUR_RADIUS) * 1.5
+}
+
+/// Render the part of the shadow of a device-space path that falls inside
+/// the device-space `visible` area as an image, with the device position of
+/// its top-left pixel.
+pub(crate) fn shadow_image(path: &Path, offset: Point, blur_radius: f32, spread: f32, color: Color, visible: Rect) -> Option<(Image, Point)> {
+    let blur_radius = blur_radius.clamp(0.0, MAX_BLUR_RADIUS);
+    let pad = (spread.max(0.0) + blur_extent(blur_radius)).ceil() + 1.0;
+    // Shape pixels further than the blur from the visible area cannot reach it
+    let offset = Point::new(offset.x.round(), offset.y.round());
+    let reach = Rect::new(visible.x - offset.x, visible.y - offset.y, visible.width, visible.height).expand(blur_extent(blur_radius).ceil() + 1.0);
+    let bounds = path.bounds().expand(pad).intersection(reach)?;
+    let left = bounds.x.floor();
+    let top = bounds.y.floor();
+    let area = Rect::new(left, top, (bounds.right().ceil() - left).max(0.0), (bounds.bottom().ceil() - top).max(0.0));
+    let (width, height) = (area.width as usize, area.height as usize);
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut coverage = vec![0.0f32; width * height];
+    let mut apply = |mask: Option<Mask>, combine: &dyn Fn(f32, f32) -> f32| {
+        let Some(mask) = mask else {
+            return;
+        };
+        for row in 0..mask.height {
+            for column in 0..mask.width {
+                let x = (mask.x - left as i32) as usize + column;
+                let y = (mask.y - top as i32) as usize + row;
+                let value = &mut coverage[y * width + x];
+                *value = combine(*value, mask.get(column, row));
+            }
+        }
+    };
+    apply(Mask::from_path(path, FillRule::NonZero, area), &|_, fill| fill);
+    if spread != 0.0 {
+        // Grow by adding a stroke along the outline, or shrink by removing it
+        let stroke = StrokeStyle::new(spread.abs() * 2.0).join(LineJoin::Round);
+        let grow = spread > 0.0;
+        apply(Mask::from_stroke(path, &stroke, area), &move |value, edge| {
+            if grow { value.max(edge) } else { (value - edge).max(0.0) }
+        });
+    }
+    gaussian_blur(&mut coverage, width, height, blur_radius / 2.0, false);
+
+    let mut image = Image::new(width as u32, height as u32);
+    for (pixel, value) in image.pixels_mut().chunks_exact_mut(4).zip(&coverage) {
+        pixel.copy_from_slice(&super::image::color_to_rgba8(color.with_alpha(color.a * value)));
+    }
+    Some((image, Point::new(left + offset.x, top + offset.y)))
+}
+
+/// Blur the part of an image under the device-space `region`, where the
+/// image's top-left pixel sits at `origin`.
+///
+/// Pixels outside the region are not read, so its edges are extended.
+pub(crate) fn blur_region(image: &mut Image, origin: (i32, i32), region: Rect, radius: f32) {
+    let sigma = radius.min(MAX_BLUR_RADIUS) / 2.0;
+    let left = (region.x.floor() as i32 - origin.0).max(0);
+    let top = (region.y.floor() as i32 - origin.1).max(0);
+    let right = (region.right().ceil() as i32 - origin.0).min(image.width() as i32);
+    let bottom = (region.bottom().ceil() as i32 - origin.1).min(image.height() as i32);
+    if sigma <= 0.0 || right <= left || bottom <= top {
+        return;
+    }
+
+    // Blur premultiplied channels so transparent pixels do not darken edges
+    let (width, height) = ((right - left) as usize, (bottom - top) as usize);
+    let mut channels = vec![vec![0.0f32; width * height]; 4];
+    for row in 0..height {
+        for column in 0..width {
+            let color = image.pixel((left as usize + column) as u32, (top as usize + row) as u32);
+            let i = row * width + column;
+            channels[0][i] = color.r * color.a;
+            channels[1][i] = color.g * color.a;
+            channels[2][i] = color.b * color.a;
+            channels[3][i] = color.a;
+        }
+    }
+    for channel in &mut channels {
+        gaussian_blur(channel, width, height, sigma, true);
+    }
+    for row in 0..height {
+        for column in 0..width {
+            let i = row * width + column;
+            let alpha = channels[3][i];
+            let color = if alpha > 0.0 {
+                Color::rgba(channels[0][i] / alpha, channels[1][i] / alpha, channels[2][i] / alpha, alpha)
+            } else {
+                Color::TRANSPARENT
+            };
+            image.set_pixel((left as usize + column) as u32, (top as usize + row) as u32, color);
+        }
+    }
+}
+
+/// Approximate a Gaussian blur of a single channel.
+///
+/// Samples outside the buffer are zero, or repeat the nearest edge sample
+/// when `clamp_edges` is set.
+pub(crate) fn gaussian_blur(values: &mut [f32], width: usize, height: usize, sigma: f32, clamp_edges: bool) {
+    if sigma <= 0.0 || width == 0 || height == 0 {
+        return;
+    }
+    let mut scratch = vec![0.0f32; values.len()];
+    for radius in box_radii(sigma) {
+        for row in 0..height {
+            box_blur_line(values, &mut scratch, row * width, 1, width, radius, clamp_edges);
+        }
+        for column in 0..width {
+            box_blur_line(&scratch, values, column, width, height, radius, clamp_edges);
+        }
+    }
+}
+
+/// Radii of the box blurs whose combination matches a Gaussian of `sigma`.
+fn box_radii(sigma: f32) -> [usize; BOX_PASSES] {
+    let n = BOX_PASSES as f32;
+    let ideal = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut lower = ideal.floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let lower = lower.max(1);
+    let l = lower as f32;
+    let small = ((12.0 * sigma * sigma - n * l * l - 4.0 * n * l - 3.0 * n) / (-4.0 * l - 4.0)).round().max(0.0) as usize;
+    let mut radii = [0; BOX_PASSES];
+    for (i, radius) in radii.iter_mut().enumerate() {
+        let size = if i < small { lower } else { lower + 2 };
+        *radius = (size as usize - 1) / 2;
+    }
+    radii
+}
+
+/// Box blur `len` samples starting at `start` and `stride` apart.
+fn box_blur_line(src: &[f32], dst: &mut [f32], start: usize, stride: usize, len: usize, radius: usize, clamp_edges: bool) {
+    let sample = |i: isize| -> f32 {
+        if i >= 0 && (i as usize) < len {
+            src[start + i as usize * stride]
+        } else if clamp_edges {
+            src[start + (i.clamp(0, len as isize - 1) as usize) * stride]
+        } else {
+            0.0
+        }
+    };
+    let r = radius as isize;
+    let scale = 1.0 / (2 * radius + 1) as f64;
+    // Sum the first window without visiting samples past either end, which
+    // all equal the nearest edge sample or zero
+    let inside = radius.min(len - 1);
+    let mut sum: f64 = (0..=inside as isize).map(|i| f64::from(sample(i))).sum();
+    if clamp_edges {
+        sum += radius as f64 * f64::from(sample(0)) + (radius - inside) as f64 * f64::from(sample(len as isize - 1));
+    }
+    for i in 0..len as isize {
+        dst[start + i as usize * stride] = (sum * scale) as f32;
+        sum += f64::from(sample(i + r + 1)) - f64::from(sample(i - r));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Rect = Rect { x: 0.0, y: 0.0, width: 100.0, height: 100.0 };
+
+    #[test]
+    fn test_blur_preserves_mass() {
+        let mut values = vec![0.0f32; 41 * 41];
+        values[20 * 41 + 20] = 1.0;
+        gaussian_blur(&mut values, 41, 41, 3.0, false);
+        let total: f32 = values.iter().sum();
+        assert!((total - 1.0).abs() < 1e-3);
+
+        // Symmetric and peaked at the center
+        assert!((values[20 * 41 + 17] - values[20 * 41 + 23]).abs() < 1e-6);
+        assert!(values[20 * 41 + 20] > values[20 * 41 + 24]);
+        assert!(values[20 * 41 + 20] > values[24 * 41 + 20]);
+
+        // A constant image stays constant when edges are clamped
+        let mut flat = vec![0.5f32; 10 * 4];
+        gaussian_blur(&mut flat, 10, 4, 2.0, true);
+        assert!(flat.iter().all(|v| (v - 0.5).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_shadow_image() {
+        let path = Path::rect(Rect::new(10.0, 10.0, 20.0, 20.0));
+        let (sharp, origin) = shadow_image(&path, Point::new(2.0, 3.0), 0.0, 0.0, Color::BLACK, SCREEN).unwrap();
+        assert_eq!(origin, Point::new(11.0, 12.0));
+        assert_eq!(sharp.pixel(10, 10), Color::BLACK);
+        assert_eq!(sharp.pixel(0, 0).a, 0.0);
+
+        let (soft, origin) = shadow_image(&path, Point::zero(), 8.0, 0.0, Color::BLACK, SCREEN).unwrap();
+        let center = (20.0 - origin.x) as u32;
+        let edge = (10.0 - origin.x) as u32;
+        assert!(soft.pixel(center, center).a > 0.95);
+        assert!((soft.pixel(edge, center).a - 0.5).abs() < 0.1);
+        assert!(soft.pixel(1, 1).a < 0.05);
+
+        let (grown, origin) = shadow_image(&path, Point::zero(), 0.0, 4.0, Color::BLACK, SCREEN).unwrap();
+        let x = (7.0 - origin.x) as u32;
+        assert_eq!(grown.pixel(x, (20.0 - origin.y) as u32).a, 1.0);
+        let (shrunk, origin) = shadow_image(&path, Point::zero(), 0.0, -4.0, Color::BLACK, SCREEN).unwrap();
+        assert_eq!(shrunk.pixel((12.0 - origin.x) as u32, (20.0 - origin.y) as u32).a, 0.0);
+    }
+
+    #[test]
+    fn test_shadow_bounds() {
+        let shadow = Shadow::new(Point::new(0.0, 4.0), 8.0, Color::BLACK).spread(2.0);
+        assert_eq!(shadow.bounds(Rect::new(0.0, 0.0, 10.0, 10.0)), Rect::new(-14.0, -10.0, 38.0, 38.0));
+    }
+
+    #[test]
+    fn test_huge_shadows_and_blurs() {
+        // Only the visible part of a huge shadow is rendered
+        let huge = Path::rect(Rect::new(-20000.0, -20000.0, 40000.0, 40000.0));
+        let (image, origin) = shadow_image(&huge, Point::zero(), 4.0, 0.0, Color::BLACK, Rect::new(0.0, 0.0, 50.0, 50.0)).unwrap();
+        assert!(image.width() < 100 && image.height() < 100);
+        assert_eq!(image.pixel((10.0 - origin.x) as u32, (10.0 - origin.y) as u32), Color::BLACK);
+
+        // Huge blur radii are clamped
+        let small = Path::rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        let (image, _) = shadow_image(&small, Point::zero(), 1e5, 0.0, Color::BLACK, SCREEN).unwrap();
+        assert!(image.width() < 1000);
+        assert!(shadow_image(&small, Point::new(500.0, 0.0), 0.0, 0.0, Color::BLACK, SCREEN).is_none());
+
+        // A radius far past the line length averages it without visiting every sample
+        let mut flat = vec![0.25f32; 50 * 50];
+        gaussian_blur(&mut flat, 50, 50, 5e8, true);
+        assert!(flat.iter().all(|v| (v - 0.25).abs() < 1e-4));
+        let mut image = Image::filled(50, 50, Color::RED);
+        blur_region(&mut image, (0, 0), Rect::new(0.0, 0.0, 50.0, 50.0), 1e9);
+        assert_eq!(image.pixel(25, 25), Color::RED);
+    }
+}
@@ -0,0 +1,141 @@
+//! Vector export.
+//!
+//! [`SvgDocument`] and [`PdfDocument`] own a [`Canvas`](super::Canvas) whose
+//! drawing context serializes every call instead of rasterizing it, so a
+//! widget tree or custom drawing rendered into them comes out as resolution
+//! independent paths, text and embedded images. Loaded fonts are embedded:
+//! SVG files carry the outlines of the glyphs used and PDF files the font
+//! file itself.
+
+pub mod svg;
+pub mod pdf;
+
+pub use svg::SvgDocument;
+pub use pdf::PdfDocument;
+
+use super::{Image, Path, PathElement, Point, Rect, Transform};
+
+/// Size in pixels assumed for text drawn with the platform font.
+const SYSTEM_FONT_SIZE: f32 = 13.0;
+
+/// Format a number with at most three decimals and no trailing zeros.
+fn number(value: f32) -> String {
+    if !value.is_finite() {
+        return "0".to_string();
+    }
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" | "" => "0".to_string(),
+        _ => text.to_string(),
+    }
+}
+
+/// Format the six components of a transform.
+fn matrix(t: Transform) -> String {
+    [t.a, t.b, t.c, t.d, t.tx, t.ty].map(number).join(" ")
+}
+
+/// Encode bytes as base64.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let value = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// Copy the whole-pixel area of an image covering `src`, returning the copy
+/// and the pixel position of its top-left corner.
+fn crop(image: &Image, src: Rect) -> Option<(Image, Point)> {
+    let left = src.x.floor().max(0.0) as u32;
+    let top = src.y.floor().max(0.0) as u32;
+    let right = (src.right().ceil() as u32).min(image.width());
+    let bottom = (src.bottom().ceil() as u32).min(image.height());
+    if right <= left || bottom <= top {
+        return None;
+    }
+    let origin = Point::new(left as f32, top as f32);
+    if (left, top, right, bottom) == (0, 0, image.width(), image.height()) {
+        return Some((image.clone(), origin));
+    }
+    let mut cropped = Image::new(right - left, bottom - top);
+    let row_bytes = (right - left) as usize * 4;
+    for row in 0..bottom - top {
+        let start = (((top + row) * image.width() + left) * 4) as usize;
+        let target = (row * (right - left) * 4) as usize;
+        cropped.pixels_mut()[target..target + row_bytes].copy_from_slice(&image.pixels()[start..start + row_bytes]);
+    }
+    Some((cropped, origin))
+}
+
+/// Walk a path with cubic curves in place of quadratic ones, as PDF has no
+/// quadratic curve operator.
+fn cubic_elements(path: &Path) -> Vec<PathElement> {
+    let mut elements = Vec::with_capacity(path.elements().len());
+    let mut start = Point::zero();
+    let mut current = Point::zero();
+    for element in path.elements() {
+        match *element {
+            PathElement::MoveTo(p) => {
+                start = p;
+                current = p;
+                elements.push(*element);
+            }
+            PathElement::LineTo(p) | PathElement::CubicTo(_, _, p) => {
+                current = p;
+                elements.push(*element);
+            }
+            PathElement::QuadTo(c, p) => {
+                let c1 = Point::new(current.x + 2.0 / 3.0 * (c.x - current.x), current.y + 2.0 / 3.0 * (c.y - current.y));
+                let c2 = Point::new(p.x + 2.0 / 3.0 * (c.x - p.x), p.y + 2.0 / 3.0 * (c.y - p.y));
+                elements.push(PathElement::CubicTo(c1, c2, p));
+                current = p;
+            }
+            PathElement::Close => {
+                current = start;
+                elements.push(*element);
+            }
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_format() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(-0.0001), "0");
+        assert_eq!(number(2.50), "2.5");
+        assert_eq!(number(1.23456), "1.235");
+        assert_eq!(number(f32::NAN), "0");
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_quadratic_to_cubic() {
+        let mut path = Path::new();
+        path.move_to(Point::new(0.0, 0.0)).quad_to(Point::new(3.0, 3.0), Point::new(6.0, 0.0));
+        let elements = cubic_elements(&path);
+        assert_eq!(elements[1], PathElement::CubicTo(Point::new(2.0, 2.0), Point::new(4.0, 2.0), Point::new(6.0, 0.0)));
+    }
+}
@@ -0,0 +1,818 @@
+//! PDF export.
+//!
+//! One device pixel is one PDF point (1/72 inch), so an A4 page is
+//! 595 × 842. Pages share their fonts, images and other resources. Text in
+//! the platform font is set in Helvetica; loaded TrueType fonts are embedded
+//! whole and their glyphs shown by index, with a map back to the characters
+//! so the text can be searched and copied. Gradients become shadings, which
+//! cannot repeat or reflect and whose stop opacities are averaged; image
+//! patterns become tiling patterns.
+
+use super::{crop, cubic_elements, matrix, number, SYSTEM_FONT_SIZE};
+use crate::drawing::codec::deflate::zlib_compress;
+use crate::drawing::path::DEFAULT_TOLERANCE;
+use crate::drawing::{Canvas, Clip, Color, FillRule, Font, GlyphPosition, GradientStop, Image, ImageFilter, ImagePattern, LineCap, LineJoin, Paint, Path, PathElement, Rect, Size, StrokeStyle, Transform};
+use crate::platform::DrawingContext;
+use crate::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A PDF document of one or more pages that can be drawn on with a [`Canvas`].
+pub struct PdfDocument {
+    output: Arc<Mutex<PdfOutput>>,
+    canvas: Canvas,
+}
+
+impl PdfDocument {
+    /// Create a document with one empty page of the given size in points.
+    pub fn new(page_size: Size) -> Self {
+        let size = (page_size.width.max(0.0).ceil() as u32, page_size.height.max(0.0).ceil() as u32);
+        let output = Arc::new(Mutex::new(PdfOutput {
+            page_size: size,
+            ..PdfOutput::default()
+        }));
+        let canvas = Canvas::new(Box::new(PdfContext { output: output.clone(), size }));
+        Self { output, canvas }
+    }
+
+    /// The canvas drawing into the current page.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Get the page size in points.
+    pub fn page_size(&self) -> Size {
+        self.canvas.size()
+    }
+
+    /// Finish the current page and start drawing on a new one.
+    ///
+    /// The canvas is replaced, so its transform and clip start afresh.
+    pub fn new_page(&mut self) {
+        let size = {
+            let mut output = self.lock();
+            let content = std::mem::take(&mut output.content);
+            output.pages.push(content);
+            output.clip.clear();
+            output.page_size
+        };
+        self.canvas = Canvas::new(Box::new(PdfContext { output: self.output.clone(), size }));
+    }
+
+    /// Get the number of pages, including the one being drawn.
+    pub fn page_count(&self) -> usize {
+        self.lock().pages.len() + 1
+    }
+
+    /// Serialize the document.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.lock().finish()
+    }
+
+    /// Write the document to a file.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PdfOutput> {
+        lock(&self.output)
+    }
+}
+
+fn lock(output: &Mutex<PdfOutput>) -> MutexGuard<'_, PdfOutput> {
+    output.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A loaded font embedded in the document.
+struct EmbeddedFont {
+    font: Font,
+    /// Advance of each glyph used, in thousandths of an em.
+    widths: BTreeMap<u16, f32>,
+    /// Character each glyph used stands for.
+    chars: BTreeMap<u16, char>,
+    /// Character mapping to each glyph of the font, built on first use.
+    cmap: Option<HashMap<u16, char>>,
+}
+
+impl EmbeddedFont {
+    /// Record the advance and character of a glyph shown with the font.
+    fn use_glyph(&mut self, glyph: u16) {
+        if self.widths.contains_key(&glyph) {
+            return;
+        }
+        self.widths.insert(glyph, self.font.advance(glyph, 1000.0));
+        let cmap = self.cmap.get_or_insert_with(|| glyph_chars(&self.font));
+        if let Some(&ch) = cmap.get(&glyph) {
+            self.chars.insert(glyph, ch);
+        }
+    }
+}
+
+/// Pages and resources written so far.
+#[derive(Default)]
+struct PdfOutput {
+    page_size: (u32, u32),
+    /// Content streams of finished pages.
+    pages: Vec<String>,
+    /// Content stream of the current page.
+    content: String,
+    /// Operators setting the current clip.
+    clip: String,
+    uses_standard_font: bool,
+    fonts: Vec<EmbeddedFont>,
+    images: Vec<(Image, bool)>,
+    /// Opacities with a graphics state, in 1/255 steps.
+    opacities: Vec<u8>,
+    shadings: Vec<String>,
+    /// Tiling patterns, as the index of their image and its transform.
+    patterns: Vec<(usize, Transform)>,
+}
+
+impl PdfOutput {
+    /// Operators selecting a graphics state with the given opacity, if it is not opaque.
+    fn opacity(&mut self, alpha: f32) -> String {
+        let alpha = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if alpha == 255 {
+            return String::new();
+        }
+        let index = match self.opacities.iter().position(|existing| *existing == alpha) {
+            Some(index) => index,
+            None => {
+                self.opacities.push(alpha);
+                self.opacities.len() - 1
+            }
+        };
+        format!("/GS{} gs ", index)
+    }
+
+    fn fill(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) {
+        let operator = if fill_rule == FillRule::EvenOdd { "f*" } else { "f" };
+        match paint {
+            Paint::LinearGradient(_) | Paint::RadialGradient(_) => {
+                let clip = if fill_rule == FillRule::EvenOdd { "W* n" } else { "W n" };
+                self.shade(path, clip, paint);
+            }
+            Paint::Pattern(pattern) => self.tile(path, operator, pattern),
+            Paint::Solid(_) => {
+                let color = paint.average_color();
+                let state = self.opacity(color.a);
+                let _ = writeln!(self.content, "q {}{} rg\n{}{}\nQ", state, rgb(color), path_operators(path), operator);
+            }
+        }
+    }
+
+    fn stroke(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) {
+        if let Paint::LinearGradient(_) | Paint::RadialGradient(_) = paint {
+            let outline = stroke.outline(path, DEFAULT_TOLERANCE);
+            self.shade(&outline, "W n", paint);
+            return;
+        }
+        if let Paint::Pattern(pattern) = paint {
+            let outline = stroke.outline(path, DEFAULT_TOLERANCE);
+            self.tile(&outline, "f", pattern);
+            return;
+        }
+        let color = paint.average_color();
+        let state = self.opacity(color.a);
+        let cap = match stroke.cap {
+            LineCap::Butt => 0,
+            LineCap::Round => 1,
+            LineCap::Square => 2,
+        };
+        let join = match stroke.join {
+            LineJoin::Miter => 0,
+            LineJoin::Round => 1,
+            LineJoin::Bevel => 2,
+        };
+        let dashes: Vec<String> = stroke.dash_pattern().into_iter().map(number).collect();
+        let _ = writeln!(
+            self.content,
+            "q {}{} RG {} w {} J {} j {} M [{}] {} d\n{}S\nQ",
+            state,
+            rgb(color),
+            number(stroke.width),
+            cap,
+            join,
+            number(stroke.miter_limit),
+            dashes.join(" "),
+            number(stroke.dash_offset),
+            path_operators(path)
+        );
+    }
+
+    /// Paint a gradient through a path used as a clip.
+    fn shade(&mut self, path: &Path, clip: &str, paint: &Paint) {
+        let (dictionary, stops, transform) = match paint {
+            Paint::LinearGradient(gradient) => (
+                format!(
+                    "/ShadingType 2 /Coords [{} {} {} {}]",
+                    number(gradient.start.x),
+                    number(gradient.start.y),
+                    number(gradient.end.x),
+                    number(gradient.end.y)
+                ),
+                &gradient.stops,
+                gradient.transform,
+            ),
+            Paint::RadialGradient(gradient) => (
+                format!(
+                    "/ShadingType 3 /Coords [{0} {1} 0 {0} {1} {2}]",
+                    number(gradient.center.x),
+                    number(gradient.center.y),
+                    number(gradient.radius)
+                ),
+                &gradient.stops,
+                gradient.transform,
+            ),
+            _ => return,
+        };
+        let Some(function) = stop_function(stops) else {
+            return;
+        };
+        let alpha = stops.iter().map(|stop| stop.color.a).sum::<f32>() / stops.len() as f32;
+        self.shadings.push(format!("<< {} /ColorSpace /DeviceRGB /Function {} /Extend [true true] >>", dictionary, function));
+        let index = self.shadings.len() - 1;
+        let state = self.opacity(alpha);
+        let _ = writeln!(self.content, "q {}\n{}{}\n{} cm /Sh{} sh\nQ", state, path_operators(path), clip, matrix(transform), index);
+    }
+
+    /// Paint a path with an image repeated in every direction.
+    fn tile(&mut self, path: &Path, operator: &str, pattern: &ImagePattern) {
+        if pattern.image.width() == 0 || pattern.image.height() == 0 {
+            return;
+        }
+        self.images.push(((*pattern.image).clone(), false));
+        self.patterns.push((self.images.len() - 1, pattern.transform));
+        let index = self.patterns.len() - 1;
+        let _ = writeln!(self.content, "q /Pattern cs /P{} scn\n{}{}\nQ", index, path_operators(path), operator);
+    }
+
+    /// Get the resource index of an embedded font, adding it if needed.
+    fn font(&mut self, font: &Font) -> usize {
+        match self.fonts.iter().position(|embedded| embedded.font.id() == font.id()) {
+            Some(index) => index,
+            None => {
+                self.fonts.push(EmbeddedFont {
+                    font: font.clone(),
+                    widths: BTreeMap::new(),
+                    chars: BTreeMap::new(),
+                    cmap: None,
+                });
+                self.fonts.len() - 1
+            }
+        }
+    }
+
+    fn finish(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        let catalog = writer.reserve();
+        let pages = writer.reserve();
+        let resources = writer.reserve();
+
+        // Resources shared by every page
+        let mut fonts = String::new();
+        if self.uses_standard_font {
+            let helvetica = writer.object("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>", None);
+            let _ = write!(fonts, "/F0 {} 0 R ", helvetica);
+        }
+        for (index, embedded) in self.fonts.iter().enumerate() {
+            let reference = write_font(&mut writer, index, embedded);
+            let _ = write!(fonts, "/F{} {} 0 R ", index + 1, reference);
+        }
+        let mut images = String::new();
+        let mut image_references = Vec::new();
+        for (index, (image, interpolate)) in self.images.iter().enumerate() {
+            let reference = write_image(&mut writer, image, *interpolate);
+            let _ = write!(images, "/Im{} {} 0 R ", index, reference);
+            image_references.push(reference);
+        }
+        let mut states = String::new();
+        for (index, alpha) in self.opacities.iter().enumerate() {
+            let alpha = number(*alpha as f32 / 255.0);
+            let reference = writer.object(&format!("<< /Type /ExtGState /ca {0} /CA {0} >>", alpha), None);
+            let _ = write!(states, "/GS{} {} 0 R ", index, reference);
+        }
+        let mut shadings = String::new();
+        for (index, shading) in self.shadings.iter().enumerate() {
+            let reference = writer.object(shading, None);
+            let _ = write!(shadings, "/Sh{} {} 0 R ", index, reference);
+        }
+        // Pattern space is the image's pixel space, placed on the unflipped page
+        let (width, height) = self.page_size;
+        let flip = Transform { a: 1.0, b: 0.0, c: 0.0, d: -1.0, tx: 0.0, ty: height as f32 };
+        let mut patterns = String::new();
+        for (index, (image, transform)) in self.patterns.iter().enumerate() {
+            let (tile_width, tile_height) = (self.images[*image].0.width(), self.images[*image].0.height());
+            let stream = format!("q {0} 0 0 -{1} 0 {1} cm /Im{2} Do Q", tile_width, tile_height, image);
+            let reference = writer.object(
+                &format!(
+                    "<< /Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 /BBox [0 0 {0} {1}] /XStep {0} /YStep {1} /Matrix [{2}] /Resources << /XObject << /Im{3} {4} 0 R >> >> >>",
+                    tile_width,
+                    tile_height,
+                    matrix(flip.combine(*transform)),
+                    image,
+                    image_references[*image]
+                ),
+                Some(stream.as_bytes()),
+            );
+            let _ = write!(patterns, "/P{} {} 0 R ", index, reference);
+        }
+        writer.write(
+            resources,
+            &format!(
+                "<< /ProcSet [/PDF /Text /ImageB /ImageC] /Font << {}>> /XObject << {}>> /ExtGState << {}>> /Shading << {}>> /Pattern << {}>> >>",
+                fonts, images, states, shadings, patterns
+            ),
+            None,
+        );
+
+        // Flip the y axis so content is drawn in device coordinates
+        let mut kids = Vec::new();
+        for content in self.pages.iter().chain(std::iter::once(&self.content)) {
+            let stream = format!("1 0 0 -1 0 {} cm\nq\n{}Q\n", height, content);
+            let contents = writer.object("<< /Filter /FlateDecode >>", Some(&zlib_compress(stream.as_bytes())));
+            let page = writer.object(
+                &format!(
+                    "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {} 0 R /Contents {} 0 R >>",
+                    pages, width, height, resources, contents
+                ),
+                None,
+            );
+            kids.push(format!("{} 0 R", page));
+        }
+        writer.write(pages, &format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), kids.len()), None);
+        writer.write(catalog, &format!("<< /Type /Catalog /Pages {} 0 R >>", pages), None);
+        writer.finish(catalog)
+    }
+}
+
+/// Drawing context that writes PDF content streams.
+struct PdfContext {
+    output: Arc<Mutex<PdfOutput>>,
+    size: (u32, u32),
+}
+
+impl PdfContext {
+    fn lock(&self) -> MutexGuard<'_, PdfOutput> {
+        lock(&self.output)
+    }
+
+    fn show_text(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) {
+        let color = color_from(color);
+        let mut output = self.lock();
+        output.uses_standard_font = true;
+        let state = output.opacity(color.a);
+        let text_matrix = transform.combine(Transform::scale(1.0, -1.0));
+        let _ = writeln!(
+            output.content,
+            "q {}{} rg BT /F0 {} Tf {} Tm ({}) Tj ET Q",
+            state,
+            rgb(color),
+            number(SYSTEM_FONT_SIZE),
+            matrix(text_matrix),
+            escape_text(text)
+        );
+    }
+}
+
+impl DrawingContext for PdfContext {
+    fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+        // Everything drawn so far is covered, so it is dropped
+        let mut output = self.lock();
+        output.content = output.clip.clone();
+        drop(output);
+        let (width, height) = self.size;
+        self.fill_rect(0.0, 0.0, width as f32, height as f32, color)
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.fill_path(&Path::rect(Rect::new(x, y, width, height)), color, FillRule::NonZero)
+    }
+
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke_path(&Path::rect(Rect::new(x, y, width, height)), color, stroke)
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.show_text(text, Transform::translate(x, y), color);
+        Ok(())
+    }
+
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.show_text(text, transform, color);
+        Ok(())
+    }
+
+    fn draw_glyph_run(&mut self, font: &Font, glyphs: &[GlyphPosition], size: f32, transform: Transform, color: (f32, f32, f32, f32)) -> Result<bool> {
+        let color = color_from(color);
+        let mut output = self.lock();
+        let state = output.opacity(color.a);
+        let mut text = String::new();
+        // Glyphs of font collections, which cannot be embedded, are filled as outlines
+        let mut outlines = Path::new();
+        for placed in glyphs {
+            let source = font.fallback_font(placed.font);
+            let placement = transform.combine(Transform::translate(placed.x, 0.0));
+            let embeddable = source.data().is_some_and(|data| !data.starts_with(b"ttcf"));
+            if !embeddable {
+                outlines.extend(&source.glyph_path(placed.glyph, size).transform(placement));
+                continue;
+            }
+            let index = output.font(&source);
+            output.fonts[index].use_glyph(placed.glyph);
+            let text_matrix = placement.combine(Transform::scale(size, -size));
+            let _ = write!(text, "/F{} 1 Tf {} Tm <{:04x}> Tj ", index + 1, matrix(text_matrix), placed.glyph);
+        }
+        if !text.is_empty() {
+            let _ = writeln!(output.content, "q {}{} rg BT {}ET Q", state, rgb(color), text);
+        }
+        if !outlines.is_empty() {
+            output.fill(&outlines, &Paint::Solid(color), FillRule::NonZero);
+        }
+        Ok(true)
+    }
+
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        let Some((cropped, origin)) = crop(image, src) else {
+            return Ok(());
+        };
+        let (width, height) = (cropped.width() as f32, cropped.height() as f32);
+        let mut output = self.lock();
+        output.images.push((cropped, filter == ImageFilter::Bilinear));
+        let index = output.images.len() - 1;
+        // Images fill the unit square with their first row at the top
+        let placement = transform
+            .combine(Transform::translate(origin.x, origin.y))
+            .combine(Transform::scale(width, height))
+            .combine(Transform { a: 1.0, b: 0.0, c: 0.0, d: -1.0, tx: 0.0, ty: 1.0 });
+        let visible = Path::rect(src).transform(transform);
+        let _ = writeln!(output.content, "q {}W n\n{} cm /Im{} Do\nQ", path_operators(&visible), matrix(placement), index);
+        Ok(())
+    }
+
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        self.fill_path_paint(path, &Paint::Solid(color_from(color)), fill_rule)
+    }
+
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke_path_paint(path, &Paint::Solid(color_from(color)), stroke)
+    }
+
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        if !path.is_empty() {
+            self.lock().fill(path, paint, fill_rule);
+        }
+        Ok(())
+    }
+
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        if !path.is_empty() && stroke.width > 0.0 {
+            self.lock().stroke(path, paint, stroke);
+        }
+        Ok(())
+    }
+
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        let mut operators = String::new();
+        if let Some(clip) = clip {
+            // Each clipping operator intersects with the clip before it
+            let _ = writeln!(operators, "{}W n", path_operators(&Path::rect(clip.bounds())));
+            for (path, fill_rule) in clip.paths() {
+                let operator = if *fill_rule == FillRule::EvenOdd { "W* n" } else { "W n" };
+                let _ = writeln!(operators, "{}{}", path_operators(path), operator);
+            }
+        }
+        let mut output = self.lock();
+        let _ = write!(output.content, "Q\nq\n{}", operators);
+        output.clip = operators;
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+/// Writes numbered objects and the cross-reference table.
+#[derive(Default)]
+struct Writer {
+    data: Vec<u8>,
+    offsets: Vec<Option<usize>>,
+}
+
+impl Writer {
+    /// Reserve an object number to be written later.
+    fn reserve(&mut self) -> usize {
+        self.offsets.push(None);
+        self.offsets.len()
+    }
+
+    /// Write a new object, returning its number.
+    fn object(&mut self, dictionary: &str, stream: Option<&[u8]>) -> usize {
+        let number = self.reserve();
+        self.write(number, dictionary, stream);
+        number
+    }
+
+    /// Write a reserved object; stream objects get their `/Length` added.
+    fn write(&mut self, number: usize, dictionary: &str, stream: Option<&[u8]>) {
+        if self.data.is_empty() {
+            self.data.extend_from_slice(b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n");
+        }
+        self.offsets[number - 1] = Some(self.data.len());
+        match stream {
+            Some(stream) => {
+                let dictionary = dictionary.trim_end().trim_end_matches(">>");
+                let header = format!("{} 0 obj\n{} /Length {} >>\nstream\n", number, dictionary.trim_end(), stream.len());
+                self.data.extend_from_slice(header.as_bytes());
+                self.data.extend_from_slice(stream);
+                self.data.extend_from_slice(b"\nendstream\nendobj\n");
+            }
+            None => self.data.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", number, dictionary).as_bytes()),
+        }
+    }
+
+    fn finish(mut self, root: usize) -> Vec<u8> {
+        let start = self.data.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            let _ = writeln!(table, "{:010} 00000 n ", offset.unwrap_or(0));
+        }
+        let _ = write!(table, "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF\n", self.offsets.len() + 1, root, start);
+        self.data.extend_from_slice(table.as_bytes());
+        self.data
+    }
+}
+
+/// Write an embedded TrueType font and return the number of its font object.
+fn write_font(writer: &mut Writer, index: usize, embedded: &EmbeddedFont) -> usize {
+    let font = &embedded.font;
+    let data = font.data().unwrap_or_default();
+    let name = format!("RXFont{}", index + 1);
+    let file = writer.object(&format!("<< /Length1 {} /Filter /FlateDecode >>", data.len()), Some(&zlib_compress(data)));
+    let (ascent, descent) = (font.ascent(1000.0), -font.descent(1000.0));
+    let flags = if font.is_italic() { 32 | 64 } else { 32 };
+    let descriptor = writer.object(
+        &format!(
+            "<< /Type /FontDescriptor /FontName /{} /Flags {} /FontBBox [0 {} 1000 {}] /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} 0 R >>",
+            name,
+            flags,
+            number(descent),
+            number(ascent),
+            number(ascent),
+            number(descent),
+            number(ascent),
+            file
+        ),
+        None,
+    );
+    let widths: Vec<String> = embedded.widths.iter().map(|(glyph, width)| format!("{} [{}]", glyph, number(*width))).collect();
+    let descendant = writer.object(
+        &format!(
+            "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> /FontDescriptor {} 0 R /CIDToGIDMap /Identity /W [{}] >>",
+            name,
+            descriptor,
+            widths.join(" ")
+        ),
+        None,
+    );
+    let to_unicode = writer.object("<< >>", Some(to_unicode_cmap(&embedded.chars).as_bytes()));
+    writer.object(
+        &format!(
+            "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+            name, descendant, to_unicode
+        ),
+        None,
+    )
+}
+
+/// Map each glyph of a font back to the lowest character that maps to it.
+fn glyph_chars(font: &Font) -> HashMap<u16, char> {
+    let mut chars = HashMap::new();
+    let Some(face) = font.face() else {
+        return chars;
+    };
+    for (first, last) in face.coverage() {
+        for ch in (first..=last).filter_map(char::from_u32) {
+            if let Some(glyph) = face.glyph_index(ch) {
+                chars.entry(glyph).or_insert(ch);
+            }
+        }
+    }
+    chars
+}
+
+/// Build a CMap mapping two-byte glyph codes to UTF-16 characters.
+fn to_unicode_cmap(chars: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let entries: Vec<(&u16, &char)> = chars.iter().collect();
+    // A block may hold at most 100 mappings
+    for block in entries.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", block.len());
+        for (glyph, ch) in block {
+            let units: String = ch.encode_utf16(&mut [0; 2]).iter().map(|unit| format!("{:04X}", unit)).collect();
+            let _ = writeln!(cmap, "<{:04X}> <{}>", glyph, units);
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    cmap
+}
+
+/// Write an image, with its alpha as a soft mask, and return its object number.
+fn write_image(writer: &mut Writer, image: &Image, interpolate: bool) -> usize {
+    let pixels = image.pixels();
+    let color: Vec<u8> = pixels.chunks_exact(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2]]).collect();
+    let header = |color_space: &str, extra: &str| {
+        format!(
+            "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent 8 /Interpolate {} /Filter /FlateDecode{} >>",
+            image.width(),
+            image.height(),
+            color_space,
+            interpolate,
+            extra
+        )
+    };
+    let mask = if pixels.chunks_exact(4).any(|pixel| pixel[3] < 255) {
+        let alpha: Vec<u8> = pixels.chunks_exact(4).map(|pixel| pixel[3]).collect();
+        let mask = writer.object(&header("DeviceGray", ""), Some(&zlib_compress(&alpha)));
+        format!(" /SMask {} 0 R", mask)
+    } else {
+        String::new()
+    };
+    writer.object(&header("DeviceRGB", &mask), Some(&zlib_compress(&color)))
+}
+
+/// Build a function mapping 0..1 to the colors of gradient stops.
+fn stop_function(stops: &[GradientStop]) -> Option<String> {
+    let (first, last) = (stops.first()?, stops.last()?);
+    let mut points: Vec<(f32, Color)> = stops.iter().map(|stop| (stop.offset, stop.color)).collect();
+    if first.offset > 0.0 {
+        points.insert(0, (0.0, first.color));
+    }
+    if last.offset < 1.0 || points.len() == 1 {
+        points.push((1.0, last.color));
+    }
+    let segment = |from: Color, to: Color| format!("<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>", rgb(from), rgb(to));
+    if points.len() == 2 {
+        return Some(segment(points[0].1, points[1].1));
+    }
+    let functions: Vec<String> = points.windows(2).map(|pair| segment(pair[0].1, pair[1].1)).collect();
+    let bounds: Vec<String> = points[1..points.len() - 1].iter().map(|(offset, _)| number(*offset)).collect();
+    let encode = vec!["0 1"; functions.len()].join(" ");
+    Some(format!(
+        "<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{}] /Encode [{}] >>",
+        functions.join(" "),
+        bounds.join(" "),
+        encode
+    ))
+}
+
+/// Path construction operators.
+fn path_operators(path: &Path) -> String {
+    let mut operators = String::new();
+    for element in cubic_elements(path) {
+        let _ = match element {
+            PathElement::MoveTo(p) => writeln!(operators, "{} {} m", number(p.x), number(p.y)),
+            PathElement::LineTo(p) => writeln!(operators, "{} {} l", number(p.x), number(p.y)),
+            PathElement::CubicTo(c1, c2, p) => writeln!(
+                operators,
+                "{} {} {} {} {} {} c",
+                number(c1.x),
+                number(c1.y),
+                number(c2.x),
+                number(c2.y),
+                number(p.x),
+                number(p.y)
+            ),
+            PathElement::QuadTo(..) => Ok(()),
+            PathElement::Close => writeln!(operators, "h"),
+        };
+    }
+    operators
+}
+
+fn color_from(color: (f32, f32, f32, f32)) -> Color {
+    Color::rgba(color.0, color.1, color.2, color.3)
+}
+
+/// Format the color channels of a color.
+fn rgb(color: Color) -> String {
+    [color.r, color.g, color.b].map(|value| number(value.clamp(0.0, 1.0))).join(" ")
+}
+
+/// Escape text for a literal string in WinAnsi encoding.
+///
+/// Characters outside Latin-1 are replaced with question marks.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::codec::inflate::zlib_decompress;
+    use crate::drawing::{LinearGradient, Point};
+
+    /// Find the offset of `needle` in `data`.
+    fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+        data.windows(needle.len()).position(|window| window == needle)
+    }
+
+    /// Decompress the content stream of the page object `number`.
+    fn page_content(data: &[u8], number: usize) -> String {
+        let start = find(data, format!("\n{} 0 obj", number).as_bytes()).unwrap();
+        let body = &data[start..];
+        let stream = find(body, b"stream\n").unwrap() + 7;
+        let end = find(body, b"\nendstream").unwrap();
+        String::from_utf8(zlib_decompress(&body[stream..end]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let mut document = PdfDocument::new(Size::new(200.0, 100.0));
+        document.canvas().fill_rect(Rect::new(10.0, 10.0, 50.0, 20.0), Color::RED.with_alpha(0.5)).unwrap();
+        document.new_page();
+        document.canvas().draw_text("(Hi)", Point::new(10.0, 50.0), &Font::system(), 13.0, Color::BLACK).unwrap();
+        assert_eq!(document.page_count(), 2);
+
+        let data = document.to_bytes();
+        assert!(data.starts_with(b"%PDF-1.4"));
+        assert!(find(&data, b"/Count 2").is_some());
+        assert!(find(&data, b"/BaseFont /Helvetica").is_some());
+        assert!(find(&data, b"/ca 0.502").is_some());
+
+        // Every cross-reference entry points at its object
+        let xref = find(&data, b"\nxref\n").unwrap() + 1;
+        let startxref = find(&data, b"startxref\n").unwrap() + 10;
+        let end = find(&data[startxref..], b"\n").unwrap() + startxref;
+        assert_eq!(std::str::from_utf8(&data[startxref..end]).unwrap().parse::<usize>().unwrap(), xref);
+        let table = std::str::from_utf8(&data[xref..]).unwrap();
+        for (number, line) in table.lines().skip(3).take_while(|line| line.ends_with(" n ")).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(data[offset..].starts_with(format!("{} 0 obj", number + 1).as_bytes()));
+        }
+
+        let first = page_content(&data, 5);
+        assert!(first.starts_with("1 0 0 -1 0 100 cm"));
+        assert!(first.contains("/GS0 gs 1 0 0 rg"));
+        let second = page_content(&data, 7);
+        assert!(second.contains("(\\(Hi\\)) Tj"));
+    }
+
+    #[test]
+    fn test_embedded_font_and_images() {
+        let font = Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap();
+        let mut document = PdfDocument::new(Size::new(100.0, 100.0));
+        let canvas = document.canvas();
+        canvas.draw_text("AB", Point::new(10.0, 20.0), &font, 12.0, Color::BLACK).unwrap();
+        canvas.draw_image_at(&Image::new(2, 2), Point::new(0.0, 0.0)).unwrap();
+        let gradient = LinearGradient::new(Point::zero(), Point::new(10.0, 0.0)).stop(0.0, Color::RED).stop(0.5, Color::GREEN).stop(1.0, Color::BLUE);
+        canvas.fill_rect(Rect::new(0.0, 50.0, 10.0, 10.0), gradient).unwrap();
+        let pattern = ImagePattern::new(Arc::new(Image::filled(4, 2, Color::RED))).offset(1.0, 0.0);
+        canvas.fill_rect(Rect::new(20.0, 50.0, 10.0, 10.0), pattern).unwrap();
+
+        let data = document.to_bytes();
+        assert!(find(&data, b"/Subtype /CIDFontType2").is_some());
+        assert!(find(&data, b"/FontFile2").is_some());
+        assert!(find(&data, b"/ToUnicode").is_some());
+        let mapping = format!("<{:04X}> <0041>", font.glyph_index('A').unwrap());
+        assert!(find(&data, mapping.as_bytes()).is_some());
+        assert!(find(&data, b"/SMask").is_some());
+        assert!(find(&data, b"/FunctionType 3").is_some());
+        assert!(find(&data, b"/Bounds [0.5]").is_some());
+        assert!(find(&data, b"/PatternType 1 /PaintType 1 /TilingType 1 /BBox [0 0 4 2] /XStep 4 /YStep 2 /Matrix [1 0 0 -1 1 100]").is_some());
+        assert!(find(&data, b"/Pattern << /P0").is_some());
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a(b)\\"), "a\\(b\\)\\\\");
+        assert_eq!(escape_text("é€"), "\\351?");
+    }
+}
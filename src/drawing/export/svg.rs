@@ -0,0 +1,556 @@
+//! SVG export.
+
+use super::{base64, crop, matrix, number, SYSTEM_FONT_SIZE};
+use crate::drawing::{Canvas, Clip, Color, CornerRadii, FillRule, Font, FontId, GlyphPosition, Image, ImageFilter, LineCap, LineJoin, Paint, Path, PathElement, Rect, Size, SpreadMode, StrokeStyle, Transform};
+use crate::platform::DrawingContext;
+use crate::Result;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Size at which glyph outlines are stored before being scaled into place.
+const GLYPH_UNITS: f32 = 1000.0;
+
+/// An SVG document that can be drawn on with a [`Canvas`].
+pub struct SvgDocument {
+    output: Arc<Mutex<SvgOutput>>,
+    canvas: Canvas,
+}
+
+impl SvgDocument {
+    /// Create an empty document of the given size in pixels.
+    pub fn new(size: Size) -> Self {
+        let size = (size.width.max(0.0).ceil() as u32, size.height.max(0.0).ceil() as u32);
+        let output = Arc::new(Mutex::new(SvgOutput {
+            size,
+            ..SvgOutput::default()
+        }));
+        let context = SvgContext { output: output.clone(), size };
+        Self {
+            output,
+            canvas: Canvas::new(Box::new(context)),
+        }
+    }
+
+    /// The canvas drawing into the document.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Get the size in pixels.
+    pub fn size(&self) -> Size {
+        self.canvas.size()
+    }
+
+    /// Serialize what has been drawn so far.
+    pub fn to_svg(&self) -> String {
+        self.lock().finish()
+    }
+
+    /// Write the document to a file.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        std::fs::write(path, self.to_svg())?;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SvgOutput> {
+        lock(&self.output)
+    }
+}
+
+fn lock(output: &Mutex<SvgOutput>) -> MutexGuard<'_, SvgOutput> {
+    output.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Markup written so far.
+#[derive(Default)]
+struct SvgOutput {
+    size: (u32, u32),
+    defs: String,
+    body: String,
+    next_id: usize,
+    /// Clip groups currently open in `body`.
+    open_groups: usize,
+    clip: Option<Clip>,
+    /// Ids of the glyph outlines defined so far.
+    glyphs: HashMap<(FontId, u16), String>,
+}
+
+impl SvgOutput {
+    fn id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}{}", prefix, self.next_id)
+    }
+
+    fn finish(&self) -> String {
+        let (width, height) = self.size;
+        let mut svg = String::new();
+        svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+            width, height
+        );
+        if !self.defs.is_empty() {
+            let _ = writeln!(svg, "<defs>\n{}</defs>", self.defs);
+        }
+        svg.push_str(&self.body);
+        for _ in 0..self.open_groups {
+            svg.push_str("</g>\n");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Open groups clipping to the current clip.
+    fn open_clip(&mut self) {
+        let Some(clip) = self.clip.clone() else {
+            return;
+        };
+        let bounds = clip.bounds();
+        let mut shapes = vec![format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            number(bounds.x),
+            number(bounds.y),
+            number(bounds.width),
+            number(bounds.height)
+        )];
+        for (path, fill_rule) in clip.paths() {
+            shapes.push(format!("<path d=\"{}\"{}/>", path_data(path), clip_rule(*fill_rule)));
+        }
+        // Nested groups intersect their clips
+        for shape in shapes {
+            let id = self.id("clip");
+            let _ = writeln!(self.defs, "<clipPath id=\"{}\">{}</clipPath>", id, shape);
+            let _ = writeln!(self.body, "<g clip-path=\"url(#{})\">", id);
+            self.open_groups += 1;
+        }
+    }
+
+    fn close_clip(&mut self) {
+        for _ in 0..self.open_groups {
+            self.body.push_str("</g>\n");
+        }
+        self.open_groups = 0;
+    }
+
+    /// Describe a paint as an attribute value, adding gradient or pattern definitions.
+    fn paint(&mut self, paint: &Paint) -> (String, f32) {
+        match paint {
+            Paint::Solid(color) => (hex(*color), color.a),
+            Paint::LinearGradient(gradient) => {
+                let id = self.id("gradient");
+                let _ = write!(
+                    self.defs,
+                    "<linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" gradientTransform=\"matrix({})\"{}>",
+                    id,
+                    number(gradient.start.x),
+                    number(gradient.start.y),
+                    number(gradient.end.x),
+                    number(gradient.end.y),
+                    matrix(gradient.transform),
+                    spread(gradient.spread)
+                );
+                self.stops(&gradient.stops);
+                self.defs.push_str("</linearGradient>\n");
+                (format!("url(#{})", id), 1.0)
+            }
+            Paint::RadialGradient(gradient) => {
+                let id = self.id("gradient");
+                let _ = write!(
+                    self.defs,
+                    "<radialGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" r=\"{}\" gradientTransform=\"matrix({})\"{}>",
+                    id,
+                    number(gradient.center.x),
+                    number(gradient.center.y),
+                    number(gradient.radius),
+                    matrix(gradient.transform),
+                    spread(gradient.spread)
+                );
+                self.stops(&gradient.stops);
+                self.defs.push_str("</radialGradient>\n");
+                (format!("url(#{})", id), 1.0)
+            }
+            Paint::Pattern(pattern) => {
+                let id = self.id("pattern");
+                let (width, height) = (pattern.image.width(), pattern.image.height());
+                let _ = writeln!(
+                    self.defs,
+                    "<pattern id=\"{}\" patternUnits=\"userSpaceOnUse\" width=\"{}\" height=\"{}\" patternTransform=\"matrix({})\">{}</pattern>",
+                    id,
+                    width,
+                    height,
+                    matrix(pattern.transform),
+                    image_element(&pattern.image, ImageFilter::Bilinear, None)
+                );
+                (format!("url(#{})", id), 1.0)
+            }
+        }
+    }
+
+    fn stops(&mut self, stops: &[crate::drawing::GradientStop]) {
+        for stop in stops {
+            let _ = write!(self.defs, "<stop offset=\"{}\" stop-color=\"{}\"", number(stop.offset), hex(stop.color));
+            if stop.color.a < 1.0 {
+                let _ = write!(self.defs, " stop-opacity=\"{}\"", number(stop.color.a));
+            }
+            self.defs.push_str("/>");
+        }
+    }
+
+    fn fill(&mut self, paint: &Paint) -> String {
+        let (value, opacity) = self.paint(paint);
+        let mut attributes = format!(" fill=\"{}\"", value);
+        if opacity < 1.0 {
+            let _ = write!(attributes, " fill-opacity=\"{}\"", number(opacity));
+        }
+        attributes
+    }
+
+    fn stroke(&mut self, paint: &Paint, stroke: &StrokeStyle) -> String {
+        let (value, opacity) = self.paint(paint);
+        let mut attributes = format!(" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"", value, number(stroke.width));
+        if opacity < 1.0 {
+            let _ = write!(attributes, " stroke-opacity=\"{}\"", number(opacity));
+        }
+        match stroke.cap {
+            LineCap::Butt => {}
+            LineCap::Round => attributes.push_str(" stroke-linecap=\"round\""),
+            LineCap::Square => attributes.push_str(" stroke-linecap=\"square\""),
+        }
+        match stroke.join {
+            LineJoin::Miter => {
+                if stroke.miter_limit != 4.0 {
+                    let _ = write!(attributes, " stroke-miterlimit=\"{}\"", number(stroke.miter_limit));
+                }
+            }
+            LineJoin::Round => attributes.push_str(" stroke-linejoin=\"round\""),
+            LineJoin::Bevel => attributes.push_str(" stroke-linejoin=\"bevel\""),
+        }
+        let dashes = stroke.dash_pattern();
+        if !dashes.is_empty() {
+            let dashes: Vec<String> = dashes.into_iter().map(number).collect();
+            let _ = write!(attributes, " stroke-dasharray=\"{}\"", dashes.join(" "));
+            if stroke.dash_offset != 0.0 {
+                let _ = write!(attributes, " stroke-dashoffset=\"{}\"", number(stroke.dash_offset));
+            }
+        }
+        attributes
+    }
+
+    fn text(&mut self, text: &str, transform: Transform, color: Color) {
+        let fill = self.fill(&Paint::Solid(color));
+        let _ = writeln!(
+            self.body,
+            "<text transform=\"matrix({})\" font-family=\"sans-serif\" font-size=\"{}\"{} xml:space=\"preserve\">{}</text>",
+            matrix(transform),
+            number(SYSTEM_FONT_SIZE),
+            fill,
+            escape(text)
+        );
+    }
+}
+
+/// Drawing context that writes SVG markup.
+struct SvgContext {
+    output: Arc<Mutex<SvgOutput>>,
+    size: (u32, u32),
+}
+
+impl SvgContext {
+    fn lock(&self) -> MutexGuard<'_, SvgOutput> {
+        lock(&self.output)
+    }
+}
+
+impl DrawingContext for SvgContext {
+    fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+        // Everything drawn so far is covered, so it is dropped
+        let mut output = self.lock();
+        output.body.clear();
+        output.open_groups = 0;
+        output.open_clip();
+        drop(output);
+        let (width, height) = self.size;
+        self.fill_rect(0.0, 0.0, width as f32, height as f32, color)
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        let mut output = self.lock();
+        let fill = output.fill(&Paint::Solid(color_from(color)));
+        let _ = writeln!(output.body, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{}/>", number(x), number(y), number(width), number(height), fill);
+        Ok(())
+    }
+
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let mut output = self.lock();
+        let attributes = output.stroke(&Paint::Solid(color_from(color)), stroke);
+        let _ = writeln!(output.body, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{}/>", number(x), number(y), number(width), number(height), attributes);
+        Ok(())
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.lock().text(text, Transform::translate(x, y), color_from(color));
+        Ok(())
+    }
+
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.lock().text(text, transform, color_from(color));
+        Ok(())
+    }
+
+    fn draw_glyph_run(&mut self, font: &Font, glyphs: &[GlyphPosition], size: f32, transform: Transform, color: (f32, f32, f32, f32)) -> Result<bool> {
+        let mut output = self.lock();
+        let fill = output.fill(&Paint::Solid(color_from(color)));
+        let _ = writeln!(output.body, "<g{}>", fill);
+        for placed in glyphs {
+            let source = font.fallback_font(placed.font);
+            let key = (source.id(), placed.glyph);
+            let id = match output.glyphs.get(&key) {
+                Some(id) => id.clone(),
+                None => {
+                    let id = output.id("glyph");
+                    let path = source.glyph_path(placed.glyph, GLYPH_UNITS);
+                    let _ = writeln!(output.defs, "<path id=\"{}\" d=\"{}\"/>", id, path_data(&path));
+                    output.glyphs.insert(key, id.clone());
+                    id
+                }
+            };
+            let placement = transform
+                .combine(Transform::translate(placed.x, 0.0))
+                .combine(Transform::scale(size / GLYPH_UNITS, size / GLYPH_UNITS));
+            let _ = writeln!(output.body, "<use xlink:href=\"#{}\" transform=\"matrix({})\"/>", id, matrix(placement));
+        }
+        output.body.push_str("</g>\n");
+        Ok(true)
+    }
+
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        let Some((cropped, origin)) = crop(image, src) else {
+            return Ok(());
+        };
+        let mut output = self.lock();
+        // Only the requested part of the cropped pixels is shown
+        let clip_id = output.id("clip");
+        let visible = Rect::new(src.x - origin.x, src.y - origin.y, src.width, src.height);
+        let _ = writeln!(
+            output.defs,
+            "<clipPath id=\"{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+            clip_id,
+            number(visible.x),
+            number(visible.y),
+            number(visible.width),
+            number(visible.height)
+        );
+        let placement = transform.combine(Transform::translate(origin.x, origin.y));
+        let _ = writeln!(
+            output.body,
+            "<g transform=\"matrix({})\">{}</g>",
+            matrix(placement),
+            image_element(&cropped, filter, Some(&clip_id))
+        );
+        Ok(())
+    }
+
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        self.fill_path_paint(path, &Paint::Solid(color_from(color)), fill_rule)
+    }
+
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke_path_paint(path, &Paint::Solid(color_from(color)), stroke)
+    }
+
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        if path.is_empty() {
+            return Ok(());
+        }
+        let mut output = self.lock();
+        let fill = output.fill(paint);
+        let rule = if fill_rule == FillRule::EvenOdd { " fill-rule=\"evenodd\"" } else { "" };
+        let _ = writeln!(output.body, "<path d=\"{}\"{}{}/>", path_data(path), fill, rule);
+        Ok(())
+    }
+
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        if path.is_empty() {
+            return Ok(());
+        }
+        let mut output = self.lock();
+        let attributes = output.stroke(paint, stroke);
+        let _ = writeln!(output.body, "<path d=\"{}\"{}/>", path_data(path), attributes);
+        Ok(())
+    }
+
+    fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32)) -> Result<()> {
+        let radii = radii.clamp_to(rect.width, rect.height);
+        if radii != CornerRadii::uniform(radii.top_left) {
+            return self.fill_path(&Path::rounded_rect(rect, radii), color, FillRule::NonZero);
+        }
+        let mut output = self.lock();
+        let fill = output.fill(&Paint::Solid(color_from(color)));
+        let _ = writeln!(
+            output.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"{}/>",
+            number(rect.x),
+            number(rect.y),
+            number(rect.width),
+            number(rect.height),
+            number(radii.top_left),
+            fill
+        );
+        Ok(())
+    }
+
+    fn fill_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        let mut output = self.lock();
+        let fill = output.fill(&Paint::Solid(color_from(color)));
+        let _ = writeln!(output.body, "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{}/>", number(cx), number(cy), number(radius_x), number(radius_y), fill);
+        Ok(())
+    }
+
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        let mut output = self.lock();
+        output.close_clip();
+        output.clip = clip.cloned();
+        output.open_clip();
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+fn color_from(color: (f32, f32, f32, f32)) -> Color {
+    Color::rgba(color.0, color.1, color.2, color.3)
+}
+
+/// Format a color as `#rrggbb`, ignoring alpha.
+fn hex(color: Color) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(color.r), channel(color.g), channel(color.b))
+}
+
+fn spread(spread: SpreadMode) -> &'static str {
+    match spread {
+        SpreadMode::Pad => "",
+        SpreadMode::Repeat => " spreadMethod=\"repeat\"",
+        SpreadMode::Reflect => " spreadMethod=\"reflect\"",
+    }
+}
+
+fn clip_rule(fill_rule: FillRule) -> &'static str {
+    match fill_rule {
+        FillRule::NonZero => "",
+        FillRule::EvenOdd => " clip-rule=\"evenodd\"",
+    }
+}
+
+/// An `<image>` element embedding the pixels as a PNG.
+fn image_element(image: &Image, filter: ImageFilter, clip_id: Option<&str>) -> String {
+    let rendering = if filter == ImageFilter::Nearest { " style=\"image-rendering:pixelated\"" } else { "" };
+    let clip = clip_id.map(|id| format!(" clip-path=\"url(#{})\"", id)).unwrap_or_default();
+    format!(
+        "<image width=\"{}\" height=\"{}\"{}{} xlink:href=\"data:image/png;base64,{}\"/>",
+        image.width(),
+        image.height(),
+        rendering,
+        clip,
+        base64(&image.to_png())
+    )
+}
+
+/// Format a path as SVG path data.
+fn path_data(path: &Path) -> String {
+    let mut data = String::new();
+    for element in path.elements() {
+        if !data.is_empty() {
+            data.push(' ');
+        }
+        let _ = match *element {
+            PathElement::MoveTo(p) => write!(data, "M{} {}", number(p.x), number(p.y)),
+            PathElement::LineTo(p) => write!(data, "L{} {}", number(p.x), number(p.y)),
+            PathElement::QuadTo(c, p) => write!(data, "Q{} {} {} {}", number(c.x), number(c.y), number(p.x), number(p.y)),
+            PathElement::CubicTo(c1, c2, p) => write!(
+                data,
+                "C{} {} {} {} {} {}",
+                number(c1.x),
+                number(c1.y),
+                number(c2.x),
+                number(c2.y),
+                number(p.x),
+                number(p.y)
+            ),
+            PathElement::Close => write!(data, "Z"),
+        };
+    }
+    data
+}
+
+/// Escape text for use in markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::{LinearGradient, Point, SvgImage};
+
+    #[test]
+    fn test_round_trip_through_import() {
+        let mut document = SvgDocument::new(Size::new(40.0, 20.0));
+        let canvas = document.canvas();
+        canvas.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::RED).unwrap();
+        let gradient = LinearGradient::new(Point::new(20.0, 0.0), Point::new(40.0, 0.0))
+            .stop(0.0, Color::BLACK)
+            .stop(1.0, Color::WHITE);
+        canvas.fill_path(&Path::rect(Rect::new(20.0, 0.0, 20.0, 20.0)), gradient, FillRule::NonZero).unwrap();
+        canvas.clip_rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        canvas.fill_circle(Point::new(10.0, 10.0), 8.0, Color::BLUE).unwrap();
+        let svg = document.to_svg();
+        assert!(svg.contains("<linearGradient"));
+        assert!(svg.contains("clip-path"));
+
+        let image = SvgImage::parse(&svg).unwrap().to_image(40, 20).unwrap();
+        assert_eq!(image.pixel(3, 17), Color::RED);
+        assert!((image.pixel(30, 10).r - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_text_and_images() {
+        let mut document = SvgDocument::new(Size::new(20.0, 20.0));
+        document.canvas().draw_text("a<b", Point::new(2.0, 12.0), &Font::system(), 13.0, Color::BLACK).unwrap();
+        document.canvas().draw_image_at(&Image::new(4, 4), Point::new(1.0, 1.0)).unwrap();
+        let svg = document.to_svg();
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert!(svg.contains("data:image/png;base64,iVBORw0KGgo"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_embedded_glyphs() {
+        let font = Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap();
+        let mut document = SvgDocument::new(Size::new(40.0, 20.0));
+        document.canvas().draw_text("AAB", Point::new(0.0, 15.0), &font, 10.0, Color::BLACK).unwrap();
+        let svg = document.to_svg();
+        // Each glyph is defined once and used wherever it appears
+        assert_eq!(svg.matches("<path id=\"glyph").count(), 2);
+        assert_eq!(svg.matches("<use ").count(), 3);
+        assert!(!svg.contains("<image"));
+    }
+}
@@ -0,0 +1,433 @@
+//! Installed font discovery and matching.
+//!
+//! A [`FontDatabase`] indexes the font files in a set of directories by
+//! family, weight, style and character coverage, and resolves a
+//! [`FontQuery`] such as `"sans-serif, 14px, bold"` to the best matching
+//! face. Fonts resolved from a database fall back per character to other
+//! installed faces for glyphs they lack.
+
+use super::ttf::Face;
+use super::Font;
+use crate::{Error, Result};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+/// Standard system font directories on Linux.
+const SYSTEM_DIRECTORIES: [&str; 2] = ["/usr/share/fonts", "/usr/local/share/fonts"];
+
+/// Font directories inside the user's home directory.
+const USER_DIRECTORIES: [&str; 2] = [".local/share/fonts", ".fonts"];
+
+/// Directories nested deeper than this are not scanned.
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// Installed families tried, in order, for each generic family name.
+const SANS_SERIF: &[&str] = &["DejaVu Sans", "Noto Sans", "Liberation Sans", "Cantarell", "Ubuntu", "FreeSans", "Arial", "Helvetica"];
+const SERIF: &[&str] = &["DejaVu Serif", "Noto Serif", "Liberation Serif", "FreeSerif", "Times New Roman"];
+const MONOSPACE: &[&str] = &["DejaVu Sans Mono", "Noto Sans Mono", "Liberation Mono", "Ubuntu Mono", "FreeMono", "Courier New"];
+
+/// Description of a font to look up, parsed from strings such as
+/// `"Noto Sans, sans-serif, 16px, bold italic"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontQuery {
+    /// Family names in order of preference; may include `serif`,
+    /// `sans-serif` and `monospace`.
+    pub families: Vec<String>,
+    /// Font size in pixels.
+    pub size: f32,
+    /// Weight class from 100 (thin) to 900 (black).
+    pub weight: u16,
+    /// Whether an italic or oblique face is wanted.
+    pub italic: bool,
+}
+
+impl FontQuery {
+    /// Query a family at the default size, weight and style.
+    pub fn new(family: impl Into<String>) -> Self {
+        Self {
+            families: vec![family.into()],
+            ..Self::default()
+        }
+    }
+
+    /// Add a family to try after the others.
+    pub fn family(mut self, family: impl Into<String>) -> Self {
+        self.families.push(family.into());
+        self
+    }
+
+    /// Set the size in pixels.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the weight class.
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Set whether an italic face is wanted.
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+
+    /// Installed family names to try, with generic families expanded.
+    fn candidates(&self) -> Vec<&str> {
+        let mut candidates = Vec::new();
+        for family in &self.families {
+            match generic_family(family) {
+                Some(names) => candidates.extend_from_slice(names),
+                None => candidates.push(family.as_str()),
+            }
+        }
+        candidates
+    }
+}
+
+impl Default for FontQuery {
+    fn default() -> Self {
+        Self {
+            families: vec!["sans-serif".to_string()],
+            size: 14.0,
+            weight: 400,
+            italic: false,
+        }
+    }
+}
+
+impl FromStr for FontQuery {
+    type Err = Error;
+
+    /// Parse comma-separated families, a size in `px` or `pt`, a weight
+    /// keyword or number, and `italic` or `oblique`, in any order.
+    fn from_str(text: &str) -> Result<Self> {
+        let mut query = FontQuery { families: Vec::new(), ..FontQuery::default() };
+        for item in text.split(',') {
+            let item = item.trim();
+            if item.is_empty() {
+                return Err(Error::resource(format!("Empty item in font description '{}'", text)));
+            }
+            if item.starts_with(['"', '\'']) {
+                query.families.push(item.trim_matches(['"', '\'']).to_string());
+                continue;
+            }
+
+            // Style words may share an item, as in "bold italic"
+            let mut family = Vec::new();
+            for word in item.split_whitespace() {
+                let lower = word.to_ascii_lowercase();
+                if let Some(size) = parse_size(&lower) {
+                    query.size = size;
+                } else if let Some(weight) = parse_weight(&lower) {
+                    query.weight = weight;
+                } else if matches!(lower.as_str(), "italic" | "oblique") {
+                    query.italic = true;
+                } else if !matches!(lower.as_str(), "normal" | "regular") {
+                    family.push(word);
+                }
+            }
+            if !family.is_empty() {
+                query.families.push(family.join(" "));
+            }
+        }
+
+        if query.families.is_empty() {
+            query.families.push("sans-serif".to_string());
+        }
+        Ok(query)
+    }
+}
+
+/// Parse `14px`, `10.5pt` or a bare number that is not a weight.
+fn parse_size(word: &str) -> Option<f32> {
+    let size = if let Some(pixels) = word.strip_suffix("px") {
+        pixels.parse().ok()?
+    } else if let Some(points) = word.strip_suffix("pt") {
+        points.parse::<f32>().ok()? * 4.0 / 3.0
+    } else {
+        let size: f32 = word.parse().ok()?;
+        if parse_weight(word).is_some() {
+            return None;
+        }
+        size
+    };
+    (size > 0.0 && size.is_finite()).then_some(size)
+}
+
+/// Parse a weight keyword or a multiple of 100 from 100 to 900.
+fn parse_weight(word: &str) -> Option<u16> {
+    let weight = match word {
+        "thin" | "hairline" => 100,
+        "extra-light" | "extralight" | "ultra-light" | "ultralight" => 200,
+        "light" => 300,
+        "medium" => 500,
+        "semi-bold" | "semibold" | "demi-bold" | "demibold" => 600,
+        "bold" => 700,
+        "extra-bold" | "extrabold" | "ultra-bold" | "ultrabold" => 800,
+        "black" | "heavy" => 900,
+        _ => return word.parse().ok().filter(|weight| (100..=900).contains(weight) && weight % 100 == 0),
+    };
+    Some(weight)
+}
+
+/// Installed families for a generic family name.
+fn generic_family(family: &str) -> Option<&'static [&'static str]> {
+    match family.to_ascii_lowercase().as_str() {
+        "sans-serif" | "sans" | "system-ui" => Some(SANS_SERIF),
+        "serif" => Some(SERIF),
+        "monospace" | "mono" => Some(MONOSPACE),
+        _ => None,
+    }
+}
+
+/// Description of one indexed font face.
+#[derive(Debug, Clone)]
+pub struct FaceInfo {
+    /// Family name, such as "DejaVu Sans".
+    pub family: String,
+    /// Weight class from 100 (thin) to 900 (black).
+    pub weight: u16,
+    /// Whether the face is italic or oblique.
+    pub italic: bool,
+    /// File the face was read from, or `None` for fonts added from memory.
+    pub path: Option<PathBuf>,
+    coverage: Vec<(u32, u32)>,
+    font: Arc<OnceLock<Option<Font>>>,
+}
+
+impl FaceInfo {
+    /// Check if the face has a glyph for `ch`.
+    pub fn covers(&self, ch: char) -> bool {
+        let code = ch as u32;
+        let i = self.coverage.partition_point(|&(_, last)| last < code);
+        self.coverage.get(i).is_some_and(|&(first, _)| first <= code)
+    }
+
+    /// Load the face, reading its file the first time.
+    pub fn font(&self) -> Option<Font> {
+        self.font
+            .get_or_init(|| self.path.as_ref().and_then(|path| Font::load(path).ok()))
+            .clone()
+    }
+
+    /// How far the face is from the wanted style; lower is better.
+    fn distance(&self, query: &FontQuery) -> u32 {
+        let style = if self.italic == query.italic { 0 } else { 1000 };
+        style + u32::from(self.weight.abs_diff(query.weight))
+    }
+}
+
+/// Index of installed fonts.
+///
+/// Databases are cheap to clone; clones share loaded fonts.
+#[derive(Debug, Clone, Default)]
+pub struct FontDatabase {
+    faces: Arc<Vec<FaceInfo>>,
+}
+
+impl FontDatabase {
+    /// Create an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fonts installed on the system and for the current user, scanned
+    /// once per process.
+    pub fn system() -> FontDatabase {
+        static SYSTEM: OnceLock<FontDatabase> = OnceLock::new();
+        SYSTEM
+            .get_or_init(|| {
+                let mut database = FontDatabase::new();
+                for directory in SYSTEM_DIRECTORIES {
+                    database.scan_directory(directory);
+                }
+                if let Some(home) = std::env::var_os("HOME") {
+                    for directory in USER_DIRECTORIES {
+                        database.scan_directory(Path::new(&home).join(directory));
+                    }
+                }
+                database
+            })
+            .clone()
+    }
+
+    /// Index every supported font file under `directory`, returning how many
+    /// faces were added. Unreadable and unsupported files are skipped.
+    pub fn scan_directory(&mut self, directory: impl AsRef<Path>) -> usize {
+        let mut added = 0;
+        let mut pending = vec![(directory.as_ref().to_path_buf(), 0)];
+        while let Some((directory, depth)) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&directory) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+            paths.sort();
+            for path in paths {
+                if path.is_dir() {
+                    if depth < MAX_SCAN_DEPTH {
+                        pending.push((path, depth + 1));
+                    }
+                } else if is_font_file(&path) && self.add_file(&path).is_ok() {
+                    added += 1;
+                }
+            }
+        }
+        added
+    }
+
+    /// Index a font file.
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .map_err(|e| Error::resource(format!("Failed to read font {}: {}", path.display(), e)))?;
+        let face = Face::parse(data)?;
+        self.add_face(&face, Some(path.to_path_buf()), OnceLock::new());
+        Ok(())
+    }
+
+    /// Index a font that is already loaded.
+    pub fn add_font(&mut self, font: &Font) -> Result<()> {
+        let face = font.face().ok_or_else(|| Error::resource("The system font cannot be added to a font database"))?;
+        self.add_face(face, None, OnceLock::from(Some(font.clone())));
+        Ok(())
+    }
+
+    fn add_face(&mut self, face: &Face, path: Option<PathBuf>, font: OnceLock<Option<Font>>) {
+        let family = face
+            .family()
+            .map(str::to_string)
+            .or_else(|| path.as_ref().and_then(|path| path.file_stem()).map(|stem| stem.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Arc::make_mut(&mut self.faces).push(FaceInfo {
+            family,
+            weight: face.weight(),
+            italic: face.is_italic(),
+            path,
+            coverage: face.coverage(),
+            font: Arc::new(font),
+        });
+    }
+
+    /// All indexed faces.
+    pub fn faces(&self) -> &[FaceInfo] {
+        &self.faces
+    }
+
+    /// Family names of the indexed faces, sorted and without duplicates.
+    pub fn families(&self) -> Vec<&str> {
+        let mut families: Vec<&str> = self.faces.iter().map(|face| face.family.as_str()).collect();
+        families.sort_unstable();
+        families.dedup();
+        families
+    }
+
+    /// The face that best matches a query: the first requested family that is
+    /// installed, in the closest style, or failing that a common sans-serif
+    /// family.
+    pub fn query(&self, query: &FontQuery) -> Option<&FaceInfo> {
+        query
+            .candidates()
+            .into_iter()
+            .chain(SANS_SERIF.iter().copied())
+            .find_map(|family| self.closest(query, |face| face.family.eq_ignore_ascii_case(family)))
+            .or_else(|| self.closest(query, |_| true))
+    }
+
+    /// The face to draw `ch` with when the font chosen for `query` lacks it,
+    /// preferring the requested families.
+    pub fn fallback(&self, ch: char, query: &FontQuery) -> Option<&FaceInfo> {
+        query
+            .candidates()
+            .into_iter()
+            .chain(SANS_SERIF.iter().copied())
+            .find_map(|family| self.closest(query, |face| face.family.eq_ignore_ascii_case(family) && face.covers(ch)))
+            .or_else(|| self.closest(query, |face| face.covers(ch)))
+    }
+
+    /// Load the best match for a query, falling back per character to other
+    /// faces in the database. Returns `None` if no face could be loaded.
+    pub fn resolve(&self, query: &FontQuery) -> Option<Font> {
+        let font = self.query(query)?.font()?;
+        Some(font.with_fallback(self.clone(), query.clone()))
+    }
+
+    fn closest(&self, query: &FontQuery, filter: impl Fn(&FaceInfo) -> bool) -> Option<&FaceInfo> {
+        self.faces
+            .iter()
+            .filter(|face| filter(face))
+            .min_by_key(|face| face.distance(query))
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension.to_ascii_lowercase().as_str(), "ttf" | "ttc" | "otf"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::font::ttf::testing::named_font;
+
+    fn database() -> FontDatabase {
+        let mut database = FontDatabase::new();
+        for (family, weight, italic, first) in [
+            ("Test Sans", 400, false, 'A'),
+            ("Test Sans", 700, false, 'A'),
+            ("Test Sans", 400, true, 'A'),
+            ("Test Symbols", 400, false, '\u{2190}'),
+        ] {
+            let font = Font::from_bytes(named_font(family, weight, italic, first)).unwrap();
+            database.add_font(&font).unwrap();
+        }
+        database
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query: FontQuery = "\"Noto Sans\", sans-serif, 16px, bold italic".parse().unwrap();
+        assert_eq!(query.families, vec!["Noto Sans", "sans-serif"]);
+        assert_eq!((query.size, query.weight, query.italic), (16.0, 700, true));
+
+        let query: FontQuery = "monospace, 12pt, 300".parse().unwrap();
+        assert_eq!(query.families, vec!["monospace"]);
+        assert_eq!((query.size, query.weight, query.italic), (16.0, 300, false));
+
+        assert_eq!("14".parse::<FontQuery>().unwrap(), FontQuery::default());
+        assert!("sans-serif,,14px".parse::<FontQuery>().is_err());
+    }
+
+    #[test]
+    fn test_matches_family_and_style() {
+        let database = database();
+        assert_eq!(database.families(), vec!["Test Sans", "Test Symbols"]);
+
+        let bold = database.query(&"Test Sans, bold".parse().unwrap()).unwrap();
+        assert_eq!((bold.weight, bold.italic), (700, false));
+        let italic = database.query(&FontQuery::new("test sans").weight(600).italic(true)).unwrap();
+        assert_eq!((italic.weight, italic.italic), (400, true));
+
+        // Unknown families still resolve to an installed face
+        assert!(database.query(&FontQuery::new("Missing")).is_some());
+        assert!(FontDatabase::new().query(&FontQuery::default()).is_none());
+    }
+
+    #[test]
+    fn test_per_character_fallback() {
+        let database = database();
+        let font = database.resolve(&FontQuery::new("Test Sans")).unwrap();
+        let metrics = font.measure("A\u{2190}?", 10.0);
+
+        // The arrow comes from the symbol font, '?' is in neither
+        assert_eq!(metrics.glyphs.iter().map(|glyph| glyph.font).collect::<Vec<_>>(), vec![0, 1, 0]);
+        assert_eq!(metrics.glyphs[1].glyph, 1);
+        assert_eq!(metrics.glyphs[2].glyph, 0);
+        assert_eq!(font.fallback_font(1).glyph_index('\u{2190}'), Some(1));
+        assert!(database.fallback('?', &FontQuery::default()).is_none());
+    }
+}
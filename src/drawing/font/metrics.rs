@@ -0,0 +1,117 @@
+//! Measured text.
+
+use crate::drawing::Size;
+
+/// Position of one glyph within a measured line of text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphPosition {
+    /// Byte offset of the character in the measured text.
+    pub index: usize,
+    /// Glyph index in the font.
+    pub glyph: u16,
+    /// Position of the font supplying the glyph in the font's fallbacks;
+    /// zero is the font itself.
+    pub font: u8,
+    /// Distance from the start of the line to the glyph origin.
+    pub x: f32,
+    /// Horizontal advance of the glyph.
+    pub advance: f32,
+}
+
+/// Size and glyph positions of a single line of text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextMetrics {
+    /// Total advance width.
+    pub width: f32,
+    /// Distance from the baseline to the top of the line.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the line.
+    pub descent: f32,
+    /// Each glyph in logical order.
+    pub glyphs: Vec<GlyphPosition>,
+    /// Length of the measured text in bytes.
+    pub len: usize,
+}
+
+impl TextMetrics {
+    /// Metrics of text measured by a platform text renderer, from the
+    /// advance of each character.
+    pub(crate) fn from_advances(text: &str, advances: impl IntoIterator<Item = f32>, ascent: f32, descent: f32) -> Self {
+        let mut x = 0.0;
+        let glyphs = text
+            .char_indices()
+            .zip(advances)
+            .map(|((index, _), advance)| {
+                let glyph = GlyphPosition { index, glyph: 0, font: 0, x, advance };
+                x += advance;
+                glyph
+            })
+            .collect();
+        TextMetrics { width: x, ascent, descent, glyphs, len: text.len() }
+    }
+
+    /// Height of the line from ascent to descent.
+    pub fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+
+    /// Width and height of the line.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height())
+    }
+
+    /// Horizontal position of a caret placed before the character at byte `index`.
+    pub fn caret_x(&self, index: usize) -> f32 {
+        self.glyphs
+            .iter()
+            .find(|glyph| glyph.index >= index)
+            .map_or(self.width, |glyph| glyph.x)
+    }
+
+    /// Byte index of the caret position closest to `x`.
+    pub fn index_at(&self, x: f32) -> usize {
+        self.glyphs
+            .iter()
+            .find(|glyph| x < glyph.x + glyph.advance / 2.0)
+            .map_or(self.len, |glyph| glyph.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics() -> TextMetrics {
+        // "aé b" with 10-pixel glyphs; 'é' is two bytes long
+        let glyphs = [0, 1, 3, 4]
+            .iter()
+            .enumerate()
+            .map(|(i, &index)| GlyphPosition { index, glyph: 0, font: 0, x: i as f32 * 10.0, advance: 10.0 })
+            .collect();
+        TextMetrics { width: 40.0, ascent: 8.0, descent: 2.0, glyphs, len: 5 }
+    }
+
+    #[test]
+    fn test_caret_positions() {
+        let metrics = metrics();
+        assert_eq!(metrics.size(), Size::new(40.0, 10.0));
+        assert_eq!(metrics.caret_x(0), 0.0);
+        assert_eq!(metrics.caret_x(3), 20.0);
+        assert_eq!(metrics.caret_x(5), 40.0);
+    }
+
+    #[test]
+    fn test_hit_testing() {
+        let metrics = metrics();
+        assert_eq!(metrics.index_at(-5.0), 0);
+        assert_eq!(metrics.index_at(4.0), 0);
+        assert_eq!(metrics.index_at(6.0), 1);
+        assert_eq!(metrics.index_at(16.0), 3);
+        assert_eq!(metrics.index_at(100.0), 5);
+    }
+
+    #[test]
+    fn test_from_advances() {
+        assert_eq!(TextMetrics::from_advances("aé b", [10.0; 4], 8.0, 2.0), metrics());
+    }
+}
@@ -0,0 +1,436 @@
+//! Fonts and glyph rasterization.
+//!
+//! A [`Font`] is either a parsed TrueType/OpenType file, whose glyphs are
+//! rasterized with anti-aliasing into the shared glyph atlas, or the platform's
+//! default font, which is drawn natively by the backend. Fonts resolved from
+//! a [`FontDatabase`] borrow glyphs they lack from other installed fonts.
+
+pub mod ttf;
+pub mod metrics;
+pub mod database;
+
+pub use metrics::{TextMetrics, GlyphPosition};
+pub use database::{FontDatabase, FontQuery, FaceInfo};
+
+use super::raster::Mask;
+use super::{FillRule, Path, Point, Rect};
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use ttf::Face;
+
+/// Character advance assumed for the system font, relative to its size,
+/// when it is measured without a drawing context to ask.
+const SYSTEM_ADVANCE: f32 = 0.6;
+
+/// Most fonts a single font falls back to.
+const MAX_FALLBACK_FONTS: usize = u8::MAX as usize;
+
+/// Source of unique font identifiers; zero is the system font.
+static NEXT_FONT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Identifies a loaded font for the lifetime of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FontId(u32);
+
+impl FontId {
+    /// The platform's default font.
+    pub const SYSTEM: FontId = FontId(0);
+}
+
+/// A font for drawing text.
+///
+/// Fonts are cheap to clone; clones share the parsed tables.
+#[derive(Clone)]
+pub struct Font {
+    id: FontId,
+    loaded: Option<Arc<LoadedFont>>,
+    fallback: Option<Arc<Fallback>>,
+}
+
+/// Fonts searched for characters the primary font has no glyph for.
+struct Fallback {
+    database: FontDatabase,
+    query: FontQuery,
+    fonts: Mutex<Vec<Font>>,
+    /// Position in `fonts` plus one for each character looked up, or zero if none has it.
+    chars: Mutex<HashMap<char, u8>>,
+}
+
+/// A parsed font.
+struct LoadedFont {
+    face: Face,
+}
+
+impl Font {
+    /// The platform's default font, drawn by the native text renderer.
+    pub fn system() -> Self {
+        Self {
+            id: FontId::SYSTEM,
+            loaded: None,
+            fallback: None,
+        }
+    }
+
+    /// Parse a TrueType or OpenType font from memory.
+    pub fn from_bytes(data: impl Into<Vec<u8>>) -> Result<Font> {
+        let face = Face::parse(data.into())?;
+        Ok(Self {
+            id: FontId(NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed)),
+            loaded: Some(Arc::new(LoadedFont { face })),
+            fallback: None,
+        })
+    }
+
+    /// Load a TrueType or OpenType font file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Font> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .map_err(|e| Error::resource(format!("Failed to read font {}: {}", path.display(), e)))?;
+        Self::from_bytes(data)
+    }
+
+    /// Draw characters this font lacks with the closest face in `database`
+    /// that has them, preferring the families and style of `query`.
+    pub fn with_fallback(mut self, database: FontDatabase, query: FontQuery) -> Font {
+        if !self.is_system() {
+            self.fallback = Some(Arc::new(Fallback {
+                database,
+                query,
+                fonts: Mutex::new(Vec::new()),
+                chars: Mutex::new(HashMap::new()),
+            }));
+        }
+        self
+    }
+
+    /// The face of this font's family closest to `weight` and `italic`, when
+    /// the font was resolved from a [`FontDatabase`]; otherwise this font.
+    pub fn styled(&self, weight: u16, italic: bool) -> Font {
+        let Some(fallback) = &self.fallback else {
+            return self.clone();
+        };
+        let query = fallback.query.clone().weight(weight).italic(italic);
+        fallback.database.resolve(&query).unwrap_or_else(|| self.clone())
+    }
+
+    /// Weight class of the face; the system font counts as regular.
+    pub fn weight(&self) -> u16 {
+        self.face().map_or(400, Face::weight)
+    }
+
+    /// Check if the face is italic.
+    pub fn is_italic(&self) -> bool {
+        self.face().is_some_and(Face::is_italic)
+    }
+
+    /// Get the font's identifier.
+    pub fn id(&self) -> FontId {
+        self.id
+    }
+
+    /// Check if this is the platform's default font.
+    pub fn is_system(&self) -> bool {
+        self.loaded.is_none()
+    }
+
+    /// Map a character to a glyph, or `None` if the font has no glyph for it.
+    pub fn glyph_index(&self, ch: char) -> Option<u16> {
+        self.face()?.glyph_index(ch)
+    }
+
+    /// Distance from the baseline to the top of the line at `size` pixels.
+    pub fn ascent(&self, size: f32) -> f32 {
+        match self.face() {
+            Some(face) => face.ascender() as f32 * self.scale(size),
+            None => size * 0.8,
+        }
+    }
+
+    /// Distance from the baseline to the bottom of the line at `size` pixels.
+    pub fn descent(&self, size: f32) -> f32 {
+        match self.face() {
+            Some(face) => -(face.descender() as f32) * self.scale(size),
+            None => size * 0.2,
+        }
+    }
+
+    /// Recommended gap between lines at `size` pixels.
+    pub fn line_gap(&self, size: f32) -> f32 {
+        match self.face() {
+            Some(face) => face.line_gap() as f32 * self.scale(size),
+            None => size * 0.2,
+        }
+    }
+
+    /// Distance between consecutive baselines at `size` pixels.
+    pub fn line_height(&self, size: f32) -> f32 {
+        self.ascent(size) + self.descent(size) + self.line_gap(size)
+    }
+
+    /// Horizontal advance of a glyph at `size` pixels.
+    pub fn advance(&self, glyph: u16, size: f32) -> f32 {
+        self.face().map_or(0.0, |face| face.advance(glyph) as f32 * self.scale(size))
+    }
+
+    /// Kerning adjustment between two glyphs at `size` pixels.
+    pub fn kerning(&self, left: u16, right: u16, size: f32) -> f32 {
+        self.face().map_or(0.0, |face| face.kerning(left, right) as f32 * self.scale(size))
+    }
+
+    /// Outline of a glyph at `size` pixels, with its origin on the baseline and y pointing down.
+    pub fn glyph_path(&self, glyph: u16, size: f32) -> Path {
+        let mut path = Path::new();
+        let Some(face) = self.face() else {
+            return path;
+        };
+        let scale = self.scale(size);
+        let map = |point: Point| Point::new(point.x * scale, -point.y * scale);
+
+        for contour in face.outline(glyph) {
+            let Some(first) = contour.first() else {
+                continue;
+            };
+            // Start on an on-curve point, or between two off-curve points
+            let last = contour[contour.len() - 1];
+            let start = if first.on_curve {
+                first.point
+            } else if last.on_curve {
+                last.point
+            } else {
+                midpoint(first.point, last.point)
+            };
+            path.move_to(map(start));
+
+            let rest = if first.on_curve {
+                &contour[1..]
+            } else if last.on_curve {
+                &contour[..contour.len() - 1]
+            } else {
+                &contour[..]
+            };
+            let closing = ttf::OutlinePoint { point: start, on_curve: true };
+            let mut control: Option<Point> = None;
+            for point in rest.iter().chain(std::iter::once(&closing)) {
+                match (point.on_curve, control) {
+                    (true, Some(c)) => {
+                        path.quad_to(map(c), map(point.point));
+                        control = None;
+                    }
+                    (true, None) => {
+                        path.line_to(map(point.point));
+                    }
+                    (false, Some(c)) => {
+                        let middle = midpoint(c, point.point);
+                        path.quad_to(map(c), map(middle));
+                        control = Some(point.point);
+                    }
+                    (false, None) => control = Some(point.point),
+                }
+            }
+            path.close();
+        }
+        path
+    }
+
+    /// Measure a single line of text at `size` pixels.
+    ///
+    /// Only the platform knows the metrics of the system font, so it is
+    /// measured approximately here; [`Canvas::measure_text`](crate::Canvas::measure_text)
+    /// asks the backend that draws it instead.
+    pub fn measure(&self, text: &str, size: f32) -> TextMetrics {
+        let glyphs = self.layout(text, size);
+        TextMetrics {
+            width: glyphs.last().map_or(0.0, |glyph| glyph.x + glyph.advance),
+            ascent: self.ascent(size),
+            descent: self.descent(size),
+            glyphs,
+            len: text.len(),
+        }
+    }
+
+    /// Place the glyphs of a single line of text, applying advances and kerning.
+    pub(crate) fn layout(&self, text: &str, size: f32) -> Vec<GlyphPosition> {
+        let mut placed = Vec::with_capacity(text.len());
+        let mut x = 0.0;
+        let mut previous = None;
+        for (index, ch) in text.char_indices() {
+            let (font, glyph) = self.resolve_glyph(ch);
+            let fallback;
+            let source = if font == 0 {
+                self
+            } else {
+                fallback = self.fallback_font(font);
+                &fallback
+            };
+            // Kerning only applies between glyphs of the same font
+            if let Some((_, previous_glyph)) = previous.filter(|&(previous_font, _)| previous_font == font) {
+                x += source.kerning(previous_glyph, glyph, size);
+            }
+            let advance = if self.is_system() { size * SYSTEM_ADVANCE } else { source.advance(glyph, size) };
+            placed.push(GlyphPosition { index, glyph, font, x, advance });
+            x += advance;
+            previous = Some((font, glyph));
+        }
+        placed
+    }
+
+    /// The font supplying a character's glyph, as a fallback position, and the glyph.
+    ///
+    /// Position zero is this font; characters no font has use its missing glyph.
+    fn resolve_glyph(&self, ch: char) -> (u8, u16) {
+        if let Some(glyph) = self.glyph_index(ch) {
+            return (0, glyph);
+        }
+        let Some(fallback) = &self.fallback else {
+            return (0, 0);
+        };
+
+        let position = *fallback.chars.lock().unwrap().entry(ch).or_insert_with(|| {
+            let Some(font) = fallback.database.fallback(ch, &fallback.query).and_then(|face| face.font()) else {
+                return 0;
+            };
+            let mut fonts = fallback.fonts.lock().unwrap();
+            match fonts.iter().position(|existing| existing.id == font.id) {
+                Some(i) => i as u8 + 1,
+                None if fonts.len() < MAX_FALLBACK_FONTS => {
+                    fonts.push(font);
+                    fonts.len() as u8
+                }
+                None => 0,
+            }
+        });
+        if position == 0 {
+            return (0, 0);
+        }
+        let glyph = self.fallback_font(position).glyph_index(ch).unwrap_or(0);
+        (position, glyph)
+    }
+
+    /// The font at a fallback position, as stored in [`GlyphPosition::font`].
+    pub fn fallback_font(&self, position: u8) -> Font {
+        let fallback = position
+            .checked_sub(1)
+            .and_then(|i| self.fallback.as_ref()?.fonts.lock().unwrap().get(i as usize).cloned());
+        fallback.unwrap_or_else(|| self.clone())
+    }
+
+    /// Rasterize the coverage of a glyph whose origin sits at `(0, 0)` plus
+    /// a fractional horizontal offset.
+    ///
+    /// Glyphs are cached by the [`GlyphAtlas`](super::GlyphAtlas), not here.
+    pub(crate) fn glyph_mask(&self, glyph: u16, size: f32, offset: f32) -> Option<Mask> {
+        let path = self.glyph_path(glyph, size).transform(super::Transform::translate(offset, 0.0));
+        if path.is_empty() {
+            return None;
+        }
+        let bounds = path.bounds();
+        let clip = Rect::new(bounds.x - 1.0, bounds.y - 1.0, bounds.width + 2.0, bounds.height + 2.0);
+        Mask::from_path(&path, FillRule::NonZero, clip)
+    }
+
+    pub(crate) fn face(&self) -> Option<&Face> {
+        self.loaded.as_ref().map(|loaded| &loaded.face)
+    }
+
+    /// Get the font file a loaded font was parsed from.
+    pub(crate) fn data(&self) -> Option<&[u8]> {
+        self.face().map(Face::data)
+    }
+
+    fn scale(&self, size: f32) -> f32 {
+        self.face().map_or(0.0, |face| size / face.units_per_em() as f32)
+    }
+}
+
+impl Default for Font {
+    /// The installed sans-serif face, so text is measured with the glyphs it
+    /// is drawn with, or the system font when no font is installed.
+    fn default() -> Self {
+        platform_face().cloned().unwrap_or_else(Self::system)
+    }
+}
+
+/// The installed face standing in for the system font where the platform
+/// cannot draw or measure it, resolved once per process.
+pub(crate) fn platform_face() -> Option<&'static Font> {
+    static FACE: OnceLock<Option<Font>> = OnceLock::new();
+    FACE.get_or_init(|| FontDatabase::system().resolve(&FontQuery::default())).as_ref()
+}
+
+impl PartialEq for Font {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl std::fmt::Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font")
+            .field("id", &self.id)
+            .field("glyph_count", &self.face().map_or(0, Face::glyph_count))
+            .finish()
+    }
+}
+
+fn midpoint(a: Point, b: Point) -> Point {
+    Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_metrics() {
+        let font = Font::from_bytes(ttf::testing::test_font()).unwrap();
+        assert_ne!(font.id(), FontId::SYSTEM);
+        assert_ne!(font.id(), Font::from_bytes(ttf::testing::test_font()).unwrap().id());
+        assert!(Font::system().is_system());
+
+        assert_eq!(font.ascent(20.0), 16.0);
+        assert_eq!(font.descent(20.0), 4.0);
+        assert_eq!(font.advance(1, 20.0), 12.0);
+    }
+
+    #[test]
+    fn test_layout_applies_kerning() {
+        let font = Font::from_bytes(ttf::testing::test_font()).unwrap();
+        let metrics = font.measure("ABA?", 10.0);
+        let xs: Vec<f32> = metrics.glyphs.iter().map(|glyph| glyph.x).collect();
+
+        // 'A' advances 6, the A-B pair kerns by -0.8, 'B' advances 4.5
+        assert_eq!(metrics.glyphs[3].glyph, 0);
+        assert!((xs[1] - 5.2).abs() < 1e-4 && (xs[2] - 9.7).abs() < 1e-4 && (xs[3] - 15.7).abs() < 1e-4);
+        assert!((metrics.width - 20.7).abs() < 1e-4);
+        assert_eq!((metrics.ascent, metrics.descent), (8.0, 2.0));
+    }
+
+    #[test]
+    fn test_system_font_metrics() {
+        let metrics = Font::system().measure("héllo", 10.0);
+        assert_eq!(metrics.width, 30.0);
+        assert_eq!(metrics.glyphs[2].index, 3);
+        assert_eq!(Font::system().line_height(10.0), 12.0);
+    }
+
+    #[test]
+    fn test_glyph_rasterization() {
+        let font = Font::from_bytes(ttf::testing::test_font()).unwrap();
+
+        // The square covers 5×5 pixels above the baseline at 10px
+        let path = font.glyph_path(1, 10.0);
+        assert_eq!(path.bounds(), Rect::new(0.0, -5.0, 5.0, 5.0));
+        let mask = font.glyph_mask(1, 10.0, 0.0).unwrap();
+        assert_eq!((mask.x, mask.y), (0, -5));
+        assert!((mask.get(2, 2) - 1.0).abs() < 1e-4);
+
+        // Empty glyphs have no mask
+        assert!(font.glyph_mask(0, 10.0, 0.0).is_none());
+
+        // The curved glyph has a single quadratic contour
+        let curved = font.glyph_path(2, 10.0);
+        assert!(curved.elements().iter().any(|element| matches!(element, crate::drawing::PathElement::QuadTo(..))));
+    }
+}
@@ -0,0 +1,808 @@
+//! TrueType and OpenType font parsing.
+//!
+//! Reads the tables needed to lay out and draw text: `cmap` for character
+//! mapping, `glyf` and `loca` for outlines, `hhea` and `hmtx` for metrics and
+//! `kern` for pair kerning, plus `name` and `OS/2` to describe the face.
+//! Fonts with CFF outlines are not supported.
+
+use crate::drawing::{Point, Transform};
+use crate::{Error, Result};
+use std::ops::Range;
+
+/// Composite glyphs nested deeper than this are treated as broken.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// A single outline point in font units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlinePoint {
+    pub point: Point,
+    pub on_curve: bool,
+}
+
+/// A parsed TrueType font face.
+#[derive(Debug)]
+pub struct Face {
+    data: Vec<u8>,
+    units_per_em: u16,
+    glyph_count: u16,
+    ascender: i16,
+    descender: i16,
+    line_gap: i16,
+    metric_count: u16,
+    long_loca: bool,
+    cmap: Option<usize>,
+    loca: Range<usize>,
+    glyf: Range<usize>,
+    hmtx: Range<usize>,
+    kern: Option<Range<usize>>,
+    family: Option<String>,
+    weight: u16,
+    italic: bool,
+}
+
+impl Face {
+    /// Parse a TrueType font, or the first face of a font collection.
+    pub fn parse(data: Vec<u8>) -> Result<Face> {
+        let start = match data.get(0..4) {
+            Some(b"ttcf") => read_u32(&data, 12).ok_or_else(truncated)? as usize,
+            Some(_) => 0,
+            None => return Err(truncated()),
+        };
+        match read_u32(&data, start) {
+            Some(0x0001_0000) | Some(0x7472_7565) => {}
+            Some(0x4f54_544f) => return Err(Error::resource("Fonts with CFF outlines are not supported")),
+            _ => return Err(Error::resource("Not a TrueType or OpenType font")),
+        }
+
+        let table_count = read_u16(&data, start + 4).ok_or_else(truncated)? as usize;
+        let find = |tag: &[u8; 4]| -> Option<Range<usize>> {
+            (0..table_count).find_map(|i| {
+                let record = start + 12 + i * 16;
+                if data.get(record..record + 4)? != tag {
+                    return None;
+                }
+                let offset = read_u32(&data, record + 8)? as usize;
+                let length = read_u32(&data, record + 12)? as usize;
+                let end = offset.checked_add(length)?;
+                (end <= data.len()).then_some(offset..end)
+            })
+        };
+        let require = |tag: &[u8; 4]| {
+            find(tag).ok_or_else(|| Error::resource(format!("Font is missing the '{}' table", String::from_utf8_lossy(tag))))
+        };
+
+        let head = require(b"head")?;
+        let hhea = require(b"hhea")?;
+        let maxp = require(b"maxp")?;
+        let hmtx = require(b"hmtx")?;
+        let loca = require(b"loca")?;
+        let glyf = require(b"glyf")?;
+        let cmap = find(b"cmap").and_then(|cmap| select_cmap(&data, cmap));
+        let kern = find(b"kern").and_then(|kern| select_kern(&data, kern));
+        let family = find(b"name").and_then(|name| family_name(&data, name));
+        let os2 = find(b"OS/2");
+        let mac_style = read_u16(&data, head.start + 44).unwrap_or(0);
+        let weight = os2
+            .as_ref()
+            .and_then(|os2| read_u16(&data, os2.start + 4))
+            .filter(|weight| (1..=1000).contains(weight))
+            .unwrap_or(if mac_style & 0x01 != 0 { 700 } else { 400 });
+        let italic = match os2.as_ref().and_then(|os2| read_u16(&data, os2.start + 62)) {
+            Some(selection) => selection & 0x01 != 0,
+            None => mac_style & 0x02 != 0,
+        };
+
+        let units_per_em = read_u16(&data, head.start + 18).ok_or_else(truncated)?;
+        if !(16..=16384).contains(&units_per_em) {
+            return Err(Error::resource(format!("Invalid font units per em {}", units_per_em)));
+        }
+        let metric_count = read_u16(&data, hhea.start + 34).ok_or_else(truncated)?;
+        if metric_count == 0 {
+            return Err(Error::resource("Font has no horizontal metrics"));
+        }
+
+        Ok(Face {
+            units_per_em,
+            glyph_count: read_u16(&data, maxp.start + 4).ok_or_else(truncated)?,
+            ascender: read_i16(&data, hhea.start + 4).ok_or_else(truncated)?,
+            descender: read_i16(&data, hhea.start + 6).ok_or_else(truncated)?,
+            line_gap: read_i16(&data, hhea.start + 8).ok_or_else(truncated)?,
+            metric_count,
+            long_loca: read_i16(&data, head.start + 50).ok_or_else(truncated)? != 0,
+            cmap,
+            loca,
+            glyf,
+            hmtx,
+            kern,
+            family,
+            weight,
+            italic,
+            data,
+        })
+    }
+
+    /// The font file the face was parsed from.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Font design units per em.
+    pub fn units_per_em(&self) -> u16 {
+        self.units_per_em
+    }
+
+    /// Number of glyphs in the font.
+    pub fn glyph_count(&self) -> u16 {
+        self.glyph_count
+    }
+
+    /// Distance from the baseline to the top of the tallest glyphs, in font units.
+    pub fn ascender(&self) -> i16 {
+        self.ascender
+    }
+
+    /// Distance from the baseline to the bottom of the lowest glyphs, in font units (usually negative).
+    pub fn descender(&self) -> i16 {
+        self.descender
+    }
+
+    /// Extra spacing between lines, in font units.
+    pub fn line_gap(&self) -> i16 {
+        self.line_gap
+    }
+
+    /// Family name from the `name` table, such as "DejaVu Sans".
+    pub fn family(&self) -> Option<&str> {
+        self.family.as_deref()
+    }
+
+    /// Weight class, from 100 (thin) to 900 (black); 400 is regular.
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// Check if the face is italic or oblique.
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    /// Ranges of characters the font has glyphs for, as inclusive code point pairs.
+    pub fn coverage(&self) -> Vec<(u32, u32)> {
+        let mut ranges: Vec<(u32, u32)> = Vec::new();
+        for (first, last) in self.cmap_ranges() {
+            for code in first..=last {
+                if char::from_u32(code).and_then(|ch| self.glyph_index(ch)).is_none() {
+                    continue;
+                }
+                match ranges.last_mut() {
+                    Some(range) if range.1 + 1 == code => range.1 = code,
+                    _ => ranges.push((code, code)),
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Character ranges listed by the cmap subtable, which may include unmapped codes.
+    fn cmap_ranges(&self) -> Vec<(u32, u32)> {
+        let data = &self.data;
+        let Some(table) = self.cmap else {
+            return Vec::new();
+        };
+        let ranges = match read_u16(data, table) {
+            Some(0) => Some(vec![(0, 255)]),
+            Some(4) => read_u16(data, table + 6).map(|count| {
+                let segments = count as usize / 2;
+                (0..segments)
+                    .filter_map(|i| {
+                        let end = read_u16(data, table + 14 + i * 2)?;
+                        let start = read_u16(data, table + 16 + segments * 2 + i * 2)?;
+                        Some((u32::from(start), u32::from(end)))
+                    })
+                    .collect()
+            }),
+            Some(6) => read_u16(data, table + 6)
+                .zip(read_u16(data, table + 8))
+                .filter(|&(_, count)| count > 0)
+                .map(|(first, count)| vec![(u32::from(first), u32::from(first) + u32::from(count) - 1)]),
+            Some(12) => read_u32(data, table + 12).map(|groups| {
+                (0..groups as usize)
+                    .filter_map(|i| {
+                        let group = table + 16 + i * 12;
+                        Some((read_u32(data, group)?, read_u32(data, group + 4)?.min(0x10FFFF)))
+                    })
+                    .collect()
+            }),
+            _ => None,
+        };
+        let mut ranges: Vec<(u32, u32)> = ranges.unwrap_or_default().into_iter().filter(|&(first, last)| first <= last).collect();
+        ranges.sort_unstable();
+        ranges
+    }
+
+    /// Map a character to a glyph index, or `None` if the font has no glyph for it.
+    pub fn glyph_index(&self, ch: char) -> Option<u16> {
+        let data = &self.data;
+        let table = self.cmap?;
+        let code = ch as u32;
+        let glyph = match read_u16(data, table)? {
+            0 => {
+                let index = usize::try_from(code).ok().filter(|&code| code < 256)?;
+                u16::from(*data.get(table + 6 + index)?)
+            }
+            4 => {
+                let code = u16::try_from(code).ok()?;
+                let segments = read_u16(data, table + 6)? as usize / 2;
+                let ends = table + 14;
+                let starts = ends + segments * 2 + 2;
+                let deltas = starts + segments * 2;
+                let range_offsets = deltas + segments * 2;
+
+                // Segments are sorted by end code
+                let (mut low, mut high) = (0, segments);
+                while low < high {
+                    let middle = (low + high) / 2;
+                    if read_u16(data, ends + middle * 2)? < code {
+                        low = middle + 1;
+                    } else {
+                        high = middle;
+                    }
+                }
+                let segment = low;
+                if segment >= segments || read_u16(data, starts + segment * 2)? > code {
+                    return None;
+                }
+                let start = read_u16(data, starts + segment * 2)?;
+                let delta = read_u16(data, deltas + segment * 2)?;
+                let range_offset_position = range_offsets + segment * 2;
+                let range_offset = read_u16(data, range_offset_position)? as usize;
+                if range_offset == 0 {
+                    code.wrapping_add(delta)
+                } else {
+                    let glyph = read_u16(data, range_offset_position + range_offset + (code - start) as usize * 2)?;
+                    if glyph == 0 {
+                        return None;
+                    }
+                    glyph.wrapping_add(delta)
+                }
+            }
+            6 => {
+                let first = u32::from(read_u16(data, table + 6)?);
+                let count = u32::from(read_u16(data, table + 8)?);
+                let index = code.checked_sub(first).filter(|&index| index < count)?;
+                read_u16(data, table + 10 + index as usize * 2)?
+            }
+            12 => {
+                let groups = read_u32(data, table + 12)? as usize;
+                let (mut low, mut high) = (0, groups);
+                while low < high {
+                    let middle = (low + high) / 2;
+                    let group = table + 16 + middle * 12;
+                    if code < read_u32(data, group)? {
+                        high = middle;
+                    } else if code > read_u32(data, group + 4)? {
+                        low = middle + 1;
+                    } else {
+                        let glyph = read_u32(data, group + 8)? + (code - read_u32(data, group)?);
+                        return u16::try_from(glyph).ok().filter(|&glyph| glyph != 0 && glyph < self.glyph_count);
+                    }
+                }
+                return None;
+            }
+            _ => return None,
+        };
+        (glyph != 0 && glyph < self.glyph_count).then_some(glyph)
+    }
+
+    /// Horizontal advance of a glyph, in font units.
+    pub fn advance(&self, glyph: u16) -> u16 {
+        let index = glyph.min(self.metric_count - 1) as usize;
+        read_u16(&self.data, self.hmtx.start + index * 4)
+            .filter(|_| self.hmtx.start + index * 4 + 2 <= self.hmtx.end)
+            .unwrap_or(0)
+    }
+
+    /// Kerning adjustment between two glyphs, in font units.
+    pub fn kerning(&self, left: u16, right: u16) -> i16 {
+        let Some(ref kern) = self.kern else {
+            return 0;
+        };
+        let pairs = (kern.end - kern.start) / 6;
+        let key = u32::from(left) << 16 | u32::from(right);
+        let (mut low, mut high) = (0, pairs);
+        while low < high {
+            let middle = (low + high) / 2;
+            let pair = kern.start + middle * 6;
+            match read_u32(&self.data, pair).map(|candidate| candidate.cmp(&key)) {
+                Some(std::cmp::Ordering::Less) => low = middle + 1,
+                Some(std::cmp::Ordering::Greater) => high = middle,
+                Some(std::cmp::Ordering::Equal) => return read_i16(&self.data, pair + 4).unwrap_or(0),
+                None => return 0,
+            }
+        }
+        0
+    }
+
+    /// Outline contours of a glyph in font units, with y pointing up.
+    ///
+    /// Returns an empty list for glyphs without outlines such as spaces.
+    pub fn outline(&self, glyph: u16) -> Vec<Vec<OutlinePoint>> {
+        let mut contours = Vec::new();
+        self.append_outline(glyph, 0, &mut contours);
+        contours
+    }
+
+    /// Byte range of a glyph within the `glyf` table.
+    fn glyph_range(&self, glyph: u16) -> Option<Range<usize>> {
+        if glyph >= self.glyph_count {
+            return None;
+        }
+        let index = glyph as usize;
+        let (start, end) = if self.long_loca {
+            (read_u32(&self.data, self.loca.start + index * 4)? as usize, read_u32(&self.data, self.loca.start + index * 4 + 4)? as usize)
+        } else {
+            (read_u16(&self.data, self.loca.start + index * 2)? as usize * 2, read_u16(&self.data, self.loca.start + index * 2 + 2)? as usize * 2)
+        };
+        let range = self.glyf.start + start..self.glyf.start + end;
+        (start < end && range.end <= self.glyf.end).then_some(range)
+    }
+
+    /// Append a glyph's contours, expanding composite glyphs.
+    fn append_outline(&self, glyph: u16, depth: usize, contours: &mut Vec<Vec<OutlinePoint>>) -> Option<()> {
+        let range = self.glyph_range(glyph)?;
+        let data = &self.data[range];
+        let contour_count = read_i16(data, 0)?;
+        if contour_count >= 0 {
+            contours.extend(simple_outline(data, contour_count as usize)?);
+            return Some(());
+        }
+        if depth >= MAX_COMPONENT_DEPTH {
+            return None;
+        }
+
+        // Composite glyph: a list of transformed references to other glyphs
+        let mut offset = 10;
+        loop {
+            let flags = read_u16(data, offset)?;
+            let component = read_u16(data, offset + 2)?;
+            offset += 4;
+            let (dx, dy) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                offset += 4;
+                (read_i16(data, offset - 4)? as f32, read_i16(data, offset - 2)? as f32)
+            } else {
+                offset += 2;
+                (*data.get(offset - 2)? as i8 as f32, *data.get(offset - 1)? as i8 as f32)
+            };
+            // Anchor-point alignment is rare and unsupported; such components are not offset
+            let (dx, dy) = if flags & ARGS_ARE_XY_VALUES != 0 { (dx, dy) } else { (0.0, 0.0) };
+
+            let mut transform = Transform::translate(dx, dy);
+            if flags & WE_HAVE_A_SCALE != 0 {
+                let scale = read_f2dot14(data, offset)?;
+                (transform.a, transform.d) = (scale, scale);
+                offset += 2;
+            } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                (transform.a, transform.d) = (read_f2dot14(data, offset)?, read_f2dot14(data, offset + 2)?);
+                offset += 4;
+            } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                transform.a = read_f2dot14(data, offset)?;
+                transform.b = read_f2dot14(data, offset + 2)?;
+                transform.c = read_f2dot14(data, offset + 4)?;
+                transform.d = read_f2dot14(data, offset + 6)?;
+                offset += 8;
+            }
+
+            let first = contours.len();
+            self.append_outline(component, depth + 1, contours);
+            for point in contours[first..].iter_mut().flatten() {
+                point.point = transform.transform_point(point.point);
+            }
+
+            if flags & MORE_COMPONENTS == 0 {
+                return Some(());
+            }
+        }
+    }
+}
+
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const REPEAT_FLAG: u8 = 0x08;
+const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+/// Decode the contours of a simple glyph.
+fn simple_outline(data: &[u8], contour_count: usize) -> Option<Vec<Vec<OutlinePoint>>> {
+    if contour_count == 0 {
+        return Some(Vec::new());
+    }
+    let ends: Vec<usize> = (0..contour_count)
+        .map(|i| read_u16(data, 10 + i * 2).map(usize::from))
+        .collect::<Option<_>>()?;
+    let point_count = ends.last()? + 1;
+    let instruction_length = read_u16(data, 10 + contour_count * 2)? as usize;
+    let mut offset = 12 + contour_count * 2 + instruction_length;
+
+    let mut flags = Vec::with_capacity(point_count);
+    while flags.len() < point_count {
+        let flag = *data.get(offset)?;
+        offset += 1;
+        let repeat = if flag & REPEAT_FLAG != 0 {
+            offset += 1;
+            *data.get(offset - 1)? as usize
+        } else {
+            0
+        };
+        flags.extend(std::iter::repeat_n(flag, repeat + 1));
+    }
+    flags.truncate(point_count);
+
+    let mut read_coordinates = |short: u8, same_or_positive: u8| -> Option<Vec<f32>> {
+        let mut value = 0i32;
+        flags
+            .iter()
+            .map(|&flag| {
+                if flag & short != 0 {
+                    let delta = i32::from(*data.get(offset)?);
+                    offset += 1;
+                    value += if flag & same_or_positive != 0 { delta } else { -delta };
+                } else if flag & same_or_positive == 0 {
+                    value += i32::from(read_i16(data, offset)?);
+                    offset += 2;
+                }
+                Some(value as f32)
+            })
+            .collect()
+    };
+    let xs = read_coordinates(X_SHORT_VECTOR, X_IS_SAME_OR_POSITIVE)?;
+    let ys = read_coordinates(Y_SHORT_VECTOR, Y_IS_SAME_OR_POSITIVE)?;
+
+    let mut contours = Vec::with_capacity(contour_count);
+    let mut start = 0;
+    for end in ends {
+        if end < start || end >= point_count {
+            return None;
+        }
+        contours.push(
+            (start..=end)
+                .map(|i| OutlinePoint {
+                    point: Point::new(xs[i], ys[i]),
+                    on_curve: flags[i] & ON_CURVE_POINT != 0,
+                })
+                .collect(),
+        );
+        start = end + 1;
+    }
+    Some(contours)
+}
+
+/// Pick the most complete Unicode subtable from a `cmap` table.
+fn select_cmap(data: &[u8], cmap: Range<usize>) -> Option<usize> {
+    let count = read_u16(data, cmap.start + 2)? as usize;
+    (0..count)
+        .filter_map(|i| {
+            let record = cmap.start + 4 + i * 8;
+            let platform = read_u16(data, record)?;
+            let encoding = read_u16(data, record + 2)?;
+            let table = cmap.start + read_u32(data, record + 4)? as usize;
+            let format = read_u16(data, table)?;
+            if table >= cmap.end || !matches!(format, 0 | 4 | 6 | 12) {
+                return None;
+            }
+            let rank = match (platform, encoding, format) {
+                (3, 10, 12) | (0, _, 12) => 0,
+                (3, 1, _) | (0, _, _) => 1,
+                (3, 0, _) => 2,
+                _ => 3,
+            };
+            Some((rank, table))
+        })
+        .min_by_key(|&(rank, _)| rank)
+        .map(|(_, table)| table)
+}
+
+/// Read the family name from a `name` table, preferring the typographic family.
+fn family_name(data: &[u8], name: Range<usize>) -> Option<String> {
+    let count = read_u16(data, name.start + 2)? as usize;
+    let strings = name.start + read_u16(data, name.start + 4)? as usize;
+    let mut best: Option<(u8, String)> = None;
+    for i in 0..count {
+        let record = name.start + 6 + i * 12;
+        let platform = read_u16(data, record)?;
+        let encoding = read_u16(data, record + 2)?;
+        let language = read_u16(data, record + 4)?;
+        let name_id = read_u16(data, record + 6)?;
+        let length = read_u16(data, record + 8)? as usize;
+        let offset = strings + read_u16(data, record + 10)? as usize;
+        let bytes = data.get(offset..offset + length)?;
+
+        let text = match (platform, encoding) {
+            (0, _) | (3, 0 | 1 | 10) => {
+                let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+                String::from_utf16_lossy(&units)
+            }
+            // Mac Roman; family names are almost always ASCII
+            (1, 0) => bytes.iter().map(|&byte| if byte.is_ascii() { byte as char } else { '?' }).collect(),
+            _ => continue,
+        };
+        let rank = match (name_id, platform, language) {
+            (16, 3, 0x409) => 0,
+            (16, _, _) => 1,
+            (1, 3, 0x409) => 2,
+            (1, _, _) => 3,
+            _ => continue,
+        };
+        if !text.is_empty() && best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) {
+            best = Some((rank, text));
+        }
+    }
+    best.map(|(_, text)| text)
+}
+
+/// Find the pair list of the first horizontal format 0 subtable in a `kern` table.
+fn select_kern(data: &[u8], kern: Range<usize>) -> Option<Range<usize>> {
+    // Only the Microsoft layout (version 0) is read; Apple's version 1 tables are ignored
+    if read_u16(data, kern.start)? != 0 {
+        return None;
+    }
+    let count = read_u16(data, kern.start + 2)?;
+    let mut subtable = kern.start + 4;
+    for _ in 0..count {
+        let length = read_u16(data, subtable + 2)? as usize;
+        let coverage = read_u16(data, subtable + 4)?;
+        // Horizontal, format 0, not minimum values and not cross-stream
+        if coverage & 0xff07 == 0x0001 {
+            let pairs = read_u16(data, subtable + 6)? as usize;
+            let start = subtable + 14;
+            let end = (start + pairs * 6).min(kern.end);
+            return Some(start..end);
+        }
+        subtable += length.max(6);
+    }
+    None
+}
+
+fn truncated() -> Error {
+    Error::resource("Font file is truncated")
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|value| value as i16)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_f2dot14(data: &[u8], offset: usize) -> Option<f32> {
+    read_i16(data, offset).map(|value| value as f32 / 16384.0)
+}
+
+/// Builds small TrueType files for tests.
+#[cfg(test)]
+pub(crate) mod testing {
+    /// A font with glyphs for 'A' (a square), 'B' (a triangle of quadratic
+    /// curves), and 'C' (a composite of 'A' shifted right and scaled), plus a
+    /// kerning pair between 'A' and 'B'.
+    pub(crate) fn test_font() -> Vec<u8> {
+        build('A', None)
+    }
+
+    /// A font like [`test_font`] mapping `first` and the two characters after
+    /// it, with a family name, weight class and italic flag.
+    pub(crate) fn named_font(family: &str, weight: u16, italic: bool, first: char) -> Vec<u8> {
+        build(first, Some((family, weight, italic)))
+    }
+
+    fn build(first: char, style: Option<(&str, u16, bool)>) -> Vec<u8> {
+        let first = first as i32;
+        let be16 = |value: i32| (value as u16).to_be_bytes();
+        let be32 = |value: u32| value.to_be_bytes();
+
+        // Glyph 1: a 500-unit square
+        let mut square = Vec::new();
+        for value in [1, 0, 0, 500, 500] {
+            square.extend_from_slice(&be16(value));
+        }
+        square.extend_from_slice(&be16(3)); // end point of the only contour
+        square.extend_from_slice(&be16(0)); // no instructions
+        square.extend_from_slice(&[0x01, 0x01, 0x01, 0x01]);
+        for delta in [0, 0, 500, 0] {
+            square.extend_from_slice(&be16(delta));
+        }
+        for delta in [0, 500, 0, -500] {
+            square.extend_from_slice(&be16(delta));
+        }
+
+        // Glyph 2: on, off, on, off points forming a curved shape
+        let mut curved = Vec::new();
+        for value in [1, 0, 0, 400, 400] {
+            curved.extend_from_slice(&be16(value));
+        }
+        curved.extend_from_slice(&be16(3));
+        curved.extend_from_slice(&be16(0));
+        curved.extend_from_slice(&[0x01, 0x00, 0x01, 0x00]);
+        for delta in [0, 200, 200, -200] {
+            curved.extend_from_slice(&be16(delta));
+        }
+        for delta in [0, 400, -400, -200] {
+            curved.extend_from_slice(&be16(delta));
+        }
+
+        // Glyph 3: glyph 1 offset by (100, 50) and scaled by one half
+        let mut composite = Vec::new();
+        for value in [-1, 0, 0, 350, 300] {
+            composite.extend_from_slice(&be16(value));
+        }
+        composite.extend_from_slice(&be16(0x0001 | 0x0002 | 0x0008));
+        composite.extend_from_slice(&be16(1));
+        composite.extend_from_slice(&be16(100));
+        composite.extend_from_slice(&be16(50));
+        composite.extend_from_slice(&be16(0x2000));
+
+        let mut glyf = Vec::new();
+        let mut loca = vec![0u32];
+        for glyph in [&Vec::new(), &square, &curved, &composite] {
+            glyf.extend_from_slice(glyph);
+            if glyf.len() % 2 == 1 {
+                glyf.push(0);
+            }
+            loca.push(glyf.len() as u32);
+        }
+        let loca: Vec<u8> = loca.iter().flat_map(|&offset| be32(offset)).collect();
+
+        let mut head = vec![0u8; 54];
+        head[18..20].copy_from_slice(&be16(1000));
+        head[50..52].copy_from_slice(&be16(1));
+
+        let mut hhea = vec![0u8; 36];
+        hhea[4..6].copy_from_slice(&be16(800));
+        hhea[6..8].copy_from_slice(&be16(-200));
+        hhea[8..10].copy_from_slice(&be16(100));
+        hhea[34..36].copy_from_slice(&be16(3));
+
+        let mut maxp = vec![0u8; 6];
+        maxp[0..4].copy_from_slice(&be32(0x5000));
+        maxp[4..6].copy_from_slice(&be16(4));
+
+        // Glyphs 0 to 2 have their own advances, glyph 3 reuses the last one
+        let mut hmtx = Vec::new();
+        for advance in [500, 600, 450] {
+            hmtx.extend_from_slice(&be16(advance));
+            hmtx.extend_from_slice(&be16(0));
+        }
+        hmtx.extend_from_slice(&be16(0));
+
+        // Format 4 subtable mapping three characters from `first` to glyphs 1..=3
+        let mut cmap = Vec::new();
+        cmap.extend_from_slice(&be16(0));
+        cmap.extend_from_slice(&be16(1));
+        cmap.extend_from_slice(&be16(3));
+        cmap.extend_from_slice(&be16(1));
+        cmap.extend_from_slice(&be32(12));
+        for value in [4, 32, 0, 4, 4, 1, 0, first + 2, 0xffff, 0, first, 0xffff, 1 - first, 1, 0, 0] {
+            cmap.extend_from_slice(&be16(value));
+        }
+
+        let mut kern = Vec::new();
+        for value in [0, 1, 0, 20, 0x0001, 1, 6, 0, 0, 1, 2, -80] {
+            kern.extend_from_slice(&be16(value));
+        }
+
+        let mut tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"cmap", cmap),
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"kern", kern),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ];
+        if let Some((family, weight, italic)) = style {
+            let mut os2 = vec![0u8; 78];
+            os2[4..6].copy_from_slice(&be16(i32::from(weight)));
+            os2[62..64].copy_from_slice(&be16(i32::from(italic)));
+
+            // One Windows Unicode family name record
+            let family: Vec<u8> = family.encode_utf16().flat_map(|unit| unit.to_be_bytes()).collect();
+            let mut name = Vec::new();
+            for value in [0, 1, 18, 3, 1, 0x409, 1, family.len() as i32, 0] {
+                name.extend_from_slice(&be16(value));
+            }
+            name.extend_from_slice(&family);
+
+            tables.insert(0, (b"OS/2", os2));
+            tables.push((b"name", name));
+        }
+        let mut font = Vec::new();
+        font.extend_from_slice(&be32(0x0001_0000));
+        font.extend_from_slice(&be16(tables.len() as i32));
+        font.extend_from_slice(&[0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, table) in &tables {
+            font.extend_from_slice(*tag);
+            font.extend_from_slice(&be32(0));
+            font.extend_from_slice(&be32(offset as u32));
+            font.extend_from_slice(&be32(table.len() as u32));
+            offset += table.len().div_ceil(4) * 4;
+        }
+        for (_, table) in &tables {
+            font.extend_from_slice(table);
+            font.resize(font.len().div_ceil(4) * 4, 0);
+        }
+        font
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_and_mapping() {
+        let face = Face::parse(testing::test_font()).unwrap();
+        assert_eq!(face.units_per_em(), 1000);
+        assert_eq!((face.ascender(), face.descender(), face.line_gap()), (800, -200, 100));
+
+        assert_eq!(face.glyph_index('A'), Some(1));
+        assert_eq!(face.glyph_index('C'), Some(3));
+        assert_eq!(face.glyph_index('D'), None);
+        assert_eq!(face.glyph_index('\u{1F600}'), None);
+
+        assert_eq!(face.advance(1), 600);
+        assert_eq!(face.advance(3), 450);
+        assert_eq!(face.kerning(1, 2), -80);
+        assert_eq!(face.kerning(2, 1), 0);
+        assert_eq!((face.family(), face.weight(), face.is_italic()), (None, 400, false));
+    }
+
+    #[test]
+    fn test_style_and_coverage() {
+        let face = Face::parse(testing::named_font("Test Sans", 700, true, 'x')).unwrap();
+        assert_eq!(face.family(), Some("Test Sans"));
+        assert_eq!(face.weight(), 700);
+        assert!(face.is_italic());
+        assert_eq!(face.coverage(), vec![(0x78, 0x7a)]);
+    }
+
+    #[test]
+    fn test_outlines() {
+        let face = Face::parse(testing::test_font()).unwrap();
+        assert!(face.outline(0).is_empty());
+
+        let square = face.outline(1);
+        assert_eq!(square.len(), 1);
+        let points: Vec<_> = square[0].iter().map(|p| (p.point.x, p.point.y)).collect();
+        assert_eq!(points, vec![(0.0, 0.0), (0.0, 500.0), (500.0, 500.0), (500.0, 0.0)]);
+
+        let curved = face.outline(2);
+        assert_eq!(curved[0].iter().filter(|p| !p.on_curve).count(), 2);
+
+        // The composite is the square scaled by one half, then offset
+        let composite = face.outline(3);
+        assert_eq!(composite[0][2].point, Point::new(350.0, 300.0));
+    }
+
+    #[test]
+    fn test_rejects_invalid_fonts() {
+        assert!(Face::parse(b"OTTO\x00\x00".to_vec()).is_err());
+        assert!(Face::parse(b"not a font".to_vec()).is_err());
+
+        let mut truncated = testing::test_font();
+        truncated.truncate(100);
+        assert!(Face::parse(truncated).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Bitmap images for the drawing canvas.
+//!
+//! Images store straight (non-premultiplied) RGBA8 pixels in row-major order.
+//! PNG, BMP and PPM files can be decoded with `Image::decode` or `Image::load`,
+//! and images encoded as PNG with `Image::to_png`.
+
+use super::{codec, Color, Point};
+use crate::{Error, Result};
+
+/// Sampling filter used when drawing scaled or transformed images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFilter {
+    /// Use the closest pixel.
+    Nearest,
+    /// Blend the four closest pixels.
+    #[default]
+    Bilinear,
+}
+
+/// An RGBA8 bitmap image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Create a transparent image of the given size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Create an image filled with a single color.
+    pub fn filled(width: u32, height: u32, color: Color) -> Self {
+        let mut image = Self::new(width, height);
+        image.fill(color);
+        image
+    }
+
+    /// Create an image from raw RGBA8 pixel data.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return Err(Error::resource(format!(
+                "Expected {} bytes of RGBA data for a {}x{} image, got {}",
+                width as usize * height as usize * 4,
+                width,
+                height,
+                pixels.len()
+            )));
+        }
+        Ok(Self { width, height, pixels })
+    }
+
+    /// Decode a PNG, BMP or PPM file, detecting the format from its contents.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        codec::decode(data)
+    }
+    
+    /// Load and decode an image file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+    
+    /// Decode a PNG file.
+    pub fn from_png(data: &[u8]) -> Result<Self> {
+        codec::png::decode(data)
+    }
+    
+    /// Decode a BMP file.
+    pub fn from_bmp(data: &[u8]) -> Result<Self> {
+        codec::bmp::decode(data)
+    }
+    
+    /// Decode a PBM, PGM or PPM file.
+    pub fn from_ppm(data: &[u8]) -> Result<Self> {
+        codec::ppm::decode(data)
+    }
+    
+    /// Encode the image as a PNG file.
+    pub fn to_png(&self) -> Vec<u8> {
+        codec::png::encode(self)
+    }
+    
+    /// Get the image width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the image height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the raw RGBA8 pixel data.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Get mutable access to the raw RGBA8 pixel data.
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    /// Get the color of a pixel, or transparent if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        match self.offset(x, y) {
+            Some(i) => Color::rgba8(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]),
+            None => Color::TRANSPARENT,
+        }
+    }
+
+    /// Set the color of a pixel. Out-of-bounds writes are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if let Some(i) = self.offset(x, y) {
+            self.pixels[i..i + 4].copy_from_slice(&color_to_rgba8(color));
+        }
+    }
+
+    /// Sample the image at a point in pixel coordinates, clamping to the edges.
+    ///
+    /// Pixel centers sit at half-integer coordinates.
+    pub fn sample(&self, point: Point, filter: ImageFilter) -> Color {
+        if self.width == 0 || self.height == 0 {
+            return Color::TRANSPARENT;
+        }
+        let max_x = self.width as f32 - 1.0;
+        let max_y = self.height as f32 - 1.0;
+        
+        match filter {
+            ImageFilter::Nearest => {
+                let x = point.x.floor().clamp(0.0, max_x) as u32;
+                let y = point.y.floor().clamp(0.0, max_y) as u32;
+                self.pixel(x, y)
+            }
+            ImageFilter::Bilinear => {
+                let fx = (point.x - 0.5).clamp(0.0, max_x);
+                let fy = (point.y - 0.5).clamp(0.0, max_y);
+                let (x0, y0) = (fx.floor(), fy.floor());
+                let (tx, ty) = (fx - x0, fy - y0);
+                let (x0, y0) = (x0 as u32, y0 as u32);
+                let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+                
+                // Interpolate premultiplied values so transparent pixels do not darken edges
+                let mut sum = [0.0f32; 4];
+                for (x, y, weight) in [
+                    (x0, y0, (1.0 - tx) * (1.0 - ty)),
+                    (x1, y0, tx * (1.0 - ty)),
+                    (x0, y1, (1.0 - tx) * ty),
+                    (x1, y1, tx * ty),
+                ] {
+                    let color = self.pixel(x, y);
+                    sum[0] += color.r * color.a * weight;
+                    sum[1] += color.g * color.a * weight;
+                    sum[2] += color.b * color.a * weight;
+                    sum[3] += color.a * weight;
+                }
+                if sum[3] <= 0.0 {
+                    return Color::TRANSPARENT;
+                }
+                Color::rgba(sum[0] / sum[3], sum[1] / sum[3], sum[2] / sum[3], sum[3])
+            }
+        }
+    }
+    
+    /// Check if every pixel is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        self.pixels.chunks_exact(4).all(|pixel| pixel[3] == 255)
+    }
+    
+    /// Fill the whole image with a color.
+    pub fn fill(&mut self, color: Color) {
+        let rgba = color_to_rgba8(color);
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&rgba);
+        }
+    }
+
+    /// Byte offset of a pixel, if it is inside the image.
+    fn offset(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some((y as usize * self.width as usize + x as usize) * 4)
+        } else {
+            None
+        }
+    }
+}
+
+/// Convert a color to RGBA8 bytes.
+pub(crate) fn color_to_rgba8(color: Color) -> [u8; 4] {
+    [
+        (color.r * 255.0).round() as u8,
+        (color.g * 255.0).round() as u8,
+        (color.b * 255.0).round() as u8,
+        (color.a * 255.0).round() as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_pixels() {
+        let mut image = Image::new(4, 3);
+        assert_eq!(image.pixels().len(), 48);
+        assert_eq!(image.pixel(1, 1), Color::TRANSPARENT);
+
+        image.set_pixel(1, 2, Color::RED);
+        assert_eq!(image.pixel(1, 2), Color::RED);
+        assert_eq!(&image.pixels()[36..40], &[255, 0, 0, 255]);
+
+        // Out-of-bounds access is harmless
+        image.set_pixel(10, 10, Color::RED);
+        assert_eq!(image.pixel(10, 10), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_image_sampling() {
+        let mut image = Image::new(2, 1);
+        image.set_pixel(0, 0, Color::RED);
+        image.set_pixel(1, 0, Color::rgba(0.0, 0.0, 1.0, 0.0));
+
+        assert_eq!(image.sample(Point::new(0.9, 0.5), ImageFilter::Nearest), Color::RED);
+        assert_eq!(image.sample(Point::new(-5.0, 0.5), ImageFilter::Bilinear), Color::RED);
+
+        // Halfway to a transparent pixel keeps the color and halves the alpha
+        let halfway = image.sample(Point::new(1.0, 0.5), ImageFilter::Bilinear);
+        assert_eq!((halfway.r, halfway.b), (1.0, 0.0));
+        assert!((halfway.a - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_image_decode() {
+        let image = Image::decode(b"P2 1 1 255 128").unwrap();
+        assert_eq!(image.pixels(), &[128, 128, 128, 255]);
+        assert!(image.is_opaque());
+        assert!(Image::decode(b"not an image").is_err());
+    }
+
+    #[test]
+    fn test_image_from_rgba() {
+        assert!(Image::from_rgba(2, 2, vec![0; 16]).is_ok());
+        assert!(Image::from_rgba(2, 2, vec![0; 15]).is_err());
+    }
+}
@@ -0,0 +1,1635 @@
+//! 2D drawing and graphics for the CX Framework.
+//! 
+//! This module provides a comprehensive 2D graphics API that abstracts
+//! over platform-specific drawing operations.
+
+use crate::{Error, Result};
+use crate::platform::DrawingContext;
+use path::DEFAULT_TOLERANCE;
+use std::sync::{Arc, Mutex};
+
+pub mod path;
+pub mod stroke;
+pub mod clip;
+pub mod paint;
+pub mod image;
+pub mod nine_patch;
+pub mod font;
+pub mod text;
+pub mod display_list;
+pub mod atlas;
+pub mod batch;
+pub mod blend;
+pub mod color;
+pub mod effects;
+pub mod svg;
+pub mod export;
+pub(crate) mod surface;
+pub(crate) mod raster;
+pub(crate) mod codec;
+
+pub use path::{Path, PathElement, FillRule, Contour, CornerRadii};
+pub use stroke::{StrokeStyle, LineCap, LineJoin};
+pub use paint::{Paint, LinearGradient, RadialGradient, ImagePattern, GradientStop, SpreadMode};
+pub use image::{Image, ImageFilter};
+pub use clip::Clip;
+pub use nine_patch::{NinePatch, Insets, PatchMode};
+pub use font::{Font, FontId, TextMetrics, GlyphPosition, FontDatabase, FontQuery, FaceInfo};
+pub use text::{TextLayout, TextLine, GlyphRun, LayoutOptions, TextDirection, RichText, TextSpan, TextStyle};
+pub use atlas::{GlyphAtlas, AtlasStats};
+pub use batch::DrawStats;
+pub use blend::BlendMode;
+pub use color::{Hsl, Hsv, Oklab, Oklch, ColorSpace};
+pub use effects::Shadow;
+pub use svg::SvgImage;
+pub use export::{SvgDocument, PdfDocument};
+pub use surface::RenderTarget;
+pub use display_list::{DisplayList, DisplayItem, DisplayCommand, Damage, RetainedRenderer};
+
+/// Horizontal offset of the second pass of synthetic bold, relative to the font size.
+const SYNTHETIC_BOLD_OFFSET: f32 = 1.0 / 24.0;
+
+/// Horizontal slant of synthetic italic per unit of height.
+const SYNTHETIC_ITALIC_SKEW: f32 = 0.2;
+
+/// Underline and strikethrough thickness, relative to the font size.
+const DECORATION_THICKNESS: f32 = 1.0 / 14.0;
+
+/// Distance of the underline below the baseline, relative to the font size.
+const UNDERLINE_OFFSET: f32 = 0.12;
+
+/// Distance of the strikethrough above the baseline, relative to the font size.
+const STRIKETHROUGH_OFFSET: f32 = 0.3;
+
+/// RGBA color representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    /// Create a new color from RGBA values (0.0 to 1.0).
+    pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self {
+            r: r.clamp(0.0, 1.0),
+            g: g.clamp(0.0, 1.0),
+            b: b.clamp(0.0, 1.0),
+            a: a.clamp(0.0, 1.0),
+        }
+    }
+    
+    /// Create a new color from RGB values (0.0 to 1.0) with full opacity.
+    pub fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+    
+    /// Create a new color from RGBA values (0 to 255).
+    pub fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::rgba(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        )
+    }
+    
+    /// Create a new color from RGB values (0 to 255) with full opacity.
+    pub fn rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba8(r, g, b, 255)
+    }
+    
+    /// Create a grayscale color.
+    pub fn gray(value: f32) -> Self {
+        Self::rgb(value, value, value)
+    }
+    
+    /// Create a grayscale color with alpha.
+    pub fn gray_alpha(value: f32, alpha: f32) -> Self {
+        Self::rgba(value, value, value, alpha)
+    }
+    
+    /// Parse a hex color string (e.g., "#FF0000", "FF0000", "#RGB", "RGB").
+    ///
+    /// Four and eight digit forms carry alpha. Use [`Color::parse`] for the
+    /// full CSS color syntax.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        color::parse_hex(hex.trim_start_matches('#')).ok_or_else(|| Error::drawing("Invalid hex color"))
+    }
+    
+    /// Convert to tuple (r, g, b, a).
+    pub fn to_tuple(self) -> (f32, f32, f32, f32) {
+        (self.r, self.g, self.b, self.a)
+    }
+    
+    /// Blend this color with another using alpha blending.
+    pub fn blend_with(self, other: Color) -> Color {
+        let alpha = other.a;
+        let inv_alpha = 1.0 - alpha;
+        
+        Color::rgba(
+            self.r * inv_alpha + other.r * alpha,
+            self.g * inv_alpha + other.g * alpha,
+            self.b * inv_alpha + other.b * alpha,
+            self.a.max(other.a),
+        )
+    }
+    
+    /// Lighten the color by a factor (0.0 to 1.0).
+    pub fn lighten(self, factor: f32) -> Color {
+        let factor = factor.clamp(0.0, 1.0);
+        Color::rgba(
+            (self.r + (1.0 - self.r) * factor).clamp(0.0, 1.0),
+            (self.g + (1.0 - self.g) * factor).clamp(0.0, 1.0),
+            (self.b + (1.0 - self.b) * factor).clamp(0.0, 1.0),
+            self.a,
+        )
+    }
+    
+    /// Darken the color by a factor (0.0 to 1.0).
+    pub fn darken(self, factor: f32) -> Color {
+        let factor = 1.0 - factor.clamp(0.0, 1.0);
+        Color::rgba(
+            self.r * factor,
+            self.g * factor,
+            self.b * factor,
+            self.a,
+        )
+    }
+    
+    /// Set the alpha channel.
+    pub fn with_alpha(mut self, alpha: f32) -> Color {
+        self.a = alpha.clamp(0.0, 1.0);
+        self
+    }
+    
+    // Common colors
+    pub const TRANSPARENT: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.0 };
+    pub const BLACK: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+    pub const WHITE: Color = Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 };
+    pub const RED: Color = Color { r: 1.0, g: 0.0, b: 0.0, a: 1.0 };
+    pub const GREEN: Color = Color { r: 0.0, g: 1.0, b: 0.0, a: 1.0 };
+    pub const BLUE: Color = Color { r: 0.0, g: 0.0, b: 1.0, a: 1.0 };
+    pub const YELLOW: Color = Color { r: 1.0, g: 1.0, b: 0.0, a: 1.0 };
+    pub const CYAN: Color = Color { r: 0.0, g: 1.0, b: 1.0, a: 1.0 };
+    pub const MAGENTA: Color = Color { r: 1.0, g: 0.0, b: 1.0, a: 1.0 };
+    pub const GRAY: Color = Color { r: 0.5, g: 0.5, b: 0.5, a: 1.0 };
+    pub const LIGHT_GRAY: Color = Color { r: 0.75, g: 0.75, b: 0.75, a: 1.0 };
+    pub const DARK_GRAY: Color = Color { r: 0.25, g: 0.25, b: 0.25, a: 1.0 };
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::TRANSPARENT
+    }
+}
+
+/// 2D point representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    /// Create a new point.
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+    
+    /// Create a point at the origin.
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+    
+    /// Calculate the distance to another point.
+    pub fn distance_to(self, other: Point) -> f32 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+    
+    /// Add another point to this point.
+    pub fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+    
+    /// Subtract another point from this point.
+    pub fn subtract(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+    
+    /// Scale the point by a factor.
+    pub fn scale(self, factor: f32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// 2D size representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    /// Create a new size.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+    
+    /// Create a zero size.
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+    
+    /// Create a square size.
+    pub fn square(size: f32) -> Self {
+        Self::new(size, size)
+    }
+    
+    /// Get the area.
+    pub fn area(self) -> f32 {
+        self.width * self.height
+    }
+    
+    /// Check if the size is empty (width or height is 0).
+    pub fn is_empty(self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+    
+    /// Scale the size by a factor.
+    pub fn scale(self, factor: f32) -> Size {
+        Size::new(self.width * factor, self.height * factor)
+    }
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// 2D rectangle representation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    /// Create a new rectangle.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self { x, y, width, height }
+    }
+    
+    /// Create a rectangle from position and size.
+    pub fn from_point_size(point: Point, size: Size) -> Self {
+        Self::new(point.x, point.y, size.width, size.height)
+    }
+    
+    /// Create a rectangle at the origin with the given size.
+    pub fn from_size(size: Size) -> Self {
+        Self::new(0.0, 0.0, size.width, size.height)
+    }
+    
+    /// Create a zero rectangle.
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0)
+    }
+    
+    /// Get the position as a point.
+    pub fn position(self) -> Point {
+        Point::new(self.x, self.y)
+    }
+    
+    /// Get the size.
+    pub fn size(self) -> Size {
+        Size::new(self.width, self.height)
+    }
+    
+    /// Get the area.
+    pub fn area(self) -> f32 {
+        self.width * self.height
+    }
+    
+    /// Check if the rectangle is empty.
+    pub fn is_empty(self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+    
+    /// Get the right edge x-coordinate.
+    pub fn right(self) -> f32 {
+        self.x + self.width
+    }
+    
+    /// Get the bottom edge y-coordinate.
+    pub fn bottom(self) -> f32 {
+        self.y + self.height
+    }
+    
+    /// Get the center point.
+    pub fn center(self) -> Point {
+        Point::new(self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+    
+    /// Check if a point is inside the rectangle.
+    pub fn contains_point(self, point: Point) -> bool {
+        point.x >= self.x && point.x < self.right() &&
+        point.y >= self.y && point.y < self.bottom()
+    }
+    
+    /// Check if this rectangle intersects with another.
+    pub fn intersects_with(self, other: Rect) -> bool {
+        self.x < other.right() && self.right() > other.x &&
+        self.y < other.bottom() && self.bottom() > other.y
+    }
+    
+    /// Get the intersection of this rectangle with another.
+    pub fn intersection(self, other: Rect) -> Option<Rect> {
+        let left = self.x.max(other.x);
+        let right = self.right().min(other.right());
+        let top = self.y.max(other.y);
+        let bottom = self.bottom().min(other.bottom());
+        
+        if left < right && top < bottom {
+            Some(Rect::new(left, top, right - left, bottom - top))
+        } else {
+            None
+        }
+    }
+    
+    /// Get the union of this rectangle with another.
+    pub fn union(self, other: Rect) -> Rect {
+        let left = self.x.min(other.x);
+        let right = self.right().max(other.right());
+        let top = self.y.min(other.y);
+        let bottom = self.bottom().max(other.bottom());
+        
+        Rect::new(left, top, right - left, bottom - top)
+    }
+    
+    /// Expand the rectangle by the given amount on all sides.
+    pub fn expand(self, amount: f32) -> Rect {
+        Rect::new(
+            self.x - amount,
+            self.y - amount,
+            self.width + 2.0 * amount,
+            self.height + 2.0 * amount,
+        )
+    }
+    
+    /// Contract the rectangle by the given amount on all sides.
+    pub fn contract(self, amount: f32) -> Rect {
+        self.expand(-amount)
+    }
+}
+
+impl Default for Rect {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// Drawing canvas that provides 2D graphics operations.
+pub struct Canvas {
+    context: Box<dyn DrawingContext>,
+    state_stack: Vec<CanvasState>,
+    current_transform: Transform,
+    current_clip: Option<Clip>,
+    clip_dirty: bool,
+    layers: Vec<Layer>,
+    draw_counters: Arc<batch::DrawCounters>,
+    /// Owner the context is handed back to when the canvas is dropped.
+    owner: Option<Arc<dyn ContextOwner>>,
+}
+
+/// Owner of a long-lived drawing context that it lends to canvases.
+pub(crate) trait ContextOwner: Send + Sync {
+    /// Take back a context lent to a canvas that is being dropped.
+    fn restore(&self, context: Box<dyn DrawingContext>);
+}
+
+/// Stands in for a context handed back to its owner.
+struct Detached;
+
+impl DrawingContext for Detached {
+    fn clear(&mut self, _color: (f32, f32, f32, f32)) -> Result<()> {
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, _x: f32, _y: f32, _width: f32, _height: f32, _color: (f32, f32, f32, f32)) -> Result<()> {
+        Ok(())
+    }
+
+    fn stroke_rect(&mut self, _x: f32, _y: f32, _width: f32, _height: f32, _color: (f32, f32, f32, f32), _stroke: &StrokeStyle) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw_text(&mut self, _text: &str, _x: f32, _y: f32, _color: (f32, f32, f32, f32)) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw_image(&mut self, _image: &Image, _src: Rect, _transform: Transform, _filter: ImageFilter) -> Result<()> {
+        Ok(())
+    }
+
+    fn fill_path(&mut self, _path: &Path, _color: (f32, f32, f32, f32), _fill_rule: FillRule) -> Result<()> {
+        Ok(())
+    }
+
+    fn stroke_path(&mut self, _path: &Path, _color: (f32, f32, f32, f32), _stroke: &StrokeStyle) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_clip(&mut self, _clip: Option<&Clip>) -> Result<()> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (0, 0)
+    }
+}
+
+/// Canvas state saved by `Canvas::save`.
+#[derive(Debug, Clone)]
+struct CanvasState {
+    transform: Transform,
+    clip: Option<Clip>,
+}
+
+/// Layer opened by `Canvas::push_layer`, with the context it draws over.
+struct Layer {
+    parent: Box<dyn DrawingContext>,
+    image: Arc<Mutex<Image>>,
+    origin: Point,
+    opacity: f32,
+    blend_mode: BlendMode,
+}
+
+/// 2D transformation matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f32, pub b: f32,
+    pub c: f32, pub d: f32,
+    pub tx: f32, pub ty: f32,
+}
+
+impl Transform {
+    /// Create an identity transform.
+    pub fn identity() -> Self {
+        Self {
+            a: 1.0, b: 0.0,
+            c: 0.0, d: 1.0,
+            tx: 0.0, ty: 0.0,
+        }
+    }
+    
+    /// Create a translation transform.
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            a: 1.0, b: 0.0,
+            c: 0.0, d: 1.0,
+            tx: x, ty: y,
+        }
+    }
+    
+    /// Create a scale transform.
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Self {
+            a: sx, b: 0.0,
+            c: 0.0, d: sy,
+            tx: 0.0, ty: 0.0,
+        }
+    }
+    
+    /// Create a skew transform that shifts x by `kx` times y and y by `ky` times x.
+    pub fn skew(kx: f32, ky: f32) -> Self {
+        Self {
+            a: 1.0, b: ky,
+            c: kx, d: 1.0,
+            tx: 0.0, ty: 0.0,
+        }
+    }
+    
+    /// Create a rotation transform (angle in radians).
+    pub fn rotate(angle: f32) -> Self {
+        let cos_a = angle.cos();
+        let sin_a = angle.sin();
+        Self {
+            a: cos_a, b: sin_a,
+            c: -sin_a, d: cos_a,
+            tx: 0.0, ty: 0.0,
+        }
+    }
+    
+    /// Transform a point.
+    pub fn transform_point(self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.tx,
+            self.b * point.x + self.d * point.y + self.ty,
+        )
+    }
+    
+    /// Combine with another transform.
+    pub fn combine(self, other: Transform) -> Transform {
+        Transform {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            tx: self.a * other.tx + self.c * other.ty + self.tx,
+            ty: self.b * other.tx + self.d * other.ty + self.ty,
+        }
+    }
+    
+    /// Get the inverse transform, or `None` if the transform is singular.
+    pub fn inverse(self) -> Option<Transform> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inverse = Transform {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        };
+        let values = [inverse.a, inverse.b, inverse.c, inverse.d, inverse.tx, inverse.ty];
+        values.iter().all(|v| v.is_finite()).then_some(inverse)
+    }
+    
+    /// Get the bounding box of a rectangle after transformation.
+    pub fn transform_rect(self, rect: Rect) -> Rect {
+        let corners = [
+            self.transform_point(Point::new(rect.x, rect.y)),
+            self.transform_point(Point::new(rect.right(), rect.y)),
+            self.transform_point(Point::new(rect.right(), rect.bottom())),
+            self.transform_point(Point::new(rect.x, rect.bottom())),
+        ];
+        let left = corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+        let top = corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        let right = corners.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+        let bottom = corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+        Rect::new(left, top, right - left, bottom - top)
+    }
+    
+    /// Check if the transform only translates.
+    pub fn is_translation(self) -> bool {
+        self.a == 1.0 && self.b == 0.0 && self.c == 0.0 && self.d == 1.0
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Canvas {
+    /// Create a new canvas with the given drawing context.
+    ///
+    /// Contexts that support batching are wrapped so consecutive text and
+    /// rectangle draws reach them as one image.
+    pub fn new(context: Box<dyn DrawingContext>) -> Self {
+        Self::with_draw_counters(context, Arc::default())
+    }
+    
+    /// Create a canvas that adds its draw counts to counters shared with
+    /// other canvases of the same surface.
+    pub(crate) fn with_draw_counters(context: Box<dyn DrawingContext>, draw_counters: Arc<batch::DrawCounters>) -> Self {
+        let context = Self::batched(context, &draw_counters);
+        Self {
+            context,
+            state_stack: Vec::new(),
+            current_transform: Transform::identity(),
+            current_clip: None,
+            clip_dirty: false,
+            layers: Vec::new(),
+            draw_counters,
+            owner: None,
+        }
+    }
+    
+    /// Wrap a context in a batching layer if it supports batching.
+    pub(crate) fn batched(context: Box<dyn DrawingContext>, draw_counters: &Arc<batch::DrawCounters>) -> Box<dyn DrawingContext> {
+        if context.supports_batching() {
+            Box::new(batch::BatchingContext::new(context, Arc::clone(draw_counters)))
+        } else {
+            context
+        }
+    }
+    
+    /// Create a canvas drawing with a context borrowed from `owner`, which
+    /// gets it back when the canvas is dropped.
+    pub(crate) fn lent(context: Box<dyn DrawingContext>, owner: Arc<dyn ContextOwner>, draw_counters: Arc<batch::DrawCounters>) -> Self {
+        let mut canvas = Self::with_draw_counters(context, draw_counters);
+        canvas.owner = Some(owner);
+        canvas
+    }
+    
+    /// Get the drawing calls made and submitted since the last call, and reset them.
+    ///
+    /// Only canvases whose backend batches its draws are counted.
+    pub fn take_draw_stats(&self) -> DrawStats {
+        self.draw_counters.take()
+    }
+    
+    /// Get the canvas size.
+    pub fn size(&self) -> Size {
+        let (width, height) = self.context.size();
+        Size::new(width as f32, height as f32)
+    }
+    
+    /// Clear the canvas with a color.
+    pub fn clear(&mut self, color: Color) -> Result<()> {
+        self.context.clear(color.to_tuple())
+    }
+    
+    /// Fill a rectangle.
+    pub fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) -> Result<()> {
+        self.sync_clip()?;
+        match (self.axis_aligned_scale(), paint.into()) {
+            (Some(_), Paint::Solid(color)) => {
+                let transformed = self.current_transform.transform_rect(rect);
+                self.context.fill_rect(
+                    transformed.x,
+                    transformed.y,
+                    transformed.width,
+                    transformed.height,
+                    color.to_tuple(),
+                )
+            }
+            (_, paint) => self.fill_path(&Path::rect(rect), paint, FillRule::NonZero),
+        }
+    }
+    
+    /// Stroke a rectangle outline.
+    pub fn stroke_rect(&mut self, rect: Rect, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.sync_clip()?;
+        let stroke = stroke.into();
+        match (self.uniform_scale(), paint.into()) {
+            (Some(scale), Paint::Solid(color)) => {
+                let transformed = self.current_transform.transform_rect(rect);
+                self.context.stroke_rect(
+                    transformed.x,
+                    transformed.y,
+                    transformed.width,
+                    transformed.height,
+                    color.to_tuple(),
+                    &stroke.scale(scale),
+                )
+            }
+            (_, paint) => self.stroke_path(&Path::rect(rect), paint, stroke),
+        }
+    }
+    
+    /// Fill a path using the given fill rule.
+    pub fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) -> Result<()> {
+        self.sync_clip()?;
+        let transformed = path.transform(self.current_transform);
+        match paint.into() {
+            Paint::Solid(color) => self.context.fill_path(&transformed, color.to_tuple(), fill_rule),
+            paint => {
+                let paint = paint.transform(self.current_transform);
+                self.context.fill_path_paint(&transformed, &paint, fill_rule)
+            }
+        }
+    }
+    
+    /// Stroke a path outline.
+    ///
+    /// Under a transform that does not preserve angles the stroke is outlined
+    /// in local space and filled, so its width follows the transform.
+    pub fn stroke_path(&mut self, path: &Path, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.sync_clip()?;
+        let stroke = stroke.into();
+        let paint = paint.into();
+        let scale = match self.conformal_scale() {
+            Some(scale) => scale,
+            None => {
+                let outline = stroke.outline(path, DEFAULT_TOLERANCE / self.max_scale());
+                return self.fill_path(&outline, paint, FillRule::NonZero);
+            }
+        };
+        
+        let transformed = path.transform(self.current_transform);
+        let stroke = stroke.scale(scale);
+        match paint {
+            Paint::Solid(color) => self.context.stroke_path(&transformed, color.to_tuple(), &stroke),
+            paint => {
+                let paint = paint.transform(self.current_transform);
+                self.context.stroke_path_paint(&transformed, &paint, &stroke)
+            }
+        }
+    }
+    
+    /// Fill a rectangle with rounded corners.
+    pub fn fill_rounded_rect(&mut self, rect: Rect, radii: impl Into<CornerRadii>, paint: impl Into<Paint>) -> Result<()> {
+        self.sync_clip()?;
+        let radii = radii.into();
+        let paint = paint.into();
+        if radii.is_zero() {
+            return self.fill_rect(rect, paint);
+        }
+        
+        match (self.uniform_scale(), &paint) {
+            (Some(scale), Paint::Solid(color)) => {
+                let transformed = self.current_transform.transform_rect(rect);
+                self.context.fill_rounded_rect(transformed, radii.scale(scale), color.to_tuple())
+            }
+            _ => self.fill_path(&Path::rounded_rect(rect, radii), paint, FillRule::NonZero),
+        }
+    }
+    
+    /// Stroke the outline of a rectangle with rounded corners.
+    pub fn stroke_rounded_rect(&mut self, rect: Rect, radii: impl Into<CornerRadii>, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.sync_clip()?;
+        let radii = radii.into();
+        let paint = paint.into();
+        let stroke = stroke.into();
+        if radii.is_zero() {
+            return self.stroke_rect(rect, paint, stroke);
+        }
+        
+        match (self.uniform_scale(), &paint) {
+            (Some(scale), Paint::Solid(color)) => {
+                let transformed = self.current_transform.transform_rect(rect);
+                self.context.stroke_rounded_rect(transformed, radii.scale(scale), color.to_tuple(), &stroke.scale(scale))
+            }
+            _ => self.stroke_path(&Path::rounded_rect(rect, radii), paint, stroke),
+        }
+    }
+    
+    /// Fill an ellipse.
+    pub fn fill_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, paint: impl Into<Paint>) -> Result<()> {
+        self.sync_clip()?;
+        let paint = paint.into();
+        match (self.axis_aligned_scale(), &paint) {
+            (Some((sx, sy)), Paint::Solid(color)) => {
+                let center = self.current_transform.transform_point(center);
+                self.context.fill_ellipse(center.x, center.y, radius_x * sx, radius_y * sy, color.to_tuple())
+            }
+            _ => self.fill_path(&Path::ellipse(center, radius_x, radius_y), paint, FillRule::NonZero),
+        }
+    }
+    
+    /// Stroke an ellipse outline.
+    pub fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.sync_clip()?;
+        let paint = paint.into();
+        let stroke = stroke.into();
+        match (self.uniform_scale(), &paint) {
+            (Some(scale), Paint::Solid(color)) => {
+                let center = self.current_transform.transform_point(center);
+                self.context.stroke_ellipse(center.x, center.y, radius_x * scale, radius_y * scale, color.to_tuple(), &stroke.scale(scale))
+            }
+            _ => self.stroke_path(&Path::ellipse(center, radius_x, radius_y), paint, stroke),
+        }
+    }
+    
+    /// Fill a circle.
+    pub fn fill_circle(&mut self, center: Point, radius: f32, paint: impl Into<Paint>) -> Result<()> {
+        self.fill_ellipse(center, radius, radius, paint)
+    }
+    
+    /// Stroke a circle outline.
+    pub fn stroke_circle(&mut self, center: Point, radius: f32, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.stroke_ellipse(center, radius, radius, paint, stroke)
+    }
+    
+    /// Draw a straight line between two points.
+    pub fn draw_line(&mut self, from: Point, to: Point, paint: impl Into<Paint>, stroke: impl Into<StrokeStyle>) -> Result<()> {
+        self.stroke_path(&Path::line(from, to), paint, stroke)
+    }
+    
+    /// Draw the `src` region of an image scaled into `dst`.
+    pub fn draw_image(&mut self, image: &Image, src: Rect, dst: Rect, filter: ImageFilter) -> Result<()> {
+        self.sync_clip()?;
+        if src.is_empty() || dst.is_empty() {
+            return Ok(());
+        }
+        let mapping = Transform::translate(dst.x, dst.y)
+            .combine(Transform::scale(dst.width / src.width, dst.height / src.height))
+            .combine(Transform::translate(-src.x, -src.y));
+        
+        // Parts of `src` outside the image draw nothing
+        let bounds = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        match src.intersection(bounds) {
+            Some(src) => self.context.draw_image(image, src, self.current_transform.combine(mapping), filter),
+            None => Ok(()),
+        }
+    }
+    
+    /// Draw a whole image at its natural size with its top-left corner at `position`.
+    pub fn draw_image_at(&mut self, image: &Image, position: Point) -> Result<()> {
+        let size = Size::new(image.width() as f32, image.height() as f32);
+        let src = Rect::new(0.0, 0.0, size.width, size.height);
+        self.draw_image(image, src, Rect::new(position.x, position.y, size.width, size.height), ImageFilter::Bilinear)
+    }
+    
+    /// Draw `image` as a nine-patch filling `dst`.
+    ///
+    /// Corners are drawn at their natural size, edges stretch or tile along
+    /// their length and the centre fills what remains.
+    pub fn draw_nine_patch(&mut self, image: &Image, insets: Insets, dst: Rect, mode: PatchMode) -> Result<()> {
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        for (src, dst) in nine_patch::slices(width, height, insets, dst, mode) {
+            self.draw_image(image, src, dst, ImageFilter::Bilinear)?;
+        }
+        Ok(())
+    }
+    
+    /// Draw an SVG image scaled into `dst`.
+    pub fn draw_svg(&mut self, svg: &SvgImage, dst: Rect) -> Result<()> {
+        svg.draw(self, dst)
+    }
+    
+    /// Measure a single line of text in `font` at `size` pixels.
+    ///
+    /// The system font is measured by the backend that draws it, at the
+    /// backend's own size, when the backend can measure it.
+    pub fn measure_text(&self, text: &str, font: &Font, size: f32) -> TextMetrics {
+        if font.is_system() {
+            if let Some(metrics) = self.context.measure_text(text) {
+                return metrics;
+            }
+        }
+        font.measure(text, size)
+    }
+
+    /// Measure text as the backend draws it with `DrawingContext::draw_text`.
+    pub(crate) fn measure_platform_text(&self, text: &str) -> Option<TextMetrics> {
+        self.context.measure_text(text)
+    }
+    
+    /// Draw text in `font` at `size` pixels with its baseline starting at `position`.
+    ///
+    /// The system font is drawn by the platform at its own size.
+    pub fn draw_text(&mut self, text: &str, position: Point, font: &Font, size: f32, color: Color) -> Result<()> {
+        self.sync_clip()?;
+        if font.is_system() {
+            if self.current_transform.is_translation() {
+                let transformed = self.current_transform.transform_point(position);
+                return self.context.draw_text(text, transformed.x, transformed.y, color.to_tuple());
+            }
+            let transform = self.current_transform.combine(Transform::translate(position.x, position.y));
+            return self.context.draw_text_transformed(text, transform, color.to_tuple());
+        }
+        
+        let glyphs = font.layout(text, size);
+        self.draw_glyphs(&glyphs, position, font, size, color, None)
+    }
+    
+    /// Draw laid out text with the top-left corner of its first line at `origin`.
+    pub fn draw_layout(&mut self, layout: &TextLayout, origin: Point, color: Color) -> Result<()> {
+        for line in layout.lines() {
+            let position = Point::new(origin.x, origin.y + line.baseline);
+            self.draw_text_line(line, position, color)?;
+        }
+        Ok(())
+    }
+    
+    /// Draw one line of a layout with its baseline starting at `position`.
+    ///
+    /// Runs use their style's color in place of `color` when they set one.
+    pub fn draw_text_line(&mut self, line: &TextLine, position: Point, color: Color) -> Result<()> {
+        let top = position.y - (line.baseline - line.top);
+        for run in &line.runs {
+            let style = run.style;
+            let color = style.color.unwrap_or(color);
+            let left = position.x + run.x;
+            if let Some(background) = style.background {
+                self.fill_rect(Rect::new(left, top, run.width, line.height), background)?;
+            }
+
+            // Faces missing the wanted weight or slant are emboldened by
+            // drawing twice and slanted by skewing
+            let origin = Point::new(left, position.y);
+            let italic = style.italic && !run.font.is_italic();
+            self.draw_run(run, origin, color, italic)?;
+            if style.is_bold() && run.font.weight() < 600 {
+                let offset = (run.size * SYNTHETIC_BOLD_OFFSET).max(0.5);
+                self.draw_run(run, Point::new(origin.x + offset, origin.y), color, italic)?;
+            }
+
+            let thickness = (run.size * DECORATION_THICKNESS).max(1.0);
+            if style.underline {
+                let y = position.y + run.size * UNDERLINE_OFFSET;
+                self.fill_rect(Rect::new(left, y, run.width, thickness), color)?;
+            }
+            if style.strikethrough {
+                let y = position.y - run.size * STRIKETHROUGH_OFFSET;
+                self.fill_rect(Rect::new(left, y - thickness / 2.0, run.width, thickness), color)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Draw the glyphs of one run with its baseline starting at `origin`.
+    fn draw_run(&mut self, run: &GlyphRun, origin: Point, color: Color, italic: bool) -> Result<()> {
+        let skew = Transform::skew(-SYNTHETIC_ITALIC_SKEW, 0.0);
+        if run.font.is_system() {
+            // The platform shapes its own font, so the run is drawn as display-ordered text
+            if !italic {
+                return self.draw_text(&run.text, origin, &run.font, run.size, color);
+            }
+            self.sync_clip()?;
+            let transform = self.current_transform.combine(Transform::translate(origin.x, origin.y)).combine(skew);
+            return self.context.draw_text_transformed(&run.text, transform, color.to_tuple());
+        }
+        
+        self.sync_clip()?;
+        let position = Point::new(origin.x - run.x, origin.y);
+        self.draw_glyphs(&run.glyphs, position, &run.font, run.size, color, italic.then_some(skew))
+    }
+    
+    /// Draw positioned glyphs of a loaded font relative to a baseline origin,
+    /// optionally skewing each glyph.
+    fn draw_glyphs(&mut self, glyphs: &[GlyphPosition], position: Point, font: &Font, size: f32, color: Color, skew: Option<Transform>) -> Result<()> {
+        let run_transform = self.current_transform.combine(Transform::translate(position.x, position.y));
+        let run_transform = skew.map_or(run_transform, |skew| run_transform.combine(skew));
+        if self.context.draw_glyph_run(font, glyphs, size, run_transform, color.to_tuple())? {
+            return Ok(());
+        }
+        
+        if skew.is_some() || !self.current_transform.is_translation() || size > atlas::MAX_GLYPH_SIZE {
+            // Rotated, scaled, slanted or very large text is filled from its outlines
+            return self.fill_glyph_outlines(glyphs, position, font, size, color, skew);
+        }
+        
+        // Composite glyphs from the atlas into one image and draw it in a single call
+        let origin = self.current_transform.transform_point(position);
+        let composited = {
+            let mut atlas = GlyphAtlas::shared().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let placed = match atlas.place_run(font, glyphs, size, origin) {
+                Some(placed) => Some(placed),
+                None => {
+                    // The atlas filled up part way through the run, so it is evicted and the run placed alone
+                    atlas.clear();
+                    atlas.place_run(font, glyphs, size, origin)
+                }
+            };
+            placed.map(|placed| {
+                let bounds = placed
+                    .iter()
+                    .map(|(glyph, (x, y))| Rect::new((x + glyph.left) as f32, (y + glyph.top) as f32, glyph.width as f32, glyph.height as f32))
+                    .reduce(Rect::union)?;
+                let mut run = Image::new(bounds.width as u32, bounds.height as u32);
+                for (glyph, position) in &placed {
+                    atlas.composite(&mut run, (bounds.x as i32, bounds.y as i32), glyph, *position, color);
+                }
+                Some((run, bounds))
+            })
+        };
+        
+        // The atlas is released before the backend draws, so other canvases are not held up
+        match composited {
+            Some(Some((run, bounds))) => {
+                let src = Rect::new(0.0, 0.0, bounds.width, bounds.height);
+                self.context.draw_image(&run, src, Transform::translate(bounds.x, bounds.y), ImageFilter::Nearest)
+            }
+            Some(None) => Ok(()),
+            None => self.fill_glyph_outlines(glyphs, position, font, size, color, skew),
+        }
+    }
+    
+    /// Fill the outlines of positioned glyphs relative to a baseline origin.
+    fn fill_glyph_outlines(&mut self, glyphs: &[GlyphPosition], position: Point, font: &Font, size: f32, color: Color, skew: Option<Transform>) -> Result<()> {
+        let mut outlines = Path::new();
+        for placed in glyphs {
+            let offset = Transform::translate(position.x + placed.x, position.y);
+            let source = font.fallback_font(placed.font);
+            let mut path = source.glyph_path(placed.glyph, size);
+            if let Some(skew) = skew {
+                path = path.transform(skew);
+            }
+            outlines.extend(&path.transform(offset));
+        }
+        self.fill_path(&outlines, color, FillRule::NonZero)
+    }
+    
+    /// Draw the blurred shadow of a shape, offset from it.
+    ///
+    /// `spread` grows the shape before blurring, or shrinks it when negative.
+    pub fn draw_shadow(&mut self, shape: impl Into<Path>, offset: Point, blur_radius: f32, spread: f32, color: Color) -> Result<()> {
+        let path = shape.into().transform(self.current_transform);
+        let t = self.current_transform;
+        let offset = Point::new(t.a * offset.x + t.c * offset.y, t.b * offset.x + t.d * offset.y);
+        let scale = self.max_scale();
+        let (width, height) = self.context.size();
+        let surface = Rect::new(0.0, 0.0, width as f32, height as f32);
+        let visible = match self.current_clip {
+            Some(ref clip) => clip.bounds().intersection(surface),
+            None => Some(surface),
+        };
+        let Some((image, origin)) = visible.and_then(|visible| effects::shadow_image(&path, offset, blur_radius * scale, spread * scale, color, visible)) else {
+            return Ok(());
+        };
+        self.sync_clip()?;
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        self.context.draw_image(&image, src, Transform::translate(origin.x, origin.y), ImageFilter::Nearest)
+    }
+    
+    /// Blur what is already drawn under a rectangle, for frosted panels drawn on top.
+    pub fn backdrop_blur(&mut self, rect: Rect, radius: f32) -> Result<()> {
+        self.sync_clip()?;
+        let region = self.current_transform.transform_rect(rect);
+        let region = match self.current_clip {
+            Some(ref clip) => match region.intersection(clip.bounds()) {
+                Some(region) => region,
+                None => return Ok(()),
+            },
+            None => region,
+        };
+        self.context.blur_region(region, radius * self.max_scale())
+    }
+    
+    /// Start drawing into a layer that is composited as one group by `pop_layer`.
+    ///
+    /// The layer covers the current clip and is drawn in software, so
+    /// `opacity` and `blend_mode` apply to its content as a whole rather
+    /// than to each primitive.
+    pub fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+        let (width, height) = self.context.size();
+        let surface = Rect::new(0.0, 0.0, width as f32, height as f32);
+        let area = match self.current_clip {
+            Some(ref clip) => clip.bounds().intersection(surface).unwrap_or_else(Rect::zero),
+            None => surface,
+        };
+        let left = area.x.floor();
+        let top = area.y.floor();
+        let (layer_width, layer_height) = ((area.right().ceil() - left) as u32, (area.bottom().ceil() - top) as u32);
+
+        let image = Arc::new(Mutex::new(Image::new(layer_width, layer_height)));
+        let context = surface::ImageContext::new(image.clone(), (left as i32, top as i32), (width, height));
+        let parent = std::mem::replace(&mut self.context, Box::new(context));
+        self.layers.push(Layer {
+            parent,
+            image,
+            origin: Point::new(left, top),
+            opacity,
+            blend_mode,
+        });
+        self.clip_dirty = true;
+    }
+    
+    /// Composite the most recent layer onto what it was pushed over.
+    pub fn pop_layer(&mut self) -> Result<()> {
+        let Some(layer) = self.layers.pop() else {
+            return Ok(());
+        };
+        self.context = layer.parent;
+        self.clip_dirty = true;
+        self.sync_clip()?;
+        let mut image = layer.image.lock().map_err(|_| Error::drawing("Failed to lock layer"))?;
+        let image = std::mem::replace(&mut *image, Image::new(0, 0));
+        if image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+        self.context.draw_layer(&image, layer.origin, layer.opacity, layer.blend_mode)
+    }
+    
+    /// Get the number of open layers.
+    pub fn layer_depth(&self) -> usize {
+        self.layers.len()
+    }
+    
+    /// Present/flush the drawing operations.
+    pub fn present(&mut self) -> Result<()> {
+        self.context.present()
+    }
+    
+    /// Present only the given device-space regions.
+    pub fn present_rects(&mut self, rects: &[Rect]) -> Result<()> {
+        self.context.present_rects(rects)
+    }
+    
+    /// Replay the part of a display list inside the device-space `region`.
+    ///
+    /// Display lists are already in device space, so the transform is ignored.
+    pub fn draw_display_list(&mut self, list: &DisplayList, region: Rect) -> Result<()> {
+        list.replay(self.context.as_mut(), region)?;
+        self.clip_dirty = true;
+        Ok(())
+    }
+    
+    /// Fill a device-space region with a color, ignoring the transform and clip.
+    pub fn fill_region(&mut self, region: Rect, color: Color) -> Result<()> {
+        self.context.set_clip(Some(&Clip::rect(region)))?;
+        self.context.fill_rect(region.x, region.y, region.width, region.height, color.to_tuple())?;
+        self.context.set_clip(None)?;
+        self.clip_dirty = true;
+        Ok(())
+    }
+    
+    /// Save the current transform and clip state.
+    pub fn save(&mut self) {
+        self.state_stack.push(CanvasState {
+            transform: self.current_transform,
+            clip: self.current_clip.clone(),
+        });
+    }
+    
+    /// Restore the previous transform and clip state.
+    pub fn restore(&mut self) {
+        if let Some(state) = self.state_stack.pop() {
+            self.current_transform = state.transform;
+            if state.clip != self.current_clip {
+                self.current_clip = state.clip;
+                self.clip_dirty = true;
+            }
+        }
+    }
+    
+    /// Intersect the clip region with a rectangle.
+    pub fn clip_rect(&mut self, rect: Rect) {
+        let clip = match self.axis_aligned_scale() {
+            Some(_) => {
+                let transformed = self.current_transform.transform_rect(rect);
+                match self.current_clip {
+                    Some(ref clip) => clip.intersect_rect(transformed),
+                    None => Clip::rect(transformed),
+                }
+            }
+            None => {
+                let path = Path::rect(rect).transform(self.current_transform);
+                match self.current_clip {
+                    Some(ref clip) => clip.intersect_path(path, FillRule::NonZero),
+                    None => Clip::path(path, FillRule::NonZero),
+                }
+            }
+        };
+        self.current_clip = Some(clip);
+        self.clip_dirty = true;
+    }
+    
+    /// Intersect the clip region with a path.
+    pub fn clip_path(&mut self, path: &Path, fill_rule: FillRule) {
+        let path = path.transform(self.current_transform);
+        let clip = match self.current_clip {
+            Some(ref clip) => clip.intersect_path(path, fill_rule),
+            None => Clip::path(path, fill_rule),
+        };
+        self.current_clip = Some(clip);
+        self.clip_dirty = true;
+    }
+    
+    /// Get the current clip region in device coordinates, if any.
+    pub fn clip(&self) -> Option<&Clip> {
+        self.current_clip.as_ref()
+    }
+    
+    /// Translate the coordinate system.
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.current_transform = self.current_transform.combine(Transform::translate(x, y));
+    }
+    
+    /// Scale the coordinate system.
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.current_transform = self.current_transform.combine(Transform::scale(sx, sy));
+    }
+    
+    /// Rotate the coordinate system (angle in radians).
+    pub fn rotate(&mut self, angle: f32) {
+        self.current_transform = self.current_transform.combine(Transform::rotate(angle));
+    }
+    
+    /// Set the transform directly.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.current_transform = transform;
+    }
+    
+    /// Get the current transform.
+    pub fn transform(&self) -> Transform {
+        self.current_transform
+    }
+    
+    /// Get the scale factors if the current transform has no rotation or skew.
+    fn axis_aligned_scale(&self) -> Option<(f32, f32)> {
+        let t = self.current_transform;
+        if t.b == 0.0 && t.c == 0.0 {
+            Some((t.a.abs(), t.d.abs()))
+        } else {
+            None
+        }
+    }
+    
+    /// Get the scale factor if the current transform is a uniform, axis-aligned scale.
+    fn uniform_scale(&self) -> Option<f32> {
+        let t = self.current_transform;
+        self.axis_aligned_scale()
+            .filter(|(sx, sy)| (sx - sy).abs() <= f32::EPSILON * sx.max(*sy))
+            .filter(|_| t.a > 0.0 && t.d > 0.0)
+            .map(|(sx, _)| sx)
+    }
+    
+    /// Get the scale factor if the current transform preserves angles.
+    fn conformal_scale(&self) -> Option<f32> {
+        let t = self.current_transform;
+        let x_len = t.a.hypot(t.b);
+        let y_len = t.c.hypot(t.d);
+        let tolerance = 1e-4 * x_len.max(y_len);
+        let orthogonal = (t.a * t.c + t.b * t.d).abs() <= tolerance * x_len.max(y_len);
+        (orthogonal && (x_len - y_len).abs() <= tolerance).then_some(x_len)
+    }
+    
+    /// Get the largest factor by which the current transform stretches lengths.
+    fn max_scale(&self) -> f32 {
+        let t = self.current_transform;
+        let scale = t.a.hypot(t.b).max(t.c.hypot(t.d));
+        if scale > 0.0 { scale } else { 1.0 }
+    }
+    
+    /// Send the current clip to the context if it changed since the last draw.
+    fn sync_clip(&mut self) -> Result<()> {
+        if self.clip_dirty {
+            self.context.set_clip(self.current_clip.as_ref())?;
+            self.clip_dirty = false;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Canvas {
+    fn drop(&mut self) {
+        if let Some(owner) = self.owner.take() {
+            // Layers left open hold the lent context as their first parent
+            let root = match self.layers.first_mut() {
+                Some(layer) => &mut layer.parent,
+                None => &mut self.context,
+            };
+            owner.restore(std::mem::replace(root, Box::new(Detached)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Drawing call seen by the recording context.
+    #[derive(Debug, Clone, PartialEq)]
+    enum Call {
+        FillRect(Rect),
+        StrokeRect(Rect, f32),
+        FillPath(Rect),
+        StrokePath(Rect, f32),
+        Text(String, Transform),
+        Image(Rect, Transform),
+    }
+
+    /// Drawing context that records device-space calls.
+    struct Recorder(Arc<Mutex<Vec<Call>>>);
+
+    impl DrawingContext for Recorder {
+        fn clear(&mut self, _color: (f32, f32, f32, f32)) -> Result<()> {
+            Ok(())
+        }
+
+        fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, _color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.lock().unwrap().push(Call::FillRect(Rect::new(x, y, width, height)));
+            Ok(())
+        }
+
+        fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, _color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.0.lock().unwrap().push(Call::StrokeRect(Rect::new(x, y, width, height), stroke.width));
+            Ok(())
+        }
+
+        fn draw_text(&mut self, text: &str, x: f32, y: f32, _color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.lock().unwrap().push(Call::Text(text.to_string(), Transform::translate(x, y)));
+            Ok(())
+        }
+
+        fn draw_text_transformed(&mut self, text: &str, transform: Transform, _color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.lock().unwrap().push(Call::Text(text.to_string(), transform));
+            Ok(())
+        }
+
+        fn draw_image(&mut self, _image: &Image, src: Rect, transform: Transform, _filter: ImageFilter) -> Result<()> {
+            self.0.lock().unwrap().push(Call::Image(src, transform));
+            Ok(())
+        }
+
+        fn fill_path(&mut self, path: &Path, _color: (f32, f32, f32, f32), _fill_rule: FillRule) -> Result<()> {
+            self.0.lock().unwrap().push(Call::FillPath(path.bounds()));
+            Ok(())
+        }
+
+        fn stroke_path(&mut self, path: &Path, _color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.0.lock().unwrap().push(Call::StrokePath(path.bounds(), stroke.width));
+            Ok(())
+        }
+
+        fn set_clip(&mut self, _clip: Option<&Clip>) -> Result<()> {
+            Ok(())
+        }
+
+        fn present(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn size(&self) -> (u32, u32) {
+            (100, 100)
+        }
+    }
+
+    fn recording_canvas() -> (Canvas, Arc<Mutex<Vec<Call>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        (Canvas::new(Box::new(Recorder(calls.clone()))), calls)
+    }
+
+    fn assert_rect(actual: Rect, expected: Rect) {
+        assert!(
+            (actual.x - expected.x).abs() < 0.01
+                && (actual.y - expected.y).abs() < 0.01
+                && (actual.width - expected.width).abs() < 0.01
+                && (actual.height - expected.height).abs() < 0.01,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_color_creation() {
+        let color = Color::rgb(1.0, 0.5, 0.0);
+        assert_eq!(color.r, 1.0);
+        assert_eq!(color.g, 0.5);
+        assert_eq!(color.b, 0.0);
+        assert_eq!(color.a, 1.0);
+        
+        let color = Color::rgba8(255, 128, 0, 128);
+        assert_eq!(color.r, 1.0);
+        assert!((color.g - 128.0/255.0).abs() < 0.01);
+        assert_eq!(color.b, 0.0);
+        assert!((color.a - 128.0/255.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_color_hex_parsing() {
+        let color = Color::from_hex("#FF8000").unwrap();
+        assert_eq!(color.r, 1.0);
+        assert!((color.g - 128.0/255.0).abs() < 0.01);
+        assert_eq!(color.b, 0.0);
+        assert_eq!(color.a, 1.0);
+        
+        let color = Color::from_hex("F80").unwrap();
+        assert_eq!(color.r, 1.0);
+        assert!((color.g - 136.0/255.0).abs() < 0.01);
+        assert_eq!(color.b, 0.0);
+    }
+
+    #[test]
+    fn test_point_operations() {
+        let p1 = Point::new(1.0, 2.0);
+        let p2 = Point::new(3.0, 4.0);
+        
+        let sum = p1.add(p2);
+        assert_eq!(sum.x, 4.0);
+        assert_eq!(sum.y, 6.0);
+        
+        let distance = p1.distance_to(p2);
+        assert!((distance - 2.828).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_rect_operations() {
+        let rect1 = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let rect2 = Rect::new(5.0, 5.0, 10.0, 10.0);
+        
+        assert!(rect1.intersects_with(rect2));
+        
+        let intersection = rect1.intersection(rect2).unwrap();
+        assert_eq!(intersection.x, 5.0);
+        assert_eq!(intersection.y, 5.0);
+        assert_eq!(intersection.width, 5.0);
+        assert_eq!(intersection.height, 5.0);
+        
+        assert!(rect1.contains_point(Point::new(5.0, 5.0)));
+        assert!(!rect1.contains_point(Point::new(15.0, 15.0)));
+    }
+
+    #[test]
+    fn test_transform() {
+        let transform = Transform::translate(10.0, 20.0);
+        let point = Point::new(5.0, 5.0);
+        let transformed = transform.transform_point(point);
+        
+        assert_eq!(transformed.x, 15.0);
+        assert_eq!(transformed.y, 25.0);
+        
+        let scale = Transform::scale(2.0, 3.0);
+        let combined = transform.combine(scale);
+        let final_point = combined.transform_point(point);
+        
+        assert_eq!(final_point.x, 20.0);
+        assert_eq!(final_point.y, 35.0);
+    }
+
+    #[test]
+    fn test_transform_inverse() {
+        let transform = Transform::translate(10.0, -4.0)
+            .combine(Transform::rotate(0.7))
+            .combine(Transform::scale(0.001, 3.0));
+        let inverse = transform.inverse().unwrap();
+        let point = Point::new(12.0, -7.5);
+        let round_trip = inverse.transform_point(transform.transform_point(point));
+        assert!(round_trip.distance_to(point) < 0.01);
+
+        assert!(Transform::scale(0.0, 1.0).inverse().is_none());
+    }
+
+    #[test]
+    fn test_transform_rect_bounds() {
+        let rotate = Transform::rotate(std::f32::consts::FRAC_PI_2);
+        assert_rect(rotate.transform_rect(Rect::new(0.0, 0.0, 10.0, 20.0)), Rect::new(-20.0, 0.0, 20.0, 10.0));
+        assert!(Transform::translate(3.0, 4.0).is_translation());
+        assert!(!rotate.is_translation());
+    }
+
+    #[test]
+    fn test_canvas_rotated_rect() {
+        let (mut canvas, calls) = recording_canvas();
+        canvas.rotate(std::f32::consts::FRAC_PI_4);
+        canvas.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED).unwrap();
+
+        // A rotated rectangle is drawn as a polygon, not as a wrong axis-aligned rectangle
+        let half_diagonal = 10.0 / std::f32::consts::SQRT_2;
+        let call = calls.lock().unwrap()[0].clone();
+        match call {
+            Call::FillPath(bounds) => assert_rect(bounds, Rect::new(-half_diagonal, 0.0, 2.0 * half_diagonal, 2.0 * half_diagonal)),
+            call => panic!("unexpected call {:?}", call),
+        }
+    }
+
+    #[test]
+    fn test_canvas_scaled_strokes() {
+        let (mut canvas, calls) = recording_canvas();
+        canvas.scale(2.0, 2.0);
+        canvas.stroke_rect(Rect::new(1.0, 1.0, 4.0, 4.0), Color::BLACK, 1.5).unwrap();
+
+        // Non-uniform scales outline the stroke so its width follows the transform
+        canvas.scale(1.0, 0.5);
+        canvas.draw_line(Point::new(0.0, 0.0), Point::new(10.0, 0.0), Color::BLACK, 2.0).unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], Call::StrokeRect(Rect::new(2.0, 2.0, 8.0, 8.0), 3.0));
+        match calls[1] {
+            Call::FillPath(bounds) => assert_rect(bounds, Rect::new(0.0, -1.0, 20.0, 2.0)),
+            ref call => panic!("unexpected call {:?}", call),
+        }
+    }
+
+    #[test]
+    fn test_canvas_transformed_text() {
+        let (mut canvas, calls) = recording_canvas();
+        canvas.translate(5.0, 5.0);
+        canvas.draw_text("a", Point::new(1.0, 2.0), &Font::system(), 14.0, Color::BLACK).unwrap();
+        canvas.rotate(1.0);
+        canvas.draw_text("b", Point::new(1.0, 0.0), &Font::system(), 14.0, Color::BLACK).unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], Call::Text("a".to_string(), Transform::translate(6.0, 7.0)));
+        let expected = Transform::translate(5.0, 5.0).combine(Transform::rotate(1.0)).combine(Transform::translate(1.0, 0.0));
+        assert_eq!(calls[1], Call::Text("b".to_string(), expected));
+    }
+
+    #[test]
+    fn test_canvas_draw_layout() {
+        let (mut canvas, calls) = recording_canvas();
+        let layout = TextLayout::new("ab \u{5d0}\u{5d1}", &Font::system(), 10.0, LayoutOptions::new());
+        canvas.draw_layout(&layout, Point::new(1.0, 2.0), Color::BLACK).unwrap();
+
+        // The Hebrew run is drawn in display order after the left-to-right run
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], Call::Text("ab ".to_string(), Transform::translate(1.0, 10.0)));
+        assert_eq!(calls[1], Call::Text("\u{5d1}\u{5d0}".to_string(), Transform::translate(19.0, 10.0)));
+    }
+
+    #[test]
+    fn test_canvas_draws_text_decorations() {
+        let (mut canvas, calls) = recording_canvas();
+        let rich = RichText::plain("a").span("b", TextStyle::new().underline().background(Color::YELLOW));
+        let layout = TextLayout::rich(&rich, &Font::system(), 10.0, LayoutOptions::new());
+        canvas.draw_layout(&layout, Point::new(0.0, 0.0), Color::BLACK).unwrap();
+
+        // Background, then the glyphs, then the underline below the baseline
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], Call::Text("a".to_string(), Transform::translate(0.0, 8.0)));
+        assert_eq!(calls[1], Call::FillRect(Rect::new(6.0, 0.0, 6.0, 12.0)));
+        assert_eq!(calls[2], Call::Text("b".to_string(), Transform::translate(6.0, 8.0)));
+        match calls[3] {
+            Call::FillRect(rect) => assert!(rect.x == 6.0 && rect.width == 6.0 && (rect.y - 9.2).abs() < 1e-4),
+            ref call => panic!("unexpected call {:?}", call),
+        }
+    }
+
+    #[test]
+    fn test_canvas_draw_image() {
+        let (mut canvas, calls) = recording_canvas();
+        let image = Image::new(10, 10);
+        canvas.translate(100.0, 0.0);
+        canvas.draw_image(&image, Rect::new(5.0, 0.0, 10.0, 10.0), Rect::new(0.0, 0.0, 20.0, 20.0), ImageFilter::Nearest).unwrap();
+        canvas.draw_image(&image, Rect::new(20.0, 0.0, 5.0, 5.0), Rect::new(0.0, 0.0, 5.0, 5.0), ImageFilter::Nearest).unwrap();
+
+        // The source is cut to the image and the transform maps image pixels to the device
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        match calls[0] {
+            Call::Image(src, transform) => {
+                assert_eq!(src, Rect::new(5.0, 0.0, 5.0, 10.0));
+                assert_eq!(transform.transform_point(Point::new(5.0, 0.0)), Point::new(100.0, 0.0));
+                assert_eq!(transform.transform_point(Point::new(10.0, 10.0)), Point::new(110.0, 20.0));
+            }
+            ref call => panic!("unexpected call {:?}", call),
+        }
+    }
+
+    #[test]
+    fn test_canvas_draw_nine_patch() {
+        let (mut canvas, calls) = recording_canvas();
+        let image = Image::new(12, 12);
+        canvas.draw_nine_patch(&image, Insets::uniform(4.0), Rect::new(0.0, 0.0, 40.0, 20.0), PatchMode::Stretch).unwrap();
+
+        // Nine slices, with the bottom-right corner mapped unscaled into place
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 9);
+        match calls[8] {
+            Call::Image(src, transform) => {
+                assert_eq!(src, Rect::new(8.0, 8.0, 4.0, 4.0));
+                assert_eq!(transform.transform_point(Point::new(8.0, 8.0)), Point::new(36.0, 16.0));
+                assert_eq!(transform.transform_point(Point::new(12.0, 12.0)), Point::new(40.0, 20.0));
+            }
+            ref call => panic!("unexpected call {:?}", call),
+        }
+    }
+
+    #[test]
+    fn test_layer_composites_as_group() {
+        let image = Arc::new(Mutex::new(Image::filled(20, 10, Color::WHITE)));
+        let context = surface::ImageContext::new(image.clone(), (0, 0), (20, 10));
+        let mut canvas = Canvas::new(Box::new(context));
+        
+        canvas.push_layer(0.5, BlendMode::Normal);
+        canvas.fill_rect(Rect::new(0.0, 0.0, 6.0, 10.0), Color::BLUE).unwrap();
+        canvas.fill_rect(Rect::new(4.0, 0.0, 6.0, 10.0), Color::BLUE).unwrap();
+        assert_eq!(canvas.layer_depth(), 1);
+        canvas.pop_layer().unwrap();
+        canvas.push_layer(1.0, BlendMode::Multiply);
+        canvas.fill_rect(Rect::new(12.0, 0.0, 8.0, 10.0), Color::rgb(1.0, 0.5, 0.5)).unwrap();
+        canvas.fill_rect(Rect::new(16.0, 0.0, 4.0, 10.0), Color::BLUE).unwrap();
+        canvas.pop_layer().unwrap();
+        canvas.pop_layer().unwrap();
+        
+        // Overlapping primitives do not add up inside a translucent layer
+        let image = image.lock().unwrap();
+        assert_eq!(image.pixel(2, 5), image.pixel(5, 5));
+        assert_eq!(image.pixel(5, 5), Color::rgba8(128, 128, 255, 255));
+        assert_eq!(image.pixel(12, 5), Color::rgba8(255, 128, 128, 255));
+        assert_eq!(image.pixel(18, 5), Color::BLUE);
+        assert_eq!(image.pixel(11, 5), Color::WHITE);
+    }
+
+    #[test]
+    fn test_canvas_shadow_and_backdrop_blur() {
+        let image = Arc::new(Mutex::new(Image::filled(40, 20, Color::WHITE)));
+        let context = surface::ImageContext::new(image.clone(), (0, 0), (40, 20));
+        let mut canvas = Canvas::new(Box::new(context));
+        
+        canvas.translate(2.0, 2.0);
+        canvas.draw_shadow(Rect::new(0.0, 0.0, 10.0, 10.0), Point::new(4.0, 4.0), 0.0, 0.0, Color::BLACK).unwrap();
+        canvas.fill_rect(Rect::new(22.0, 0.0, 2.0, 16.0), Color::BLACK).unwrap();
+        canvas.backdrop_blur(Rect::new(18.0, 0.0, 10.0, 16.0), 4.0).unwrap();
+        
+        let image = image.lock().unwrap();
+        assert_eq!(image.pixel(4, 4), Color::WHITE);
+        assert_eq!(image.pixel(10, 10), Color::BLACK);
+        assert_eq!(image.pixel(15, 15), Color::BLACK);
+        assert_eq!(image.pixel(17, 17), Color::WHITE);
+        
+        // The bar is smeared sideways but not outside the blurred area
+        let bar = image.pixel(24, 8);
+        let beside = image.pixel(22, 8);
+        assert!(bar.r > 0.2 && bar.r < 0.9 && beside.r < 0.99);
+        assert_eq!(image.pixel(24, 19), Color::WHITE);
+    }
+}
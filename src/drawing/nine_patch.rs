@@ -0,0 +1,203 @@
+//! Nine-patch (nine-slice) images.
+//!
+//! A nine-patch splits an image into a 3×3 grid using four insets. The
+//! corners are drawn at their natural size, the edges stretch or tile along
+//! one axis and the centre fills the remaining space, so a single small image
+//! can skin widgets of any size.
+
+use super::{Image, Rect};
+use std::sync::Arc;
+
+/// Distances from each edge of an image to its stretchable centre, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Insets {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Insets {
+    /// Create insets from individual edges.
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self { left, top, right, bottom }
+    }
+
+    /// Create equal insets on all edges.
+    pub fn uniform(value: f32) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Create insets with separate horizontal and vertical values.
+    pub fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        Self::new(horizontal, vertical, horizontal, vertical)
+    }
+}
+
+impl From<f32> for Insets {
+    fn from(value: f32) -> Self {
+        Self::uniform(value)
+    }
+}
+
+/// How the edges and centre of a nine-patch fill their space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatchMode {
+    /// Scale each slice to fit.
+    #[default]
+    Stretch,
+    /// Repeat each slice at its natural size, cropping the last copy.
+    Tile,
+}
+
+/// An image with nine-patch insets, usable as a widget background.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NinePatch {
+    pub image: Arc<Image>,
+    pub insets: Insets,
+    pub mode: PatchMode,
+}
+
+impl NinePatch {
+    /// Create a stretching nine-patch.
+    pub fn new(image: impl Into<Arc<Image>>, insets: impl Into<Insets>) -> Self {
+        Self {
+            image: image.into(),
+            insets: insets.into(),
+            mode: PatchMode::Stretch,
+        }
+    }
+
+    /// Set how the edges and centre fill their space.
+    pub fn mode(mut self, mode: PatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// Split a nine-patch into `(source, destination)` rectangle pairs.
+///
+/// Insets larger than the image or the destination shrink proportionally so
+/// opposite corners never overlap.
+pub(crate) fn slices(width: f32, height: f32, insets: Insets, dst: Rect, mode: PatchMode) -> Vec<(Rect, Rect)> {
+    let (left, right) = fit(insets.left, insets.right, width);
+    let (top, bottom) = fit(insets.top, insets.bottom, height);
+    let src_columns = [0.0, left, width - right, width];
+    let src_rows = [0.0, top, height - bottom, height];
+
+    // Inner boundaries land on whole units so neighbouring slices do not seam
+    let (dst_left, dst_right) = fit(left, right, dst.width);
+    let (dst_top, dst_bottom) = fit(top, bottom, dst.height);
+    let column_start = (dst.x + dst_left).round().min(dst.right());
+    let column_end = (dst.right() - dst_right).round().clamp(column_start, dst.right());
+    let row_start = (dst.y + dst_top).round().min(dst.bottom());
+    let row_end = (dst.bottom() - dst_bottom).round().clamp(row_start, dst.bottom());
+    let dst_columns = [dst.x, column_start, column_end, dst.right()];
+    let dst_rows = [dst.y, row_start, row_end, dst.bottom()];
+
+    let mut slices = Vec::new();
+    for row in 0..3 {
+        for column in 0..3 {
+            let src = Rect::new(
+                src_columns[column],
+                src_rows[row],
+                src_columns[column + 1] - src_columns[column],
+                src_rows[row + 1] - src_rows[row],
+            );
+            let dst = Rect::new(
+                dst_columns[column],
+                dst_rows[row],
+                dst_columns[column + 1] - dst_columns[column],
+                dst_rows[row + 1] - dst_rows[row],
+            );
+            if src.is_empty() || dst.is_empty() {
+                continue;
+            }
+
+            // Corners never tile; edges tile along their length only
+            let tile_x = mode == PatchMode::Tile && column == 1;
+            let tile_y = mode == PatchMode::Tile && row == 1;
+            tile(src, dst, tile_x, tile_y, &mut slices);
+        }
+    }
+    slices
+}
+
+/// Shrink a pair of insets proportionally so they fit within `length`.
+fn fit(start: f32, end: f32, length: f32) -> (f32, f32) {
+    let (start, end) = (start.max(0.0), end.max(0.0));
+    let total = start + end;
+    if total > length && total > 0.0 {
+        let scale = length.max(0.0) / total;
+        (start * scale, end * scale)
+    } else {
+        (start, end)
+    }
+}
+
+/// Cover `dst` with copies of `src`, cropping the last copy on each axis.
+fn tile(src: Rect, dst: Rect, tile_x: bool, tile_y: bool, slices: &mut Vec<(Rect, Rect)>) {
+    // A slice tiled on one axis keeps its aspect ratio on that axis
+    let tile_width = match (tile_x, tile_y) {
+        (false, _) => dst.width,
+        (true, true) => src.width,
+        (true, false) => src.width * dst.height / src.height,
+    };
+    let tile_height = match (tile_y, tile_x) {
+        (false, _) => dst.height,
+        (true, true) => src.height,
+        (true, false) => src.height * dst.width / src.width,
+    };
+
+    let mut y = dst.y;
+    while y < dst.bottom() {
+        let height = tile_height.min(dst.bottom() - y);
+        let mut x = dst.x;
+        while x < dst.right() {
+            let width = tile_width.min(dst.right() - x);
+            slices.push((
+                Rect::new(src.x, src.y, src.width * width / tile_width, src.height * height / tile_height),
+                Rect::new(x, y, width, height),
+            ));
+            x += tile_width;
+        }
+        y += tile_height;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stretch_slices() {
+        let slices = slices(30.0, 30.0, Insets::uniform(10.0), Rect::new(0.0, 0.0, 100.0, 50.0), PatchMode::Stretch);
+        assert_eq!(slices.len(), 9);
+
+        // Corners keep their size, the centre takes the rest
+        assert_eq!(slices[0], (Rect::new(0.0, 0.0, 10.0, 10.0), Rect::new(0.0, 0.0, 10.0, 10.0)));
+        assert_eq!(slices[4], (Rect::new(10.0, 10.0, 10.0, 10.0), Rect::new(10.0, 10.0, 80.0, 30.0)));
+        assert_eq!(slices[8], (Rect::new(20.0, 20.0, 10.0, 10.0), Rect::new(90.0, 40.0, 10.0, 10.0)));
+    }
+
+    #[test]
+    fn test_small_destination_shrinks_corners() {
+        let slices = slices(30.0, 30.0, Insets::uniform(10.0), Rect::new(0.0, 0.0, 10.0, 40.0), PatchMode::Stretch);
+
+        // No room for a centre column, so each corner gets half the width
+        assert_eq!(slices.len(), 6);
+        assert_eq!(slices[0].1, Rect::new(0.0, 0.0, 5.0, 10.0));
+        assert_eq!(slices[1].1, Rect::new(5.0, 0.0, 5.0, 10.0));
+    }
+
+    #[test]
+    fn test_tiled_edges() {
+        let slices = slices(30.0, 30.0, Insets::uniform(10.0), Rect::new(0.0, 0.0, 45.0, 30.0), PatchMode::Tile);
+
+        // The 25-wide top edge takes two full tiles and one cropped tile
+        let top: Vec<_> = slices.iter().filter(|(src, dst)| src.y == 0.0 && dst.x >= 10.0 && dst.right() <= 35.0).collect();
+        assert_eq!(top.len(), 3);
+        assert_eq!(top[2].0, Rect::new(10.0, 0.0, 5.0, 10.0));
+        assert_eq!(top[2].1, Rect::new(30.0, 0.0, 5.0, 10.0));
+    }
+}
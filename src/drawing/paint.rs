@@ -0,0 +1,434 @@
+//! Paints used to fill and stroke shapes.
+//!
+//! A `Paint` is either a solid color, a linear or radial gradient, or a
+//! repeating image pattern. Gradients and patterns carry their own transform
+//! from paint space into user space, so they follow the canvas transform.
+
+use super::{Color, Image, Point, Transform};
+use std::sync::Arc;
+
+/// How a gradient behaves outside its 0..1 range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpreadMode {
+    /// Extend the end colors.
+    #[default]
+    Pad,
+    /// Repeat the gradient.
+    Repeat,
+    /// Repeat the gradient, mirroring every other copy.
+    Reflect,
+}
+
+/// A color at a position along a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+/// Gradient along a line from `start` to `end`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    pub start: Point,
+    pub end: Point,
+    pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub transform: Transform,
+}
+
+impl LinearGradient {
+    /// Create a gradient between two points with no stops.
+    pub fn new(start: Point, end: Point) -> Self {
+        Self {
+            start,
+            end,
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            transform: Transform::identity(),
+        }
+    }
+
+    /// Add a color stop at `offset` (0.0 to 1.0).
+    pub fn stop(mut self, offset: f32, color: Color) -> Self {
+        add_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Set the spread mode.
+    pub fn spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+}
+
+/// Gradient radiating from `center` out to `radius`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    pub center: Point,
+    pub radius: f32,
+    pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub transform: Transform,
+}
+
+impl RadialGradient {
+    /// Create a circular gradient with no stops.
+    pub fn new(center: Point, radius: f32) -> Self {
+        Self {
+            center,
+            radius,
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            transform: Transform::identity(),
+        }
+    }
+
+    /// Add a color stop at `offset` (0.0 to 1.0).
+    pub fn stop(mut self, offset: f32, color: Color) -> Self {
+        add_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Set the spread mode.
+    pub fn spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+}
+
+/// An image repeated across the plane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePattern {
+    pub image: Arc<Image>,
+    pub transform: Transform,
+}
+
+impl ImagePattern {
+    /// Create a pattern with the image's top-left corner at the origin.
+    pub fn new(image: Arc<Image>) -> Self {
+        Self {
+            image,
+            transform: Transform::identity(),
+        }
+    }
+
+    /// Move the pattern origin.
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.transform = Transform::translate(x, y).combine(self.transform);
+        self
+    }
+
+    /// Scale the pattern.
+    pub fn scale(mut self, sx: f32, sy: f32) -> Self {
+        self.transform = self.transform.combine(Transform::scale(sx, sy));
+        self
+    }
+}
+
+/// Fill or stroke source.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Color),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+    Pattern(ImagePattern),
+}
+
+impl Paint {
+    /// Get the color if this is a solid paint.
+    pub fn as_solid(&self) -> Option<Color> {
+        match self {
+            Paint::Solid(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    /// Return a copy of this paint mapped through `transform`.
+    pub fn transform(&self, transform: Transform) -> Paint {
+        match self {
+            Paint::Solid(color) => Paint::Solid(*color),
+            Paint::LinearGradient(gradient) => Paint::LinearGradient(LinearGradient {
+                transform: transform.combine(gradient.transform),
+                ..gradient.clone()
+            }),
+            Paint::RadialGradient(gradient) => Paint::RadialGradient(RadialGradient {
+                transform: transform.combine(gradient.transform),
+                ..gradient.clone()
+            }),
+            Paint::Pattern(pattern) => Paint::Pattern(ImagePattern {
+                transform: transform.combine(pattern.transform),
+                ..pattern.clone()
+            }),
+        }
+    }
+
+    /// Sample the paint at a point.
+    pub fn color_at(&self, point: Point) -> Color {
+        self.shader().color_at(point)
+    }
+
+    /// Get a single color approximating the paint.
+    ///
+    /// Used by backends that can only draw solid colors.
+    pub fn average_color(&self) -> Color {
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::LinearGradient(gradient) => average_stops(&gradient.stops),
+            Paint::RadialGradient(gradient) => average_stops(&gradient.stops),
+            Paint::Pattern(pattern) => {
+                let pixels = pattern.image.pixels();
+                let count = (pixels.len() / 4).max(1) as f32;
+                let mut sum = [0.0f32; 4];
+                for pixel in pixels.chunks_exact(4) {
+                    for (total, value) in sum.iter_mut().zip(pixel) {
+                        *total += *value as f32 / 255.0;
+                    }
+                }
+                Color::rgba(sum[0] / count, sum[1] / count, sum[2] / count, sum[3] / count)
+            }
+        }
+    }
+
+    /// Prepare the paint for sampling many points.
+    pub(crate) fn shader(&self) -> Shader<'_> {
+        match self {
+            Paint::Solid(color) => Shader::Solid(*color),
+            Paint::LinearGradient(gradient) => {
+                let delta = gradient.end.subtract(gradient.start);
+                let length_sq = delta.x * delta.x + delta.y * delta.y;
+                match gradient.transform.inverse() {
+                    Some(inverse) if length_sq > 0.0 => Shader::Linear {
+                        gradient,
+                        inverse,
+                        delta: delta.scale(1.0 / length_sq),
+                    },
+                    _ => Shader::Solid(last_stop(&gradient.stops)),
+                }
+            }
+            Paint::RadialGradient(gradient) => match gradient.transform.inverse() {
+                Some(inverse) if gradient.radius > 0.0 => Shader::Radial { gradient, inverse },
+                _ => Shader::Solid(last_stop(&gradient.stops)),
+            },
+            Paint::Pattern(pattern) => match pattern.transform.inverse() {
+                Some(inverse) if pattern.image.width() > 0 && pattern.image.height() > 0 => {
+                    Shader::Pattern { image: &pattern.image, inverse }
+                }
+                _ => Shader::Solid(Color::TRANSPARENT),
+            },
+        }
+    }
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Paint::Solid(Color::default())
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Paint {
+    fn from(gradient: LinearGradient) -> Self {
+        Paint::LinearGradient(gradient)
+    }
+}
+
+impl From<RadialGradient> for Paint {
+    fn from(gradient: RadialGradient) -> Self {
+        Paint::RadialGradient(gradient)
+    }
+}
+
+impl From<ImagePattern> for Paint {
+    fn from(pattern: ImagePattern) -> Self {
+        Paint::Pattern(pattern)
+    }
+}
+
+impl From<&Paint> for Paint {
+    fn from(paint: &Paint) -> Self {
+        paint.clone()
+    }
+}
+
+/// A paint prepared for per-pixel sampling.
+pub(crate) enum Shader<'a> {
+    Solid(Color),
+    Linear {
+        gradient: &'a LinearGradient,
+        inverse: Transform,
+        delta: Point,
+    },
+    Radial {
+        gradient: &'a RadialGradient,
+        inverse: Transform,
+    },
+    Pattern {
+        image: &'a Image,
+        inverse: Transform,
+    },
+}
+
+impl Shader<'_> {
+    /// Sample the paint at a point.
+    pub(crate) fn color_at(&self, point: Point) -> Color {
+        match self {
+            Shader::Solid(color) => *color,
+            Shader::Linear { gradient, inverse, delta } => {
+                let local = inverse.transform_point(point).subtract(gradient.start);
+                let t = local.x * delta.x + local.y * delta.y;
+                sample_stops(&gradient.stops, apply_spread(t, gradient.spread))
+            }
+            Shader::Radial { gradient, inverse } => {
+                let local = inverse.transform_point(point);
+                let t = local.distance_to(gradient.center) / gradient.radius;
+                sample_stops(&gradient.stops, apply_spread(t, gradient.spread))
+            }
+            Shader::Pattern { image, inverse } => {
+                let local = inverse.transform_point(point);
+                let x = (local.x.floor() as i64).rem_euclid(image.width() as i64) as u32;
+                let y = (local.y.floor() as i64).rem_euclid(image.height() as i64) as u32;
+                image.pixel(x, y)
+            }
+        }
+    }
+}
+
+/// Insert a stop, keeping stops ordered by offset.
+fn add_stop(stops: &mut Vec<GradientStop>, offset: f32, color: Color) {
+    let offset = offset.clamp(0.0, 1.0);
+    let index = stops.iter().position(|stop| stop.offset > offset).unwrap_or(stops.len());
+    stops.insert(index, GradientStop { offset, color });
+}
+
+/// Map a gradient position into 0..1 according to the spread mode.
+fn apply_spread(t: f32, spread: SpreadMode) -> f32 {
+    match spread {
+        SpreadMode::Pad => t.clamp(0.0, 1.0),
+        SpreadMode::Repeat => t.rem_euclid(1.0),
+        SpreadMode::Reflect => {
+            let t = t.rem_euclid(2.0);
+            if t > 1.0 { 2.0 - t } else { t }
+        }
+    }
+}
+
+/// Interpolate the stop colors at `t`, blending in premultiplied space.
+fn sample_stops(stops: &[GradientStop], t: f32) -> Color {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Color::TRANSPARENT,
+    };
+    if t <= first.offset {
+        return first.color;
+    }
+    if t >= last.offset {
+        return last.color;
+    }
+
+    let index = stops.iter().position(|stop| stop.offset > t).unwrap_or(stops.len() - 1);
+    let (from, to) = (stops[index - 1], stops[index]);
+    let span = to.offset - from.offset;
+    let f = if span > 0.0 { (t - from.offset) / span } else { 1.0 };
+
+    let lerp = |a: f32, b: f32| a + (b - a) * f;
+    let alpha = lerp(from.color.a, to.color.a);
+    if alpha <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+    Color::rgba(
+        lerp(from.color.r * from.color.a, to.color.r * to.color.a) / alpha,
+        lerp(from.color.g * from.color.a, to.color.g * to.color.a) / alpha,
+        lerp(from.color.b * from.color.a, to.color.b * to.color.a) / alpha,
+        alpha,
+    )
+}
+
+/// Average color of a gradient's stops.
+fn average_stops(stops: &[GradientStop]) -> Color {
+    if stops.is_empty() {
+        return Color::TRANSPARENT;
+    }
+    let count = stops.len() as f32;
+    let sum = stops.iter().fold((0.0, 0.0, 0.0, 0.0), |acc, stop| {
+        (acc.0 + stop.color.r, acc.1 + stop.color.g, acc.2 + stop.color.b, acc.3 + stop.color.a)
+    });
+    Color::rgba(sum.0 / count, sum.1 / count, sum.2 / count, sum.3 / count)
+}
+
+/// Color of the last stop, used for degenerate gradients.
+fn last_stop(stops: &[GradientStop]) -> Color {
+    stops.last().map(|stop| stop.color).unwrap_or(Color::TRANSPARENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color(actual: Color, expected: Color) {
+        assert!(
+            (actual.r - expected.r).abs() < 0.01
+                && (actual.g - expected.g).abs() < 0.01
+                && (actual.b - expected.b).abs() < 0.01
+                && (actual.a - expected.a).abs() < 0.01,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_linear_gradient() {
+        let paint = Paint::from(
+            LinearGradient::new(Point::new(0.0, 0.0), Point::new(100.0, 0.0))
+                .stop(0.0, Color::BLACK)
+                .stop(1.0, Color::WHITE),
+        );
+
+        assert_color(paint.color_at(Point::new(0.0, 50.0)), Color::BLACK);
+        assert_color(paint.color_at(Point::new(50.0, 10.0)), Color::gray(0.5));
+        assert_color(paint.color_at(Point::new(150.0, 0.0)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_spread_modes() {
+        let gradient = LinearGradient::new(Point::new(0.0, 0.0), Point::new(10.0, 0.0))
+            .stop(0.0, Color::BLACK)
+            .stop(1.0, Color::WHITE);
+
+        let repeat = Paint::from(gradient.clone().spread(SpreadMode::Repeat));
+        assert_color(repeat.color_at(Point::new(12.5, 0.0)), Color::gray(0.25));
+
+        let reflect = Paint::from(gradient.spread(SpreadMode::Reflect));
+        assert_color(reflect.color_at(Point::new(12.5, 0.0)), Color::gray(0.75));
+    }
+
+    #[test]
+    fn test_radial_gradient_transform() {
+        let paint = Paint::from(
+            RadialGradient::new(Point::new(0.0, 0.0), 10.0)
+                .stop(0.0, Color::RED)
+                .stop(1.0, Color::BLUE),
+        );
+        let moved = paint.transform(Transform::translate(50.0, 50.0));
+
+        assert_color(moved.color_at(Point::new(50.0, 50.0)), Color::RED);
+        assert_color(moved.color_at(Point::new(50.0, 65.0)), Color::BLUE);
+    }
+
+    #[test]
+    fn test_image_pattern_repeats() {
+        let mut image = Image::new(2, 1);
+        image.set_pixel(0, 0, Color::RED);
+        image.set_pixel(1, 0, Color::GREEN);
+        let paint = Paint::from(ImagePattern::new(Arc::new(image)).offset(1.0, 0.0));
+
+        assert_color(paint.color_at(Point::new(1.5, 0.5)), Color::RED);
+        assert_color(paint.color_at(Point::new(2.5, 7.5)), Color::GREEN);
+        assert_color(paint.color_at(Point::new(0.5, 0.5)), Color::GREEN);
+    }
+}
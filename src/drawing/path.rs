@@ -0,0 +1,578 @@
+//! Vector paths for the drawing canvas.
+//!
+//! A `Path` is a sequence of move, line, curve and close commands that can
+//! be filled or stroked on a `Canvas`. Paths are transformed exactly (curves
+//! stay curves) and flattened into polygons only when a backend needs them.
+
+use super::{Point, Rect, Transform};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+/// Default flattening tolerance in device pixels.
+pub const DEFAULT_TOLERANCE: f32 = 0.25;
+
+/// Fill rule used to decide which regions of a path are inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// A point is inside if the winding number around it is non-zero.
+    #[default]
+    NonZero,
+    /// A point is inside if a ray from it crosses an odd number of edges.
+    EvenOdd,
+}
+
+/// A single path command.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathElement {
+    /// Start a new subpath at the given point.
+    MoveTo(Point),
+    /// Straight line to the given point.
+    LineTo(Point),
+    /// Quadratic Bézier curve (control point, end point).
+    QuadTo(Point, Point),
+    /// Cubic Bézier curve (first control, second control, end point).
+    CubicTo(Point, Point, Point),
+    /// Close the current subpath with a line back to its start.
+    Close,
+}
+
+/// A flattened subpath made of straight segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    pub points: Vec<Point>,
+    pub closed: bool,
+}
+
+/// Per-corner radii for rounded rectangles.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Create radii for each corner, clockwise from the top-left.
+    pub fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self { top_left, top_right, bottom_right, bottom_left }
+    }
+
+    /// Create radii with the same value on every corner.
+    pub fn uniform(radius: f32) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    /// Check if every corner is square.
+    pub fn is_zero(self) -> bool {
+        self.top_left <= 0.0 && self.top_right <= 0.0 && self.bottom_right <= 0.0 && self.bottom_left <= 0.0
+    }
+
+    /// Scale every radius by a factor.
+    pub fn scale(self, factor: f32) -> Self {
+        Self::new(
+            self.top_left * factor,
+            self.top_right * factor,
+            self.bottom_right * factor,
+            self.bottom_left * factor,
+        )
+    }
+
+    /// Shrink the radii proportionally so adjacent corners fit within the given size.
+    pub fn clamp_to(self, width: f32, height: f32) -> Self {
+        let radii = Self::new(
+            self.top_left.max(0.0),
+            self.top_right.max(0.0),
+            self.bottom_right.max(0.0),
+            self.bottom_left.max(0.0),
+        );
+        let fit = |length: f32, a: f32, b: f32| if a + b > length { length.max(0.0) / (a + b) } else { 1.0 };
+        let factor = fit(width, radii.top_left, radii.top_right)
+            .min(fit(width, radii.bottom_left, radii.bottom_right))
+            .min(fit(height, radii.top_left, radii.bottom_left))
+            .min(fit(height, radii.top_right, radii.bottom_right));
+        radii.scale(factor)
+    }
+}
+
+impl From<Rect> for Path {
+    fn from(rect: Rect) -> Self {
+        Path::rect(rect)
+    }
+}
+
+impl From<f32> for CornerRadii {
+    fn from(radius: f32) -> Self {
+        Self::uniform(radius)
+    }
+}
+
+/// Vector path made of lines, Bézier curves and arcs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    elements: Vec<PathElement>,
+    start: Option<Point>,
+    current: Option<Point>,
+}
+
+impl Path {
+    /// Create an empty path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a closed rectangular path.
+    pub fn rect(rect: Rect) -> Self {
+        let mut path = Self::new();
+        path.move_to(Point::new(rect.x, rect.y))
+            .line_to(Point::new(rect.right(), rect.y))
+            .line_to(Point::new(rect.right(), rect.bottom()))
+            .line_to(Point::new(rect.x, rect.bottom()))
+            .close();
+        path
+    }
+
+    /// Create a closed rectangle with rounded corners.
+    ///
+    /// Radii that do not fit the rectangle are scaled down proportionally.
+    pub fn rounded_rect(rect: Rect, radii: CornerRadii) -> Self {
+        let radii = radii.clamp_to(rect.width, rect.height);
+        let (left, top, right, bottom) = (rect.x, rect.y, rect.right(), rect.bottom());
+        let mut path = Self::new();
+
+        let r = radii.top_left;
+        if r > 0.0 {
+            path.elliptic_arc(Point::new(left + r, top + r), r, r, PI, FRAC_PI_2);
+        } else {
+            path.move_to(Point::new(left, top));
+        }
+
+        let r = radii.top_right;
+        if r > 0.0 {
+            path.elliptic_arc(Point::new(right - r, top + r), r, r, -FRAC_PI_2, FRAC_PI_2);
+        } else {
+            path.line_to(Point::new(right, top));
+        }
+
+        let r = radii.bottom_right;
+        if r > 0.0 {
+            path.elliptic_arc(Point::new(right - r, bottom - r), r, r, 0.0, FRAC_PI_2);
+        } else {
+            path.line_to(Point::new(right, bottom));
+        }
+
+        let r = radii.bottom_left;
+        if r > 0.0 {
+            path.elliptic_arc(Point::new(left + r, bottom - r), r, r, FRAC_PI_2, FRAC_PI_2);
+        } else {
+            path.line_to(Point::new(left, bottom));
+        }
+
+        path.close();
+        path
+    }
+
+    /// Create a closed ellipse around `center`.
+    pub fn ellipse(center: Point, radius_x: f32, radius_y: f32) -> Self {
+        let mut path = Self::new();
+        path.elliptic_arc(center, radius_x, radius_y, 0.0, 2.0 * PI);
+        path.close();
+        path
+    }
+
+    /// Create a closed circle around `center`.
+    pub fn circle(center: Point, radius: f32) -> Self {
+        Self::ellipse(center, radius, radius)
+    }
+
+    /// Create a path with a single straight line.
+    pub fn line(from: Point, to: Point) -> Self {
+        let mut path = Self::new();
+        path.move_to(from).line_to(to);
+        path
+    }
+
+    /// Create a closed polygon through the given points.
+    pub fn polygon(points: &[Point]) -> Self {
+        let mut path = Self::new();
+        if let Some((first, rest)) = points.split_first() {
+            path.move_to(*first);
+            for point in rest {
+                path.line_to(*point);
+            }
+            path.close();
+        }
+        path
+    }
+
+    /// Start a new subpath at the given point.
+    pub fn move_to(&mut self, point: Point) -> &mut Self {
+        self.elements.push(PathElement::MoveTo(point));
+        self.start = Some(point);
+        self.current = Some(point);
+        self
+    }
+
+    /// Add a straight line from the current point.
+    pub fn line_to(&mut self, point: Point) -> &mut Self {
+        self.ensure_subpath(point);
+        self.elements.push(PathElement::LineTo(point));
+        self.current = Some(point);
+        self
+    }
+
+    /// Add a quadratic Bézier curve from the current point.
+    pub fn quad_to(&mut self, control: Point, point: Point) -> &mut Self {
+        self.ensure_subpath(control);
+        self.elements.push(PathElement::QuadTo(control, point));
+        self.current = Some(point);
+        self
+    }
+
+    /// Add a cubic Bézier curve from the current point.
+    pub fn cubic_to(&mut self, control1: Point, control2: Point, point: Point) -> &mut Self {
+        self.ensure_subpath(control1);
+        self.elements.push(PathElement::CubicTo(control1, control2, point));
+        self.current = Some(point);
+        self
+    }
+
+    /// Add a circular arc around `center` from `start_angle` to `end_angle`.
+    ///
+    /// Angles are in radians, measured from the positive x axis and growing
+    /// clockwise on screen. A negative sweep draws counter-clockwise. If the
+    /// path already has a current point, a line is added to the arc start.
+    pub fn arc(&mut self, center: Point, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self {
+        self.elliptic_arc(center, radius, radius, start_angle, end_angle - start_angle);
+        self
+    }
+
+    /// Close the current subpath.
+    pub fn close(&mut self) -> &mut Self {
+        if self.current.is_some() {
+            self.elements.push(PathElement::Close);
+            self.current = self.start;
+        }
+        self
+    }
+
+    /// Append all subpaths of another path.
+    pub fn extend(&mut self, other: &Path) -> &mut Self {
+        if !other.is_empty() {
+            self.elements.extend_from_slice(&other.elements);
+            self.start = other.start;
+            self.current = other.current;
+        }
+        self
+    }
+
+    /// Get the path elements.
+    pub fn elements(&self) -> &[PathElement] {
+        &self.elements
+    }
+
+    /// Check if the path has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Get the current point, if any.
+    pub fn current_point(&self) -> Option<Point> {
+        self.current
+    }
+
+    /// Get the bounding rectangle of all points, including control points.
+    pub fn bounds(&self) -> Rect {
+        let mut min = Point::new(f32::MAX, f32::MAX);
+        let mut max = Point::new(f32::MIN, f32::MIN);
+        let mut extend = |p: Point| {
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        };
+
+        for element in &self.elements {
+            match *element {
+                PathElement::MoveTo(p) | PathElement::LineTo(p) => extend(p),
+                PathElement::QuadTo(c, p) => {
+                    extend(c);
+                    extend(p);
+                }
+                PathElement::CubicTo(c1, c2, p) => {
+                    extend(c1);
+                    extend(c2);
+                    extend(p);
+                }
+                PathElement::Close => {}
+            }
+        }
+
+        if min.x > max.x {
+            Rect::zero()
+        } else {
+            Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+        }
+    }
+
+    /// Check if a point lies inside the filled path.
+    pub fn contains_point(&self, point: Point, fill_rule: FillRule) -> bool {
+        let mut winding = 0;
+        for contour in self.flatten(DEFAULT_TOLERANCE) {
+            let count = contour.points.len();
+            for i in 0..count {
+                let from = contour.points[i];
+                let to = contour.points[(i + 1) % count];
+                if (from.y <= point.y) != (to.y <= point.y) {
+                    let x = from.x + (point.y - from.y) / (to.y - from.y) * (to.x - from.x);
+                    if x > point.x {
+                        winding += if to.y > from.y { 1 } else { -1 };
+                    }
+                }
+            }
+        }
+
+        match fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Return a copy of this path with every point mapped through `transform`.
+    pub fn transform(&self, transform: Transform) -> Path {
+        let map = |p: Point| transform.transform_point(p);
+        Path {
+            elements: self.elements.iter().map(|element| match *element {
+                PathElement::MoveTo(p) => PathElement::MoveTo(map(p)),
+                PathElement::LineTo(p) => PathElement::LineTo(map(p)),
+                PathElement::QuadTo(c, p) => PathElement::QuadTo(map(c), map(p)),
+                PathElement::CubicTo(c1, c2, p) => PathElement::CubicTo(map(c1), map(c2), map(p)),
+                PathElement::Close => PathElement::Close,
+            }).collect(),
+            start: self.start.map(map),
+            current: self.current.map(map),
+        }
+    }
+
+    /// Flatten the path into polylines.
+    ///
+    /// Curves are subdivided so that no point of a segment deviates from the
+    /// true curve by more than `tolerance`.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Contour> {
+        let tolerance = tolerance.max(0.01);
+        let mut contours = Vec::new();
+        let mut points: Vec<Point> = Vec::new();
+        let mut last = Point::zero();
+
+        let finish = |points: &mut Vec<Point>, contours: &mut Vec<Contour>, closed: bool| {
+            if points.len() > 1 || (closed && !points.is_empty()) {
+                contours.push(Contour { points: std::mem::take(points), closed });
+            } else {
+                points.clear();
+            }
+        };
+
+        for element in &self.elements {
+            match *element {
+                PathElement::MoveTo(p) => {
+                    finish(&mut points, &mut contours, false);
+                    points.push(p);
+                    last = p;
+                }
+                PathElement::LineTo(p) => {
+                    points.push(p);
+                    last = p;
+                }
+                PathElement::QuadTo(c, p) => {
+                    let dd = last.subtract(c.scale(2.0)).add(p);
+                    let dev = (dd.x * dd.x + dd.y * dd.y).sqrt();
+                    let segments = segment_count(dev / (4.0 * tolerance));
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        points.push(Point::new(
+                            mt * mt * last.x + 2.0 * mt * t * c.x + t * t * p.x,
+                            mt * mt * last.y + 2.0 * mt * t * c.y + t * t * p.y,
+                        ));
+                    }
+                    last = p;
+                }
+                PathElement::CubicTo(c1, c2, p) => {
+                    let dd1 = last.subtract(c1.scale(2.0)).add(c2);
+                    let dd2 = c1.subtract(c2.scale(2.0)).add(p);
+                    let dev = (dd1.x * dd1.x + dd1.y * dd1.y).max(dd2.x * dd2.x + dd2.y * dd2.y).sqrt();
+                    let segments = segment_count(3.0 * dev / (4.0 * tolerance));
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+                        points.push(Point::new(
+                            a * last.x + b * c1.x + c * c2.x + d * p.x,
+                            a * last.y + b * c1.y + c * c2.y + d * p.y,
+                        ));
+                    }
+                    last = p;
+                }
+                PathElement::Close => {
+                    let start = points.first().copied();
+                    finish(&mut points, &mut contours, true);
+                    if let Some(start) = start {
+                        // Drawing after a close continues from the subpath start
+                        points.push(start);
+                        last = start;
+                    }
+                }
+            }
+        }
+        finish(&mut points, &mut contours, false);
+
+        contours
+    }
+
+    /// Append an elliptical arc as cubic Bézier segments of at most 90°.
+    pub(crate) fn elliptic_arc(&mut self, center: Point, rx: f32, ry: f32, start_angle: f32, sweep: f32) {
+        let sweep = sweep.clamp(-2.0 * PI, 2.0 * PI);
+        let point_at = |angle: f32| Point::new(center.x + rx * angle.cos(), center.y + ry * angle.sin());
+
+        let start = point_at(start_angle);
+        if self.current.is_some() {
+            self.line_to(start);
+        } else {
+            self.move_to(start);
+        }
+
+        let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / segments as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+        let mut angle = start_angle;
+        for _ in 0..segments {
+            let next = angle + step;
+            let (sin_a, cos_a) = angle.sin_cos();
+            let (sin_b, cos_b) = next.sin_cos();
+            let control1 = Point::new(
+                center.x + rx * (cos_a - k * sin_a),
+                center.y + ry * (sin_a + k * cos_a),
+            );
+            let control2 = Point::new(
+                center.x + rx * (cos_b + k * sin_b),
+                center.y + ry * (sin_b - k * cos_b),
+            );
+            self.cubic_to(control1, control2, point_at(next));
+            angle = next;
+        }
+    }
+
+    /// Start an implicit subpath if a drawing command has no current point.
+    fn ensure_subpath(&mut self, point: Point) {
+        if self.current.is_none() {
+            self.move_to(point);
+        }
+    }
+}
+
+/// Number of line segments needed for a curve with the given flatness ratio.
+fn segment_count(ratio: f32) -> usize {
+    (ratio.sqrt().ceil() as usize).clamp(1, 256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_building() {
+        let mut path = Path::new();
+        path.move_to(Point::new(0.0, 0.0))
+            .line_to(Point::new(10.0, 0.0))
+            .quad_to(Point::new(15.0, 5.0), Point::new(10.0, 10.0))
+            .close();
+
+        assert_eq!(path.elements().len(), 4);
+        assert_eq!(path.current_point(), Some(Point::new(0.0, 0.0)));
+        assert_eq!(path.bounds(), Rect::new(0.0, 0.0, 15.0, 10.0));
+    }
+
+    #[test]
+    fn test_flatten_lines() {
+        let path = Path::rect(Rect::new(1.0, 2.0, 3.0, 4.0));
+        let contours = path.flatten(DEFAULT_TOLERANCE);
+
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].closed);
+        assert_eq!(contours[0].points.len(), 4);
+        assert_eq!(contours[0].points[2], Point::new(4.0, 6.0));
+    }
+
+    #[test]
+    fn test_flatten_curve_tolerance() {
+        let mut path = Path::new();
+        path.move_to(Point::new(0.0, 0.0))
+            .cubic_to(Point::new(0.0, 100.0), Point::new(100.0, 100.0), Point::new(100.0, 0.0));
+
+        let coarse = path.flatten(5.0);
+        let fine = path.flatten(0.1);
+        assert!(fine[0].points.len() > coarse[0].points.len());
+        assert_eq!(*fine[0].points.last().unwrap(), Point::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn test_arc_stays_on_circle() {
+        let center = Point::new(50.0, 50.0);
+        let mut path = Path::new();
+        path.arc(center, 20.0, 0.0, PI);
+
+        let contours = path.flatten(0.05);
+        for point in &contours[0].points {
+            assert!((point.distance_to(center) - 20.0).abs() < 0.1);
+        }
+        let end = *contours[0].points.last().unwrap();
+        assert!(end.distance_to(Point::new(30.0, 50.0)) < 0.01);
+    }
+
+    #[test]
+    fn test_rounded_rect() {
+        let rect = Rect::new(0.0, 0.0, 40.0, 20.0);
+        let path = Path::rounded_rect(rect, CornerRadii::new(5.0, 0.0, 50.0, 0.0));
+        assert_eq!(path.bounds(), rect);
+
+        // Radii that don't fit are scaled down so adjacent corners meet
+        let radii = CornerRadii::new(5.0, 0.0, 50.0, 0.0).clamp_to(40.0, 20.0);
+        assert!((radii.bottom_right - 20.0).abs() < 0.001);
+        assert!((radii.top_left - 2.0).abs() < 0.001);
+
+        let square = Path::rounded_rect(rect, CornerRadii::default());
+        assert_eq!(square, Path::rect(rect));
+    }
+
+    #[test]
+    fn test_ellipse() {
+        let path = Path::ellipse(Point::new(10.0, 10.0), 8.0, 4.0);
+        let contours = path.flatten(0.05);
+
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].closed);
+        for point in &contours[0].points {
+            let dx = (point.x - 10.0) / 8.0;
+            let dy = (point.y - 10.0) / 4.0;
+            assert!((dx * dx + dy * dy - 1.0).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let mut path = Path::rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        path.extend(&Path::rect(Rect::new(3.0, 3.0, 4.0, 4.0)));
+
+        assert!(path.contains_point(Point::new(5.0, 5.0), FillRule::NonZero));
+        assert!(!path.contains_point(Point::new(5.0, 5.0), FillRule::EvenOdd));
+        assert!(path.contains_point(Point::new(1.0, 1.0), FillRule::EvenOdd));
+        assert!(!path.contains_point(Point::new(11.0, 5.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_path_transform() {
+        let path = Path::line(Point::new(1.0, 1.0), Point::new(2.0, 3.0));
+        let moved = path.transform(Transform::translate(10.0, 20.0));
+
+        assert_eq!(moved.elements()[1], PathElement::LineTo(Point::new(12.0, 23.0)));
+        assert_eq!(moved.current_point(), Some(Point::new(12.0, 23.0)));
+    }
+}
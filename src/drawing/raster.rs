@@ -0,0 +1,466 @@
+//! Software rasterizer for paths and paints.
+//!
+//! Paths are converted into anti-aliased coverage masks with a scanline
+//! algorithm (exact horizontal coverage, sixteen vertical sub-samples) and
+//! then composited onto an `Image` with source-over blending. Backends use
+//! this for anything their native APIs cannot draw, such as gradients.
+
+use super::{BlendMode, Color, FillRule, Image, ImageFilter, Paint, Path, Point, Rect, StrokeStyle, Transform};
+use super::path::DEFAULT_TOLERANCE;
+
+/// Vertical sub-samples per pixel row.
+const SUBSAMPLES: usize = 16;
+
+/// Anti-aliased coverage of a path over a pixel rectangle.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Mask {
+    pub x: i32,
+    pub y: i32,
+    pub width: usize,
+    pub height: usize,
+    pub coverage: Vec<f32>,
+}
+
+impl Mask {
+    /// Rasterize a path (in device pixels) limited to the `clip` pixel rectangle.
+    pub fn from_path(path: &Path, fill_rule: FillRule, clip: Rect) -> Option<Mask> {
+        let bounds = pixel_bounds(path.bounds())?;
+        let area = bounds.intersection(pixel_bounds(clip)?)?;
+        let (x, y) = (area.x as i32, area.y as i32);
+        let (width, height) = (area.width as usize, area.height as usize);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let mut edges: Vec<Edge> = Vec::new();
+        for contour in path.flatten(DEFAULT_TOLERANCE) {
+            let count = contour.points.len();
+            for i in 0..count {
+                // Filling always treats subpaths as closed
+                let from = contour.points[i];
+                let to = contour.points[(i + 1) % count];
+                if from.y != to.y {
+                    edges.push(Edge::new(from, to));
+                }
+            }
+        }
+        edges.sort_by(|a, b| a.y_min.total_cmp(&b.y_min));
+
+        let mut coverage = vec![0.0f32; width * height];
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        let mut first_active = 0;
+
+        for row in 0..height {
+            let row_top = (y + row as i32) as f32;
+            let row_bottom = row_top + 1.0;
+            while first_active < edges.len() && edges[first_active].y_max <= row_top {
+                first_active += 1;
+            }
+            let active: Vec<&Edge> = edges[first_active..]
+                .iter()
+                .take_while(|edge| edge.y_min < row_bottom)
+                .filter(|edge| edge.y_max > row_top)
+                .collect();
+            if active.is_empty() {
+                continue;
+            }
+
+            let line = &mut coverage[row * width..(row + 1) * width];
+            for sample in 0..SUBSAMPLES {
+                let sample_y = row_top + (sample as f32 + 0.5) / SUBSAMPLES as f32;
+                crossings.clear();
+                for edge in &active {
+                    if sample_y >= edge.y_min && sample_y < edge.y_max {
+                        crossings.push((edge.x_at(sample_y) - x as f32, edge.direction));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match fill_rule {
+                        FillRule::NonZero => winding != 0,
+                        FillRule::EvenOdd => winding % 2 != 0,
+                    };
+                    if inside {
+                        add_span(line, pair[0].0, pair[1].0, 1.0 / SUBSAMPLES as f32);
+                    }
+                }
+            }
+        }
+
+        Some(Mask { x, y, width, height, coverage })
+    }
+
+    /// Rasterize the stroke of a path (in device pixels).
+    pub fn from_stroke(path: &Path, stroke: &StrokeStyle, clip: Rect) -> Option<Mask> {
+        let outline = stroke.outline(path, DEFAULT_TOLERANCE);
+        Mask::from_path(&outline, FillRule::NonZero, clip)
+    }
+
+    /// Get the coverage at a mask-relative pixel.
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        self.coverage[y * self.width + x].min(1.0)
+    }
+
+    /// Bilinearly sample the coverage at a point in mask space.
+    pub fn sample(&self, point: Point) -> f32 {
+        let fx = point.x - self.x as f32 - 0.5;
+        let fy = point.y - self.y as f32 - 0.5;
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let at = |x: f32, y: f32| {
+            if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+                0.0
+            } else {
+                self.get(x as usize, y as usize)
+            }
+        };
+        let top = at(x0, y0) * (1.0 - tx) + at(x0 + 1.0, y0) * tx;
+        let bottom = at(x0, y0 + 1.0) * (1.0 - tx) + at(x0 + 1.0, y0 + 1.0) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+}
+
+/// A non-horizontal polygon edge.
+struct Edge {
+    y_min: f32,
+    y_max: f32,
+    x_at_y_min: f32,
+    slope: f32,
+    direction: i32,
+}
+
+impl Edge {
+    fn new(from: Point, to: Point) -> Self {
+        let (top, bottom, direction) = if from.y < to.y { (from, to, 1) } else { (to, from, -1) };
+        Self {
+            y_min: top.y,
+            y_max: bottom.y,
+            x_at_y_min: top.x,
+            slope: (bottom.x - top.x) / (bottom.y - top.y),
+            direction,
+        }
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        self.x_at_y_min + (y - self.y_min) * self.slope
+    }
+}
+
+/// Add `weight` times the horizontal overlap of `[from, to)` to each pixel of a row.
+fn add_span(line: &mut [f32], from: f32, to: f32, weight: f32) {
+    let width = line.len() as f32;
+    let from = from.clamp(0.0, width);
+    let to = to.clamp(0.0, width);
+    if to <= from {
+        return;
+    }
+
+    let first = from.floor() as usize;
+    let last = (to.ceil() as usize).min(line.len()) - 1;
+    if first == last {
+        line[first] += (to - from) * weight;
+        return;
+    }
+
+    line[first] += (first as f32 + 1.0 - from) * weight;
+    for value in &mut line[first + 1..last] {
+        *value += weight;
+    }
+    line[last] += (to - last as f32) * weight;
+}
+
+/// Round a rectangle outwards to whole pixels.
+fn pixel_bounds(rect: Rect) -> Option<Rect> {
+    if !(rect.x.is_finite() && rect.y.is_finite() && rect.width.is_finite() && rect.height.is_finite()) {
+        return None;
+    }
+    let left = rect.x.floor();
+    let top = rect.y.floor();
+    Some(Rect::new(left, top, (rect.right().ceil() - left).max(0.0), (rect.bottom().ceil() - top).max(0.0)))
+}
+
+/// Blend a color onto an RGBA8 pixel with source-over compositing.
+pub(crate) fn blend_pixel(pixel: &mut [u8], color: Color, coverage: f32) {
+    let src_alpha = color.a * coverage.clamp(0.0, 1.0);
+    if src_alpha <= 0.0 {
+        return;
+    }
+
+    let dst_alpha = pixel[3] as f32 / 255.0;
+    let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+    let blend = |src: f32, dst: u8| {
+        let dst = dst as f32 / 255.0;
+        let value = (src * src_alpha + dst * dst_alpha * (1.0 - src_alpha)) / out_alpha;
+        (value * 255.0).round().clamp(0.0, 255.0) as u8
+    };
+
+    pixel[0] = blend(color.r, pixel[0]);
+    pixel[1] = blend(color.g, pixel[1]);
+    pixel[2] = blend(color.b, pixel[2]);
+    pixel[3] = (out_alpha * 255.0).round() as u8;
+}
+
+/// Composite a paint through a mask onto an image whose top-left pixel sits at `origin`.
+///
+/// The paint must already be in device space.
+pub(crate) fn composite_mask(target: &mut Image, origin: (i32, i32), mask: &Mask, paint: &Paint) {
+    let shader = paint.shader();
+    let (target_width, target_height) = (target.width() as i32, target.height() as i32);
+    let pixels = target.pixels_mut();
+
+    for row in 0..mask.height {
+        let device_y = mask.y + row as i32;
+        let target_y = device_y - origin.1;
+        if target_y < 0 || target_y >= target_height {
+            continue;
+        }
+
+        for column in 0..mask.width {
+            let coverage = mask.get(column, row);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let device_x = mask.x + column as i32;
+            let target_x = device_x - origin.0;
+            if target_x < 0 || target_x >= target_width {
+                continue;
+            }
+
+            let color = shader.color_at(Point::new(device_x as f32 + 0.5, device_y as f32 + 0.5));
+            let offset = (target_y as usize * target_width as usize + target_x as usize) * 4;
+            blend_pixel(&mut pixels[offset..offset + 4], color, coverage);
+        }
+    }
+}
+
+/// Composite a paint through a mask mapped to device space by `transform`.
+///
+/// The paint must already be in device space.
+pub(crate) fn composite_transformed_mask(target: &mut Image, origin: (i32, i32), mask: &Mask, transform: Transform, paint: &Paint) {
+    let inverse = match transform.inverse() {
+        Some(inverse) => inverse,
+        None => return,
+    };
+    let local = Rect::new(mask.x as f32, mask.y as f32, mask.width as f32, mask.height as f32);
+    let target_rect = Rect::new(origin.0 as f32, origin.1 as f32, target.width() as f32, target.height() as f32);
+    let area = match pixel_bounds(transform.transform_rect(local)).and_then(|bounds| bounds.intersection(target_rect)) {
+        Some(area) => area,
+        None => return,
+    };
+
+    let shader = paint.shader();
+    let target_width = target.width() as usize;
+    let pixels = target.pixels_mut();
+    for device_y in area.y as i32..area.bottom() as i32 {
+        for device_x in area.x as i32..area.right() as i32 {
+            let center = Point::new(device_x as f32 + 0.5, device_y as f32 + 0.5);
+            let coverage = mask.sample(inverse.transform_point(center));
+            if coverage <= 0.0 {
+                continue;
+            }
+            let offset = ((device_y - origin.1) as usize * target_width + (device_x - origin.0) as usize) * 4;
+            blend_pixel(&mut pixels[offset..offset + 4], shader.color_at(center), coverage);
+        }
+    }
+}
+
+/// Draw the `src` region of an image, mapped to device space by `transform`.
+///
+/// Samples are clamped to `src` so neighbouring pixels never bleed in, and
+/// the edges of the destination are anti-aliased.
+pub(crate) fn draw_image(target: &mut Image, origin: (i32, i32), image: &Image, src: Rect, transform: Transform, filter: ImageFilter) {
+    let inverse = match transform.inverse() {
+        Some(inverse) => inverse,
+        None => return,
+    };
+    let clip = Rect::new(origin.0 as f32, origin.1 as f32, target.width() as f32, target.height() as f32);
+    let mask = match Mask::from_path(&Path::rect(src).transform(transform), FillRule::NonZero, clip) {
+        Some(mask) => mask,
+        None => return,
+    };
+
+    let inner = src.contract(0.5);
+    let clamp = |value: f32, low: f32, high: f32| if low > high { (low + high) / 2.0 } else { value.clamp(low, high) };
+    let target_width = target.width() as usize;
+    let pixels = target.pixels_mut();
+    for row in 0..mask.height {
+        for column in 0..mask.width {
+            let coverage = mask.get(column, row);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let (device_x, device_y) = (mask.x + column as i32, mask.y + row as i32);
+            let local = inverse.transform_point(Point::new(device_x as f32 + 0.5, device_y as f32 + 0.5));
+            let local = Point::new(
+                clamp(local.x, inner.x, inner.right()),
+                clamp(local.y, inner.y, inner.bottom()),
+            );
+            let color = image.sample(local, filter);
+            let offset = ((device_y - origin.1) as usize * target_width + (device_x - origin.0) as usize) * 4;
+            blend_pixel(&mut pixels[offset..offset + 4], color, coverage);
+        }
+    }
+}
+
+/// Fill a device-space path onto an image whose top-left pixel sits at `origin`.
+pub(crate) fn fill_path(target: &mut Image, origin: (i32, i32), path: &Path, paint: &Paint, fill_rule: FillRule) {
+    let clip = Rect::new(origin.0 as f32, origin.1 as f32, target.width() as f32, target.height() as f32);
+    if let Some(mask) = Mask::from_path(path, fill_rule, clip) {
+        composite_mask(target, origin, &mask, paint);
+    }
+}
+
+/// Stroke a device-space path onto an image whose top-left pixel sits at `origin`.
+pub(crate) fn stroke_path(target: &mut Image, origin: (i32, i32), path: &Path, paint: &Paint, stroke: &StrokeStyle) {
+    let clip = Rect::new(origin.0 as f32, origin.1 as f32, target.width() as f32, target.height() as f32);
+    if let Some(mask) = Mask::from_stroke(path, stroke, clip) {
+        composite_mask(target, origin, &mask, paint);
+    }
+}
+
+/// Composite a layer whose top-left pixel sits at `layer_origin` onto an
+/// image whose top-left pixel sits at `origin`.
+///
+/// Only pixels under the layer are touched, even for modes such as
+/// `SourceIn` that would also erase the backdrop outside it.
+pub(crate) fn composite_layer(target: &mut Image, origin: (i32, i32), layer: &Image, layer_origin: (i32, i32), opacity: f32, mode: BlendMode) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let (target_width, target_height) = (target.width() as i32, target.height() as i32);
+    let target_pixels = target.pixels_mut();
+    for row in 0..layer.height() as i32 {
+        let target_y = layer_origin.1 + row - origin.1;
+        if target_y < 0 || target_y >= target_height {
+            continue;
+        }
+        for column in 0..layer.width() as i32 {
+            let target_x = layer_origin.0 + column - origin.0;
+            if target_x < 0 || target_x >= target_width {
+                continue;
+            }
+            let src = layer.pixel(column as u32, row as u32);
+            let src = src.with_alpha(src.a * opacity);
+            let offset = (target_y as usize * target_width as usize + target_x as usize) * 4;
+            let pixel = &mut target_pixels[offset..offset + 4];
+            let dst = Color::rgba8(pixel[0], pixel[1], pixel[2], pixel[3]);
+            pixel.copy_from_slice(&super::image::color_to_rgba8(mode.blend(src, dst)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::LinearGradient;
+
+    #[test]
+    fn test_rect_coverage() {
+        let path = Path::rect(Rect::new(1.0, 1.0, 2.0, 2.0));
+        let mask = Mask::from_path(&path, FillRule::NonZero, Rect::new(0.0, 0.0, 10.0, 10.0)).unwrap();
+
+        assert_eq!((mask.x, mask.y, mask.width, mask.height), (1, 1, 2, 2));
+        assert!(mask.coverage.iter().all(|c| (c - 1.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn test_partial_coverage() {
+        let path = Path::rect(Rect::new(0.5, 0.0, 1.0, 1.0));
+        let mask = Mask::from_path(&path, FillRule::NonZero, Rect::new(0.0, 0.0, 10.0, 10.0)).unwrap();
+
+        assert_eq!(mask.width, 2);
+        assert!((mask.get(0, 0) - 0.5).abs() < 0.001);
+        assert!((mask.get(1, 0) - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fill_rules() {
+        // Two nested squares wound the same way
+        let mut path = Path::rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        path.extend(&Path::rect(Rect::new(3.0, 3.0, 4.0, 4.0)));
+        let clip = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        let nonzero = Mask::from_path(&path, FillRule::NonZero, clip).unwrap();
+        let evenodd = Mask::from_path(&path, FillRule::EvenOdd, clip).unwrap();
+        assert!((nonzero.get(5, 5) - 1.0).abs() < 0.001);
+        assert!(evenodd.get(5, 5).abs() < 0.001);
+        assert!((evenodd.get(1, 1) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fill_path_with_gradient() {
+        let mut image = Image::filled(4, 1, Color::WHITE);
+        let paint = Paint::from(
+            LinearGradient::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0))
+                .stop(0.0, Color::BLACK)
+                .stop(1.0, Color::RED),
+        );
+        fill_path(&mut image, (0, 0), &Path::rect(Rect::new(0.0, 0.0, 4.0, 1.0)), &paint, FillRule::NonZero);
+
+        let left = image.pixel(0, 0);
+        let right = image.pixel(3, 0);
+        assert!(left.r < right.r);
+        assert_eq!(right.a, 1.0);
+        assert!(left.g < 0.01 && right.b < 0.01);
+    }
+
+    #[test]
+    fn test_transformed_mask() {
+        let mask = Mask { x: 0, y: 0, width: 2, height: 1, coverage: vec![1.0, 1.0] };
+        let mut image = Image::new(4, 4);
+        let transform = Transform::translate(1.0, 0.0).combine(Transform::rotate(std::f32::consts::FRAC_PI_2));
+        composite_transformed_mask(&mut image, (0, 0), &mask, transform, &Paint::from(Color::RED));
+
+        // The horizontal strip is now a vertical strip just left of x = 1
+        assert!(image.pixel(0, 0).a > 0.4 && image.pixel(0, 1).a > 0.4);
+        assert_eq!(image.pixel(2, 0).a, 0.0);
+        assert_eq!(image.pixel(0, 3).a, 0.0);
+    }
+
+    #[test]
+    fn test_draw_image_scaled() {
+        let mut source = Image::new(2, 1);
+        source.set_pixel(0, 0, Color::RED);
+        source.set_pixel(1, 0, Color::BLUE);
+        let src = Rect::new(0.0, 0.0, 2.0, 1.0);
+        let transform = Transform::scale(2.0, 2.0);
+
+        let mut nearest = Image::filled(4, 2, Color::WHITE);
+        draw_image(&mut nearest, (0, 0), &source, src, transform, ImageFilter::Nearest);
+        assert_eq!(nearest.pixel(1, 1), Color::RED);
+        assert_eq!(nearest.pixel(2, 0), Color::BLUE);
+
+        // Bilinear blends in the middle but the clamped edges stay pure
+        let mut bilinear = Image::filled(4, 2, Color::WHITE);
+        draw_image(&mut bilinear, (0, 0), &source, src, transform, ImageFilter::Bilinear);
+        assert_eq!(bilinear.pixel(0, 0), Color::RED);
+        assert_eq!(bilinear.pixel(3, 1), Color::BLUE);
+        let middle = bilinear.pixel(1, 0);
+        assert!(middle.r > 0.5 && middle.b > 0.1);
+    }
+
+    #[test]
+    fn test_blend_pixel() {
+        let mut pixel = [0, 0, 255, 255];
+        blend_pixel(&mut pixel, Color::RED, 0.5);
+        assert_eq!(pixel, [128, 0, 128, 255]);
+
+        let mut empty = [0, 0, 0, 0];
+        blend_pixel(&mut empty, Color::GREEN, 1.0);
+        assert_eq!(empty, [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn test_composite_layer() {
+        let mut target = Image::filled(4, 1, Color::rgb(0.4, 0.8, 0.0));
+        let layer = Image::filled(2, 1, Color::rgb(0.5, 0.5, 0.5));
+        composite_layer(&mut target, (10, 0), &layer, (11, 0), 1.0, BlendMode::Multiply);
+        assert_eq!(target.pixel(0, 0), Color::rgba8(102, 204, 0, 255));
+        assert_eq!(target.pixel(1, 0), Color::rgba8(51, 102, 0, 255));
+        assert_eq!(target.pixel(3, 0), Color::rgba8(102, 204, 0, 255));
+
+        composite_layer(&mut target, (10, 0), &layer, (10, 0), 0.0, BlendMode::Source);
+        assert_eq!(target.pixel(0, 0).a, 0.0);
+    }
+}
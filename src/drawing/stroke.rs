@@ -0,0 +1,461 @@
+//! Stroke styles for outlining paths and shapes.
+//!
+//! A `StrokeStyle` describes how an outline is drawn: its width, how open
+//! ends and corners are shaped, and an optional dash pattern.
+
+use super::{Contour, Path, Point};
+
+/// Most dash pieces a contour is split into; finer patterns are stretched to fit.
+const MAX_DASHES: f32 = 100_000.0;
+
+/// Shape used at the open ends of a stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// The stroke ends exactly at the endpoint.
+    #[default]
+    Butt,
+    /// The stroke ends with a half circle around the endpoint.
+    Round,
+    /// The stroke extends half its width past the endpoint.
+    Square,
+}
+
+/// Shape used where two stroke segments meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet, up to the miter limit.
+    #[default]
+    Miter,
+    /// Round the corner with an arc.
+    Round,
+    /// Cut the corner off with a straight line.
+    Bevel,
+}
+
+/// Style used when stroking paths and shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f32,
+    pub dashes: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    /// Create a solid stroke style with the given width.
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            miter_limit: 4.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    /// Set the line cap.
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set the line join.
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Set the miter limit, as a ratio of miter length to stroke width.
+    pub fn miter_limit(mut self, limit: f32) -> Self {
+        self.miter_limit = limit.max(1.0);
+        self
+    }
+
+    /// Set the dash pattern as alternating on/off lengths.
+    ///
+    /// On contours so long that the pattern would split them into more than
+    /// 100 000 pieces, it is stretched evenly until it does not.
+    pub fn dashes(mut self, dashes: &[f32], offset: f32) -> Self {
+        self.dashes = dashes.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+
+    /// Check if the stroke is dashed.
+    pub fn is_dashed(&self) -> bool {
+        !self.dash_pattern().is_empty()
+    }
+    
+    /// Scale the width and dash lengths by a factor.
+    pub fn scale(&self, factor: f32) -> StrokeStyle {
+        StrokeStyle {
+            width: self.width * factor,
+            dashes: self.dashes.iter().map(|d| d * factor).collect(),
+            dash_offset: self.dash_offset * factor,
+            ..self.clone()
+        }
+    }
+
+    /// Get the effective dash pattern.
+    ///
+    /// Odd-length patterns are repeated to make them even, and patterns with
+    /// negative or all-zero lengths are treated as solid.
+    pub fn dash_pattern(&self) -> Vec<f32> {
+        if self.dashes.iter().any(|d| *d < 0.0 || !d.is_finite()) || self.dashes.iter().sum::<f32>() <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut pattern = self.dashes.clone();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&self.dashes);
+        }
+        pattern
+    }
+
+    /// Split contours into the "on" pieces of the dash pattern.
+    ///
+    /// Solid styles return the contours unchanged. Contours that would split
+    /// into more than `MAX_DASHES` pieces are dashed with the pattern
+    /// stretched so they do not.
+    pub fn apply_dashes(&self, contours: &[Contour]) -> Vec<Contour> {
+        let base = self.dash_pattern();
+        if base.is_empty() {
+            return contours.to_vec();
+        }
+
+        let base_total: f32 = base.iter().sum();
+        let mut dashed = Vec::new();
+
+        for contour in contours {
+            let mut points = contour.points.clone();
+            if contour.closed {
+                if let Some(first) = points.first().copied() {
+                    points.push(first);
+                }
+            }
+
+            // Dashes too short to see would only cost time and memory, so the
+            // pattern is coarsened until the contour splits into few enough
+            let contour_length: f32 = points.windows(2).map(|segment| segment[0].distance_to(segment[1])).sum();
+            let scale = (contour_length / base_total * base.len() as f32 / MAX_DASHES).max(1.0);
+            let pattern: Vec<f32> = base.iter().map(|length| length * scale).collect();
+            let total = base_total * scale;
+
+            // Find where the offset lands in the pattern
+            let mut index = 0;
+            let mut remaining = pattern[0];
+            let mut offset = (self.dash_offset * scale).rem_euclid(total);
+            while offset > 0.0 {
+                if offset < remaining {
+                    remaining -= offset;
+                    break;
+                }
+                offset -= remaining;
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+            }
+
+            let mut current: Vec<Point> = Vec::new();
+            if index % 2 == 0 {
+                if let Some(first) = points.first() {
+                    current.push(*first);
+                }
+            }
+
+            for segment in points.windows(2) {
+                let (mut from, to) = (segment[0], segment[1]);
+                let mut length = from.distance_to(to);
+
+                while length > 0.0 {
+                    // Finish the segment once a step is too small to shorten it
+                    let step = if remaining > 0.0 && length - remaining == length { length } else { remaining.min(length) };
+                    let t = step / length;
+                    let point = Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+                    if index % 2 == 0 {
+                        current.push(point);
+                    }
+
+                    remaining -= step;
+                    length -= step;
+                    from = point;
+
+                    if remaining <= 0.0 {
+                        if index % 2 == 0 && current.len() > 1 {
+                            dashed.push(Contour { points: std::mem::take(&mut current), closed: false });
+                        }
+                        current.clear();
+                        index = (index + 1) % pattern.len();
+                        remaining = pattern[index];
+                        if index % 2 == 0 {
+                            current.push(point);
+                        }
+                    }
+                }
+            }
+
+            if current.len() > 1 {
+                dashed.push(Contour { points: current, closed: false });
+            }
+        }
+
+        dashed
+    }
+}
+
+impl StrokeStyle {
+    /// Convert the stroke of a path into a fillable outline.
+    ///
+    /// The outline is made of overlapping pieces that all wind the same way,
+    /// so it must be filled with `FillRule::NonZero`.
+    pub fn outline(&self, path: &Path, tolerance: f32) -> Path {
+        let half = self.width / 2.0;
+        let mut outline = Path::new();
+        if half <= 0.0 {
+            return outline;
+        }
+
+        for contour in self.apply_dashes(&path.flatten(tolerance)) {
+            let mut points: Vec<Point> = Vec::with_capacity(contour.points.len());
+            for point in contour.points {
+                if points.last().is_none_or(|last| last.distance_to(point) > 1e-4) {
+                    points.push(point);
+                }
+            }
+            let closed = contour.closed && points.len() > 2;
+            if closed && points[0].distance_to(points[points.len() - 1]) <= 1e-4 {
+                points.pop();
+            }
+
+            if points.len() == 1 {
+                // Zero-length subpaths only show up as round or square dots
+                match self.cap {
+                    LineCap::Butt => {}
+                    LineCap::Round => {
+                        outline.extend(&Path::circle(points[0], half));
+                    }
+                    LineCap::Square => {
+                        let p = points[0];
+                        push_polygon(&mut outline, &[
+                            Point::new(p.x - half, p.y - half),
+                            Point::new(p.x + half, p.y - half),
+                            Point::new(p.x + half, p.y + half),
+                            Point::new(p.x - half, p.y + half),
+                        ]);
+                    }
+                }
+                continue;
+            }
+            if points.len() < 2 {
+                continue;
+            }
+
+            let count = points.len();
+            let segment_count = if closed { count } else { count - 1 };
+            for i in 0..segment_count {
+                let mut from = points[i];
+                let mut to = points[(i + 1) % count];
+                let direction = unit(to.subtract(from));
+
+                if !closed && self.cap == LineCap::Square {
+                    if i == 0 {
+                        from = from.subtract(direction.scale(half));
+                    }
+                    if i == segment_count - 1 {
+                        to = to.add(direction.scale(half));
+                    }
+                }
+
+                let normal = Point::new(-direction.y, direction.x).scale(half);
+                push_polygon(&mut outline, &[
+                    from.add(normal),
+                    to.add(normal),
+                    to.subtract(normal),
+                    from.subtract(normal),
+                ]);
+            }
+
+            let join_range = if closed { 0..count } else { 1..count - 1 };
+            for i in join_range {
+                let vertex = points[i];
+                let incoming = unit(vertex.subtract(points[(i + count - 1) % count]));
+                let outgoing = unit(points[(i + 1) % count].subtract(vertex));
+                self.push_join(&mut outline, vertex, incoming, outgoing, half);
+            }
+
+            if !closed && self.cap == LineCap::Round {
+                outline.extend(&Path::circle(points[0], half));
+                outline.extend(&Path::circle(points[count - 1], half));
+            }
+        }
+
+        outline
+    }
+
+    /// Add the join piece between two segments meeting at `vertex`.
+    fn push_join(&self, outline: &mut Path, vertex: Point, incoming: Point, outgoing: Point, half: f32) {
+        let cross = incoming.x * outgoing.y - incoming.y * outgoing.x;
+        let dot = incoming.x * outgoing.x + incoming.y * outgoing.y;
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            return;
+        }
+
+        if self.join == LineJoin::Round {
+            outline.extend(&Path::circle(vertex, half));
+            return;
+        }
+
+        // The outer side of the turn is opposite the direction we turn towards
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let normal_in = Point::new(-incoming.y, incoming.x).scale(half * side);
+        let normal_out = Point::new(-outgoing.y, outgoing.x).scale(half * side);
+        let outer_in = vertex.add(normal_in);
+        let outer_out = vertex.add(normal_out);
+
+        let miter_ratio = (2.0 / (1.0 + dot).max(1e-6)).sqrt();
+        if self.join == LineJoin::Miter && miter_ratio <= self.miter_limit {
+            let tip = vertex.add(normal_in.add(normal_out).scale(1.0 / (1.0 + dot)));
+            push_polygon(outline, &[vertex, outer_in, tip, outer_out]);
+        } else {
+            push_polygon(outline, &[vertex, outer_in, outer_out]);
+        }
+    }
+}
+
+/// Normalize a vector, returning zero for degenerate input.
+fn unit(vector: Point) -> Point {
+    let length = (vector.x * vector.x + vector.y * vector.y).sqrt();
+    if length > 0.0 {
+        vector.scale(1.0 / length)
+    } else {
+        Point::zero()
+    }
+}
+
+/// Add a closed polygon to the outline with positive orientation.
+fn push_polygon(outline: &mut Path, points: &[Point]) {
+    let area: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+
+    if area >= 0.0 {
+        outline.extend(&Path::polygon(points));
+    } else {
+        let reversed: Vec<Point> = points.iter().rev().copied().collect();
+        outline.extend(&Path::polygon(&reversed));
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl From<f32> for StrokeStyle {
+    fn from(width: f32) -> Self {
+        Self::new(width)
+    }
+}
+
+impl From<&StrokeStyle> for StrokeStyle {
+    fn from(style: &StrokeStyle) -> Self {
+        style.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Rect;
+
+    #[test]
+    fn test_stroke_style_builder() {
+        let style = StrokeStyle::from(2.0)
+            .cap(LineCap::Round)
+            .join(LineJoin::Bevel)
+            .dashes(&[4.0, 2.0], 1.0);
+
+        assert_eq!(style.width, 2.0);
+        assert_eq!(style.cap, LineCap::Round);
+        assert_eq!(style.join, LineJoin::Bevel);
+        assert!(style.is_dashed());
+        assert!(!StrokeStyle::default().is_dashed());
+
+        let scaled = style.scale(2.0);
+        assert_eq!((scaled.width, scaled.dash_offset), (4.0, 2.0));
+        assert_eq!(scaled.dashes, vec![8.0, 4.0]);
+    }
+
+    #[test]
+    fn test_dash_pattern_normalization() {
+        assert_eq!(StrokeStyle::new(1.0).dashes(&[3.0], 0.0).dash_pattern(), vec![3.0, 3.0]);
+        assert!(StrokeStyle::new(1.0).dashes(&[0.0, 0.0], 0.0).dash_pattern().is_empty());
+        assert!(StrokeStyle::new(1.0).dashes(&[2.0, -1.0], 0.0).dash_pattern().is_empty());
+    }
+
+    #[test]
+    fn test_outline_covers_stroke() {
+        let line = Path::line(Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+
+        let butt = StrokeStyle::new(4.0).outline(&line, 0.1);
+        assert_eq!(butt.bounds(), Rect::new(0.0, -2.0, 10.0, 4.0));
+
+        let square = StrokeStyle::new(4.0).cap(LineCap::Square).outline(&line, 0.1);
+        assert_eq!(square.bounds(), Rect::new(-2.0, -2.0, 14.0, 4.0));
+
+        // A right-angle miter reaches the outer corner
+        let mut corner = Path::new();
+        corner.move_to(Point::new(0.0, 0.0))
+            .line_to(Point::new(10.0, 0.0))
+            .line_to(Point::new(10.0, 10.0));
+        let mitered = StrokeStyle::new(2.0).outline(&corner, 0.1);
+        assert_eq!(mitered.bounds(), Rect::new(0.0, -1.0, 11.0, 11.0));
+    }
+
+    fn assert_points(actual: &[Point], expected: &[(f32, f32)]) {
+        assert_eq!(actual.len(), expected.len());
+        for (point, (x, y)) in actual.iter().zip(expected) {
+            assert!((point.x - x).abs() < 0.001 && (point.y - y).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_apply_dashes() {
+        let line = Contour {
+            points: vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0)],
+            closed: false,
+        };
+
+        let dashed = StrokeStyle::new(1.0).dashes(&[3.0, 2.0], 0.0).apply_dashes(std::slice::from_ref(&line));
+        assert_eq!(dashed.len(), 2);
+        assert_points(&dashed[0].points, &[(0.0, 0.0), (3.0, 0.0)]);
+        assert_points(&dashed[1].points, &[(5.0, 0.0), (8.0, 0.0)]);
+
+        // An offset shifts the pattern along the line
+        let shifted = StrokeStyle::new(1.0).dashes(&[3.0, 2.0], 4.0).apply_dashes(&[line]);
+        assert_points(&shifted[0].points, &[(1.0, 0.0), (4.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_tiny_dashes() {
+        let line = Contour {
+            points: vec![Point::new(0.0, 0.0), Point::new(1000.0, 0.0)],
+            closed: false,
+        };
+
+        // Too many dashes to draw are stretched until there are few enough
+        let coarse = StrokeStyle::new(1.0).dashes(&[1e-5], 0.0).apply_dashes(std::slice::from_ref(&line));
+        assert!(coarse.len() > 1 && coarse.len() as f32 <= MAX_DASHES / 2.0 + 1.0);
+        let covered: f32 = coarse.iter().map(|dash| dash.points[0].distance_to(dash.points[dash.points.len() - 1])).sum();
+        assert!((covered - 500.0).abs() < 1.0, "{}", covered);
+
+        // Dashes too short to move along the segment still finish it
+        let dashed = StrokeStyle::new(1.0).dashes(&[1e-5, 1.0], 0.0).apply_dashes(&[line]);
+        assert!(!dashed.is_empty() && dashed.len() <= 1000);
+    }
+}
@@ -0,0 +1,330 @@
+//! Software drawing into images.
+//!
+//! [`ImageContext`] is a `DrawingContext` that rasterizes every call into an
+//! `Image` with the software rasterizer. Canvas layers draw their content
+//! through it before compositing the result onto the parent surface, and
+//! [`RenderTarget`] exposes it for rendering thumbnails, drag images and
+//! test snapshots without a window.
+
+use super::{effects, raster, BlendMode, Canvas, Clip, Color, FillRule, FontQuery, Image, ImageFilter, Paint, Path, Point, Rect, StrokeStyle, TextMetrics, Transform};
+use super::font::platform_face;
+use super::raster::Mask;
+use crate::platform::DrawingContext;
+use crate::{Error, Result, Size};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Drawing context that rasterizes into a shared image.
+pub(crate) struct ImageContext {
+    image: Arc<Mutex<Image>>,
+    origin: (i32, i32),
+    size: (u32, u32),
+    clip: Option<Clip>,
+}
+
+impl ImageContext {
+    /// Draw into `image`, whose top-left pixel sits at device `origin` of a
+    /// surface of the given size.
+    pub fn new(image: Arc<Mutex<Image>>, origin: (i32, i32), size: (u32, u32)) -> Self {
+        Self {
+            image,
+            origin,
+            size,
+            clip: None,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Image> {
+        self.image.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run `draw` on the part of the image under `area`, honouring the clip.
+    ///
+    /// Clipped drawing goes to a copy of the affected pixels, which is then
+    /// mixed back by the clip's coverage so clip edges stay anti-aliased.
+    fn paint(&mut self, area: Rect, draw: impl FnOnce(&mut Image, (i32, i32))) {
+        let origin = self.origin;
+        let Some(clip) = self.clip.clone() else {
+            draw(&mut self.lock(), origin);
+            return;
+        };
+
+        let mut image = self.lock();
+        let bounds = Rect::new(origin.0 as f32, origin.1 as f32, image.width() as f32, image.height() as f32);
+        let Some(region) = area.expand(1.0).intersection(bounds).and_then(|area| area.intersection(clip.bounds())) else {
+            return;
+        };
+        let left = region.x.floor() as i32;
+        let top = region.y.floor() as i32;
+        let width = (region.right().ceil() as i32 - left).max(0) as u32;
+        let height = (region.bottom().ceil() as i32 - top).max(0) as u32;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let mut scratch = Image::new(width, height);
+        for row in 0..height {
+            for column in 0..width {
+                let (x, y) = ((left - origin.0) as u32 + column, (top - origin.1) as u32 + row);
+                scratch.set_pixel(column, row, image.pixel(x, y));
+            }
+        }
+        draw(&mut scratch, (left, top));
+
+        let pixel_area = Rect::new(left as f32, top as f32, width as f32, height as f32);
+        let masks: Vec<Mask> = clip
+            .paths()
+            .iter()
+            .filter_map(|(path, fill_rule)| Mask::from_path(path, *fill_rule, pixel_area))
+            .collect();
+        if masks.len() < clip.paths().len() {
+            // A clip path misses this area entirely
+            return;
+        }
+        for row in 0..height {
+            for column in 0..width {
+                let (device_x, device_y) = (left + column as i32, top + row as i32);
+                let center = Point::new(device_x as f32 + 0.5, device_y as f32 + 0.5);
+                let mut coverage = if clip.bounds().contains_point(center) { 1.0 } else { 0.0 };
+                for mask in &masks {
+                    let (mx, my) = (device_x - mask.x, device_y - mask.y);
+                    coverage *= if mx >= 0 && my >= 0 && (mx as usize) < mask.width && (my as usize) < mask.height {
+                        mask.get(mx as usize, my as usize)
+                    } else {
+                        0.0
+                    };
+                }
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let (x, y) = ((device_x - origin.0) as u32, (device_y - origin.1) as u32);
+                let mixed = mix(image.pixel(x, y), scratch.pixel(column, row), coverage);
+                image.set_pixel(x, y, mixed);
+            }
+        }
+    }
+
+    fn fill(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) {
+        self.paint(path.bounds(), |image, origin| raster::fill_path(image, origin, path, paint, fill_rule));
+    }
+
+    fn stroke(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) {
+        let area = path.bounds().expand(stroke.width.max(1.0) * stroke.miter_limit.max(1.0));
+        self.paint(area, |image, origin| raster::stroke_path(image, origin, path, paint, stroke));
+    }
+}
+
+impl DrawingContext for ImageContext {
+    fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+        let color = Color::rgba(color.0, color.1, color.2, color.3);
+        let area = Rect::new(self.origin.0 as f32, self.origin.1 as f32, self.size.0 as f32, self.size.1 as f32);
+        self.paint(area, |image, _| image.fill(color));
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.fill_path(&Path::rect(Rect::new(x, y, width, height)), color, FillRule::NonZero)
+    }
+
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke_path(&Path::rect(Rect::new(x, y, width, height)), color, stroke)
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        self.draw_text_transformed(text, Transform::translate(x, y), color)
+    }
+
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        // The platform font cannot draw into images, so its text is filled
+        // from the outlines of the default sans-serif face instead
+        let font = platform_face().ok_or_else(|| Error::resource("No installed font to draw system-font text into an image"))?;
+        let size = FontQuery::default().size;
+        let mut outlines = Path::new();
+        for placed in font.layout(text, size) {
+            let glyph = font.fallback_font(placed.font).glyph_path(placed.glyph, size);
+            outlines.extend(&glyph.transform(Transform::translate(placed.x, 0.0)));
+        }
+        self.fill_path(&outlines.transform(transform), color, FillRule::NonZero)
+    }
+
+    fn measure_text(&self, text: &str) -> Option<TextMetrics> {
+        platform_face().map(|font| font.measure(text, FontQuery::default().size))
+    }
+
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        self.paint(transform.transform_rect(src), |target, origin| raster::draw_image(target, origin, image, src, transform, filter));
+        Ok(())
+    }
+
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        self.paint(region, |image, origin| effects::blur_region(image, origin, region, radius));
+        Ok(())
+    }
+
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let layer_origin = (origin.x as i32, origin.y as i32);
+        let area = Rect::new(origin.x, origin.y, layer.width() as f32, layer.height() as f32);
+        self.paint(area, |target, target_origin| {
+            raster::composite_layer(target, target_origin, layer, layer_origin, opacity, blend_mode);
+        });
+        Ok(())
+    }
+
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        self.fill(path, &Paint::Solid(Color::rgba(color.0, color.1, color.2, color.3)), fill_rule);
+        Ok(())
+    }
+
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke(path, &Paint::Solid(Color::rgba(color.0, color.1, color.2, color.3)), stroke);
+        Ok(())
+    }
+
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        self.fill(path, paint, fill_rule);
+        Ok(())
+    }
+
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        self.stroke(path, paint, stroke);
+        Ok(())
+    }
+
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        self.clip = clip.cloned();
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn read_pixels(&mut self) -> Result<Image> {
+        Ok(self.lock().clone())
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+/// An offscreen image that can be drawn on with a [`Canvas`].
+pub struct RenderTarget {
+    image: Arc<Mutex<Image>>,
+    canvas: Canvas,
+}
+
+impl RenderTarget {
+    /// Create a transparent render target of the given size in pixels.
+    pub fn new(size: Size) -> Self {
+        let (width, height) = (size.width.max(0.0).ceil() as u32, size.height.max(0.0).ceil() as u32);
+        let image = Arc::new(Mutex::new(Image::new(width, height)));
+        let context = ImageContext::new(image.clone(), (0, 0), (width, height));
+        Self {
+            image,
+            canvas: Canvas::new(Box::new(context)),
+        }
+    }
+
+    /// The canvas drawing into the target.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Get the size in pixels.
+    pub fn size(&self) -> Size {
+        self.canvas.size()
+    }
+
+    /// A copy of what has been drawn so far.
+    pub fn image(&self) -> Image {
+        self.lock().clone()
+    }
+
+    /// Finish drawing and take the image.
+    pub fn into_image(self) -> Image {
+        let RenderTarget { image, canvas } = self;
+        drop(canvas);
+        match Arc::try_unwrap(image) {
+            Ok(image) => image.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()),
+            Err(image) => image.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Image> {
+        self.image.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Interpolate between two colors, including alpha.
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let lerp = |a: f32, b: f32| a + (b - a) * amount;
+    Color::rgba(lerp(from.r, to.r), lerp(from.g, to.g), lerp(from.b, to.b), lerp(from.a, to.a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::Font;
+
+    fn context(width: u32, height: u32) -> (ImageContext, Arc<Mutex<Image>>) {
+        let image = Arc::new(Mutex::new(Image::new(width, height)));
+        (ImageContext::new(image.clone(), (0, 0), (width, height)), image)
+    }
+
+    #[test]
+    fn test_draws_into_image() {
+        let (mut context, image) = context(8, 8);
+        context.clear(Color::WHITE.to_tuple()).unwrap();
+        context.fill_rect(2.0, 2.0, 4.0, 4.0, Color::RED.to_tuple()).unwrap();
+        let image = image.lock().unwrap();
+        assert_eq!(image.pixel(3, 3), Color::RED);
+        assert_eq!(image.pixel(0, 0), Color::WHITE);
+    }
+
+    #[test]
+    fn test_clip_limits_drawing() {
+        let (mut context, image) = context(8, 8);
+        context.set_clip(Some(&Clip::rect(Rect::new(0.0, 0.0, 4.0, 8.0)))).unwrap();
+        context.fill_rect(0.0, 0.0, 8.0, 8.0, Color::BLUE.to_tuple()).unwrap();
+        let circle = Clip::path(Path::ellipse(Point::new(6.0, 6.0), 1.5, 1.5), FillRule::NonZero);
+        context.set_clip(Some(&circle)).unwrap();
+        context.fill_rect(0.0, 0.0, 8.0, 8.0, Color::RED.to_tuple()).unwrap();
+
+        let image = image.lock().unwrap();
+        assert_eq!(image.pixel(3, 7), Color::BLUE);
+        assert_eq!(image.pixel(4, 0).a, 0.0);
+        assert_eq!(image.pixel(6, 6), Color::RED);
+        assert_eq!(image.pixel(7, 0).a, 0.0);
+    }
+
+    #[test]
+    fn test_offset_origin() {
+        let image = Arc::new(Mutex::new(Image::new(4, 4)));
+        let mut context = ImageContext::new(image.clone(), (10, 20), (100, 100));
+        context.fill_rect(11.0, 21.0, 1.0, 1.0, Color::GREEN.to_tuple()).unwrap();
+        assert_eq!(image.lock().unwrap().pixel(1, 1), Color::GREEN);
+    }
+
+    #[test]
+    fn test_render_target() {
+        let mut target = RenderTarget::new(Size::new(6.0, 4.0));
+        assert_eq!(target.size(), Size::new(6.0, 4.0));
+        target.canvas().fill_rect(Rect::new(0.0, 0.0, 3.0, 4.0), Color::RED).unwrap();
+        assert_eq!(target.image().pixel(1, 1), Color::RED);
+        target.canvas().clear(Color::BLUE).unwrap();
+        let image = target.into_image();
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.pixel(5, 3), Color::BLUE);
+    }
+
+    #[test]
+    fn test_measures_system_text() {
+        let mut target = RenderTarget::new(Size::new(4.0, 4.0));
+        let metrics = target.canvas().measure_text("Wi", &Font::system(), 30.0);
+        match platform_face() {
+            // Measured as it is drawn, with the stand-in face at its own size
+            Some(face) => assert_eq!(metrics, face.measure("Wi", FontQuery::default().size)),
+            None => assert_eq!(metrics, Font::system().measure("Wi", 30.0)),
+        }
+    }
+}
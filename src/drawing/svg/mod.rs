@@ -0,0 +1,672 @@
+//! SVG images.
+//!
+//! A practical subset of SVG is supported: paths and basic shapes, groups
+//! with transforms and opacity, solid and gradient fills and strokes, and
+//! `viewBox` scaling with `preserveAspectRatio`. Documents are parsed once
+//! into a tree of paths that is replayed through `Canvas` calls, so an
+//! `SvgImage` stays sharp at any size.
+//!
+//! Text, images, clipping paths, masks, filters, `<use>` references and CSS
+//! style sheets are not supported and are skipped.
+
+pub mod xml;
+pub mod parse;
+
+use super::{BlendMode, Canvas, Color, FillRule, Image, LineCap, LineJoin, LinearGradient, Paint, Path, Point, RadialGradient, Rect, RenderTarget, Size, SpreadMode, StrokeStyle, Transform};
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::sync::Arc;
+use xml::Element;
+
+/// Size used when a document gives neither a size nor a `viewBox`.
+const DEFAULT_SIZE: f32 = 100.0;
+
+/// Longest chain of gradients inheriting from each other through `href`.
+const MAX_GRADIENT_REFERENCES: usize = 16;
+
+/// A parsed SVG document.
+///
+/// Images are cheap to clone; clones share the parsed shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgImage {
+    size: Size,
+    view_box: Rect,
+    aspect: Option<Alignment>,
+    nodes: Arc<Vec<Node>>,
+}
+
+/// How the `viewBox` is fitted to the drawing area when it is not stretched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Alignment {
+    x: f32,
+    y: f32,
+    slice: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Group {
+        transform: Transform,
+        opacity: f32,
+        children: Vec<Node>,
+    },
+    Shape {
+        path: Path,
+        fill: Option<(Paint, FillRule)>,
+        stroke: Option<(Paint, StrokeStyle)>,
+    },
+}
+
+impl SvgImage {
+    /// Parse an SVG document.
+    pub fn parse(source: &str) -> Result<Self> {
+        let root = xml::parse(source)?;
+        if root.name != "svg" {
+            return Err(Error::resource("Not an SVG document"));
+        }
+
+        let view_box = root.attribute("viewBox").map(parse::numbers)
+            .filter(|values| values.len() == 4 && values[2] > 0.0 && values[3] > 0.0)
+            .map(|values| Rect::new(values[0], values[1], values[2], values[3]));
+        let dimension = |name: &str, fallback: Option<f32>| {
+            root.attribute(name)
+                .filter(|value| !value.trim().ends_with('%'))
+                .and_then(|value| parse::length(value, 0.0))
+                .filter(|value| *value > 0.0)
+                .or(fallback)
+                .unwrap_or(DEFAULT_SIZE)
+        };
+        let width = dimension("width", view_box.map(|view_box| view_box.width));
+        let height = dimension("height", view_box.map(|view_box| view_box.height));
+        let view_box = view_box.unwrap_or(Rect::new(0.0, 0.0, width, height));
+
+        let mut gradients = HashMap::new();
+        collect_gradients(&root, &mut gradients);
+        let builder = Builder { gradients, view_box };
+        let nodes = builder.children(&root, &Style::default());
+
+        Ok(Self {
+            size: Size::new(width, height),
+            view_box,
+            aspect: parse_aspect(root.attribute("preserveAspectRatio").unwrap_or("")),
+            nodes: Arc::new(nodes),
+        })
+    }
+
+    /// Parse an SVG document from bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let source = std::str::from_utf8(data).map_err(|_| Error::resource("SVG document is not valid UTF-8"))?;
+        Self::parse(source)
+    }
+
+    /// Load and parse an SVG file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Get the intrinsic size given by the document.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Get the area of the drawing that is shown.
+    pub fn view_box(&self) -> Rect {
+        self.view_box
+    }
+
+    /// Map from `viewBox` coordinates into `dst`.
+    pub fn view_transform(&self, dst: Rect) -> Transform {
+        let view_box = self.view_box;
+        let mut sx = dst.width / view_box.width;
+        let mut sy = dst.height / view_box.height;
+        let (mut x, mut y) = (dst.x, dst.y);
+        if let Some(alignment) = self.aspect {
+            let scale = if alignment.slice { sx.max(sy) } else { sx.min(sy) };
+            sx = scale;
+            sy = scale;
+            x += (dst.width - view_box.width * scale) * alignment.x;
+            y += (dst.height - view_box.height * scale) * alignment.y;
+        }
+        Transform::translate(x, y)
+            .combine(Transform::scale(sx, sy))
+            .combine(Transform::translate(-view_box.x, -view_box.y))
+    }
+
+    /// Draw the image scaled into `dst`.
+    pub fn draw(&self, canvas: &mut Canvas, dst: Rect) -> Result<()> {
+        if dst.is_empty() {
+            return Ok(());
+        }
+        canvas.save();
+        canvas.clip_rect(dst);
+        canvas.set_transform(canvas.transform().combine(self.view_transform(dst)));
+        let result = draw_nodes(canvas, &self.nodes);
+        canvas.restore();
+        result
+    }
+
+    /// Render the image into a new image of the given size.
+    pub fn to_image(&self, width: u32, height: u32) -> Result<Image> {
+        let mut target = RenderTarget::new(Size::new(width as f32, height as f32));
+        self.draw(target.canvas(), Rect::new(0.0, 0.0, width as f32, height as f32))?;
+        Ok(target.into_image())
+    }
+}
+
+fn draw_nodes(canvas: &mut Canvas, nodes: &[Node]) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Group { transform, opacity, children } => {
+                canvas.save();
+                canvas.set_transform(canvas.transform().combine(*transform));
+                let layered = *opacity < 1.0;
+                if layered {
+                    canvas.push_layer(*opacity, BlendMode::Normal);
+                }
+                let result = draw_nodes(canvas, children);
+                let popped = if layered { canvas.pop_layer() } else { Ok(()) };
+                canvas.restore();
+                result.and(popped)?;
+            }
+            Node::Shape { path, fill, stroke } => {
+                if let Some((paint, fill_rule)) = fill {
+                    canvas.fill_path(path, paint, *fill_rule)?;
+                }
+                if let Some((paint, style)) = stroke {
+                    canvas.stroke_path(path, paint, style.clone())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse a `preserveAspectRatio` value; `None` stretches the image.
+fn parse_aspect(value: &str) -> Option<Alignment> {
+    let mut parts = value.split_whitespace();
+    let align = parts.next().unwrap_or("xMidYMid");
+    if align == "none" {
+        return None;
+    }
+    let fraction = |name: &str| match name {
+        "Min" => 0.0,
+        "Max" => 1.0,
+        _ => 0.5,
+    };
+    let (x, y) = match (align.strip_prefix('x'), align.find('Y')) {
+        (Some(_), Some(split)) => (fraction(&align[1..split]), fraction(&align[split + 1..])),
+        _ => (0.5, 0.5),
+    };
+    Some(Alignment { x, y, slice: parts.next() == Some("slice") })
+}
+
+/// A fill or stroke before gradient references are resolved.
+#[derive(Debug, Clone, PartialEq)]
+enum PaintSpec {
+    None,
+    Color(Color),
+    CurrentColor,
+    Reference(String, Option<Color>),
+}
+
+impl PaintSpec {
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value == "none" {
+            return Some(PaintSpec::None);
+        }
+        if value == "currentColor" {
+            return Some(PaintSpec::CurrentColor);
+        }
+        if let Some(rest) = value.strip_prefix("url(") {
+            let close = rest.find(')')?;
+            let id = rest[..close].trim().trim_matches(|c| c == '\'' || c == '"').trim_start_matches('#');
+            let fallback = Color::parse(&rest[close + 1..]).ok();
+            return Some(PaintSpec::Reference(id.to_string(), fallback));
+        }
+        Color::parse(value).ok().map(PaintSpec::Color)
+    }
+}
+
+/// Inherited presentation properties.
+#[derive(Debug, Clone)]
+struct Style {
+    fill: PaintSpec,
+    fill_rule: FillRule,
+    fill_opacity: f32,
+    stroke: PaintSpec,
+    stroke_opacity: f32,
+    stroke_style: StrokeStyle,
+    color: Color,
+    visible: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: PaintSpec::Color(Color::BLACK),
+            fill_rule: FillRule::NonZero,
+            fill_opacity: 1.0,
+            stroke: PaintSpec::None,
+            stroke_opacity: 1.0,
+            stroke_style: StrokeStyle::new(1.0),
+            color: Color::BLACK,
+            visible: true,
+        }
+    }
+}
+
+/// Get a presentation property, preferring the `style` attribute.
+fn property<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    let declared = element.attribute("style").and_then(|style| {
+        style.split(';').filter_map(|declaration| declaration.split_once(':'))
+            .filter(|(key, _)| key.trim() == name)
+            .map(|(_, value)| value.trim())
+            .next_back()
+    });
+    declared.or_else(|| element.attribute(name).map(str::trim)).filter(|value| *value != "inherit")
+}
+
+fn opacity(value: Option<&str>) -> Option<f32> {
+    value.and_then(|value| parse::length(value, 1.0)).map(|value| value.clamp(0.0, 1.0))
+}
+
+impl Style {
+    /// Apply the properties an element sets on top of the inherited ones.
+    fn cascade(&self, element: &Element) -> Style {
+        let mut style = self.clone();
+        if let Some(color) = property(element, "color").and_then(|value| Color::parse(value).ok()) {
+            style.color = color;
+        }
+        if let Some(fill) = property(element, "fill").and_then(PaintSpec::parse) {
+            style.fill = fill;
+        }
+        if let Some(stroke) = property(element, "stroke").and_then(PaintSpec::parse) {
+            style.stroke = stroke;
+        }
+        match property(element, "fill-rule") {
+            Some("evenodd") => style.fill_rule = FillRule::EvenOdd,
+            Some("nonzero") => style.fill_rule = FillRule::NonZero,
+            _ => {}
+        }
+        if let Some(value) = opacity(property(element, "fill-opacity")) {
+            style.fill_opacity = value;
+        }
+        if let Some(value) = opacity(property(element, "stroke-opacity")) {
+            style.stroke_opacity = value;
+        }
+
+        let stroke = &mut style.stroke_style;
+        if let Some(width) = property(element, "stroke-width").and_then(|value| parse::length(value, 1.0)) {
+            stroke.width = width.max(0.0);
+        }
+        match property(element, "stroke-linecap") {
+            Some("butt") => stroke.cap = LineCap::Butt,
+            Some("round") => stroke.cap = LineCap::Round,
+            Some("square") => stroke.cap = LineCap::Square,
+            _ => {}
+        }
+        match property(element, "stroke-linejoin") {
+            Some("miter") => stroke.join = LineJoin::Miter,
+            Some("round") => stroke.join = LineJoin::Round,
+            Some("bevel") => stroke.join = LineJoin::Bevel,
+            _ => {}
+        }
+        if let Some(limit) = property(element, "stroke-miterlimit").and_then(|value| value.parse::<f32>().ok()) {
+            stroke.miter_limit = limit.max(1.0);
+        }
+        if let Some(dashes) = property(element, "stroke-dasharray") {
+            stroke.dashes = if dashes == "none" { Vec::new() } else { parse::numbers(dashes) };
+        }
+        if let Some(offset) = property(element, "stroke-dashoffset").and_then(|value| parse::length(value, 0.0)) {
+            stroke.dash_offset = offset;
+        }
+        match property(element, "visibility") {
+            Some("hidden") | Some("collapse") => style.visible = false,
+            Some("visible") => style.visible = true,
+            _ => {}
+        }
+        style
+    }
+}
+
+/// Index gradient elements by id, wherever they appear.
+fn collect_gradients<'a>(element: &'a Element, gradients: &mut HashMap<String, &'a Element>) {
+    for child in &element.children {
+        if matches!(child.name.as_str(), "linearGradient" | "radialGradient") {
+            if let Some(id) = child.attribute("id") {
+                gradients.entry(id.to_string()).or_insert(child);
+            }
+        }
+        collect_gradients(child, gradients);
+    }
+}
+
+/// Turns document elements into drawable nodes.
+struct Builder<'a> {
+    gradients: HashMap<String, &'a Element>,
+    view_box: Rect,
+}
+
+impl Builder<'_> {
+    fn children(&self, element: &Element, style: &Style) -> Vec<Node> {
+        element.children.iter().filter_map(|child| self.node(child, style)).collect()
+    }
+
+    fn node(&self, element: &Element, inherited: &Style) -> Option<Node> {
+        if property(element, "display") == Some("none") {
+            return None;
+        }
+        let style = inherited.cascade(element);
+        let transform = match element.attribute("transform") {
+            Some(value) => parse::transform(value).unwrap_or_default(),
+            None => Transform::identity(),
+        };
+        let opacity = opacity(property(element, "opacity")).unwrap_or(1.0);
+
+        let node = match element.name.as_str() {
+            "g" | "a" | "switch" => {
+                let children = self.children(element, &style);
+                return (!children.is_empty()).then_some(Node::Group { transform, opacity, children });
+            }
+            "svg" => {
+                // Nested documents are drawn as groups at their position
+                let x = self.length(element, "x", self.view_box.width).unwrap_or(0.0);
+                let y = self.length(element, "y", self.view_box.height).unwrap_or(0.0);
+                let children = self.children(element, &style);
+                return (!children.is_empty()).then_some(Node::Group {
+                    transform: Transform::translate(x, y).combine(transform),
+                    opacity,
+                    children,
+                });
+            }
+            _ => self.shape(element, &style, opacity)?,
+        };
+        if transform == Transform::identity() {
+            Some(node)
+        } else {
+            Some(Node::Group { transform, opacity: 1.0, children: vec![node] })
+        }
+    }
+
+    fn length(&self, element: &Element, name: &str, reference: f32) -> Option<f32> {
+        element.attribute(name).and_then(|value| parse::length(value, reference))
+    }
+
+    /// Build a shape; an element's own opacity is folded into its paints.
+    fn shape(&self, element: &Element, style: &Style, opacity: f32) -> Option<Node> {
+        let (width, height) = (self.view_box.width, self.view_box.height);
+        let diagonal = (width * width + height * height).sqrt() / std::f32::consts::SQRT_2;
+        let get = |name: &str, reference: f32| self.length(element, name, reference).unwrap_or(0.0);
+        let path = match element.name.as_str() {
+            "path" => parse::path_data(element.attribute("d")?),
+            "rect" => {
+                let rect = Rect::new(get("x", width), get("y", height), get("width", width), get("height", height));
+                let rx = self.length(element, "rx", width);
+                let ry = self.length(element, "ry", height);
+                let (rx, ry) = match (rx, ry) {
+                    (Some(rx), Some(ry)) => (rx, ry),
+                    (Some(r), None) | (None, Some(r)) => (r, r),
+                    (None, None) => (0.0, 0.0),
+                };
+                if rect.is_empty() {
+                    return None;
+                }
+                rounded_rect(rect, rx.clamp(0.0, rect.width / 2.0), ry.clamp(0.0, rect.height / 2.0))
+            }
+            "circle" => {
+                let radius = get("r", diagonal);
+                if radius <= 0.0 {
+                    return None;
+                }
+                Path::circle(Point::new(get("cx", width), get("cy", height)), radius)
+            }
+            "ellipse" => {
+                let (rx, ry) = (get("rx", width), get("ry", height));
+                if rx <= 0.0 || ry <= 0.0 {
+                    return None;
+                }
+                Path::ellipse(Point::new(get("cx", width), get("cy", height)), rx, ry)
+            }
+            "line" => Path::line(Point::new(get("x1", width), get("y1", height)), Point::new(get("x2", width), get("y2", height))),
+            "polyline" | "polygon" => {
+                let values = parse::numbers(element.attribute("points")?);
+                let points: Vec<Point> = values.chunks_exact(2).map(|pair| Point::new(pair[0], pair[1])).collect();
+                let mut path = Path::new();
+                if let Some((first, rest)) = points.split_first() {
+                    path.move_to(*first);
+                    for point in rest {
+                        path.line_to(*point);
+                    }
+                    if element.name == "polygon" {
+                        path.close();
+                    }
+                }
+                path
+            }
+            _ => return None,
+        };
+        if path.is_empty() || !style.visible {
+            return None;
+        }
+
+        // Lines have no interior to fill
+        let fill = if element.name == "line" {
+            None
+        } else {
+            self.paint(&style.fill, style, style.fill_opacity * opacity, &path).map(|paint| (paint, style.fill_rule))
+        };
+        let stroke = if style.stroke_style.width > 0.0 {
+            self.paint(&style.stroke, style, style.stroke_opacity * opacity, &path)
+                .map(|paint| (paint, style.stroke_style.clone()))
+        } else {
+            None
+        };
+        (fill.is_some() || stroke.is_some()).then_some(Node::Shape { path, fill, stroke })
+    }
+
+    fn paint(&self, spec: &PaintSpec, style: &Style, opacity: f32, path: &Path) -> Option<Paint> {
+        let paint = match spec {
+            PaintSpec::None => return None,
+            PaintSpec::Color(color) => Paint::Solid(*color),
+            PaintSpec::CurrentColor => Paint::Solid(style.color),
+            PaintSpec::Reference(id, fallback) => match self.gradients.get(id) {
+                Some(gradient) => self.gradient(gradient, path)?,
+                None => Paint::Solid((*fallback)?),
+            },
+        };
+        Some(fade(paint, opacity))
+    }
+
+    /// Resolve a gradient for a shape, following `href` for missing attributes and stops.
+    fn gradient(&self, element: &Element, path: &Path) -> Option<Paint> {
+        let mut chain = vec![element];
+        while chain.len() < MAX_GRADIENT_REFERENCES {
+            let next = chain.last().and_then(|element| element.attribute("href"))
+                .and_then(|href| self.gradients.get(href.trim_start_matches('#')));
+            match next {
+                Some(next) if !chain.iter().any(|seen| std::ptr::eq(*seen, *next)) => chain.push(next),
+                _ => break,
+            }
+        }
+        let attribute = |name: &str| chain.iter().find_map(|element| element.attribute(name));
+        let stops_element = chain.iter().find(|element| element.children.iter().any(|child| child.name == "stop"))?;
+
+        let mut stops = Vec::new();
+        let mut last_offset = 0.0f32;
+        for stop in stops_element.children.iter().filter(|child| child.name == "stop") {
+            let offset = stop.attribute("offset").and_then(|value| parse::length(value, 1.0)).unwrap_or(0.0);
+            last_offset = offset.clamp(last_offset, 1.0);
+            let color = property(stop, "stop-color").and_then(|value| Color::parse(value).ok()).unwrap_or(Color::BLACK);
+            let alpha = opacity(property(stop, "stop-opacity")).unwrap_or(1.0);
+            stops.push((last_offset, color.with_alpha(color.a * alpha)));
+        }
+        if stops.len() == 1 {
+            return Some(Paint::Solid(stops[0].1));
+        }
+
+        let bounding_box = attribute("gradientUnits") != Some("userSpaceOnUse");
+        let mut transform = attribute("gradientTransform").and_then(|value| parse::transform(value).ok()).unwrap_or_default();
+        let (width, height) = if bounding_box {
+            let bounds = path.bounds();
+            if bounds.width <= 0.0 || bounds.height <= 0.0 {
+                return None;
+            }
+            transform = Transform::translate(bounds.x, bounds.y)
+                .combine(Transform::scale(bounds.width, bounds.height))
+                .combine(transform);
+            (1.0, 1.0)
+        } else {
+            (self.view_box.width, self.view_box.height)
+        };
+        let get = |name: &str, reference: f32, default: f32| attribute(name).and_then(|value| parse::length(value, reference)).unwrap_or(default);
+        let spread = match attribute("spreadMethod") {
+            Some("reflect") => SpreadMode::Reflect,
+            Some("repeat") => SpreadMode::Repeat,
+            _ => SpreadMode::Pad,
+        };
+
+        let mut paint = if chain[0].name == "linearGradient" {
+            let start = Point::new(get("x1", width, 0.0), get("y1", height, 0.0));
+            let end = Point::new(get("x2", width, width), get("y2", height, 0.0));
+            let mut gradient = LinearGradient::new(start, end).spread(spread);
+            for (offset, color) in &stops {
+                gradient = gradient.stop(*offset, *color);
+            }
+            Paint::LinearGradient(gradient)
+        } else {
+            let diagonal = (width * width + height * height).sqrt() / std::f32::consts::SQRT_2;
+            let center = Point::new(get("cx", width, width / 2.0), get("cy", height, height / 2.0));
+            let mut gradient = RadialGradient::new(center, get("r", diagonal, diagonal / 2.0)).spread(spread);
+            for (offset, color) in &stops {
+                gradient = gradient.stop(*offset, *color);
+            }
+            Paint::RadialGradient(gradient)
+        };
+        if stops.is_empty() {
+            return None;
+        }
+        paint = paint.transform(transform);
+        Some(paint)
+    }
+}
+
+/// Multiply a paint's alpha by `opacity`.
+fn fade(paint: Paint, opacity: f32) -> Paint {
+    if opacity >= 1.0 {
+        return paint;
+    }
+    let fade_stops = |stops: &mut Vec<super::GradientStop>| {
+        for stop in stops {
+            stop.color = stop.color.with_alpha(stop.color.a * opacity);
+        }
+    };
+    match paint {
+        Paint::Solid(color) => Paint::Solid(color.with_alpha(color.a * opacity)),
+        Paint::LinearGradient(mut gradient) => {
+            fade_stops(&mut gradient.stops);
+            Paint::LinearGradient(gradient)
+        }
+        Paint::RadialGradient(mut gradient) => {
+            fade_stops(&mut gradient.stops);
+            Paint::RadialGradient(gradient)
+        }
+        paint => paint,
+    }
+}
+
+/// Create a rectangle with elliptical corners.
+fn rounded_rect(rect: Rect, rx: f32, ry: f32) -> Path {
+    if rx <= 0.0 || ry <= 0.0 {
+        return Path::rect(rect);
+    }
+    let (left, top, right, bottom) = (rect.x, rect.y, rect.right(), rect.bottom());
+    let mut path = Path::new();
+    path.elliptic_arc(Point::new(left + rx, top + ry), rx, ry, PI, FRAC_PI_2);
+    path.elliptic_arc(Point::new(right - rx, top + ry), rx, ry, -FRAC_PI_2, FRAC_PI_2);
+    path.elliptic_arc(Point::new(right - rx, bottom - ry), rx, ry, 0.0, FRAC_PI_2);
+    path.elliptic_arc(Point::new(left + rx, bottom - ry), rx, ry, FRAC_PI_2, FRAC_PI_2);
+    path.close();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Color, expected: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.02;
+        assert!(
+            close(actual.r, expected.r) && close(actual.g, expected.g) && close(actual.b, expected.b) && close(actual.a, expected.a),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_view_box_scaling() {
+        let svg = SvgImage::parse(r#"<svg viewBox="0 0 10 10" width="20" height="20">
+            <rect x="0" y="0" width="5" height="10" fill="red"/>
+            <circle cx="7.5" cy="5" r="2" style="fill: rgb(0, 0, 255)"/>
+        </svg>"#).unwrap();
+        assert_eq!(svg.size(), Size::new(20.0, 20.0));
+
+        let image = svg.to_image(40, 40).unwrap();
+        assert_near(image.pixel(10, 20), Color::RED);
+        assert_near(image.pixel(30, 20), Color::BLUE);
+        assert_eq!(image.pixel(30, 2).a, 0.0);
+
+        // A wide area centers the square view box
+        let wide = svg.to_image(80, 40).unwrap();
+        assert_eq!(wide.pixel(10, 20).a, 0.0);
+        assert_near(wide.pixel(30, 20), Color::RED);
+    }
+
+    #[test]
+    fn test_groups_and_styles() {
+        let svg = SvgImage::parse(r##"<svg width="20" height="20">
+            <defs><linearGradient id="g"><stop offset="0" stop-color="#000"/></linearGradient></defs>
+            <g fill="lime" transform="translate(10 0)">
+                <rect width="10" height="10"/>
+                <rect y="10" width="10" height="10" fill="none" stroke="blue" stroke-width="4"/>
+            </g>
+            <g opacity="0.5"><rect width="10" height="10" fill="url(#missing) red"/></g>
+            <rect y="10" width="10" height="10" fill="url(#g)" display="none"/>
+        </svg>"##).unwrap();
+        let image = svg.to_image(20, 20).unwrap();
+        assert_near(image.pixel(15, 5), Color::rgb8(0, 255, 0));
+        assert_near(image.pixel(11, 15), Color::BLUE);
+        assert_eq!(image.pixel(15, 15).a, 0.0);
+        assert_near(image.pixel(5, 5), Color::RED.with_alpha(0.5));
+        assert_eq!(image.pixel(5, 15).a, 0.0);
+    }
+
+    #[test]
+    fn test_gradients() {
+        let svg = SvgImage::parse(r##"<svg viewBox="0 0 100 10">
+            <linearGradient id="base" spreadMethod="pad">
+                <stop offset="0%" stop-color="black"/>
+                <stop offset="100%" stop-color="white"/>
+            </linearGradient>
+            <linearGradient id="shifted" href="#base" x1="0.5"/>
+            <rect width="50" height="10" fill="url(#base)"/>
+            <rect x="50" width="50" height="10" fill="url(#shifted)"/>
+        </svg>"##).unwrap();
+        let image = svg.to_image(100, 10).unwrap();
+        // Bounding-box units follow each shape
+        assert!(image.pixel(2, 5).r < 0.1);
+        assert!((image.pixel(25, 5).r - 0.5).abs() < 0.05);
+        assert!(image.pixel(60, 5).r < 0.05);
+        assert!((image.pixel(87, 5).r - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(SvgImage::parse("<html/>").is_err());
+        assert!(SvgImage::from_bytes(&[0xff, 0xfe]).is_err());
+        let empty = SvgImage::parse("<svg/>").unwrap();
+        assert_eq!(empty.size(), Size::new(DEFAULT_SIZE, DEFAULT_SIZE));
+    }
+}
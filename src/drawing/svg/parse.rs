@@ -0,0 +1,389 @@
+//! Parsers for SVG attribute values: path data, transforms and lengths.
+
+use crate::drawing::{Path, Point, Transform};
+use crate::{Error, Result};
+use std::f32::consts::PI;
+
+/// Parse a length or number, ignoring absolute units.
+///
+/// Percentages are resolved against `reference`.
+pub fn length(value: &str, reference: f32) -> Option<f32> {
+    let value = value.trim();
+    if let Some(percent) = value.strip_suffix('%') {
+        return percent.trim().parse::<f32>().ok().map(|p| p / 100.0 * reference);
+    }
+    let mut scanner = Scanner::new(value);
+    let number = scanner.number()?;
+    let unit = scanner.rest().trim();
+    let scale = match unit {
+        "" | "px" => 1.0,
+        "pt" => 4.0 / 3.0,
+        "pc" => 16.0,
+        "mm" => 96.0 / 25.4,
+        "cm" => 96.0 / 2.54,
+        "in" => 96.0,
+        // Relative to a 16 pixel default font
+        "em" => 16.0,
+        "ex" => 8.0,
+        _ => return None,
+    };
+    Some(number * scale)
+}
+
+/// Parse a whitespace or comma separated list of numbers.
+pub fn numbers(value: &str) -> Vec<f32> {
+    let mut scanner = Scanner::new(value);
+    let mut values = Vec::new();
+    while let Some(number) = scanner.number() {
+        values.push(number);
+    }
+    values
+}
+
+/// Parse a transform list such as `translate(10 20) rotate(45)`.
+pub fn transform(value: &str) -> Result<Transform> {
+    let mut result = Transform::identity();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let open = rest.find('(').ok_or_else(|| Error::resource("Expected '(' in SVG transform"))?;
+        let close = rest.find(')').ok_or_else(|| Error::resource("Expected ')' in SVG transform"))?;
+        if close < open {
+            return Err(Error::resource("Malformed SVG transform"));
+        }
+        let name = rest[..open].trim();
+        let args = numbers(&rest[open + 1..close]);
+        let arg = |i: usize| args.get(i).copied();
+        let next = match (name, args.len()) {
+            ("matrix", 6) => Transform { a: args[0], b: args[1], c: args[2], d: args[3], tx: args[4], ty: args[5] },
+            ("translate", 1 | 2) => Transform::translate(args[0], arg(1).unwrap_or(0.0)),
+            ("scale", 1 | 2) => Transform::scale(args[0], arg(1).unwrap_or(args[0])),
+            ("rotate", 1) => Transform::rotate(args[0].to_radians()),
+            ("rotate", 3) => Transform::translate(args[1], args[2])
+                .combine(Transform::rotate(args[0].to_radians()))
+                .combine(Transform::translate(-args[1], -args[2])),
+            ("skewX", 1) => Transform::skew(args[0].to_radians().tan(), 0.0),
+            ("skewY", 1) => Transform::skew(0.0, args[0].to_radians().tan()),
+            _ => return Err(Error::resource(format!("Unsupported SVG transform '{}'", name))),
+        };
+        result = result.combine(next);
+        rest = rest[close + 1..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Ok(result)
+}
+
+/// Parse path data.
+///
+/// As the SVG specification asks, an error stops parsing and the path drawn
+/// so far is kept.
+pub fn path_data(data: &str) -> Path {
+    let mut path = Path::new();
+    let mut scanner = Scanner::new(data);
+    let mut command = None;
+    let mut start = Point::zero();
+    let mut current = Point::zero();
+    // Reflected control point for smooth curves, with the command it came from
+    let mut last_control: Option<(char, Point)> = None;
+
+    loop {
+        scanner.skip_separators();
+        match scanner.peek() {
+            None => break,
+            Some(c) if c.is_ascii_alphabetic() => {
+                scanner.advance();
+                command = Some(c);
+                if matches!(c, 'Z' | 'z') {
+                    path.close();
+                    current = start;
+                    last_control = None;
+                    continue;
+                }
+            }
+            Some(_) => {}
+        }
+        let Some(c) = command else {
+            break;
+        };
+        if matches!(c, 'Z' | 'z') {
+            // Numbers cannot follow a close command
+            break;
+        }
+
+        let relative = c.is_ascii_lowercase();
+        let origin = if relative { current } else { Point::zero() };
+        let point = |scanner: &mut Scanner| -> Option<Point> {
+            let x = scanner.number()?;
+            let y = scanner.number()?;
+            Some(Point::new(origin.x + x, origin.y + y))
+        };
+
+        let mut control = None;
+        let parsed = match c.to_ascii_uppercase() {
+            'M' => point(&mut scanner).map(|p| {
+                path.move_to(p);
+                start = p;
+                current = p;
+                // Further coordinate pairs are implicit line commands
+                command = Some(if relative { 'l' } else { 'L' });
+            }),
+            'L' => point(&mut scanner).map(|p| {
+                path.line_to(p);
+                current = p;
+            }),
+            'H' => scanner.number().map(|x| {
+                current = Point::new(if relative { current.x + x } else { x }, current.y);
+                path.line_to(current);
+            }),
+            'V' => scanner.number().map(|y| {
+                current = Point::new(current.x, if relative { current.y + y } else { y });
+                path.line_to(current);
+            }),
+            'C' => (|| Some((point(&mut scanner)?, point(&mut scanner)?, point(&mut scanner)?)))().map(|(c1, c2, p)| {
+                path.cubic_to(c1, c2, p);
+                control = Some(('C', c2));
+                current = p;
+            }),
+            'S' => (|| Some((point(&mut scanner)?, point(&mut scanner)?)))().map(|(c2, p)| {
+                let c1 = reflect(last_control, 'C', current);
+                path.cubic_to(c1, c2, p);
+                control = Some(('C', c2));
+                current = p;
+            }),
+            'Q' => (|| Some((point(&mut scanner)?, point(&mut scanner)?)))().map(|(c1, p)| {
+                path.quad_to(c1, p);
+                control = Some(('Q', c1));
+                current = p;
+            }),
+            'T' => point(&mut scanner).map(|p| {
+                let c1 = reflect(last_control, 'Q', current);
+                path.quad_to(c1, p);
+                control = Some(('Q', c1));
+                current = p;
+            }),
+            'A' => (|| {
+                let rx = scanner.number()?;
+                let ry = scanner.number()?;
+                let rotation = scanner.number()?;
+                let large_arc = scanner.flag()?;
+                let sweep = scanner.flag()?;
+                Some((rx, ry, rotation, large_arc, sweep, point(&mut scanner)?))
+            })().map(|(rx, ry, rotation, large_arc, sweep, p)| {
+                arc_to(&mut path, current, rx, ry, rotation, large_arc, sweep, p);
+                current = p;
+            }),
+            _ => None,
+        };
+        if parsed.is_none() {
+            break;
+        }
+        last_control = control;
+    }
+    path
+}
+
+/// Mirror the previous control point through the current point, if the
+/// previous segment was a curve of the same kind.
+fn reflect(last_control: Option<(char, Point)>, kind: char, current: Point) -> Point {
+    match last_control {
+        Some((previous, control)) if previous == kind => Point::new(2.0 * current.x - control.x, 2.0 * current.y - control.y),
+        _ => current,
+    }
+}
+
+/// Append an SVG endpoint arc, converted to its center parameterization.
+#[allow(clippy::too_many_arguments)]
+fn arc_to(path: &mut Path, from: Point, rx: f32, ry: f32, rotation: f32, large_arc: bool, sweep: bool, to: Point) {
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if from == to {
+        return;
+    }
+    if rx == 0.0 || ry == 0.0 {
+        path.line_to(to);
+        return;
+    }
+
+    let (sin_phi, cos_phi) = rotation.to_radians().sin_cos();
+    let dx = (from.x - to.x) / 2.0;
+    let dy = (from.y - to.y) / 2.0;
+    let x1 = cos_phi * dx + sin_phi * dy;
+    let y1 = -sin_phi * dx + cos_phi * dy;
+
+    // Grow radii that are too small to span the endpoints
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = (rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1).max(0.0);
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = if denominator > 0.0 { (numerator / denominator).sqrt() } else { 0.0 };
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+
+    let angle = |ux: f32, uy: f32| uy.atan2(ux);
+    let start_angle = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start_angle;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    // Build the arc around the origin, then rotate and move it into place
+    let center = Point::new(
+        cos_phi * cx1 - sin_phi * cy1 + (from.x + to.x) / 2.0,
+        sin_phi * cx1 + cos_phi * cy1 + (from.y + to.y) / 2.0,
+    );
+    let placement = Transform::translate(center.x, center.y).combine(Transform::rotate(rotation.to_radians()));
+    let mut arc = Path::new();
+    arc.elliptic_arc(Point::zero(), rx, ry, start_angle, delta);
+    for element in arc.transform(placement).elements() {
+        if let crate::drawing::PathElement::CubicTo(c1, c2, p) = *element {
+            path.cubic_to(c1, c2, p);
+        }
+    }
+}
+
+/// Reads numbers and flags from attribute text.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { bytes: text.as_bytes(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.bytes.get(self.pos).map(|b| *b as char)
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+    }
+
+    fn rest(&self) -> &'a str {
+        std::str::from_utf8(&self.bytes[self.pos..]).unwrap_or("")
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_ascii_whitespace() || c == ',') {
+            self.pos += 1;
+        }
+    }
+
+    /// Read a number, which may run straight into the next one as in `1-2.5.5`.
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.pos += 1;
+        }
+        let digits = |scanner: &mut Self| {
+            let from = scanner.pos;
+            while matches!(scanner.peek(), Some(c) if c.is_ascii_digit()) {
+                scanner.pos += 1;
+            }
+            scanner.pos > from
+        };
+        let mut any = digits(self);
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if !any {
+            self.pos = start;
+            return None;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mark;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+
+    /// Read an arc flag, which need not be separated from what follows.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.peek()? {
+            '0' => false,
+            '1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::{FillRule, PathElement, Rect};
+
+    #[test]
+    fn test_numbers_and_lengths() {
+        assert_eq!(numbers("1-2.5.5e1,+3 4E-1"), vec![1.0, -2.5, 5.0, 3.0, 0.4]);
+        assert_eq!(length("12px", 0.0), Some(12.0));
+        assert_eq!(length("1in", 0.0), Some(96.0));
+        assert_eq!(length("50%", 40.0), Some(20.0));
+        assert_eq!(length("wide", 0.0), None);
+    }
+
+    #[test]
+    fn test_transform_list() {
+        let t = transform("translate(10, 20) scale(2)").unwrap();
+        assert_eq!(t.transform_point(Point::new(1.0, 1.0)), Point::new(12.0, 22.0));
+        let r = transform("rotate(90 5 5)").unwrap().transform_point(Point::new(10.0, 5.0));
+        assert!((r.x - 5.0).abs() < 1e-4 && (r.y - 10.0).abs() < 1e-4);
+        assert!(transform("spin(3)").is_err());
+    }
+
+    #[test]
+    fn test_path_data() {
+        let path = path_data("M10 10h20v20H10z m5 5 l1-1");
+        let elements = path.elements();
+        assert_eq!(elements[0], PathElement::MoveTo(Point::new(10.0, 10.0)));
+        assert_eq!(elements[1], PathElement::LineTo(Point::new(30.0, 10.0)));
+        assert_eq!(elements[2], PathElement::LineTo(Point::new(30.0, 30.0)));
+        assert_eq!(elements[3], PathElement::LineTo(Point::new(10.0, 30.0)));
+        assert_eq!(elements[4], PathElement::Close);
+        // Relative moves after a close start from the subpath start
+        assert_eq!(elements[5], PathElement::MoveTo(Point::new(15.0, 15.0)));
+        assert_eq!(elements[6], PathElement::LineTo(Point::new(16.0, 14.0)));
+
+        // Smooth curves reflect the previous control point
+        let smooth = path_data("M0 0 C0 10 10 10 10 0 S20 -10 20 0");
+        assert_eq!(smooth.elements()[2], PathElement::CubicTo(Point::new(10.0, -10.0), Point::new(20.0, -10.0), Point::new(20.0, 0.0)));
+
+        // Errors keep what was parsed so far
+        assert_eq!(path_data("M0 0 L10 10 L20").elements().len(), 2);
+    }
+
+    #[test]
+    fn test_arcs() {
+        // Two half circles with compact flags make a full circle of radius 5
+        let circle = path_data("M0 5a5 5 0 1 1 10 0A5 5 0 1110 5z");
+        let bounds = circle.bounds();
+        assert!((bounds.width - 10.0).abs() < 0.5 && bounds.y < 0.5);
+        assert!(circle.contains_point(Point::new(5.0, 5.0), FillRule::NonZero));
+        assert!(circle.contains_point(Point::new(5.0, 0.5), FillRule::NonZero));
+        assert!(!circle.contains_point(Point::new(0.5, 0.5), FillRule::NonZero));
+
+        // Radii too small to span the endpoints are scaled up
+        let scaled = path_data("M0 0 A1 1 0 0 1 20 0");
+        let bounds = scaled.bounds();
+        assert!(bounds.y < -9.0 && bounds.height < 11.5);
+        assert_eq!(path_data("M0 0 A0 5 0 0 1 10 0").elements()[1], PathElement::LineTo(Point::new(10.0, 0.0)));
+        assert_ne!(scaled.bounds(), Rect::zero());
+    }
+}
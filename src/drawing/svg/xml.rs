@@ -0,0 +1,231 @@
+//! Minimal XML reader for SVG documents.
+//!
+//! Only elements and attributes are kept. Text, comments, processing
+//! instructions and doctype declarations are skipped, and namespace prefixes
+//! are dropped so `svg:rect` and `xlink:href` read as `rect` and `href`.
+
+use crate::{Error, Result};
+
+/// An element with its attributes and child elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Get an attribute value by name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse a document and return its root element.
+pub fn parse(source: &str) -> Result<Element> {
+    let mut reader = Reader { source, pos: 0 };
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+
+    while let Some(start) = reader.source[reader.pos..].find('<').map(|offset| reader.pos + offset) {
+        reader.pos = start;
+        let rest = &reader.source[start..];
+        if rest.starts_with("<!--") {
+            reader.skip_past("-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            reader.skip_past("]]>")?;
+        } else if rest.starts_with("<?") {
+            reader.skip_past("?>")?;
+        } else if rest.starts_with("<!") {
+            reader.skip_declaration()?;
+        } else if rest.starts_with("</") {
+            reader.pos += 2;
+            let name = local_name(reader.name()?);
+            reader.skip_whitespace();
+            reader.expect('>')?;
+            let element = stack.pop().filter(|element| element.name == name)
+                .ok_or_else(|| Error::resource(format!("Unexpected closing tag </{}>", name)))?;
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+        } else {
+            reader.pos += 1;
+            let (element, closed) = reader.start_tag()?;
+            if closed {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            } else {
+                stack.push(element);
+            }
+        }
+        if root.is_some() {
+            break;
+        }
+    }
+
+    root.ok_or_else(|| Error::resource("XML document has no complete root element"))
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Parse the rest of a start tag, returning the element and whether it
+    /// was self-closing.
+    fn start_tag(&mut self) -> Result<(Element, bool)> {
+        let name = local_name(self.name()?).to_string();
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = &self.source[self.pos..];
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok((Element { name, attributes, children: Vec::new() }, true));
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok((Element { name, attributes, children: Vec::new() }, false));
+            }
+
+            let key = local_name(self.name()?).to_string();
+            self.skip_whitespace();
+            self.expect('=')?;
+            self.skip_whitespace();
+            let quote = self.source[self.pos..].chars().next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| Error::resource("Expected a quoted attribute value"))?;
+            self.pos += 1;
+            let end = self.source[self.pos..].find(quote)
+                .ok_or_else(|| Error::resource("Unterminated attribute value"))?;
+            let value = unescape(&self.source[self.pos..self.pos + end]);
+            self.pos += end + 1;
+            attributes.push((key, value));
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let rest = &self.source[self.pos..];
+        let len = rest.find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '=')).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(Error::resource("Expected an XML name"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.source[self.pos..].starts_with(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(Error::resource(format!("Expected '{}' in XML", c)))
+        }
+    }
+
+    fn skip_past(&mut self, terminator: &str) -> Result<()> {
+        let end = self.source[self.pos..].find(terminator)
+            .ok_or_else(|| Error::resource("Unterminated XML markup"))?;
+        self.pos += end + terminator.len();
+        Ok(())
+    }
+
+    /// Skip a `<!DOCTYPE ...>` declaration, which may hold a bracketed subset.
+    fn skip_declaration(&mut self) -> Result<()> {
+        let mut depth = 0;
+        for (offset, c) in self.source[self.pos..].char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '>' if depth <= 0 => {
+                    self.pos += offset + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        Err(Error::resource("Unterminated XML declaration"))
+    }
+}
+
+/// Drop a namespace prefix.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Replace character and predefined entity references.
+fn unescape(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elements() {
+        let source = r#"<?xml version="1.0"?>
+            <!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "x" [ <!ENTITY a "b"> ]>
+            <!-- icon -->
+            <svg:svg xmlns:svg="http://www.w3.org/2000/svg" width='10'>
+                <g id="a &amp; b"><rect x="1"/></g>
+                <title>Text is <ignored/></title>
+            </svg:svg>"#;
+        let root = parse(source).unwrap();
+        assert_eq!(root.name, "svg");
+        assert_eq!(root.attribute("width"), Some("10"));
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].attribute("id"), Some("a & b"));
+        assert_eq!(root.children[0].children[0].name, "rect");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("<svg><g></svg>").is_err());
+        assert!(parse("<svg width=10/>").is_err());
+        assert!(parse("no markup").is_err());
+    }
+}
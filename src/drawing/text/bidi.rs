@@ -0,0 +1,598 @@
+//! Bidirectional text (UAX #9).
+//!
+//! Resolves an embedding level for every character of a paragraph, so that
+//! right-to-left scripts such as Arabic and Hebrew display correctly when
+//! mixed with left-to-right text and numbers, and reorders lines for display.
+
+use super::tables::{BIDI_CLASS, BRACKET_PAIRS, MIRRORED};
+use super::lookup;
+use std::ops::Range;
+
+/// Deepest embedding level allowed by explicit formatting characters.
+const MAX_DEPTH: u8 = 125;
+
+/// Maximum number of nested bracket pairs tracked per run (BD16).
+const MAX_BRACKET_DEPTH: usize = 63;
+
+/// Bidi class of a character.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BidiClass {
+    L, R, AL, EN, ES, ET, AN, CS, NSM, BN, B, S, WS, ON,
+    LRE, LRO, RLE, RLO, PDF, LRI, RLI, FSI, PDI,
+}
+
+use BidiClass::*;
+
+/// Base direction of a paragraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+/// Get the bidi class of a character.
+pub fn bidi_class(ch: char) -> BidiClass {
+    lookup(&BIDI_CLASS, ch).unwrap_or(L)
+}
+
+/// The character to display in place of `ch` in right-to-left text, such as
+/// `)` for `(`.
+pub fn mirrored(ch: char) -> Option<char> {
+    let code = ch as u32;
+    MIRRORED
+        .binary_search_by_key(&code, |&(from, _)| from)
+        .ok()
+        .and_then(|i| char::from_u32(MIRRORED[i].1))
+}
+
+/// Resolved embedding levels of one paragraph.
+///
+/// Even levels are left-to-right and odd levels right-to-left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BidiParagraph {
+    /// Level of the paragraph itself.
+    pub level: u8,
+    /// Level of each character, indexed by character position.
+    pub levels: Vec<u8>,
+    classes: Vec<BidiClass>,
+}
+
+impl BidiParagraph {
+    /// Resolve the levels of a paragraph, detecting its direction from its
+    /// first strong character unless `direction` is given.
+    pub fn new(text: &str, direction: Option<TextDirection>) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let classes: Vec<BidiClass> = chars.iter().map(|&ch| bidi_class(ch)).collect();
+        let level = match direction {
+            Some(TextDirection::LeftToRight) => 0,
+            Some(TextDirection::RightToLeft) => 1,
+            None => first_strong_level(&classes).unwrap_or(0),
+        };
+
+        let mut resolved = classes.clone();
+        let mut levels = explicit_levels(&mut resolved, level);
+        let removed: Vec<bool> = classes.iter().map(|&class| is_removed(class)).collect();
+        for sequence in isolating_run_sequences(&classes, &levels, &removed, level) {
+            resolve_sequence(&sequence, &chars, &classes, &mut resolved);
+        }
+
+        // Implicit levels (I1, I2)
+        for (i, level) in levels.iter_mut().enumerate() {
+            if removed[i] {
+                continue;
+            }
+            *level += match (*level % 2, resolved[i]) {
+                (0, R) => 1,
+                (0, AN | EN) => 2,
+                (1, L | EN | AN) => 1,
+                _ => 0,
+            };
+        }
+
+        // Removed characters take the level of their neighbour so they stay inside runs
+        for i in 0..levels.len() {
+            if removed[i] {
+                levels[i] = if i > 0 { levels[i - 1] } else { level };
+            }
+        }
+
+        Self { level, levels, classes }
+    }
+
+    /// Check if the paragraph's base direction is right-to-left.
+    pub fn is_rtl(&self) -> bool {
+        self.level % 2 == 1
+    }
+
+    /// Levels of the characters in `line` after trailing whitespace and
+    /// separators are reset to the paragraph level (L1).
+    pub fn line_levels(&self, line: Range<usize>) -> Vec<u8> {
+        let mut levels = self.levels[line.clone()].to_vec();
+        let classes = &self.classes[line];
+        let mut trailing = true;
+        for i in (0..levels.len()).rev() {
+            match classes[i] {
+                S | B => {
+                    levels[i] = self.level;
+                    trailing = true;
+                }
+                WS | FSI | LRI | RLI | PDI => {
+                    if trailing {
+                        levels[i] = self.level;
+                    }
+                }
+                class if is_removed(class) => {
+                    if trailing {
+                        levels[i] = self.level;
+                    }
+                }
+                _ => trailing = false,
+            }
+        }
+        levels
+    }
+
+    /// Split a line into runs of equal level, in visual order (L2).
+    ///
+    /// Ranges index characters of the paragraph; characters of odd-level runs
+    /// display in reverse.
+    pub fn visual_runs(&self, line: Range<usize>) -> Vec<(Range<usize>, u8)> {
+        let levels = self.line_levels(line.clone());
+        let mut runs: Vec<(Range<usize>, u8)> = Vec::new();
+        for (i, &level) in levels.iter().enumerate() {
+            let index = line.start + i;
+            match runs.last_mut() {
+                Some((range, run_level)) if *run_level == level => range.end = index + 1,
+                _ => runs.push((index..index + 1, level)),
+            }
+        }
+
+        let (Some(highest), Some(lowest_odd)) = (
+            levels.iter().copied().max(),
+            levels.iter().copied().filter(|level| level % 2 == 1).min(),
+        ) else {
+            return runs;
+        };
+        for level in (lowest_odd..=highest).rev() {
+            let mut i = 0;
+            while i < runs.len() {
+                if runs[i].1 >= level {
+                    let start = i;
+                    while i < runs.len() && runs[i].1 >= level {
+                        i += 1;
+                    }
+                    runs[start..i].reverse();
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        runs
+    }
+}
+
+/// Formatting characters removed by rule X9.
+fn is_removed(class: BidiClass) -> bool {
+    matches!(class, RLE | LRE | RLO | LRO | PDF | BN)
+}
+
+fn is_isolate_initiator(class: BidiClass) -> bool {
+    matches!(class, LRI | RLI | FSI)
+}
+
+/// Neutral and isolate formatting classes (NI).
+fn is_neutral(class: BidiClass) -> bool {
+    matches!(class, B | S | WS | ON | LRI | RLI | FSI | PDI)
+}
+
+/// Level implied by the first strong character, skipping isolates (P2, P3).
+fn first_strong_level(classes: &[BidiClass]) -> Option<u8> {
+    let mut isolates = 0usize;
+    for &class in classes {
+        match class {
+            LRI | RLI | FSI => isolates += 1,
+            PDI if isolates > 0 => isolates -= 1,
+            L if isolates == 0 => return Some(0),
+            R | AL if isolates == 0 => return Some(1),
+            B => break,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Index of the PDI matching each isolate initiator (BD9).
+fn matching_pdis(classes: &[BidiClass]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; classes.len()];
+    let mut open = Vec::new();
+    for (i, &class) in classes.iter().enumerate() {
+        match class {
+            LRI | RLI | FSI => open.push(i),
+            PDI => {
+                if let Some(start) = open.pop() {
+                    matches[start] = Some(i);
+                }
+            }
+            B => open.clear(),
+            _ => {}
+        }
+    }
+    matches
+}
+
+/// Apply explicit embeddings, overrides and isolates (X1 to X8).
+fn explicit_levels(classes: &mut [BidiClass], paragraph_level: u8) -> Vec<u8> {
+    struct Status {
+        level: u8,
+        override_class: Option<BidiClass>,
+        isolate: bool,
+    }
+
+    let original = classes.to_vec();
+    let pdis = matching_pdis(&original);
+    let mut levels = vec![paragraph_level; classes.len()];
+    let mut stack = vec![Status { level: paragraph_level, override_class: None, isolate: false }];
+    let (mut overflow_isolates, mut overflow_embeddings, mut valid_isolates) = (0usize, 0usize, 0usize);
+
+    for i in 0..classes.len() {
+        let current = stack.last().map_or(paragraph_level, |status| status.level);
+        let current_override = stack.last().and_then(|status| status.override_class);
+        match original[i] {
+            RLE | LRE | RLO | LRO => {
+                levels[i] = current;
+                let rtl = matches!(original[i], RLE | RLO);
+                let level = if rtl { (current + 1) | 1 } else { (current + 2) & !1 };
+                if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                    let override_class = match original[i] {
+                        RLO => Some(R),
+                        LRO => Some(L),
+                        _ => None,
+                    };
+                    stack.push(Status { level, override_class, isolate: false });
+                } else if overflow_isolates == 0 {
+                    overflow_embeddings += 1;
+                }
+            }
+            RLI | LRI | FSI => {
+                levels[i] = current;
+                if let Some(class) = current_override {
+                    classes[i] = class;
+                }
+                let rtl = match original[i] {
+                    RLI => true,
+                    LRI => false,
+                    _ => {
+                        let end = pdis[i].unwrap_or(original.len());
+                        first_strong_level(&original[i + 1..end]) == Some(1)
+                    }
+                };
+                let level = if rtl { (current + 1) | 1 } else { (current + 2) & !1 };
+                if level <= MAX_DEPTH && overflow_isolates == 0 && overflow_embeddings == 0 {
+                    valid_isolates += 1;
+                    stack.push(Status { level, override_class: None, isolate: true });
+                } else {
+                    overflow_isolates += 1;
+                }
+            }
+            PDI => {
+                if overflow_isolates > 0 {
+                    overflow_isolates -= 1;
+                } else if valid_isolates > 0 {
+                    overflow_embeddings = 0;
+                    while stack.last().is_some_and(|status| !status.isolate) {
+                        stack.pop();
+                    }
+                    stack.pop();
+                    valid_isolates -= 1;
+                }
+                let status = stack.last();
+                levels[i] = status.map_or(paragraph_level, |status| status.level);
+                if let Some(class) = status.and_then(|status| status.override_class) {
+                    classes[i] = class;
+                }
+            }
+            PDF => {
+                levels[i] = current;
+                // A PDF inside an overflowed isolate matches nothing
+                if overflow_isolates > 0 {
+                    continue;
+                }
+                if overflow_embeddings > 0 {
+                    overflow_embeddings -= 1;
+                } else if stack.len() >= 2 && !stack.last().is_some_and(|status| status.isolate) {
+                    stack.pop();
+                }
+            }
+            B => levels[i] = paragraph_level,
+            BN => levels[i] = current,
+            _ => {
+                levels[i] = current;
+                if let Some(class) = current_override {
+                    classes[i] = class;
+                }
+            }
+        }
+    }
+    levels
+}
+
+/// An isolating run sequence with its start and end directions.
+struct Sequence {
+    indices: Vec<usize>,
+    sos: BidiClass,
+    eos: BidiClass,
+    level: u8,
+}
+
+/// Group level runs into isolating run sequences (BD13, X10).
+fn isolating_run_sequences(classes: &[BidiClass], levels: &[u8], removed: &[bool], paragraph_level: u8) -> Vec<Sequence> {
+    let pdis = matching_pdis(classes);
+
+    // Level runs over the characters that survive X9
+    let mut runs: Vec<Vec<usize>> = Vec::new();
+    let mut last_level = None;
+    for i in (0..classes.len()).filter(|&i| !removed[i]) {
+        if last_level == Some(levels[i]) {
+            runs.last_mut().unwrap().push(i);
+        } else {
+            runs.push(vec![i]);
+            last_level = Some(levels[i]);
+        }
+    }
+
+    // Runs ending in an isolate initiator continue at the run starting with its PDI
+    let mut sequences = Vec::new();
+    let run_starting_at = |index: usize| runs.iter().position(|run| run[0] == index);
+    let continues: Vec<bool> = runs
+        .iter()
+        .map(|run| classes[run[0]] == PDI && (0..run[0]).any(|j| pdis[j] == Some(run[0])))
+        .collect();
+    for (r, run) in runs.iter().enumerate() {
+        if continues[r] {
+            continue;
+        }
+        let mut indices = run.clone();
+        while let Some(&last) = indices.last() {
+            let next = match pdis[last] {
+                Some(pdi) if is_isolate_initiator(classes[last]) => run_starting_at(pdi),
+                _ => None,
+            };
+            match next {
+                Some(next) => indices.extend_from_slice(&runs[next]),
+                None => break,
+            }
+        }
+
+        let first = indices[0];
+        let last = *indices.last().unwrap();
+        let level = levels[first];
+        let before = (0..first).rev().find(|&j| !removed[j]).map_or(paragraph_level, |j| levels[j]);
+        let after = if is_isolate_initiator(classes[last]) {
+            paragraph_level
+        } else {
+            (last + 1..classes.len()).find(|&j| !removed[j]).map_or(paragraph_level, |j| levels[j])
+        };
+        let direction = |other: u8| if level.max(other) % 2 == 1 { R } else { L };
+        sequences.push(Sequence { indices, sos: direction(before), eos: direction(after), level });
+    }
+    sequences
+}
+
+/// Resolve weak and neutral types within one sequence (W1 to W7, N0 to N2).
+fn resolve_sequence(sequence: &Sequence, chars: &[char], original: &[BidiClass], classes: &mut [BidiClass]) {
+    let indices = &sequence.indices;
+    let mut types: Vec<BidiClass> = indices.iter().map(|&i| classes[i]).collect();
+    let count = types.len();
+
+    // W1: non-spacing marks take the type of what they follow
+    for k in 0..count {
+        if types[k] == NSM {
+            types[k] = match k.checked_sub(1).map(|p| types[p]) {
+                None => sequence.sos,
+                Some(LRI | RLI | FSI | PDI) => ON,
+                Some(previous) => previous,
+            };
+        }
+    }
+
+    // W2, W3: European numbers after Arabic letters become Arabic numbers
+    let mut last_strong = sequence.sos;
+    for class in types.iter_mut() {
+        match *class {
+            EN if last_strong == AL => *class = AN,
+            L | R | AL => last_strong = *class,
+            _ => {}
+        }
+    }
+    for class in types.iter_mut().filter(|class| **class == AL) {
+        *class = R;
+    }
+
+    // W4: single separators between numbers of the same type
+    for k in 1..count.saturating_sub(1) {
+        types[k] = match (types[k - 1], types[k], types[k + 1]) {
+            (EN, ES | CS, EN) => EN,
+            (AN, CS, AN) => AN,
+            _ => types[k],
+        };
+    }
+
+    // W5: terminators next to European numbers
+    let mut k = 0;
+    while k < count {
+        if types[k] == ET {
+            let start = k;
+            while k < count && types[k] == ET {
+                k += 1;
+            }
+            let touches_number = (start > 0 && types[start - 1] == EN) || (k < count && types[k] == EN);
+            if touches_number {
+                types[start..k].fill(EN);
+            }
+        } else {
+            k += 1;
+        }
+    }
+
+    // W6: remaining separators and terminators are neutral
+    for class in types.iter_mut() {
+        if matches!(*class, ES | ET | CS) {
+            *class = ON;
+        }
+    }
+
+    // W7: European numbers in left-to-right context
+    let mut last_strong = sequence.sos;
+    for class in types.iter_mut() {
+        match *class {
+            EN if last_strong == L => *class = L,
+            L | R => last_strong = *class,
+            _ => {}
+        }
+    }
+
+    resolve_brackets(sequence, chars, original, &mut types);
+
+    // N1, N2: neutrals between strong types of one direction take it, the rest the embedding direction
+    let embedding = if sequence.level % 2 == 1 { R } else { L };
+    let strong = |class: BidiClass| match class {
+        L => Some(L),
+        R | EN | AN => Some(R),
+        _ => None,
+    };
+    let mut k = 0;
+    while k < count {
+        if is_neutral(types[k]) {
+            let start = k;
+            while k < count && is_neutral(types[k]) {
+                k += 1;
+            }
+            let before = if start == 0 { Some(sequence.sos) } else { strong(types[start - 1]) };
+            let after = if k == count { Some(sequence.eos) } else { strong(types[k]) };
+            let resolved = match (before, after) {
+                (Some(a), Some(b)) if a == b => a,
+                _ => embedding,
+            };
+            types[start..k].fill(resolved);
+        } else {
+            k += 1;
+        }
+    }
+
+    for (k, &i) in indices.iter().enumerate() {
+        classes[i] = types[k];
+    }
+}
+
+/// Resolve paired brackets to a common direction (N0).
+fn resolve_brackets(sequence: &Sequence, chars: &[char], original: &[BidiClass], types: &mut [BidiClass]) {
+    let indices = &sequence.indices;
+
+    // Identify bracket pairs (BD16)
+    let mut pairs = Vec::new();
+    let mut open: Vec<(char, usize)> = Vec::new();
+    for (k, &i) in indices.iter().enumerate() {
+        if types[k] != ON {
+            continue;
+        }
+        let code = chars[i] as u32;
+        if let Some(&(_, close)) = BRACKET_PAIRS.iter().find(|&&(opening, _)| opening == code) {
+            if open.len() == MAX_BRACKET_DEPTH {
+                break;
+            }
+            open.push((char::from_u32(close).unwrap_or(chars[i]), k));
+        } else if BRACKET_PAIRS.iter().any(|&(_, close)| close == code) {
+            if let Some(depth) = open.iter().rposition(|&(close, _)| close == chars[i]) {
+                pairs.push((open[depth].1, k));
+                open.truncate(depth);
+            }
+        }
+    }
+    pairs.sort_unstable();
+
+    let embedding = if sequence.level % 2 == 1 { R } else { L };
+    let strong = |class: BidiClass| match class {
+        L => Some(L),
+        R | AL | EN | AN => Some(R),
+        _ => None,
+    };
+    for (opening, closing) in pairs {
+        let inside: Vec<BidiClass> = types[opening + 1..closing].iter().filter_map(|&class| strong(class)).collect();
+        let resolved = if inside.contains(&embedding) {
+            Some(embedding)
+        } else if let Some(&opposite) = inside.first() {
+            let context = types[..opening]
+                .iter()
+                .rev()
+                .find_map(|&class| strong(class))
+                .unwrap_or(sequence.sos);
+            Some(if context == opposite { opposite } else { embedding })
+        } else {
+            None
+        };
+
+        if let Some(resolved) = resolved {
+            for bracket in [opening, closing] {
+                types[bracket] = resolved;
+                // Marks that followed the bracket take its new type
+                for k in bracket + 1..types.len() {
+                    if original[indices[k]] != NSM {
+                        break;
+                    }
+                    types[k] = resolved;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Display order of a single-line paragraph.
+    fn visual(text: &str, direction: Option<TextDirection>) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let paragraph = BidiParagraph::new(text, direction);
+        let mut display = String::new();
+        for (range, level) in paragraph.visual_runs(0..chars.len()) {
+            if level % 2 == 1 {
+                display.extend(chars[range].iter().rev().map(|&ch| mirrored(ch).unwrap_or(ch)));
+            } else {
+                display.extend(&chars[range]);
+            }
+        }
+        display
+    }
+
+    #[test]
+    fn test_paragraph_direction() {
+        assert!(!BidiParagraph::new("abc", None).is_rtl());
+        assert!(BidiParagraph::new("123 \u{5d0}", None).is_rtl());
+        assert!(!BidiParagraph::new("\u{2067}\u{5d0}\u{2069}abc", None).is_rtl());
+        assert_eq!(BidiParagraph::new("abc", Some(TextDirection::RightToLeft)).level, 1);
+    }
+
+    #[test]
+    fn test_mixed_text_levels() {
+        // Hebrew inside English, with a number inside the Hebrew
+        let paragraph = BidiParagraph::new("a \u{5d0}\u{5d1} 12 \u{5d2} b", None);
+        assert_eq!(paragraph.levels, vec![0, 0, 1, 1, 1, 2, 2, 1, 1, 0, 0]);
+        assert_eq!(visual("ab \u{5d0}\u{5d1}\u{5d2} cd", None), "ab \u{5d2}\u{5d1}\u{5d0} cd");
+    }
+
+    #[test]
+    fn test_numbers_and_brackets_in_rtl() {
+        assert_eq!(visual("\u{5d0} 123 \u{5d1}", None), "\u{5d1} 123 \u{5d0}");
+        // Brackets around right-to-left text are mirrored and stay paired
+        assert_eq!(visual("\u{5d0}(\u{5d1})", None), "(\u{5d1})\u{5d0}");
+        assert_eq!(visual("a(b)\u{5d0}", Some(TextDirection::RightToLeft)), "\u{5d0}a(b)");
+    }
+
+    #[test]
+    fn test_trailing_whitespace_takes_paragraph_level() {
+        let paragraph = BidiParagraph::new("\u{5d0} a ", None);
+        assert_eq!(paragraph.line_levels(0..4), vec![1, 1, 2, 1]);
+    }
+}
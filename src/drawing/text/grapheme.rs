@@ -0,0 +1,150 @@
+//! Grapheme cluster segmentation (UAX #29).
+//!
+//! A grapheme cluster is what users perceive as one character, such as a
+//! letter with combining accents, a Hangul syllable, a flag or an emoji
+//! joined with zero-width joiners. Carets and deletions move by clusters.
+
+use super::tables::{EXTENDED_PICTOGRAPHIC, GRAPHEME_BREAK};
+use super::{hangul_syllable, lookup};
+
+/// Grapheme cluster break property of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphemeBreak {
+    Other,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+}
+
+/// Get the grapheme cluster break property of a character.
+pub fn grapheme_break(ch: char) -> GraphemeBreak {
+    match hangul_syllable(ch) {
+        Some(true) => GraphemeBreak::Lv,
+        Some(false) => GraphemeBreak::Lvt,
+        None => lookup(&GRAPHEME_BREAK, ch).unwrap_or(GraphemeBreak::Other),
+    }
+}
+
+/// Check if a character has the Extended_Pictographic property.
+fn is_extended_pictographic(ch: char) -> bool {
+    let code = ch as u32;
+    EXTENDED_PICTOGRAPHIC
+        .binary_search_by(|&(first, last)| {
+            if last < code {
+                std::cmp::Ordering::Less
+            } else if first > code {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Byte offsets of every grapheme cluster boundary, including `0` and `text.len()`.
+pub fn grapheme_boundaries(text: &str) -> Vec<usize> {
+    use GraphemeBreak::*;
+
+    let mut boundaries = vec![0];
+    let mut chars = text.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return boundaries;
+    };
+
+    let mut previous = grapheme_break(first);
+    let mut regional_indicators = usize::from(previous == RegionalIndicator);
+    // Inside `ExtPict Extend*`, and whether that sequence just ended in a ZWJ
+    let mut in_emoji = is_extended_pictographic(first);
+    let mut emoji_joiner = false;
+
+    for (index, ch) in chars {
+        let class = grapheme_break(ch);
+        let pictographic = is_extended_pictographic(ch);
+        let boundary = match (previous, class) {
+            (Cr, Lf) => false,
+            (Control | Cr | Lf, _) | (_, Control | Cr | Lf) => true,
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => false,
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => false,
+            (Zwj, _) if emoji_joiner && pictographic => false,
+            // Regional indicators pair up into flags
+            (RegionalIndicator, RegionalIndicator) => regional_indicators.is_multiple_of(2),
+            _ => true,
+        };
+        if boundary {
+            boundaries.push(index);
+        }
+
+        regional_indicators = match class {
+            RegionalIndicator => regional_indicators + 1,
+            _ => 0,
+        };
+        emoji_joiner = in_emoji && class == Zwj;
+        in_emoji = pictographic || (in_emoji && class == Extend);
+        previous = class;
+    }
+
+    boundaries.push(text.len());
+    boundaries
+}
+
+/// The first grapheme boundary after `index`, or `text.len()`.
+pub fn next_grapheme_boundary(text: &str, index: usize) -> usize {
+    grapheme_boundaries(text)
+        .into_iter()
+        .find(|&boundary| boundary > index)
+        .unwrap_or(text.len())
+}
+
+/// The last grapheme boundary before `index`, or `0`.
+pub fn previous_grapheme_boundary(text: &str, index: usize) -> usize {
+    grapheme_boundaries(text)
+        .into_iter()
+        .rev()
+        .find(|&boundary| boundary < index)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clusters(text: &str) -> Vec<&str> {
+        grapheme_boundaries(text).windows(2).map(|pair| &text[pair[0]..pair[1]]).collect()
+    }
+
+    #[test]
+    fn test_combining_marks_and_hangul() {
+        assert_eq!(clusters("e\u{301}a\r\n"), vec!["e\u{301}", "a", "\r\n"]);
+        // Conjoining jamo form one syllable; precomposed syllables stand alone
+        assert_eq!(clusters("\u{1100}\u{1161}\u{11A8}\u{AC00}\u{AC01}"), vec!["\u{1100}\u{1161}\u{11A8}", "\u{AC00}", "\u{AC01}"]);
+        assert_eq!(clusters(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_emoji_and_flags() {
+        // Family emoji joined with ZWJ, a skin tone modifier, and two flags
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(clusters(family), vec![family]);
+        assert_eq!(clusters("\u{1F44B}\u{1F3FD}!"), vec!["\u{1F44B}\u{1F3FD}", "!"]);
+        assert_eq!(clusters("\u{1F1EB}\u{1F1F7}\u{1F1E9}\u{1F1EA}\u{1F1EB}"), vec!["\u{1F1EB}\u{1F1F7}", "\u{1F1E9}\u{1F1EA}", "\u{1F1EB}"]);
+    }
+
+    #[test]
+    fn test_boundary_navigation() {
+        let text = "ae\u{301}b";
+        assert_eq!(next_grapheme_boundary(text, 1), 4);
+        assert_eq!(previous_grapheme_boundary(text, 4), 1);
+        assert_eq!(previous_grapheme_boundary(text, 0), 0);
+        assert_eq!(next_grapheme_boundary(text, 5), 5);
+    }
+}
@@ -0,0 +1,205 @@
+//! Line breaking (UAX #14).
+//!
+//! Finds the positions where a line of text may wrap, following the pair
+//! rules of the Unicode line breaking algorithm. Ideographic text may break
+//! between most characters, while words, numbers and punctuation stay
+//! together.
+
+use super::tables::LINE_BREAK;
+use super::{hangul_syllable, lookup};
+
+/// Line break class of a character, after rule LB1 resolves the ambiguous,
+/// surrogate, unknown, complex-context and conditional Japanese classes.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineBreak {
+    AL, B2, BA, BB, BK, CB, CL, CM, CP, CR, EB, EM, EX, GL, H2, H3, HL, HY, ID, IN,
+    IS, JL, JT, JV, LF, NL, NS, NU, OP, PO, PR, QU, RI, SP, SY, WJ, ZW, ZWJ,
+}
+
+/// A position where a line may or must end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakOpportunity {
+    /// Byte offset of the first character on the next line.
+    pub index: usize,
+    /// Whether the text forces a break here, as after a newline.
+    pub mandatory: bool,
+}
+
+/// Get the line break class of a character.
+pub fn line_break(ch: char) -> LineBreak {
+    match hangul_syllable(ch) {
+        Some(true) => LineBreak::H2,
+        Some(false) => LineBreak::H3,
+        None => lookup(&LINE_BREAK, ch).unwrap_or(LineBreak::AL),
+    }
+}
+
+/// Approximate East_Asian_Width of Wide or Fullwidth, used by rule LB30.
+fn is_east_asian(ch: char) -> bool {
+    matches!(ch as u32, 0x1100..=0x115F | 0x2E80..=0xA4CF | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD)
+}
+
+/// Find every line break opportunity in `text`.
+///
+/// The end of non-empty text is always a mandatory break.
+pub fn line_breaks(text: &str) -> Vec<BreakOpportunity> {
+    use LineBreak::*;
+
+    let mut breaks = Vec::new();
+    let mut chars = text.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return breaks;
+    };
+
+    // Combining marks at the start of text act as letters (LB10)
+    let mut previous = match line_break(first) {
+        CM | ZWJ => AL,
+        class => class,
+    };
+    let mut previous_char = first;
+    let mut before_previous: Option<LineBreak> = None;
+    // The class before any run of spaces, for the `X SP* Y` rules
+    let mut before_spaces = previous;
+    let mut after_joiner = line_break(first) == ZWJ;
+    let mut regional_indicators = usize::from(previous == RI);
+
+    for (index, ch) in chars {
+        let raw = line_break(ch);
+        let decision = match (previous, raw) {
+            (BK, _) => Some(true),
+            (CR, LF) => Some(false),
+            (CR | LF | NL, _) => Some(true),
+            (_, BK | CR | LF | NL | SP | ZW) => Some(false),
+            _ if before_spaces == ZW => Some(true),
+            _ if after_joiner => Some(false),
+            _ => None,
+        };
+
+        // Combining marks attach to the preceding character and take its class (LB9)
+        let attaches = matches!(raw, CM | ZWJ) && !matches!(previous, BK | CR | LF | NL | SP | ZW);
+        let class = match raw {
+            CM | ZWJ if !attaches => AL,
+            class => class,
+        };
+
+        let allowed = match decision {
+            Some(mandatory @ true) => {
+                breaks.push(BreakOpportunity { index, mandatory });
+                false
+            }
+            Some(false) => false,
+            None if attaches => false,
+            None => pair_allows_break(previous, before_spaces, before_previous, class, previous_char, ch, regional_indicators),
+        };
+        if allowed {
+            breaks.push(BreakOpportunity { index, mandatory: false });
+        }
+
+        after_joiner = raw == ZWJ;
+        if !attaches {
+            regional_indicators = if class == RI { regional_indicators + 1 } else { 0 };
+            before_previous = Some(previous);
+            previous = class;
+            previous_char = ch;
+            if class != SP {
+                before_spaces = class;
+            }
+        }
+    }
+
+    breaks.push(BreakOpportunity { index: text.len(), mandatory: true });
+    breaks
+}
+
+/// Apply rules LB11 to LB31 between two characters.
+fn pair_allows_break(
+    previous: LineBreak,
+    before_spaces: LineBreak,
+    before_previous: Option<LineBreak>,
+    next: LineBreak,
+    previous_char: char,
+    next_char: char,
+    regional_indicators: usize,
+) -> bool {
+    use LineBreak::*;
+
+    match (previous, next) {
+        (WJ, _) | (_, WJ) | (GL, _) => false,
+        (SP | BA | HY, GL) => true,
+        (_, GL) => false,
+        (_, CL | CP | EX | IS | SY) => false,
+        _ if before_spaces == OP => false,
+        _ if before_spaces == QU && next == OP => false,
+        _ if matches!(before_spaces, CL | CP) && next == NS => false,
+        _ if before_spaces == B2 && next == B2 => false,
+        (SP, _) => true,
+        (QU, _) | (_, QU) => false,
+        (CB, _) | (_, CB) => true,
+        (_, BA | HY | NS) | (BB, _) => false,
+        (HY | BA, _) if before_previous == Some(HL) => false,
+        (SY, HL) | (_, IN) => false,
+        (AL | HL, NU) | (NU, AL | HL) => false,
+        (PR, ID | EB | EM) | (ID | EB | EM, PO) => false,
+        (PR | PO, AL | HL) | (AL | HL, PR | PO) => false,
+        // Numeric expressions (LB25, in its simplified pair form)
+        (CL | CP | NU, PO | PR) | (PO | PR, OP | NU) | (HY | IS | NU | SY, NU) => false,
+        // Korean syllable blocks (LB26, LB27)
+        (JL, JL | JV | H2 | H3) | (JV | H2, JV | JT) | (JT | H3, JT) => false,
+        (JL | JV | JT | H2 | H3, PO) | (PR, JL | JV | JT | H2 | H3) => false,
+        (AL | HL, AL | HL) | (IS, AL | HL) => false,
+        (AL | HL | NU, OP) if !is_east_asian(next_char) => false,
+        (CP, AL | HL | NU) if !is_east_asian(previous_char) => false,
+        // Flags are pairs of regional indicators
+        (RI, RI) => regional_indicators.is_multiple_of(2),
+        (EB, EM) => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Split text at every break opportunity.
+    fn segments(text: &str) -> Vec<&str> {
+        let mut start = 0;
+        line_breaks(text)
+            .into_iter()
+            .map(|opportunity| {
+                let segment = &text[start..opportunity.index];
+                start = opportunity.index;
+                segment
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_words_and_punctuation() {
+        assert_eq!(segments("Hello, world! (a-b) $5.00"), vec!["Hello, ", "world! ", "(a-", "b) ", "$5.00"]);
+        assert_eq!(segments("e\u{301}t\u{e9} x"), vec!["e\u{301}t\u{e9} ", "x"]);
+        assert!(line_breaks("").is_empty());
+    }
+
+    #[test]
+    fn test_ideographs_break_between_characters() {
+        assert_eq!(segments("漢字かな。"), vec!["漢", "字", "か", "な。"]);
+        // Hangul breaks between syllable blocks but not inside them
+        assert_eq!(segments("한국어 텍스트"), vec!["한", "국", "어 ", "텍", "스", "트"]);
+    }
+
+    #[test]
+    fn test_mandatory_breaks() {
+        let breaks = line_breaks("a\r\nb\nc");
+        assert_eq!(
+            breaks,
+            vec![
+                BreakOpportunity { index: 3, mandatory: true },
+                BreakOpportunity { index: 5, mandatory: true },
+                BreakOpportunity { index: 6, mandatory: true },
+            ]
+        );
+        // No break inside a non-breaking space or before closing punctuation
+        assert_eq!(segments("a\u{a0}b c )"), vec!["a\u{a0}b ", "c )"]);
+    }
+}
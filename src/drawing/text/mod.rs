@@ -0,0 +1,562 @@
+//! Unicode text layout.
+//!
+//! [`TextLayout`] splits text into paragraphs and wrapped lines at UAX #14
+//! break opportunities, reorders each line for display with the UAX #9
+//! bidirectional algorithm and places the glyphs of every run. Carets and
+//! hit-testing snap to grapheme cluster boundaries. Layouts of [`RichText`]
+//! give each styled span its own font face and size.
+
+pub mod bidi;
+pub mod grapheme;
+pub mod line_break;
+pub mod rich;
+mod tables;
+
+pub use bidi::{BidiParagraph, TextDirection};
+pub use rich::{RichText, TextSpan, TextStyle};
+pub use grapheme::{grapheme_boundaries, next_grapheme_boundary, previous_grapheme_boundary};
+pub use line_break::{line_breaks, BreakOpportunity};
+
+use super::font::{Font, GlyphPosition};
+use super::{Point, Rect, Size};
+use std::ops::Range;
+
+/// Tolerance for line widths that only exceed the maximum through rounding.
+const WIDTH_EPSILON: f32 = 0.001;
+
+/// Find the value of the range containing `ch` in a sorted range table.
+pub(crate) fn lookup<T: Copy>(table: &[(u32, u32, T)], ch: char) -> Option<T> {
+    let code = ch as u32;
+    table
+        .binary_search_by(|&(first, last, _)| {
+            if last < code {
+                std::cmp::Ordering::Less
+            } else if first > code {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|i| table[i].2)
+}
+
+/// For a precomposed Hangul syllable, whether it is an LV syllable (`true`)
+/// or an LVT syllable (`false`).
+pub(crate) fn hangul_syllable(ch: char) -> Option<bool> {
+    let code = ch as u32;
+    (0xAC00..=0xD7A3).contains(&code).then(|| (code - 0xAC00).is_multiple_of(28))
+}
+
+/// Options controlling how text is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LayoutOptions {
+    /// Width at which lines wrap, or `None` to only break at newlines.
+    pub max_width: Option<f32>,
+    /// Base direction of every paragraph, or `None` to detect it from the text.
+    pub direction: Option<TextDirection>,
+}
+
+impl LayoutOptions {
+    /// Create options that never wrap and detect the text direction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap lines at `width`.
+    pub fn max_width(mut self, width: f32) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Force the base direction of every paragraph.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+}
+
+/// Consecutive glyphs of one embedding level and style, in display order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphRun {
+    /// Byte range of the run in the laid out text.
+    pub range: Range<usize>,
+    /// Bidi embedding level; odd levels are right-to-left.
+    pub level: u8,
+    /// Distance from the start of the line to the left edge of the run.
+    pub x: f32,
+    /// Total advance of the run.
+    pub width: f32,
+    /// The characters of the run in display order, with mirrored brackets.
+    pub text: String,
+    /// Glyphs from left to right, positioned relative to the start of the line.
+    /// Each glyph's `index` is its byte offset in the laid out text.
+    pub glyphs: Vec<GlyphPosition>,
+    /// Font the glyphs come from.
+    pub font: Font,
+    /// Font size in pixels.
+    pub size: f32,
+    /// Style of the span the run belongs to.
+    pub style: TextStyle,
+}
+
+impl GlyphRun {
+    /// Check if the run displays right-to-left.
+    pub fn is_rtl(&self) -> bool {
+        self.level % 2 == 1
+    }
+}
+
+/// One displayed line of a [`TextLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// Byte range of the line, excluding the newline or the spaces it wrapped at.
+    pub range: Range<usize>,
+    /// Runs from left to right.
+    pub runs: Vec<GlyphRun>,
+    /// Total advance of the line.
+    pub width: f32,
+    /// Distance from the top of the layout to the top of the line.
+    pub top: f32,
+    /// Distance from the top of the line to the next line.
+    pub height: f32,
+    /// Distance from the top of the layout to the line's baseline.
+    pub baseline: f32,
+    /// Whether the paragraph containing the line is right-to-left.
+    pub rtl: bool,
+}
+
+impl TextLine {
+    fn glyphs(&self) -> impl Iterator<Item = (&GlyphRun, &GlyphPosition)> {
+        self.runs.iter().flat_map(|run| run.glyphs.iter().map(move |glyph| (run, glyph)))
+    }
+}
+
+/// A byte range of the text laid out in one face, size and style.
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    range: Range<usize>,
+    font: Font,
+    size: f32,
+    style: TextStyle,
+}
+
+/// Text broken into lines, reordered and positioned for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    text: String,
+    font: Font,
+    size: f32,
+    line_height: f32,
+    lines: Vec<TextLine>,
+    boundaries: Vec<usize>,
+    segments: Vec<Segment>,
+}
+
+impl TextLayout {
+    /// Lay out `text` in `font` at `size` pixels.
+    pub fn new(text: &str, font: &Font, size: f32, options: LayoutOptions) -> Self {
+        Self::rich(&RichText::plain(text), font, size, options)
+    }
+
+    /// Lay out styled text, using `font` and `size` where spans do not set
+    /// their own. Bold and italic spans use the matching face of the font's
+    /// family when it was resolved from a font database.
+    pub fn rich(rich: &RichText, font: &Font, size: f32, options: LayoutOptions) -> Self {
+        let text = rich.text();
+        let segments = rich
+            .spans()
+            .iter()
+            .map(|span| {
+                let style = span.style;
+                let font = if style.weight.is_some() || style.italic {
+                    font.styled(style.weight.unwrap_or(font.weight()), style.italic || font.is_italic())
+                } else {
+                    font.clone()
+                };
+                Segment { range: span.range.clone(), font, size: style.size.unwrap_or(size), style }
+            })
+            .collect();
+        let mut layout = Self {
+            text: text.to_string(),
+            font: font.clone(),
+            size,
+            line_height: font.line_height(size),
+            lines: Vec::new(),
+            boundaries: grapheme_boundaries(text),
+            segments,
+        };
+
+        let mut start = 0;
+        for opportunity in line_breaks(text).into_iter().filter(|opportunity| opportunity.mandatory) {
+            let paragraph = text[start..opportunity.index].trim_end_matches(is_newline);
+            layout.add_paragraph(start, paragraph, options);
+            start = opportunity.index;
+        }
+        // Empty text and text ending in a newline still have a line to place the caret on
+        if text.is_empty() || text.ends_with(is_newline) {
+            layout.add_paragraph(text.len(), "", options);
+        }
+        layout
+    }
+
+    /// The laid out text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The font the text is laid out in.
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    /// The font size in pixels.
+    pub fn font_size(&self) -> f32 {
+        self.size
+    }
+
+    /// Distance between the baselines of consecutive lines in the base font.
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// The lines from top to bottom.
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Width of the widest line.
+    pub fn width(&self) -> f32 {
+        self.lines.iter().map(|line| line.width).fold(0.0, f32::max)
+    }
+
+    /// Height of all lines.
+    pub fn height(&self) -> f32 {
+        self.lines.last().map_or(0.0, |line| line.top + line.height)
+    }
+
+    /// Width and height of the laid out text.
+    pub fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+
+    /// Snap a byte index to the grapheme cluster boundary at or before it.
+    pub fn snap(&self, index: usize) -> usize {
+        let index = index.min(self.text.len());
+        match self.boundaries.binary_search(&index) {
+            Ok(_) => index,
+            Err(i) => self.boundaries[i.saturating_sub(1)],
+        }
+    }
+
+    /// Index of the line containing the caret position `index`.
+    pub fn line_at_index(&self, index: usize) -> usize {
+        let index = self.snap(index);
+        self.lines
+            .iter()
+            .enumerate()
+            .position(|(i, line)| index < line.range.end || (index == line.range.end && !self.wraps_after(i)))
+            .unwrap_or(self.lines.len() - 1)
+    }
+
+    /// Top-left position of a caret placed before the grapheme at byte `index`.
+    pub fn caret_position(&self, index: usize) -> Point {
+        let index = self.snap(index);
+        let line_index = self.line_at_index(index);
+        let line = &self.lines[line_index];
+        Point::new(self.caret_x(line, index), line.top)
+    }
+
+    /// Byte index of the caret position closest to `point`.
+    pub fn index_at(&self, point: Point) -> usize {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| point.y < line.top + line.height)
+            .unwrap_or(&self.lines[self.lines.len() - 1]);
+        let first = self.boundaries.partition_point(|&boundary| boundary < line.range.start);
+        self.boundaries[first..]
+            .iter()
+            .take_while(|&&boundary| boundary <= line.range.end)
+            .copied()
+            .min_by(|&a, &b| {
+                let distance = |index| (self.caret_x(line, index) - point.x).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(line.range.start)
+    }
+
+    /// Rectangles covering the glyphs of a byte range, one per contiguous
+    /// stretch on each line.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects: Vec<Rect> = Vec::new();
+        for line in &self.lines {
+            let mut current: Option<(f32, f32)> = None;
+            for (_, glyph) in line.glyphs() {
+                if range.contains(&glyph.index) {
+                    let (left, right) = current.get_or_insert((glyph.x, glyph.x));
+                    *left = left.min(glyph.x);
+                    *right = right.max(glyph.x + glyph.advance);
+                } else if let Some((left, right)) = current.take() {
+                    rects.push(Rect::new(left, line.top, right - left, line.height));
+                }
+            }
+            if let Some((left, right)) = current {
+                rects.push(Rect::new(left, line.top, right - left, line.height));
+            }
+        }
+        rects
+    }
+
+    /// Whether the line after `line_index` continues the same paragraph.
+    fn wraps_after(&self, line_index: usize) -> bool {
+        let line = &self.lines[line_index];
+        self.lines
+            .get(line_index + 1)
+            .is_some_and(|next| !self.text[line.range.end..next.range.start].contains(is_newline))
+    }
+
+    /// Horizontal caret position for `index` on `line`.
+    fn caret_x(&self, line: &TextLine, index: usize) -> f32 {
+        if let Some((run, glyph)) = line.glyphs().find(|(_, glyph)| glyph.index == index) {
+            return if run.is_rtl() { glyph.x + glyph.advance } else { glyph.x };
+        }
+        // After the last character: the trailing edge of the previous one
+        let previous = line
+            .glyphs()
+            .filter(|(_, glyph)| glyph.index < index)
+            .max_by_key(|(_, glyph)| glyph.index);
+        match previous {
+            Some((run, glyph)) if run.is_rtl() => glyph.x,
+            Some((_, glyph)) => glyph.x + glyph.advance,
+            None if line.rtl => line.width,
+            None => 0.0,
+        }
+    }
+
+    /// Wrap and position one paragraph starting at byte `offset`.
+    fn add_paragraph(&mut self, offset: usize, paragraph: &str, options: LayoutOptions) {
+        let chars: Vec<(usize, char)> = paragraph.char_indices().collect();
+        let count = chars.len();
+        let byte = |i: usize| chars.get(i).map_or(paragraph.len(), |&(index, _)| index);
+
+        // Pen position before each character, and the total at the end
+        let mut pen = Vec::with_capacity(count + 1);
+        let mut x = 0.0;
+        for (range, segment) in self.pieces(offset..offset + paragraph.len()) {
+            let placed = segment.font.layout(&self.text[range], segment.size);
+            pen.extend(placed.iter().map(|glyph| x + glyph.x));
+            x += placed.last().map_or(0.0, |glyph| glyph.x + glyph.advance);
+        }
+        pen.push(x);
+
+        let char_index = |byte_index: usize| chars.partition_point(|&(index, _)| index < byte_index);
+        let breaks: Vec<usize> = line_breaks(paragraph).iter().map(|opportunity| char_index(opportunity.index)).collect();
+        let graphemes: Vec<usize> = grapheme_boundaries(paragraph).into_iter().map(char_index).collect();
+        let trim = |start: usize, mut end: usize| {
+            while end > start && chars[end - 1].1.is_whitespace() {
+                end -= 1;
+            }
+            end
+        };
+
+        // Greedy wrapping at break opportunities, splitting between graphemes
+        // when a single word is too wide
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while let Some(max_width) = options.max_width.filter(|_| start < count) {
+            let fits = |end: usize| pen[end] - pen[start] <= max_width + WIDTH_EPSILON;
+            let mut end = None;
+            for &candidate in breaks.iter().filter(|&&candidate| candidate > start) {
+                if fits(trim(start, candidate)) {
+                    end = Some(candidate);
+                } else {
+                    break;
+                }
+            }
+            let end = end.unwrap_or_else(|| {
+                let limit = breaks.iter().copied().find(|&candidate| candidate > start).unwrap_or(count);
+                let mut candidates = graphemes.iter().copied().filter(|&g| g > start && g <= limit);
+                let mut end = candidates.next().unwrap_or(count);
+                for candidate in candidates {
+                    if !fits(candidate) {
+                        break;
+                    }
+                    end = candidate;
+                }
+                end
+            });
+            if end == count {
+                break;
+            }
+            ranges.push((start, trim(start, end)));
+            start = end;
+        }
+        ranges.push((start, count));
+
+        let bidi = BidiParagraph::new(paragraph, options.direction);
+        for (start, end) in ranges {
+            let mut runs = Vec::new();
+            let mut x = 0.0;
+            for (range, level) in bidi.visual_runs(start..end) {
+                let range = offset + byte(range.start)..offset + byte(range.end);
+                let mut pieces = self.pieces(range);
+                if level % 2 == 1 {
+                    pieces.reverse();
+                }
+                for (range, segment) in pieces {
+                    let run = self.shape_run(range, segment, level, x);
+                    x += run.width;
+                    runs.push(run);
+                }
+            }
+
+            let range = offset + byte(start)..offset + byte(end);
+            let (ascent, descent, gap) = self.line_metrics(range.clone());
+            let top = self.height();
+            self.lines.push(TextLine {
+                range,
+                runs,
+                width: x,
+                top,
+                height: ascent + descent + gap,
+                baseline: top + ascent,
+                rtl: bidi.is_rtl(),
+            });
+        }
+    }
+
+    /// Split a byte range at segment boundaries.
+    fn pieces(&self, range: Range<usize>) -> Vec<(Range<usize>, &Segment)> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.range.start < range.end && range.start < segment.range.end)
+            .map(|segment| (segment.range.start.max(range.start)..segment.range.end.min(range.end), segment))
+            .collect()
+    }
+
+    /// Largest ascent, descent and line gap of the segments on a line.
+    fn line_metrics(&self, range: Range<usize>) -> (f32, f32, f32) {
+        let mut fonts: Vec<(&Font, f32)> = self.pieces(range.clone()).into_iter().map(|(_, segment)| (&segment.font, segment.size)).collect();
+        if fonts.is_empty() {
+            // Empty lines take the style of the text before them
+            let segment = self.segments.iter().rev().find(|segment| segment.range.start < range.start.max(1));
+            fonts.push(segment.map_or((&self.font, self.size), |segment| (&segment.font, segment.size)));
+        }
+        fonts.iter().fold((0.0, 0.0, 0.0), |(ascent, descent, gap), &(font, size)| {
+            (ascent.max(font.ascent(size)), descent.max(font.descent(size)), gap.max(font.line_gap(size)))
+        })
+    }
+
+    /// Place the glyphs of one run starting at `x`.
+    fn shape_run(&self, range: Range<usize>, segment: &Segment, level: u8, x: f32) -> GlyphRun {
+        let run_text = &self.text[range.clone()];
+        let mut display = String::with_capacity(run_text.len());
+        let mut sources = Vec::new();
+        if level % 2 == 1 {
+            // Whole grapheme clusters reverse so marks stay on their base character
+            let clusters = grapheme_boundaries(run_text);
+            for pair in clusters.windows(2).rev() {
+                for (i, (index, ch)) in run_text[pair[0]..pair[1]].char_indices().enumerate() {
+                    display.push(if i == 0 { bidi::mirrored(ch).unwrap_or(ch) } else { ch });
+                    sources.push(range.start + pair[0] + index);
+                }
+            }
+        } else {
+            display.push_str(run_text);
+            sources.extend(run_text.char_indices().map(|(index, _)| range.start + index));
+        }
+
+        let glyphs: Vec<GlyphPosition> = segment
+            .font
+            .layout(&display, segment.size)
+            .into_iter()
+            .zip(sources)
+            .map(|(glyph, index)| GlyphPosition { index, x: x + glyph.x, ..glyph })
+            .collect();
+        let width = glyphs.last().map_or(0.0, |glyph| glyph.x + glyph.advance - x);
+        GlyphRun {
+            range,
+            level,
+            x,
+            width,
+            text: display,
+            glyphs,
+            font: segment.font.clone(),
+            size: segment.size,
+            style: segment.style,
+        }
+    }
+}
+
+fn is_newline(ch: char) -> bool {
+    matches!(ch, '\r' | '\n' | '\u{b}' | '\u{c}' | '\u{85}' | '\u{2028}' | '\u{2029}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::font::ttf::testing::test_font;
+
+    fn font() -> Font {
+        Font::from_bytes(test_font()).unwrap()
+    }
+
+    fn line_texts(layout: &TextLayout) -> Vec<&str> {
+        layout.lines().iter().map(|line| &layout.text()[line.range.clone()]).collect()
+    }
+
+    #[test]
+    fn test_wrapping_and_paragraphs() {
+        // 'A' advances 6 pixels at size 10 and the space 5
+        let layout = TextLayout::new("AAA AAA AA\nA", &font(), 10.0, LayoutOptions::new().max_width(36.0));
+        assert_eq!(line_texts(&layout), vec!["AAA", "AAA AA", "A"]);
+        assert_eq!(layout.lines()[1].width, 35.0);
+        assert_eq!(layout.height(), 3.0 * layout.line_height());
+
+        // A word wider than the line breaks between graphemes
+        let layout = TextLayout::new("AAAAAA", &font(), 10.0, LayoutOptions::new().max_width(20.0));
+        assert_eq!(line_texts(&layout), vec!["AAA", "AAA"]);
+
+        assert_eq!(TextLayout::new("", &font(), 10.0, LayoutOptions::new()).lines().len(), 1);
+        assert_eq!(TextLayout::new("A\n", &font(), 10.0, LayoutOptions::new()).lines().len(), 2);
+    }
+
+    #[test]
+    fn test_caret_and_hit_testing() {
+        let system = Font::system();
+        let layout = TextLayout::new("ae\u{301}b\ncd", &system, 10.0, LayoutOptions::new());
+        // System glyphs advance 6 pixels; the accent sits on the 'e'
+        assert_eq!(layout.caret_position(1), Point::new(6.0, 0.0));
+        assert_eq!(layout.caret_position(2), Point::new(6.0, 0.0));
+        assert_eq!(layout.caret_position(4), Point::new(18.0, 0.0));
+        assert_eq!(layout.caret_position(5), Point::new(24.0, 0.0));
+        assert_eq!(layout.caret_position(7), Point::new(6.0, layout.line_height()));
+
+        assert_eq!(layout.index_at(Point::new(8.0, 1.0)), 1);
+        assert_eq!(layout.index_at(Point::new(14.0, 1.0)), 4);
+        assert_eq!(layout.index_at(Point::new(100.0, 1.0)), 5);
+        assert_eq!(layout.index_at(Point::new(0.0, layout.line_height() + 1.0)), 6);
+    }
+
+    #[test]
+    fn test_right_to_left_runs() {
+        let system = Font::system();
+        // "ab " then three Hebrew letters, each two bytes long
+        let text = "ab \u{5d0}\u{5d1}\u{5d2}";
+        let layout = TextLayout::new(text, &system, 10.0, LayoutOptions::new());
+        let line = &layout.lines()[0];
+        assert_eq!(line.runs.len(), 2);
+        assert_eq!(line.runs[1].text, "\u{5d2}\u{5d1}\u{5d0}");
+        assert_eq!(line.runs[1].x, 18.0);
+
+        // The first Hebrew letter is displayed rightmost
+        assert_eq!(layout.caret_position(3).x, 36.0);
+        assert_eq!(layout.caret_position(5).x, 30.0);
+        assert_eq!(layout.index_at(Point::new(31.0, 1.0)), 5);
+        assert_eq!(layout.selection_rects(3..5), vec![Rect::new(30.0, 0.0, 6.0, layout.line_height())]);
+
+        let layout = TextLayout::new("(a)", &system, 10.0, LayoutOptions::new().direction(TextDirection::RightToLeft));
+        assert!(layout.lines()[0].rtl);
+    }
+}
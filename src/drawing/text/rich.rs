@@ -0,0 +1,326 @@
+//! Attributed text.
+//!
+//! [`RichText`] is a string with [`TextStyle`] spans, built up piece by piece
+//! or parsed from a small markup language:
+//!
+//! ```text
+//! <b>Saved</b> 3 files, <color=#c00>1 <i>failed</i></color>
+//! ```
+//!
+//! Supported tags are `<b>`, `<i>`, `<u>`, `<s>`, `<color=...>`, `<bg=...>`,
+//! `<size=...>` and `<weight=...>`; colors use CSS syntax. `&lt;`, `&gt;`,
+//! `&amp;` and `&quot;` escape markup characters.
+
+use crate::drawing::Color;
+use crate::{Error, Result};
+use std::ops::Range;
+
+/// Attributes of a span of text. Unset values use the defaults of whatever
+/// draws the text.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextStyle {
+    /// Text color.
+    pub color: Option<Color>,
+    /// Font size in pixels.
+    pub size: Option<f32>,
+    /// Weight class from 100 (thin) to 900 (black).
+    pub weight: Option<u16>,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// Whether a line is drawn under the text.
+    pub underline: bool,
+    /// Whether a line is drawn through the text.
+    pub strikethrough: bool,
+    /// Highlight color behind the text.
+    pub background: Option<Color>,
+}
+
+impl TextStyle {
+    /// Create a style that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the text color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the font size.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the weight class.
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Use a bold weight.
+    pub fn bold(self) -> Self {
+        self.weight(700)
+    }
+
+    /// Use an italic face.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Underline the text.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Strike through the text.
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Highlight the text with a background color.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Check if the weight asks for a bold face.
+    pub fn is_bold(&self) -> bool {
+        self.weight.is_some_and(|weight| weight >= 600)
+    }
+}
+
+/// A styled byte range of a [`RichText`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    /// Byte range of the span.
+    pub range: Range<usize>,
+    /// Style of the span.
+    pub style: TextStyle,
+}
+
+/// Text with styled spans.
+///
+/// Spans are sorted, do not overlap and cover the whole text.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RichText {
+    text: String,
+    spans: Vec<TextSpan>,
+}
+
+impl RichText {
+    /// Create empty rich text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create rich text with a single unstyled span.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self::new().span(text, TextStyle::default())
+    }
+
+    /// Parse markup such as `"<b>Error:</b> <color=#f00>disk full</color>"`.
+    pub fn parse(markup: &str) -> Result<RichText> {
+        let mut rich = RichText::new();
+        let mut stack: Vec<(&str, TextStyle)> = Vec::new();
+        let mut style = TextStyle::default();
+        let mut rest = markup;
+
+        while !rest.is_empty() {
+            let next = rest.find(['<', '&']).unwrap_or(rest.len());
+            if next > 0 {
+                rich.push(&rest[..next], style);
+                rest = &rest[next..];
+                continue;
+            }
+
+            if rest.starts_with('&') {
+                let (entity, text) = [("&lt;", "<"), ("&gt;", ">"), ("&amp;", "&"), ("&quot;", "\"")]
+                    .into_iter()
+                    .find(|(entity, _)| rest.starts_with(entity))
+                    .unwrap_or(("&", "&"));
+                rich.push(text, style);
+                rest = &rest[entity.len()..];
+                continue;
+            }
+
+            let end = rest
+                .find('>')
+                .ok_or_else(|| Error::resource(format!("Unterminated tag in markup '{}'", markup)))?;
+            let tag = rest[1..end].trim();
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim();
+                match stack.pop() {
+                    Some((open, previous)) if open.eq_ignore_ascii_case(name) => style = previous,
+                    Some((open, _)) => {
+                        return Err(Error::resource(format!("Closing tag </{}> does not match <{}>", name, open)));
+                    }
+                    None => return Err(Error::resource(format!("Closing tag </{}> was never opened", name))),
+                }
+                continue;
+            }
+
+            let (name, value) = match tag.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches(['"', '\'']))),
+                None => (tag, None),
+            };
+            stack.push((name, style));
+            style = apply_tag(style, name, value)?;
+        }
+
+        if let Some((open, _)) = stack.last() {
+            return Err(Error::resource(format!("Tag <{}> is never closed", open)));
+        }
+        Ok(rich)
+    }
+
+    /// Append text in a style.
+    pub fn push(&mut self, text: &str, style: TextStyle) {
+        if text.is_empty() {
+            return;
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        match self.spans.last_mut() {
+            Some(span) if span.style == style => span.range.end = self.text.len(),
+            _ => self.spans.push(TextSpan { range: start..self.text.len(), style }),
+        }
+    }
+
+    /// Append text in a style, builder-style.
+    pub fn span(mut self, text: impl Into<String>, style: TextStyle) -> Self {
+        self.push(&text.into(), style);
+        self
+    }
+
+    /// The text without styling.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The styled spans.
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Check if there is no text.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Style of the character at byte `index`.
+    pub fn style_at(&self, index: usize) -> TextStyle {
+        self.spans
+            .iter()
+            .find(|span| span.range.contains(&index))
+            .map_or_else(TextStyle::default, |span| span.style)
+    }
+
+    /// The same text with line breaks shown as spaces, keeping every span in place.
+    pub(crate) fn single_line(&self) -> RichText {
+        RichText {
+            text: self.text.replace(['\n', '\r'], " "),
+            spans: self.spans.clone(),
+        }
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        Self::plain(text)
+    }
+}
+
+/// Parse a tag's color value, which may also be hex digits without the `#`.
+fn color(value: &str) -> Result<Color> {
+    Color::parse(value).or_else(|error| Color::from_hex(value).map_err(|_| error))
+}
+
+/// Apply an opening markup tag to the current style.
+fn apply_tag(style: TextStyle, name: &str, value: Option<&str>) -> Result<TextStyle> {
+    let required = || value.ok_or_else(|| Error::resource(format!("Tag <{}> needs a value", name)));
+    let style = match name.to_ascii_lowercase().as_str() {
+        "b" | "strong" => style.bold(),
+        "i" | "em" => style.italic(),
+        "u" => style.underline(),
+        "s" | "strike" => style.strikethrough(),
+        "color" => style.color(color(required()?)?),
+        "bg" | "background" => style.background(color(required()?)?),
+        "size" => {
+            let size: f32 = required()?
+                .trim_end_matches("px")
+                .parse()
+                .ok()
+                .filter(|size: &f32| *size > 0.0 && size.is_finite())
+                .ok_or_else(|| Error::resource(format!("Invalid size in <{}={}>", name, value.unwrap_or_default())))?;
+            style.size(size)
+        }
+        "weight" => {
+            let weight = required()?
+                .parse()
+                .ok()
+                .filter(|weight| (1..=1000).contains(weight))
+                .ok_or_else(|| Error::resource(format!("Invalid weight in <{}={}>", name, value.unwrap_or_default())))?;
+            style.weight(weight)
+        }
+        _ => return Err(Error::resource(format!("Unknown markup tag <{}>", name))),
+    };
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builds_contiguous_spans() {
+        let rich = RichText::plain("Status: ")
+            .span("ok", TextStyle::new().bold().color(Color::GREEN))
+            .span("", TextStyle::new().italic())
+            .span(" now", TextStyle::default());
+        assert_eq!(rich.text(), "Status: ok now");
+        let ranges: Vec<_> = rich.spans().iter().map(|span| span.range.clone()).collect();
+        assert_eq!(ranges, vec![0..8, 8..10, 10..14]);
+        assert!(rich.style_at(9).is_bold());
+        assert_eq!(rich.style_at(20), TextStyle::default());
+    }
+
+    #[test]
+    fn test_parse_markup() {
+        let rich = RichText::parse("<b>Saved</b> 3 files, <color=#c00>1 <i>failed</i></color> &lt;ok&gt;").unwrap();
+        assert_eq!(rich.text(), "Saved 3 files, 1 failed <ok>");
+        assert_eq!(rich.style_at(0), TextStyle::new().bold());
+        let red = Color::from_hex("#c00").unwrap();
+        assert_eq!(rich.style_at(15), TextStyle::new().color(red));
+        assert_eq!(rich.style_at(17), TextStyle::new().color(red).italic());
+        assert_eq!(rich.style_at(24), TextStyle::default());
+
+        let rich = RichText::parse("<u><s>x</s></u><size=\"20px\"><bg=ff0>y</bg></size>").unwrap();
+        assert!(rich.style_at(0).underline && rich.style_at(0).strikethrough);
+        assert_eq!(rich.style_at(1).size, Some(20.0));
+        assert!(rich.style_at(1).background.is_some());
+    }
+
+    #[test]
+    fn test_invalid_markup() {
+        assert!(RichText::parse("<b>bold").is_err());
+        assert!(RichText::parse("<b><i>x</b></i>").is_err());
+        assert!(RichText::parse("x</b>").is_err());
+        assert!(RichText::parse("<blink>x</blink>").is_err());
+        assert!(RichText::parse("<color>x</color>").is_err());
+        assert!(RichText::parse("a < b").is_err());
+        assert_eq!(RichText::parse("a & b").unwrap().text(), "a & b");
+    }
+}
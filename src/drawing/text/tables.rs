@@ -0,0 +1,1115 @@
+//! Unicode character property tables.
+//!
+//! Generated from the Unicode 14.0 Character Database. Range tables list
+//! sorted, non-overlapping `(first, last, value)` entries; code points not
+//! listed take the property's default value. Hangul syllables are left out
+//! because their classes follow arithmetically from the code point.
+
+use super::bidi::BidiClass;
+use super::bidi::BidiClass::*;
+use super::grapheme::GraphemeBreak;
+use super::line_break::LineBreak;
+
+/// Line break classes after UAX #14 rule LB1; unlisted code points are `AL`.
+pub(crate) const LINE_BREAK: [(u32, u32, LineBreak); 1073] = [
+    (0x0, 0x8, LineBreak::CM), (0x9, 0x9, LineBreak::BA), (0xA, 0xA, LineBreak::LF),
+    (0xB, 0xC, LineBreak::BK), (0xD, 0xD, LineBreak::CR), (0xE, 0x1F, LineBreak::CM),
+    (0x20, 0x20, LineBreak::SP), (0x21, 0x21, LineBreak::EX), (0x22, 0x22, LineBreak::QU),
+    (0x24, 0x24, LineBreak::PR), (0x25, 0x25, LineBreak::PO), (0x27, 0x27, LineBreak::QU),
+    (0x28, 0x28, LineBreak::OP), (0x29, 0x29, LineBreak::CP), (0x2B, 0x2B, LineBreak::PR),
+    (0x2C, 0x2C, LineBreak::IS), (0x2D, 0x2D, LineBreak::HY), (0x2E, 0x2E, LineBreak::IS),
+    (0x2F, 0x2F, LineBreak::SY), (0x30, 0x39, LineBreak::NU), (0x3A, 0x3B, LineBreak::IS),
+    (0x3F, 0x3F, LineBreak::EX), (0x5B, 0x5B, LineBreak::OP), (0x5C, 0x5C, LineBreak::PR),
+    (0x5D, 0x5D, LineBreak::CP), (0x7B, 0x7B, LineBreak::OP), (0x7C, 0x7C, LineBreak::BA),
+    (0x7D, 0x7D, LineBreak::CL), (0x7F, 0x84, LineBreak::CM), (0x85, 0x85, LineBreak::NL),
+    (0x86, 0x9F, LineBreak::CM), (0xA0, 0xA0, LineBreak::GL), (0xA1, 0xA1, LineBreak::OP),
+    (0xA2, 0xA2, LineBreak::PO), (0xA3, 0xA5, LineBreak::PR), (0xAB, 0xAB, LineBreak::QU),
+    (0xAD, 0xAD, LineBreak::BA), (0xB0, 0xB0, LineBreak::PO), (0xB1, 0xB1, LineBreak::PR),
+    (0xB4, 0xB4, LineBreak::BB), (0xBB, 0xBB, LineBreak::QU), (0xBF, 0xBF, LineBreak::OP),
+    (0x2C8, 0x2C8, LineBreak::BB), (0x2CC, 0x2CC, LineBreak::BB), (0x2DF, 0x2DF, LineBreak::BB),
+    (0x300, 0x34E, LineBreak::CM), (0x34F, 0x34F, LineBreak::GL), (0x350, 0x35B, LineBreak::CM),
+    (0x35C, 0x362, LineBreak::GL), (0x363, 0x36F, LineBreak::CM), (0x37E, 0x37E, LineBreak::IS),
+    (0x483, 0x489, LineBreak::CM), (0x589, 0x589, LineBreak::IS), (0x58A, 0x58A, LineBreak::BA),
+    (0x58F, 0x58F, LineBreak::PR), (0x591, 0x5BD, LineBreak::CM), (0x5BE, 0x5BE, LineBreak::BA),
+    (0x5BF, 0x5BF, LineBreak::CM), (0x5C1, 0x5C2, LineBreak::CM), (0x5C4, 0x5C5, LineBreak::CM),
+    (0x5C6, 0x5C6, LineBreak::EX), (0x5C7, 0x5C7, LineBreak::CM), (0x5D0, 0x5EA, LineBreak::HL),
+    (0x5EF, 0x5F2, LineBreak::HL), (0x609, 0x60B, LineBreak::PO), (0x60C, 0x60D, LineBreak::IS),
+    (0x610, 0x61A, LineBreak::CM), (0x61B, 0x61B, LineBreak::EX), (0x61C, 0x61C, LineBreak::CM),
+    (0x61D, 0x61F, LineBreak::EX), (0x64B, 0x65F, LineBreak::CM), (0x660, 0x669, LineBreak::NU),
+    (0x66A, 0x66A, LineBreak::PO), (0x66B, 0x66C, LineBreak::NU), (0x670, 0x670, LineBreak::CM),
+    (0x6D4, 0x6D4, LineBreak::EX), (0x6D6, 0x6DC, LineBreak::CM), (0x6DF, 0x6E4, LineBreak::CM),
+    (0x6E7, 0x6E8, LineBreak::CM), (0x6EA, 0x6ED, LineBreak::CM), (0x6F0, 0x6F9, LineBreak::NU),
+    (0x711, 0x711, LineBreak::CM), (0x730, 0x74A, LineBreak::CM), (0x7A6, 0x7B0, LineBreak::CM),
+    (0x7C0, 0x7C9, LineBreak::NU), (0x7EB, 0x7F3, LineBreak::CM), (0x7F8, 0x7F8, LineBreak::IS),
+    (0x7F9, 0x7F9, LineBreak::EX), (0x7FD, 0x7FD, LineBreak::CM), (0x7FE, 0x7FF, LineBreak::PR),
+    (0x816, 0x819, LineBreak::CM), (0x81B, 0x823, LineBreak::CM), (0x825, 0x827, LineBreak::CM),
+    (0x829, 0x82D, LineBreak::CM), (0x859, 0x85B, LineBreak::CM), (0x898, 0x89F, LineBreak::CM),
+    (0x8CA, 0x8E1, LineBreak::CM), (0x8E3, 0x903, LineBreak::CM), (0x93A, 0x93C, LineBreak::CM),
+    (0x93E, 0x94F, LineBreak::CM), (0x951, 0x957, LineBreak::CM), (0x962, 0x963, LineBreak::CM),
+    (0x964, 0x965, LineBreak::BA), (0x966, 0x96F, LineBreak::NU), (0x981, 0x983, LineBreak::CM),
+    (0x9BC, 0x9BC, LineBreak::CM), (0x9BE, 0x9C4, LineBreak::CM), (0x9C7, 0x9C8, LineBreak::CM),
+    (0x9CB, 0x9CD, LineBreak::CM), (0x9D7, 0x9D7, LineBreak::CM), (0x9E2, 0x9E3, LineBreak::CM),
+    (0x9E6, 0x9EF, LineBreak::NU), (0x9F2, 0x9F3, LineBreak::PO), (0x9F9, 0x9F9, LineBreak::PO),
+    (0x9FB, 0x9FB, LineBreak::PR), (0x9FE, 0x9FE, LineBreak::CM), (0xA01, 0xA03, LineBreak::CM),
+    (0xA3C, 0xA3C, LineBreak::CM), (0xA3E, 0xA42, LineBreak::CM), (0xA47, 0xA48, LineBreak::CM),
+    (0xA4B, 0xA4D, LineBreak::CM), (0xA51, 0xA51, LineBreak::CM), (0xA66, 0xA6F, LineBreak::NU),
+    (0xA70, 0xA71, LineBreak::CM), (0xA75, 0xA75, LineBreak::CM), (0xA81, 0xA83, LineBreak::CM),
+    (0xABC, 0xABC, LineBreak::CM), (0xABE, 0xAC5, LineBreak::CM), (0xAC7, 0xAC9, LineBreak::CM),
+    (0xACB, 0xACD, LineBreak::CM), (0xAE2, 0xAE3, LineBreak::CM), (0xAE6, 0xAEF, LineBreak::NU),
+    (0xAF1, 0xAF1, LineBreak::PR), (0xAFA, 0xAFF, LineBreak::CM), (0xB01, 0xB03, LineBreak::CM),
+    (0xB3C, 0xB3C, LineBreak::CM), (0xB3E, 0xB44, LineBreak::CM), (0xB47, 0xB48, LineBreak::CM),
+    (0xB4B, 0xB4D, LineBreak::CM), (0xB55, 0xB57, LineBreak::CM), (0xB62, 0xB63, LineBreak::CM),
+    (0xB66, 0xB6F, LineBreak::NU), (0xB82, 0xB82, LineBreak::CM), (0xBBE, 0xBC2, LineBreak::CM),
+    (0xBC6, 0xBC8, LineBreak::CM), (0xBCA, 0xBCD, LineBreak::CM), (0xBD7, 0xBD7, LineBreak::CM),
+    (0xBE6, 0xBEF, LineBreak::NU), (0xBF9, 0xBF9, LineBreak::PR), (0xC00, 0xC04, LineBreak::CM),
+    (0xC3C, 0xC3C, LineBreak::CM), (0xC3E, 0xC44, LineBreak::CM), (0xC46, 0xC48, LineBreak::CM),
+    (0xC4A, 0xC4D, LineBreak::CM), (0xC55, 0xC56, LineBreak::CM), (0xC62, 0xC63, LineBreak::CM),
+    (0xC66, 0xC6F, LineBreak::NU), (0xC77, 0xC77, LineBreak::BB), (0xC81, 0xC83, LineBreak::CM),
+    (0xC84, 0xC84, LineBreak::BB), (0xCBC, 0xCBC, LineBreak::CM), (0xCBE, 0xCC4, LineBreak::CM),
+    (0xCC6, 0xCC8, LineBreak::CM), (0xCCA, 0xCCD, LineBreak::CM), (0xCD5, 0xCD6, LineBreak::CM),
+    (0xCE2, 0xCE3, LineBreak::CM), (0xCE6, 0xCEF, LineBreak::NU), (0xD00, 0xD03, LineBreak::CM),
+    (0xD3B, 0xD3C, LineBreak::CM), (0xD3E, 0xD44, LineBreak::CM), (0xD46, 0xD48, LineBreak::CM),
+    (0xD4A, 0xD4D, LineBreak::CM), (0xD57, 0xD57, LineBreak::CM), (0xD62, 0xD63, LineBreak::CM),
+    (0xD66, 0xD6F, LineBreak::NU), (0xD79, 0xD79, LineBreak::PO), (0xD81, 0xD83, LineBreak::CM),
+    (0xDCA, 0xDCA, LineBreak::CM), (0xDCF, 0xDD4, LineBreak::CM), (0xDD6, 0xDD6, LineBreak::CM),
+    (0xDD8, 0xDDF, LineBreak::CM), (0xDE6, 0xDEF, LineBreak::NU), (0xDF2, 0xDF3, LineBreak::CM),
+    (0xE31, 0xE31, LineBreak::CM), (0xE34, 0xE3A, LineBreak::CM), (0xE3F, 0xE3F, LineBreak::PR),
+    (0xE47, 0xE4E, LineBreak::CM), (0xE50, 0xE59, LineBreak::NU), (0xE5A, 0xE5B, LineBreak::BA),
+    (0xEB1, 0xEB1, LineBreak::CM), (0xEB4, 0xEBC, LineBreak::CM), (0xEC8, 0xECD, LineBreak::CM),
+    (0xED0, 0xED9, LineBreak::NU), (0xF01, 0xF04, LineBreak::BB), (0xF06, 0xF07, LineBreak::BB),
+    (0xF08, 0xF08, LineBreak::GL), (0xF09, 0xF0A, LineBreak::BB), (0xF0B, 0xF0B, LineBreak::BA),
+    (0xF0C, 0xF0C, LineBreak::GL), (0xF0D, 0xF11, LineBreak::EX), (0xF12, 0xF12, LineBreak::GL),
+    (0xF14, 0xF14, LineBreak::EX), (0xF18, 0xF19, LineBreak::CM), (0xF20, 0xF29, LineBreak::NU),
+    (0xF34, 0xF34, LineBreak::BA), (0xF35, 0xF35, LineBreak::CM), (0xF37, 0xF37, LineBreak::CM),
+    (0xF39, 0xF39, LineBreak::CM), (0xF3A, 0xF3A, LineBreak::OP), (0xF3B, 0xF3B, LineBreak::CL),
+    (0xF3C, 0xF3C, LineBreak::OP), (0xF3D, 0xF3D, LineBreak::CL), (0xF3E, 0xF3F, LineBreak::CM),
+    (0xF71, 0xF7E, LineBreak::CM), (0xF7F, 0xF7F, LineBreak::BA), (0xF80, 0xF84, LineBreak::CM),
+    (0xF85, 0xF85, LineBreak::BA), (0xF86, 0xF87, LineBreak::CM), (0xF8D, 0xF97, LineBreak::CM),
+    (0xF99, 0xFBC, LineBreak::CM), (0xFBE, 0xFBF, LineBreak::BA), (0xFC6, 0xFC6, LineBreak::CM),
+    (0xFD0, 0xFD1, LineBreak::BB), (0xFD2, 0xFD2, LineBreak::BA), (0xFD3, 0xFD3, LineBreak::BB),
+    (0xFD9, 0xFDA, LineBreak::GL), (0x102B, 0x103E, LineBreak::CM), (0x1040, 0x1049, LineBreak::NU),
+    (0x104A, 0x104B, LineBreak::BA), (0x1056, 0x1059, LineBreak::CM),
+    (0x105E, 0x1060, LineBreak::CM), (0x1062, 0x1064, LineBreak::CM),
+    (0x1067, 0x106D, LineBreak::CM), (0x1071, 0x1074, LineBreak::CM),
+    (0x1082, 0x108D, LineBreak::CM), (0x108F, 0x108F, LineBreak::CM),
+    (0x1090, 0x1099, LineBreak::NU), (0x109A, 0x109D, LineBreak::CM),
+    (0x1100, 0x115F, LineBreak::JL), (0x1160, 0x11A7, LineBreak::JV),
+    (0x11A8, 0x11FF, LineBreak::JT), (0x135D, 0x135F, LineBreak::CM),
+    (0x1361, 0x1361, LineBreak::BA), (0x1400, 0x1400, LineBreak::BA),
+    (0x1680, 0x1680, LineBreak::BA), (0x169B, 0x169B, LineBreak::OP),
+    (0x169C, 0x169C, LineBreak::CL), (0x16EB, 0x16ED, LineBreak::BA),
+    (0x1712, 0x1715, LineBreak::CM), (0x1732, 0x1734, LineBreak::CM),
+    (0x1735, 0x1736, LineBreak::BA), (0x1752, 0x1753, LineBreak::CM),
+    (0x1772, 0x1773, LineBreak::CM), (0x17B4, 0x17D3, LineBreak::CM),
+    (0x17D4, 0x17D5, LineBreak::BA), (0x17D6, 0x17D6, LineBreak::NS),
+    (0x17D8, 0x17D8, LineBreak::BA), (0x17DA, 0x17DA, LineBreak::BA),
+    (0x17DB, 0x17DB, LineBreak::PR), (0x17DD, 0x17DD, LineBreak::CM),
+    (0x17E0, 0x17E9, LineBreak::NU), (0x1802, 0x1803, LineBreak::EX),
+    (0x1804, 0x1805, LineBreak::BA), (0x1806, 0x1806, LineBreak::BB),
+    (0x1808, 0x1809, LineBreak::EX), (0x180B, 0x180D, LineBreak::CM),
+    (0x180E, 0x180E, LineBreak::GL), (0x180F, 0x180F, LineBreak::CM),
+    (0x1810, 0x1819, LineBreak::NU), (0x1885, 0x1886, LineBreak::CM),
+    (0x18A9, 0x18A9, LineBreak::CM), (0x1920, 0x192B, LineBreak::CM),
+    (0x1930, 0x193B, LineBreak::CM), (0x1944, 0x1945, LineBreak::EX),
+    (0x1946, 0x194F, LineBreak::NU), (0x19D0, 0x19D9, LineBreak::NU),
+    (0x1A17, 0x1A1B, LineBreak::CM), (0x1A55, 0x1A5E, LineBreak::CM),
+    (0x1A60, 0x1A7C, LineBreak::CM), (0x1A7F, 0x1A7F, LineBreak::CM),
+    (0x1A80, 0x1A89, LineBreak::NU), (0x1A90, 0x1A99, LineBreak::NU),
+    (0x1AB0, 0x1ACE, LineBreak::CM), (0x1B00, 0x1B04, LineBreak::CM),
+    (0x1B34, 0x1B44, LineBreak::CM), (0x1B50, 0x1B59, LineBreak::NU),
+    (0x1B5A, 0x1B5B, LineBreak::BA), (0x1B5D, 0x1B60, LineBreak::BA),
+    (0x1B6B, 0x1B73, LineBreak::CM), (0x1B7D, 0x1B7E, LineBreak::BA),
+    (0x1B80, 0x1B82, LineBreak::CM), (0x1BA1, 0x1BAD, LineBreak::CM),
+    (0x1BB0, 0x1BB9, LineBreak::NU), (0x1BE6, 0x1BF3, LineBreak::CM),
+    (0x1C24, 0x1C37, LineBreak::CM), (0x1C3B, 0x1C3F, LineBreak::BA),
+    (0x1C40, 0x1C49, LineBreak::NU), (0x1C50, 0x1C59, LineBreak::NU),
+    (0x1C7E, 0x1C7F, LineBreak::BA), (0x1CD0, 0x1CD2, LineBreak::CM),
+    (0x1CD4, 0x1CE8, LineBreak::CM), (0x1CED, 0x1CED, LineBreak::CM),
+    (0x1CF4, 0x1CF4, LineBreak::CM), (0x1CF7, 0x1CF9, LineBreak::CM),
+    (0x1DC0, 0x1DFF, LineBreak::CM), (0x1FFD, 0x1FFD, LineBreak::BB),
+    (0x2000, 0x2006, LineBreak::BA), (0x2007, 0x2007, LineBreak::GL),
+    (0x2008, 0x200A, LineBreak::BA), (0x200B, 0x200B, LineBreak::ZW),
+    (0x200C, 0x200C, LineBreak::CM), (0x200D, 0x200D, LineBreak::ZWJ),
+    (0x200E, 0x200F, LineBreak::CM), (0x2010, 0x2010, LineBreak::BA),
+    (0x2011, 0x2011, LineBreak::GL), (0x2012, 0x2013, LineBreak::BA),
+    (0x2014, 0x2014, LineBreak::B2), (0x2018, 0x2019, LineBreak::QU),
+    (0x201A, 0x201A, LineBreak::OP), (0x201B, 0x201D, LineBreak::QU),
+    (0x201E, 0x201E, LineBreak::OP), (0x201F, 0x201F, LineBreak::QU),
+    (0x2024, 0x2026, LineBreak::IN), (0x2027, 0x2027, LineBreak::BA),
+    (0x2028, 0x2029, LineBreak::BK), (0x202A, 0x202E, LineBreak::CM),
+    (0x202F, 0x202F, LineBreak::GL), (0x2030, 0x2037, LineBreak::PO),
+    (0x2039, 0x203A, LineBreak::QU), (0x203C, 0x203D, LineBreak::NS),
+    (0x2044, 0x2044, LineBreak::IS), (0x2045, 0x2045, LineBreak::OP),
+    (0x2046, 0x2046, LineBreak::CL), (0x2047, 0x2049, LineBreak::NS),
+    (0x2056, 0x2056, LineBreak::BA), (0x2058, 0x205B, LineBreak::BA),
+    (0x205D, 0x205F, LineBreak::BA), (0x2060, 0x2060, LineBreak::WJ),
+    (0x2066, 0x206F, LineBreak::CM), (0x207D, 0x207D, LineBreak::OP),
+    (0x207E, 0x207E, LineBreak::CL), (0x208D, 0x208D, LineBreak::OP),
+    (0x208E, 0x208E, LineBreak::CL), (0x20A0, 0x20A6, LineBreak::PR),
+    (0x20A7, 0x20A7, LineBreak::PO), (0x20A8, 0x20B5, LineBreak::PR),
+    (0x20B6, 0x20B6, LineBreak::PO), (0x20B7, 0x20BA, LineBreak::PR),
+    (0x20BB, 0x20BB, LineBreak::PO), (0x20BC, 0x20BD, LineBreak::PR),
+    (0x20BE, 0x20BE, LineBreak::PO), (0x20BF, 0x20BF, LineBreak::PR),
+    (0x20C0, 0x20C0, LineBreak::PO), (0x20C1, 0x20CF, LineBreak::PR),
+    (0x20D0, 0x20F0, LineBreak::CM), (0x2103, 0x2103, LineBreak::PO),
+    (0x2109, 0x2109, LineBreak::PO), (0x2116, 0x2116, LineBreak::PR),
+    (0x2212, 0x2213, LineBreak::PR), (0x22EF, 0x22EF, LineBreak::IN),
+    (0x2308, 0x2308, LineBreak::OP), (0x2309, 0x2309, LineBreak::CL),
+    (0x230A, 0x230A, LineBreak::OP), (0x230B, 0x230B, LineBreak::CL),
+    (0x231A, 0x231B, LineBreak::ID), (0x2329, 0x2329, LineBreak::OP),
+    (0x232A, 0x232A, LineBreak::CL), (0x23F0, 0x23F3, LineBreak::ID),
+    (0x2600, 0x2603, LineBreak::ID), (0x2614, 0x2615, LineBreak::ID),
+    (0x2618, 0x2618, LineBreak::ID), (0x261A, 0x261C, LineBreak::ID),
+    (0x261D, 0x261D, LineBreak::EB), (0x261E, 0x261F, LineBreak::ID),
+    (0x2639, 0x263B, LineBreak::ID), (0x2668, 0x2668, LineBreak::ID),
+    (0x267F, 0x267F, LineBreak::ID), (0x26BD, 0x26C8, LineBreak::ID),
+    (0x26CD, 0x26CD, LineBreak::ID), (0x26CF, 0x26D1, LineBreak::ID),
+    (0x26D3, 0x26D4, LineBreak::ID), (0x26D8, 0x26D9, LineBreak::ID),
+    (0x26DC, 0x26DC, LineBreak::ID), (0x26DF, 0x26E1, LineBreak::ID),
+    (0x26EA, 0x26EA, LineBreak::ID), (0x26F1, 0x26F5, LineBreak::ID),
+    (0x26F7, 0x26F8, LineBreak::ID), (0x26F9, 0x26F9, LineBreak::EB),
+    (0x26FA, 0x26FA, LineBreak::ID), (0x26FD, 0x2704, LineBreak::ID),
+    (0x2708, 0x2709, LineBreak::ID), (0x270A, 0x270D, LineBreak::EB),
+    (0x275B, 0x2760, LineBreak::QU), (0x2762, 0x2763, LineBreak::EX),
+    (0x2764, 0x2764, LineBreak::ID), (0x2768, 0x2768, LineBreak::OP),
+    (0x2769, 0x2769, LineBreak::CL), (0x276A, 0x276A, LineBreak::OP),
+    (0x276B, 0x276B, LineBreak::CL), (0x276C, 0x276C, LineBreak::OP),
+    (0x276D, 0x276D, LineBreak::CL), (0x276E, 0x276E, LineBreak::OP),
+    (0x276F, 0x276F, LineBreak::CL), (0x2770, 0x2770, LineBreak::OP),
+    (0x2771, 0x2771, LineBreak::CL), (0x2772, 0x2772, LineBreak::OP),
+    (0x2773, 0x2773, LineBreak::CL), (0x2774, 0x2774, LineBreak::OP),
+    (0x2775, 0x2775, LineBreak::CL), (0x27C5, 0x27C5, LineBreak::OP),
+    (0x27C6, 0x27C6, LineBreak::CL), (0x27E6, 0x27E6, LineBreak::OP),
+    (0x27E7, 0x27E7, LineBreak::CL), (0x27E8, 0x27E8, LineBreak::OP),
+    (0x27E9, 0x27E9, LineBreak::CL), (0x27EA, 0x27EA, LineBreak::OP),
+    (0x27EB, 0x27EB, LineBreak::CL), (0x27EC, 0x27EC, LineBreak::OP),
+    (0x27ED, 0x27ED, LineBreak::CL), (0x27EE, 0x27EE, LineBreak::OP),
+    (0x27EF, 0x27EF, LineBreak::CL), (0x2983, 0x2983, LineBreak::OP),
+    (0x2984, 0x2984, LineBreak::CL), (0x2985, 0x2985, LineBreak::OP),
+    (0x2986, 0x2986, LineBreak::CL), (0x2987, 0x2987, LineBreak::OP),
+    (0x2988, 0x2988, LineBreak::CL), (0x2989, 0x2989, LineBreak::OP),
+    (0x298A, 0x298A, LineBreak::CL), (0x298B, 0x298B, LineBreak::OP),
+    (0x298C, 0x298C, LineBreak::CL), (0x298D, 0x298D, LineBreak::OP),
+    (0x298E, 0x298E, LineBreak::CL), (0x298F, 0x298F, LineBreak::OP),
+    (0x2990, 0x2990, LineBreak::CL), (0x2991, 0x2991, LineBreak::OP),
+    (0x2992, 0x2992, LineBreak::CL), (0x2993, 0x2993, LineBreak::OP),
+    (0x2994, 0x2994, LineBreak::CL), (0x2995, 0x2995, LineBreak::OP),
+    (0x2996, 0x2996, LineBreak::CL), (0x2997, 0x2997, LineBreak::OP),
+    (0x2998, 0x2998, LineBreak::CL), (0x29D8, 0x29D8, LineBreak::OP),
+    (0x29D9, 0x29D9, LineBreak::CL), (0x29DA, 0x29DA, LineBreak::OP),
+    (0x29DB, 0x29DB, LineBreak::CL), (0x29FC, 0x29FC, LineBreak::OP),
+    (0x29FD, 0x29FD, LineBreak::CL), (0x2CEF, 0x2CF1, LineBreak::CM),
+    (0x2CF9, 0x2CF9, LineBreak::EX), (0x2CFA, 0x2CFC, LineBreak::BA),
+    (0x2CFE, 0x2CFE, LineBreak::EX), (0x2CFF, 0x2CFF, LineBreak::BA),
+    (0x2D70, 0x2D70, LineBreak::BA), (0x2D7F, 0x2D7F, LineBreak::CM),
+    (0x2DE0, 0x2DFF, LineBreak::CM), (0x2E00, 0x2E0D, LineBreak::QU),
+    (0x2E0E, 0x2E15, LineBreak::BA), (0x2E17, 0x2E17, LineBreak::BA),
+    (0x2E18, 0x2E18, LineBreak::OP), (0x2E19, 0x2E19, LineBreak::BA),
+    (0x2E1C, 0x2E1D, LineBreak::QU), (0x2E20, 0x2E21, LineBreak::QU),
+    (0x2E22, 0x2E22, LineBreak::OP), (0x2E23, 0x2E23, LineBreak::CL),
+    (0x2E24, 0x2E24, LineBreak::OP), (0x2E25, 0x2E25, LineBreak::CL),
+    (0x2E26, 0x2E26, LineBreak::OP), (0x2E27, 0x2E27, LineBreak::CL),
+    (0x2E28, 0x2E28, LineBreak::OP), (0x2E29, 0x2E29, LineBreak::CL),
+    (0x2E2A, 0x2E2D, LineBreak::BA), (0x2E2E, 0x2E2E, LineBreak::EX),
+    (0x2E30, 0x2E31, LineBreak::BA), (0x2E33, 0x2E34, LineBreak::BA),
+    (0x2E3A, 0x2E3B, LineBreak::B2), (0x2E3C, 0x2E3E, LineBreak::BA),
+    (0x2E40, 0x2E41, LineBreak::BA), (0x2E42, 0x2E42, LineBreak::OP),
+    (0x2E43, 0x2E4A, LineBreak::BA), (0x2E4C, 0x2E4C, LineBreak::BA),
+    (0x2E4E, 0x2E4F, LineBreak::BA), (0x2E53, 0x2E54, LineBreak::EX),
+    (0x2E55, 0x2E55, LineBreak::OP), (0x2E56, 0x2E56, LineBreak::CL),
+    (0x2E57, 0x2E57, LineBreak::OP), (0x2E58, 0x2E58, LineBreak::CL),
+    (0x2E59, 0x2E59, LineBreak::OP), (0x2E5A, 0x2E5A, LineBreak::CL),
+    (0x2E5B, 0x2E5B, LineBreak::OP), (0x2E5C, 0x2E5C, LineBreak::CL),
+    (0x2E5D, 0x2E5D, LineBreak::BA), (0x2E80, 0x2E99, LineBreak::ID),
+    (0x2E9B, 0x2EF3, LineBreak::ID), (0x2F00, 0x2FD5, LineBreak::ID),
+    (0x2FF0, 0x2FFB, LineBreak::ID), (0x3000, 0x3000, LineBreak::BA),
+    (0x3001, 0x3002, LineBreak::CL), (0x3003, 0x3004, LineBreak::ID),
+    (0x3005, 0x3005, LineBreak::NS), (0x3006, 0x3007, LineBreak::ID),
+    (0x3008, 0x3008, LineBreak::OP), (0x3009, 0x3009, LineBreak::CL),
+    (0x300A, 0x300A, LineBreak::OP), (0x300B, 0x300B, LineBreak::CL),
+    (0x300C, 0x300C, LineBreak::OP), (0x300D, 0x300D, LineBreak::CL),
+    (0x300E, 0x300E, LineBreak::OP), (0x300F, 0x300F, LineBreak::CL),
+    (0x3010, 0x3010, LineBreak::OP), (0x3011, 0x3011, LineBreak::CL),
+    (0x3012, 0x3013, LineBreak::ID), (0x3014, 0x3014, LineBreak::OP),
+    (0x3015, 0x3015, LineBreak::CL), (0x3016, 0x3016, LineBreak::OP),
+    (0x3017, 0x3017, LineBreak::CL), (0x3018, 0x3018, LineBreak::OP),
+    (0x3019, 0x3019, LineBreak::CL), (0x301A, 0x301A, LineBreak::OP),
+    (0x301B, 0x301B, LineBreak::CL), (0x301C, 0x301C, LineBreak::NS),
+    (0x301D, 0x301D, LineBreak::OP), (0x301E, 0x301F, LineBreak::CL),
+    (0x3020, 0x3029, LineBreak::ID), (0x302A, 0x302F, LineBreak::CM),
+    (0x3030, 0x3034, LineBreak::ID), (0x3035, 0x3035, LineBreak::CM),
+    (0x3036, 0x303A, LineBreak::ID), (0x303B, 0x303C, LineBreak::NS),
+    (0x303D, 0x303F, LineBreak::ID), (0x3041, 0x3041, LineBreak::NS),
+    (0x3042, 0x3042, LineBreak::ID), (0x3043, 0x3043, LineBreak::NS),
+    (0x3044, 0x3044, LineBreak::ID), (0x3045, 0x3045, LineBreak::NS),
+    (0x3046, 0x3046, LineBreak::ID), (0x3047, 0x3047, LineBreak::NS),
+    (0x3048, 0x3048, LineBreak::ID), (0x3049, 0x3049, LineBreak::NS),
+    (0x304A, 0x3062, LineBreak::ID), (0x3063, 0x3063, LineBreak::NS),
+    (0x3064, 0x3082, LineBreak::ID), (0x3083, 0x3083, LineBreak::NS),
+    (0x3084, 0x3084, LineBreak::ID), (0x3085, 0x3085, LineBreak::NS),
+    (0x3086, 0x3086, LineBreak::ID), (0x3087, 0x3087, LineBreak::NS),
+    (0x3088, 0x308D, LineBreak::ID), (0x308E, 0x308E, LineBreak::NS),
+    (0x308F, 0x3094, LineBreak::ID), (0x3095, 0x3096, LineBreak::NS),
+    (0x3099, 0x309A, LineBreak::CM), (0x309B, 0x309E, LineBreak::NS),
+    (0x309F, 0x309F, LineBreak::ID), (0x30A0, 0x30A1, LineBreak::NS),
+    (0x30A2, 0x30A2, LineBreak::ID), (0x30A3, 0x30A3, LineBreak::NS),
+    (0x30A4, 0x30A4, LineBreak::ID), (0x30A5, 0x30A5, LineBreak::NS),
+    (0x30A6, 0x30A6, LineBreak::ID), (0x30A7, 0x30A7, LineBreak::NS),
+    (0x30A8, 0x30A8, LineBreak::ID), (0x30A9, 0x30A9, LineBreak::NS),
+    (0x30AA, 0x30C2, LineBreak::ID), (0x30C3, 0x30C3, LineBreak::NS),
+    (0x30C4, 0x30E2, LineBreak::ID), (0x30E3, 0x30E3, LineBreak::NS),
+    (0x30E4, 0x30E4, LineBreak::ID), (0x30E5, 0x30E5, LineBreak::NS),
+    (0x30E6, 0x30E6, LineBreak::ID), (0x30E7, 0x30E7, LineBreak::NS),
+    (0x30E8, 0x30ED, LineBreak::ID), (0x30EE, 0x30EE, LineBreak::NS),
+    (0x30EF, 0x30F4, LineBreak::ID), (0x30F5, 0x30F6, LineBreak::NS),
+    (0x30F7, 0x30FA, LineBreak::ID), (0x30FB, 0x30FE, LineBreak::NS),
+    (0x30FF, 0x30FF, LineBreak::ID), (0x3105, 0x312F, LineBreak::ID),
+    (0x3131, 0x318E, LineBreak::ID), (0x3190, 0x31E3, LineBreak::ID),
+    (0x31F0, 0x31FF, LineBreak::NS), (0x3200, 0x321E, LineBreak::ID),
+    (0x3220, 0x3247, LineBreak::ID), (0x3250, 0x4DBF, LineBreak::ID),
+    (0x4E00, 0xA014, LineBreak::ID), (0xA015, 0xA015, LineBreak::NS),
+    (0xA016, 0xA48C, LineBreak::ID), (0xA490, 0xA4C6, LineBreak::ID),
+    (0xA4FE, 0xA4FF, LineBreak::BA), (0xA60D, 0xA60D, LineBreak::BA),
+    (0xA60E, 0xA60E, LineBreak::EX), (0xA60F, 0xA60F, LineBreak::BA),
+    (0xA620, 0xA629, LineBreak::NU), (0xA66F, 0xA672, LineBreak::CM),
+    (0xA674, 0xA67D, LineBreak::CM), (0xA69E, 0xA69F, LineBreak::CM),
+    (0xA6F0, 0xA6F1, LineBreak::CM), (0xA6F3, 0xA6F7, LineBreak::BA),
+    (0xA802, 0xA802, LineBreak::CM), (0xA806, 0xA806, LineBreak::CM),
+    (0xA80B, 0xA80B, LineBreak::CM), (0xA823, 0xA827, LineBreak::CM),
+    (0xA82C, 0xA82C, LineBreak::CM), (0xA838, 0xA838, LineBreak::PO),
+    (0xA874, 0xA875, LineBreak::BB), (0xA876, 0xA877, LineBreak::EX),
+    (0xA880, 0xA881, LineBreak::CM), (0xA8B4, 0xA8C5, LineBreak::CM),
+    (0xA8CE, 0xA8CF, LineBreak::BA), (0xA8D0, 0xA8D9, LineBreak::NU),
+    (0xA8E0, 0xA8F1, LineBreak::CM), (0xA8FC, 0xA8FC, LineBreak::BB),
+    (0xA8FF, 0xA8FF, LineBreak::CM), (0xA900, 0xA909, LineBreak::NU),
+    (0xA926, 0xA92D, LineBreak::CM), (0xA92E, 0xA92F, LineBreak::BA),
+    (0xA947, 0xA953, LineBreak::CM), (0xA960, 0xA97C, LineBreak::JL),
+    (0xA980, 0xA983, LineBreak::CM), (0xA9B3, 0xA9C0, LineBreak::CM),
+    (0xA9C7, 0xA9C9, LineBreak::BA), (0xA9D0, 0xA9D9, LineBreak::NU),
+    (0xA9E5, 0xA9E5, LineBreak::CM), (0xA9F0, 0xA9F9, LineBreak::NU),
+    (0xAA29, 0xAA36, LineBreak::CM), (0xAA43, 0xAA43, LineBreak::CM),
+    (0xAA4C, 0xAA4D, LineBreak::CM), (0xAA50, 0xAA59, LineBreak::NU),
+    (0xAA5D, 0xAA5F, LineBreak::BA), (0xAA7B, 0xAA7D, LineBreak::CM),
+    (0xAAB0, 0xAAB0, LineBreak::CM), (0xAAB2, 0xAAB4, LineBreak::CM),
+    (0xAAB7, 0xAAB8, LineBreak::CM), (0xAABE, 0xAABF, LineBreak::CM),
+    (0xAAC1, 0xAAC1, LineBreak::CM), (0xAAEB, 0xAAEF, LineBreak::CM),
+    (0xAAF0, 0xAAF1, LineBreak::BA), (0xAAF5, 0xAAF6, LineBreak::CM),
+    (0xABE3, 0xABEA, LineBreak::CM), (0xABEB, 0xABEB, LineBreak::BA),
+    (0xABEC, 0xABED, LineBreak::CM), (0xABF0, 0xABF9, LineBreak::NU),
+    (0xD7B0, 0xD7C6, LineBreak::JV), (0xD7CB, 0xD7FB, LineBreak::JT),
+    (0xF900, 0xFAFF, LineBreak::ID), (0xFB1D, 0xFB1D, LineBreak::HL),
+    (0xFB1E, 0xFB1E, LineBreak::CM), (0xFB1F, 0xFB28, LineBreak::HL),
+    (0xFB2A, 0xFB36, LineBreak::HL), (0xFB38, 0xFB3C, LineBreak::HL),
+    (0xFB3E, 0xFB3E, LineBreak::HL), (0xFB40, 0xFB41, LineBreak::HL),
+    (0xFB43, 0xFB44, LineBreak::HL), (0xFB46, 0xFB4F, LineBreak::HL),
+    (0xFD3E, 0xFD3E, LineBreak::CL), (0xFD3F, 0xFD3F, LineBreak::OP),
+    (0xFDFC, 0xFDFC, LineBreak::PO), (0xFE00, 0xFE0F, LineBreak::CM),
+    (0xFE10, 0xFE10, LineBreak::IS), (0xFE11, 0xFE12, LineBreak::CL),
+    (0xFE13, 0xFE14, LineBreak::IS), (0xFE15, 0xFE16, LineBreak::EX),
+    (0xFE17, 0xFE17, LineBreak::OP), (0xFE18, 0xFE18, LineBreak::CL),
+    (0xFE19, 0xFE19, LineBreak::IN), (0xFE20, 0xFE2F, LineBreak::CM),
+    (0xFE30, 0xFE34, LineBreak::ID), (0xFE35, 0xFE35, LineBreak::OP),
+    (0xFE36, 0xFE36, LineBreak::CL), (0xFE37, 0xFE37, LineBreak::OP),
+    (0xFE38, 0xFE38, LineBreak::CL), (0xFE39, 0xFE39, LineBreak::OP),
+    (0xFE3A, 0xFE3A, LineBreak::CL), (0xFE3B, 0xFE3B, LineBreak::OP),
+    (0xFE3C, 0xFE3C, LineBreak::CL), (0xFE3D, 0xFE3D, LineBreak::OP),
+    (0xFE3E, 0xFE3E, LineBreak::CL), (0xFE3F, 0xFE3F, LineBreak::OP),
+    (0xFE40, 0xFE40, LineBreak::CL), (0xFE41, 0xFE41, LineBreak::OP),
+    (0xFE42, 0xFE42, LineBreak::CL), (0xFE43, 0xFE43, LineBreak::OP),
+    (0xFE44, 0xFE44, LineBreak::CL), (0xFE45, 0xFE46, LineBreak::ID),
+    (0xFE47, 0xFE47, LineBreak::OP), (0xFE48, 0xFE48, LineBreak::CL),
+    (0xFE49, 0xFE4F, LineBreak::ID), (0xFE50, 0xFE50, LineBreak::CL),
+    (0xFE51, 0xFE51, LineBreak::ID), (0xFE52, 0xFE52, LineBreak::CL),
+    (0xFE54, 0xFE55, LineBreak::NS), (0xFE56, 0xFE57, LineBreak::EX),
+    (0xFE58, 0xFE58, LineBreak::ID), (0xFE59, 0xFE59, LineBreak::OP),
+    (0xFE5A, 0xFE5A, LineBreak::CL), (0xFE5B, 0xFE5B, LineBreak::OP),
+    (0xFE5C, 0xFE5C, LineBreak::CL), (0xFE5D, 0xFE5D, LineBreak::OP),
+    (0xFE5E, 0xFE5E, LineBreak::CL), (0xFE5F, 0xFE66, LineBreak::ID),
+    (0xFE68, 0xFE68, LineBreak::ID), (0xFE69, 0xFE69, LineBreak::PR),
+    (0xFE6A, 0xFE6A, LineBreak::PO), (0xFE6B, 0xFE6B, LineBreak::ID),
+    (0xFEFF, 0xFEFF, LineBreak::WJ), (0xFF01, 0xFF01, LineBreak::EX),
+    (0xFF02, 0xFF03, LineBreak::ID), (0xFF04, 0xFF04, LineBreak::PR),
+    (0xFF05, 0xFF05, LineBreak::PO), (0xFF06, 0xFF07, LineBreak::ID),
+    (0xFF08, 0xFF08, LineBreak::OP), (0xFF09, 0xFF09, LineBreak::CL),
+    (0xFF0A, 0xFF0B, LineBreak::ID), (0xFF0C, 0xFF0C, LineBreak::CL),
+    (0xFF0D, 0xFF0D, LineBreak::ID), (0xFF0E, 0xFF0E, LineBreak::CL),
+    (0xFF0F, 0xFF19, LineBreak::ID), (0xFF1A, 0xFF1B, LineBreak::NS),
+    (0xFF1C, 0xFF1E, LineBreak::ID), (0xFF1F, 0xFF1F, LineBreak::EX),
+    (0xFF20, 0xFF3A, LineBreak::ID), (0xFF3B, 0xFF3B, LineBreak::OP),
+    (0xFF3C, 0xFF3C, LineBreak::ID), (0xFF3D, 0xFF3D, LineBreak::CL),
+    (0xFF3E, 0xFF5A, LineBreak::ID), (0xFF5B, 0xFF5B, LineBreak::OP),
+    (0xFF5C, 0xFF5C, LineBreak::ID), (0xFF5D, 0xFF5D, LineBreak::CL),
+    (0xFF5E, 0xFF5E, LineBreak::ID), (0xFF5F, 0xFF5F, LineBreak::OP),
+    (0xFF60, 0xFF61, LineBreak::CL), (0xFF62, 0xFF62, LineBreak::OP),
+    (0xFF63, 0xFF64, LineBreak::CL), (0xFF65, 0xFF65, LineBreak::NS),
+    (0xFF66, 0xFF66, LineBreak::ID), (0xFF67, 0xFF70, LineBreak::NS),
+    (0xFF71, 0xFF9D, LineBreak::ID), (0xFF9E, 0xFF9F, LineBreak::NS),
+    (0xFFA0, 0xFFBE, LineBreak::ID), (0xFFC2, 0xFFC7, LineBreak::ID),
+    (0xFFCA, 0xFFCF, LineBreak::ID), (0xFFD2, 0xFFD7, LineBreak::ID),
+    (0xFFDA, 0xFFDC, LineBreak::ID), (0xFFE0, 0xFFE0, LineBreak::PO),
+    (0xFFE1, 0xFFE1, LineBreak::PR), (0xFFE2, 0xFFE4, LineBreak::ID),
+    (0xFFE5, 0xFFE6, LineBreak::PR), (0xFFF9, 0xFFFB, LineBreak::CM),
+    (0xFFFC, 0xFFFC, LineBreak::CB), (0x10100, 0x10102, LineBreak::BA),
+    (0x101FD, 0x101FD, LineBreak::CM), (0x102E0, 0x102E0, LineBreak::CM),
+    (0x10376, 0x1037A, LineBreak::CM), (0x1039F, 0x1039F, LineBreak::BA),
+    (0x103D0, 0x103D0, LineBreak::BA), (0x104A0, 0x104A9, LineBreak::NU),
+    (0x10857, 0x10857, LineBreak::BA), (0x1091F, 0x1091F, LineBreak::BA),
+    (0x10A01, 0x10A03, LineBreak::CM), (0x10A05, 0x10A06, LineBreak::CM),
+    (0x10A0C, 0x10A0F, LineBreak::CM), (0x10A38, 0x10A3A, LineBreak::CM),
+    (0x10A3F, 0x10A3F, LineBreak::CM), (0x10A50, 0x10A57, LineBreak::BA),
+    (0x10AE5, 0x10AE6, LineBreak::CM), (0x10AF0, 0x10AF5, LineBreak::BA),
+    (0x10AF6, 0x10AF6, LineBreak::IN), (0x10B39, 0x10B3F, LineBreak::BA),
+    (0x10D24, 0x10D27, LineBreak::CM), (0x10D30, 0x10D39, LineBreak::NU),
+    (0x10EAB, 0x10EAC, LineBreak::CM), (0x10EAD, 0x10EAD, LineBreak::BA),
+    (0x10F46, 0x10F50, LineBreak::CM), (0x10F82, 0x10F85, LineBreak::CM),
+    (0x11000, 0x11002, LineBreak::CM), (0x11038, 0x11046, LineBreak::CM),
+    (0x11047, 0x11048, LineBreak::BA), (0x11066, 0x1106F, LineBreak::NU),
+    (0x11070, 0x11070, LineBreak::CM), (0x11073, 0x11074, LineBreak::CM),
+    (0x1107F, 0x11082, LineBreak::CM), (0x110B0, 0x110BA, LineBreak::CM),
+    (0x110BE, 0x110C1, LineBreak::BA), (0x110C2, 0x110C2, LineBreak::CM),
+    (0x110F0, 0x110F9, LineBreak::NU), (0x11100, 0x11102, LineBreak::CM),
+    (0x11127, 0x11134, LineBreak::CM), (0x11136, 0x1113F, LineBreak::NU),
+    (0x11140, 0x11143, LineBreak::BA), (0x11145, 0x11146, LineBreak::CM),
+    (0x11173, 0x11173, LineBreak::CM), (0x11175, 0x11175, LineBreak::BB),
+    (0x11180, 0x11182, LineBreak::CM), (0x111B3, 0x111C0, LineBreak::CM),
+    (0x111C5, 0x111C6, LineBreak::BA), (0x111C8, 0x111C8, LineBreak::BA),
+    (0x111C9, 0x111CC, LineBreak::CM), (0x111CE, 0x111CF, LineBreak::CM),
+    (0x111D0, 0x111D9, LineBreak::NU), (0x111DB, 0x111DB, LineBreak::BB),
+    (0x111DD, 0x111DF, LineBreak::BA), (0x1122C, 0x11237, LineBreak::CM),
+    (0x11238, 0x11239, LineBreak::BA), (0x1123B, 0x1123C, LineBreak::BA),
+    (0x1123E, 0x1123E, LineBreak::CM), (0x112A9, 0x112A9, LineBreak::BA),
+    (0x112DF, 0x112EA, LineBreak::CM), (0x112F0, 0x112F9, LineBreak::NU),
+    (0x11300, 0x11303, LineBreak::CM), (0x1133B, 0x1133C, LineBreak::CM),
+    (0x1133E, 0x11344, LineBreak::CM), (0x11347, 0x11348, LineBreak::CM),
+    (0x1134B, 0x1134D, LineBreak::CM), (0x11357, 0x11357, LineBreak::CM),
+    (0x11362, 0x11363, LineBreak::CM), (0x11366, 0x1136C, LineBreak::CM),
+    (0x11370, 0x11374, LineBreak::CM), (0x11435, 0x11446, LineBreak::CM),
+    (0x1144B, 0x1144E, LineBreak::BA), (0x11450, 0x11459, LineBreak::NU),
+    (0x1145A, 0x1145B, LineBreak::BA), (0x1145E, 0x1145E, LineBreak::CM),
+    (0x114B0, 0x114C3, LineBreak::CM), (0x114D0, 0x114D9, LineBreak::NU),
+    (0x115AF, 0x115B5, LineBreak::CM), (0x115B8, 0x115C0, LineBreak::CM),
+    (0x115C1, 0x115C1, LineBreak::BB), (0x115C2, 0x115C3, LineBreak::BA),
+    (0x115C4, 0x115C5, LineBreak::EX), (0x115C9, 0x115D7, LineBreak::BA),
+    (0x115DC, 0x115DD, LineBreak::CM), (0x11630, 0x11640, LineBreak::CM),
+    (0x11641, 0x11642, LineBreak::BA), (0x11650, 0x11659, LineBreak::NU),
+    (0x11660, 0x1166C, LineBreak::BB), (0x116AB, 0x116B7, LineBreak::CM),
+    (0x116C0, 0x116C9, LineBreak::NU), (0x1171D, 0x1172B, LineBreak::CM),
+    (0x11730, 0x11739, LineBreak::NU), (0x1173C, 0x1173E, LineBreak::BA),
+    (0x1182C, 0x1183A, LineBreak::CM), (0x118E0, 0x118E9, LineBreak::NU),
+    (0x11930, 0x11935, LineBreak::CM), (0x11937, 0x11938, LineBreak::CM),
+    (0x1193B, 0x1193E, LineBreak::CM), (0x11940, 0x11940, LineBreak::CM),
+    (0x11942, 0x11943, LineBreak::CM), (0x11944, 0x11946, LineBreak::BA),
+    (0x11950, 0x11959, LineBreak::NU), (0x119D1, 0x119D7, LineBreak::CM),
+    (0x119DA, 0x119E0, LineBreak::CM), (0x119E2, 0x119E2, LineBreak::BB),
+    (0x119E4, 0x119E4, LineBreak::CM), (0x11A01, 0x11A0A, LineBreak::CM),
+    (0x11A33, 0x11A39, LineBreak::CM), (0x11A3B, 0x11A3E, LineBreak::CM),
+    (0x11A3F, 0x11A3F, LineBreak::BB), (0x11A41, 0x11A44, LineBreak::BA),
+    (0x11A45, 0x11A45, LineBreak::BB), (0x11A47, 0x11A47, LineBreak::CM),
+    (0x11A51, 0x11A5B, LineBreak::CM), (0x11A8A, 0x11A99, LineBreak::CM),
+    (0x11A9A, 0x11A9C, LineBreak::BA), (0x11A9E, 0x11AA0, LineBreak::BB),
+    (0x11AA1, 0x11AA2, LineBreak::BA), (0x11C2F, 0x11C36, LineBreak::CM),
+    (0x11C38, 0x11C3F, LineBreak::CM), (0x11C41, 0x11C45, LineBreak::BA),
+    (0x11C50, 0x11C59, LineBreak::NU), (0x11C70, 0x11C70, LineBreak::BB),
+    (0x11C71, 0x11C71, LineBreak::EX), (0x11C92, 0x11CA7, LineBreak::CM),
+    (0x11CA9, 0x11CB6, LineBreak::CM), (0x11D31, 0x11D36, LineBreak::CM),
+    (0x11D3A, 0x11D3A, LineBreak::CM), (0x11D3C, 0x11D3D, LineBreak::CM),
+    (0x11D3F, 0x11D45, LineBreak::CM), (0x11D47, 0x11D47, LineBreak::CM),
+    (0x11D50, 0x11D59, LineBreak::NU), (0x11D8A, 0x11D8E, LineBreak::CM),
+    (0x11D90, 0x11D91, LineBreak::CM), (0x11D93, 0x11D97, LineBreak::CM),
+    (0x11DA0, 0x11DA9, LineBreak::NU), (0x11EF3, 0x11EF6, LineBreak::CM),
+    (0x11FDD, 0x11FE0, LineBreak::PO), (0x11FFF, 0x11FFF, LineBreak::BA),
+    (0x12470, 0x12474, LineBreak::BA), (0x13258, 0x1325A, LineBreak::OP),
+    (0x1325B, 0x1325D, LineBreak::CL), (0x13282, 0x13282, LineBreak::CL),
+    (0x13286, 0x13286, LineBreak::OP), (0x13287, 0x13287, LineBreak::CL),
+    (0x13288, 0x13288, LineBreak::OP), (0x13289, 0x13289, LineBreak::CL),
+    (0x13379, 0x13379, LineBreak::OP), (0x1337A, 0x1337B, LineBreak::CL),
+    (0x13430, 0x13436, LineBreak::GL), (0x13437, 0x13437, LineBreak::OP),
+    (0x13438, 0x13438, LineBreak::CL), (0x145CE, 0x145CE, LineBreak::OP),
+    (0x145CF, 0x145CF, LineBreak::CL), (0x16A60, 0x16A69, LineBreak::NU),
+    (0x16A6E, 0x16A6F, LineBreak::BA), (0x16AC0, 0x16AC9, LineBreak::NU),
+    (0x16AF0, 0x16AF4, LineBreak::CM), (0x16AF5, 0x16AF5, LineBreak::BA),
+    (0x16B30, 0x16B36, LineBreak::CM), (0x16B37, 0x16B39, LineBreak::BA),
+    (0x16B44, 0x16B44, LineBreak::BA), (0x16B50, 0x16B59, LineBreak::NU),
+    (0x16E97, 0x16E98, LineBreak::BA), (0x16F4F, 0x16F4F, LineBreak::CM),
+    (0x16F51, 0x16F87, LineBreak::CM), (0x16F8F, 0x16F92, LineBreak::CM),
+    (0x16FE0, 0x16FE3, LineBreak::NS), (0x16FE4, 0x16FE4, LineBreak::GL),
+    (0x16FF0, 0x16FF1, LineBreak::CM), (0x17000, 0x187F7, LineBreak::ID),
+    (0x18800, 0x18AFF, LineBreak::ID), (0x18D00, 0x18D08, LineBreak::ID),
+    (0x1B000, 0x1B122, LineBreak::ID), (0x1B150, 0x1B152, LineBreak::NS),
+    (0x1B164, 0x1B167, LineBreak::NS), (0x1B170, 0x1B2FB, LineBreak::ID),
+    (0x1BC9D, 0x1BC9E, LineBreak::CM), (0x1BC9F, 0x1BC9F, LineBreak::BA),
+    (0x1BCA0, 0x1BCA3, LineBreak::CM), (0x1CF00, 0x1CF2D, LineBreak::CM),
+    (0x1CF30, 0x1CF46, LineBreak::CM), (0x1D165, 0x1D169, LineBreak::CM),
+    (0x1D16D, 0x1D182, LineBreak::CM), (0x1D185, 0x1D18B, LineBreak::CM),
+    (0x1D1AA, 0x1D1AD, LineBreak::CM), (0x1D242, 0x1D244, LineBreak::CM),
+    (0x1D7CE, 0x1D7FF, LineBreak::NU), (0x1DA00, 0x1DA36, LineBreak::CM),
+    (0x1DA3B, 0x1DA6C, LineBreak::CM), (0x1DA75, 0x1DA75, LineBreak::CM),
+    (0x1DA84, 0x1DA84, LineBreak::CM), (0x1DA87, 0x1DA8A, LineBreak::BA),
+    (0x1DA9B, 0x1DA9F, LineBreak::CM), (0x1DAA1, 0x1DAAF, LineBreak::CM),
+    (0x1E000, 0x1E006, LineBreak::CM), (0x1E008, 0x1E018, LineBreak::CM),
+    (0x1E01B, 0x1E021, LineBreak::CM), (0x1E023, 0x1E024, LineBreak::CM),
+    (0x1E026, 0x1E02A, LineBreak::CM), (0x1E130, 0x1E136, LineBreak::CM),
+    (0x1E140, 0x1E149, LineBreak::NU), (0x1E2AE, 0x1E2AE, LineBreak::CM),
+    (0x1E2EC, 0x1E2EF, LineBreak::CM), (0x1E2F0, 0x1E2F9, LineBreak::NU),
+    (0x1E2FF, 0x1E2FF, LineBreak::PR), (0x1E8D0, 0x1E8D6, LineBreak::CM),
+    (0x1E944, 0x1E94A, LineBreak::CM), (0x1E950, 0x1E959, LineBreak::NU),
+    (0x1E95E, 0x1E95F, LineBreak::OP), (0x1ECAC, 0x1ECAC, LineBreak::PO),
+    (0x1ECB0, 0x1ECB0, LineBreak::PO), (0x1F000, 0x1F0FF, LineBreak::ID),
+    (0x1F10D, 0x1F10F, LineBreak::ID), (0x1F16D, 0x1F16F, LineBreak::ID),
+    (0x1F1AD, 0x1F1E5, LineBreak::ID), (0x1F1E6, 0x1F1FF, LineBreak::RI),
+    (0x1F200, 0x1F384, LineBreak::ID), (0x1F385, 0x1F385, LineBreak::EB),
+    (0x1F386, 0x1F39B, LineBreak::ID), (0x1F39E, 0x1F3B4, LineBreak::ID),
+    (0x1F3B7, 0x1F3BB, LineBreak::ID), (0x1F3BD, 0x1F3C1, LineBreak::ID),
+    (0x1F3C2, 0x1F3C4, LineBreak::EB), (0x1F3C5, 0x1F3C6, LineBreak::ID),
+    (0x1F3C7, 0x1F3C7, LineBreak::EB), (0x1F3C8, 0x1F3C9, LineBreak::ID),
+    (0x1F3CA, 0x1F3CC, LineBreak::EB), (0x1F3CD, 0x1F3FA, LineBreak::ID),
+    (0x1F3FB, 0x1F3FF, LineBreak::EM), (0x1F400, 0x1F441, LineBreak::ID),
+    (0x1F442, 0x1F443, LineBreak::EB), (0x1F444, 0x1F445, LineBreak::ID),
+    (0x1F446, 0x1F450, LineBreak::EB), (0x1F451, 0x1F465, LineBreak::ID),
+    (0x1F466, 0x1F478, LineBreak::EB), (0x1F479, 0x1F47B, LineBreak::ID),
+    (0x1F47C, 0x1F47C, LineBreak::EB), (0x1F47D, 0x1F480, LineBreak::ID),
+    (0x1F481, 0x1F483, LineBreak::EB), (0x1F484, 0x1F484, LineBreak::ID),
+    (0x1F485, 0x1F487, LineBreak::EB), (0x1F488, 0x1F48E, LineBreak::ID),
+    (0x1F48F, 0x1F48F, LineBreak::EB), (0x1F490, 0x1F490, LineBreak::ID),
+    (0x1F491, 0x1F491, LineBreak::EB), (0x1F492, 0x1F49F, LineBreak::ID),
+    (0x1F4A1, 0x1F4A1, LineBreak::ID), (0x1F4A3, 0x1F4A3, LineBreak::ID),
+    (0x1F4A5, 0x1F4A9, LineBreak::ID), (0x1F4AA, 0x1F4AA, LineBreak::EB),
+    (0x1F4AB, 0x1F4AE, LineBreak::ID), (0x1F4B0, 0x1F4B0, LineBreak::ID),
+    (0x1F4B3, 0x1F4FF, LineBreak::ID), (0x1F507, 0x1F516, LineBreak::ID),
+    (0x1F525, 0x1F531, LineBreak::ID), (0x1F54A, 0x1F573, LineBreak::ID),
+    (0x1F574, 0x1F575, LineBreak::EB), (0x1F576, 0x1F579, LineBreak::ID),
+    (0x1F57A, 0x1F57A, LineBreak::EB), (0x1F57B, 0x1F58F, LineBreak::ID),
+    (0x1F590, 0x1F590, LineBreak::EB), (0x1F591, 0x1F594, LineBreak::ID),
+    (0x1F595, 0x1F596, LineBreak::EB), (0x1F597, 0x1F5D3, LineBreak::ID),
+    (0x1F5DC, 0x1F5F3, LineBreak::ID), (0x1F5FA, 0x1F644, LineBreak::ID),
+    (0x1F645, 0x1F647, LineBreak::EB), (0x1F648, 0x1F64A, LineBreak::ID),
+    (0x1F64B, 0x1F64F, LineBreak::EB), (0x1F676, 0x1F678, LineBreak::QU),
+    (0x1F679, 0x1F67B, LineBreak::NS), (0x1F680, 0x1F6A2, LineBreak::ID),
+    (0x1F6A3, 0x1F6A3, LineBreak::EB), (0x1F6A4, 0x1F6B3, LineBreak::ID),
+    (0x1F6B4, 0x1F6B6, LineBreak::EB), (0x1F6B7, 0x1F6BF, LineBreak::ID),
+    (0x1F6C0, 0x1F6C0, LineBreak::EB), (0x1F6C1, 0x1F6CB, LineBreak::ID),
+    (0x1F6CC, 0x1F6CC, LineBreak::EB), (0x1F6CD, 0x1F6FF, LineBreak::ID),
+    (0x1F774, 0x1F77F, LineBreak::ID), (0x1F7D5, 0x1F7FF, LineBreak::ID),
+    (0x1F80C, 0x1F80F, LineBreak::ID), (0x1F848, 0x1F84F, LineBreak::ID),
+    (0x1F85A, 0x1F85F, LineBreak::ID), (0x1F888, 0x1F88F, LineBreak::ID),
+    (0x1F8AE, 0x1F8FF, LineBreak::ID), (0x1F90C, 0x1F90C, LineBreak::EB),
+    (0x1F90D, 0x1F90E, LineBreak::ID), (0x1F90F, 0x1F90F, LineBreak::EB),
+    (0x1F910, 0x1F917, LineBreak::ID), (0x1F918, 0x1F91F, LineBreak::EB),
+    (0x1F920, 0x1F925, LineBreak::ID), (0x1F926, 0x1F926, LineBreak::EB),
+    (0x1F927, 0x1F92F, LineBreak::ID), (0x1F930, 0x1F939, LineBreak::EB),
+    (0x1F93A, 0x1F93B, LineBreak::ID), (0x1F93C, 0x1F93E, LineBreak::EB),
+    (0x1F93F, 0x1F976, LineBreak::ID), (0x1F977, 0x1F977, LineBreak::EB),
+    (0x1F978, 0x1F9B4, LineBreak::ID), (0x1F9B5, 0x1F9B6, LineBreak::EB),
+    (0x1F9B7, 0x1F9B7, LineBreak::ID), (0x1F9B8, 0x1F9B9, LineBreak::EB),
+    (0x1F9BA, 0x1F9BA, LineBreak::ID), (0x1F9BB, 0x1F9BB, LineBreak::EB),
+    (0x1F9BC, 0x1F9CC, LineBreak::ID), (0x1F9CD, 0x1F9CF, LineBreak::EB),
+    (0x1F9D0, 0x1F9D0, LineBreak::ID), (0x1F9D1, 0x1F9DD, LineBreak::EB),
+    (0x1F9DE, 0x1F9FF, LineBreak::ID), (0x1FA54, 0x1FAC2, LineBreak::ID),
+    (0x1FAC3, 0x1FAC5, LineBreak::EB), (0x1FAC6, 0x1FAEF, LineBreak::ID),
+    (0x1FAF0, 0x1FAF6, LineBreak::EB), (0x1FAF7, 0x1FAFF, LineBreak::ID),
+    (0x1FBF0, 0x1FBF9, LineBreak::NU), (0x1FC00, 0x1FFFD, LineBreak::ID),
+    (0x20000, 0x2FFFD, LineBreak::ID), (0x30000, 0x3FFFD, LineBreak::ID),
+    (0xE0001, 0xE0001, LineBreak::CM), (0xE0020, 0xE007F, LineBreak::CM),
+    (0xE0100, 0xE01EF, LineBreak::CM),
+];
+
+/// Grapheme cluster break classes; unlisted code points are `Other`.
+pub(crate) const GRAPHEME_BREAK: [(u32, u32, GraphemeBreak); 557] = [
+    (0x0, 0x9, GraphemeBreak::Control), (0xA, 0xA, GraphemeBreak::Lf),
+    (0xB, 0xC, GraphemeBreak::Control), (0xD, 0xD, GraphemeBreak::Cr),
+    (0xE, 0x1F, GraphemeBreak::Control), (0x7F, 0x9F, GraphemeBreak::Control),
+    (0xAD, 0xAD, GraphemeBreak::Control), (0x300, 0x36F, GraphemeBreak::Extend),
+    (0x483, 0x489, GraphemeBreak::Extend), (0x591, 0x5BD, GraphemeBreak::Extend),
+    (0x5BF, 0x5BF, GraphemeBreak::Extend), (0x5C1, 0x5C2, GraphemeBreak::Extend),
+    (0x5C4, 0x5C5, GraphemeBreak::Extend), (0x5C7, 0x5C7, GraphemeBreak::Extend),
+    (0x600, 0x605, GraphemeBreak::Prepend), (0x610, 0x61A, GraphemeBreak::Extend),
+    (0x61C, 0x61C, GraphemeBreak::Control), (0x64B, 0x65F, GraphemeBreak::Extend),
+    (0x670, 0x670, GraphemeBreak::Extend), (0x6D6, 0x6DC, GraphemeBreak::Extend),
+    (0x6DD, 0x6DD, GraphemeBreak::Prepend), (0x6DF, 0x6E4, GraphemeBreak::Extend),
+    (0x6E7, 0x6E8, GraphemeBreak::Extend), (0x6EA, 0x6ED, GraphemeBreak::Extend),
+    (0x70F, 0x70F, GraphemeBreak::Prepend), (0x711, 0x711, GraphemeBreak::Extend),
+    (0x730, 0x74A, GraphemeBreak::Extend), (0x7A6, 0x7B0, GraphemeBreak::Extend),
+    (0x7EB, 0x7F3, GraphemeBreak::Extend), (0x7FD, 0x7FD, GraphemeBreak::Extend),
+    (0x816, 0x819, GraphemeBreak::Extend), (0x81B, 0x823, GraphemeBreak::Extend),
+    (0x825, 0x827, GraphemeBreak::Extend), (0x829, 0x82D, GraphemeBreak::Extend),
+    (0x859, 0x85B, GraphemeBreak::Extend), (0x890, 0x891, GraphemeBreak::Prepend),
+    (0x898, 0x89F, GraphemeBreak::Extend), (0x8CA, 0x8E1, GraphemeBreak::Extend),
+    (0x8E2, 0x8E2, GraphemeBreak::Prepend), (0x8E3, 0x902, GraphemeBreak::Extend),
+    (0x903, 0x903, GraphemeBreak::SpacingMark), (0x93A, 0x93A, GraphemeBreak::Extend),
+    (0x93B, 0x93B, GraphemeBreak::SpacingMark), (0x93C, 0x93C, GraphemeBreak::Extend),
+    (0x93E, 0x940, GraphemeBreak::SpacingMark), (0x941, 0x948, GraphemeBreak::Extend),
+    (0x949, 0x94C, GraphemeBreak::SpacingMark), (0x94D, 0x94D, GraphemeBreak::Extend),
+    (0x94E, 0x94F, GraphemeBreak::SpacingMark), (0x951, 0x957, GraphemeBreak::Extend),
+    (0x962, 0x963, GraphemeBreak::Extend), (0x981, 0x981, GraphemeBreak::Extend),
+    (0x982, 0x983, GraphemeBreak::SpacingMark), (0x9BC, 0x9BC, GraphemeBreak::Extend),
+    (0x9BE, 0x9BE, GraphemeBreak::Extend), (0x9BF, 0x9C0, GraphemeBreak::SpacingMark),
+    (0x9C1, 0x9C4, GraphemeBreak::Extend), (0x9C7, 0x9C8, GraphemeBreak::SpacingMark),
+    (0x9CB, 0x9CC, GraphemeBreak::SpacingMark), (0x9CD, 0x9CD, GraphemeBreak::Extend),
+    (0x9D7, 0x9D7, GraphemeBreak::Extend), (0x9E2, 0x9E3, GraphemeBreak::Extend),
+    (0x9FE, 0x9FE, GraphemeBreak::Extend), (0xA01, 0xA02, GraphemeBreak::Extend),
+    (0xA03, 0xA03, GraphemeBreak::SpacingMark), (0xA3C, 0xA3C, GraphemeBreak::Extend),
+    (0xA3E, 0xA40, GraphemeBreak::SpacingMark), (0xA41, 0xA42, GraphemeBreak::Extend),
+    (0xA47, 0xA48, GraphemeBreak::Extend), (0xA4B, 0xA4D, GraphemeBreak::Extend),
+    (0xA51, 0xA51, GraphemeBreak::Extend), (0xA70, 0xA71, GraphemeBreak::Extend),
+    (0xA75, 0xA75, GraphemeBreak::Extend), (0xA81, 0xA82, GraphemeBreak::Extend),
+    (0xA83, 0xA83, GraphemeBreak::SpacingMark), (0xABC, 0xABC, GraphemeBreak::Extend),
+    (0xABE, 0xAC0, GraphemeBreak::SpacingMark), (0xAC1, 0xAC5, GraphemeBreak::Extend),
+    (0xAC7, 0xAC8, GraphemeBreak::Extend), (0xAC9, 0xAC9, GraphemeBreak::SpacingMark),
+    (0xACB, 0xACC, GraphemeBreak::SpacingMark), (0xACD, 0xACD, GraphemeBreak::Extend),
+    (0xAE2, 0xAE3, GraphemeBreak::Extend), (0xAFA, 0xAFF, GraphemeBreak::Extend),
+    (0xB01, 0xB01, GraphemeBreak::Extend), (0xB02, 0xB03, GraphemeBreak::SpacingMark),
+    (0xB3C, 0xB3C, GraphemeBreak::Extend), (0xB3E, 0xB3F, GraphemeBreak::Extend),
+    (0xB40, 0xB40, GraphemeBreak::SpacingMark), (0xB41, 0xB44, GraphemeBreak::Extend),
+    (0xB47, 0xB48, GraphemeBreak::SpacingMark), (0xB4B, 0xB4C, GraphemeBreak::SpacingMark),
+    (0xB4D, 0xB4D, GraphemeBreak::Extend), (0xB55, 0xB57, GraphemeBreak::Extend),
+    (0xB62, 0xB63, GraphemeBreak::Extend), (0xB82, 0xB82, GraphemeBreak::Extend),
+    (0xBBE, 0xBBE, GraphemeBreak::Extend), (0xBBF, 0xBBF, GraphemeBreak::SpacingMark),
+    (0xBC0, 0xBC0, GraphemeBreak::Extend), (0xBC1, 0xBC2, GraphemeBreak::SpacingMark),
+    (0xBC6, 0xBC8, GraphemeBreak::SpacingMark), (0xBCA, 0xBCC, GraphemeBreak::SpacingMark),
+    (0xBCD, 0xBCD, GraphemeBreak::Extend), (0xBD7, 0xBD7, GraphemeBreak::Extend),
+    (0xC00, 0xC00, GraphemeBreak::Extend), (0xC01, 0xC03, GraphemeBreak::SpacingMark),
+    (0xC04, 0xC04, GraphemeBreak::Extend), (0xC3C, 0xC3C, GraphemeBreak::Extend),
+    (0xC3E, 0xC40, GraphemeBreak::Extend), (0xC41, 0xC44, GraphemeBreak::SpacingMark),
+    (0xC46, 0xC48, GraphemeBreak::Extend), (0xC4A, 0xC4D, GraphemeBreak::Extend),
+    (0xC55, 0xC56, GraphemeBreak::Extend), (0xC62, 0xC63, GraphemeBreak::Extend),
+    (0xC81, 0xC81, GraphemeBreak::Extend), (0xC82, 0xC83, GraphemeBreak::SpacingMark),
+    (0xCBC, 0xCBC, GraphemeBreak::Extend), (0xCBE, 0xCBE, GraphemeBreak::SpacingMark),
+    (0xCBF, 0xCBF, GraphemeBreak::Extend), (0xCC0, 0xCC1, GraphemeBreak::SpacingMark),
+    (0xCC2, 0xCC2, GraphemeBreak::Extend), (0xCC3, 0xCC4, GraphemeBreak::SpacingMark),
+    (0xCC6, 0xCC6, GraphemeBreak::Extend), (0xCC7, 0xCC8, GraphemeBreak::SpacingMark),
+    (0xCCA, 0xCCB, GraphemeBreak::SpacingMark), (0xCCC, 0xCCD, GraphemeBreak::Extend),
+    (0xCD5, 0xCD6, GraphemeBreak::Extend), (0xCE2, 0xCE3, GraphemeBreak::Extend),
+    (0xD00, 0xD01, GraphemeBreak::Extend), (0xD02, 0xD03, GraphemeBreak::SpacingMark),
+    (0xD3B, 0xD3C, GraphemeBreak::Extend), (0xD3E, 0xD3E, GraphemeBreak::Extend),
+    (0xD3F, 0xD40, GraphemeBreak::SpacingMark), (0xD41, 0xD44, GraphemeBreak::Extend),
+    (0xD46, 0xD48, GraphemeBreak::SpacingMark), (0xD4A, 0xD4C, GraphemeBreak::SpacingMark),
+    (0xD4D, 0xD4D, GraphemeBreak::Extend), (0xD4E, 0xD4E, GraphemeBreak::Prepend),
+    (0xD57, 0xD57, GraphemeBreak::Extend), (0xD62, 0xD63, GraphemeBreak::Extend),
+    (0xD81, 0xD81, GraphemeBreak::Extend), (0xD82, 0xD83, GraphemeBreak::SpacingMark),
+    (0xDCA, 0xDCA, GraphemeBreak::Extend), (0xDCF, 0xDCF, GraphemeBreak::Extend),
+    (0xDD0, 0xDD1, GraphemeBreak::SpacingMark), (0xDD2, 0xDD4, GraphemeBreak::Extend),
+    (0xDD6, 0xDD6, GraphemeBreak::Extend), (0xDD8, 0xDDE, GraphemeBreak::SpacingMark),
+    (0xDDF, 0xDDF, GraphemeBreak::Extend), (0xDF2, 0xDF3, GraphemeBreak::SpacingMark),
+    (0xE31, 0xE31, GraphemeBreak::Extend), (0xE33, 0xE33, GraphemeBreak::SpacingMark),
+    (0xE34, 0xE3A, GraphemeBreak::Extend), (0xE47, 0xE4E, GraphemeBreak::Extend),
+    (0xEB1, 0xEB1, GraphemeBreak::Extend), (0xEB3, 0xEB3, GraphemeBreak::SpacingMark),
+    (0xEB4, 0xEBC, GraphemeBreak::Extend), (0xEC8, 0xECD, GraphemeBreak::Extend),
+    (0xF18, 0xF19, GraphemeBreak::Extend), (0xF35, 0xF35, GraphemeBreak::Extend),
+    (0xF37, 0xF37, GraphemeBreak::Extend), (0xF39, 0xF39, GraphemeBreak::Extend),
+    (0xF3E, 0xF3F, GraphemeBreak::SpacingMark), (0xF71, 0xF7E, GraphemeBreak::Extend),
+    (0xF7F, 0xF7F, GraphemeBreak::SpacingMark), (0xF80, 0xF84, GraphemeBreak::Extend),
+    (0xF86, 0xF87, GraphemeBreak::Extend), (0xF8D, 0xF97, GraphemeBreak::Extend),
+    (0xF99, 0xFBC, GraphemeBreak::Extend), (0xFC6, 0xFC6, GraphemeBreak::Extend),
+    (0x102D, 0x1030, GraphemeBreak::Extend), (0x1031, 0x1031, GraphemeBreak::SpacingMark),
+    (0x1032, 0x1037, GraphemeBreak::Extend), (0x1039, 0x103A, GraphemeBreak::Extend),
+    (0x103B, 0x103C, GraphemeBreak::SpacingMark), (0x103D, 0x103E, GraphemeBreak::Extend),
+    (0x1056, 0x1057, GraphemeBreak::SpacingMark), (0x1058, 0x1059, GraphemeBreak::Extend),
+    (0x105E, 0x1060, GraphemeBreak::Extend), (0x1071, 0x1074, GraphemeBreak::Extend),
+    (0x1082, 0x1082, GraphemeBreak::Extend), (0x1084, 0x1084, GraphemeBreak::SpacingMark),
+    (0x1085, 0x1086, GraphemeBreak::Extend), (0x108D, 0x108D, GraphemeBreak::Extend),
+    (0x109D, 0x109D, GraphemeBreak::Extend), (0x1100, 0x115F, GraphemeBreak::L),
+    (0x1160, 0x11A7, GraphemeBreak::V), (0x11A8, 0x11FF, GraphemeBreak::T),
+    (0x135D, 0x135F, GraphemeBreak::Extend), (0x1712, 0x1714, GraphemeBreak::Extend),
+    (0x1715, 0x1715, GraphemeBreak::SpacingMark), (0x1732, 0x1733, GraphemeBreak::Extend),
+    (0x1734, 0x1734, GraphemeBreak::SpacingMark), (0x1752, 0x1753, GraphemeBreak::Extend),
+    (0x1772, 0x1773, GraphemeBreak::Extend), (0x17B4, 0x17B5, GraphemeBreak::Extend),
+    (0x17B6, 0x17B6, GraphemeBreak::SpacingMark), (0x17B7, 0x17BD, GraphemeBreak::Extend),
+    (0x17BE, 0x17C5, GraphemeBreak::SpacingMark), (0x17C6, 0x17C6, GraphemeBreak::Extend),
+    (0x17C7, 0x17C8, GraphemeBreak::SpacingMark), (0x17C9, 0x17D3, GraphemeBreak::Extend),
+    (0x17DD, 0x17DD, GraphemeBreak::Extend), (0x180B, 0x180D, GraphemeBreak::Extend),
+    (0x180E, 0x180E, GraphemeBreak::Control), (0x180F, 0x180F, GraphemeBreak::Extend),
+    (0x1885, 0x1886, GraphemeBreak::Extend), (0x18A9, 0x18A9, GraphemeBreak::Extend),
+    (0x1920, 0x1922, GraphemeBreak::Extend), (0x1923, 0x1926, GraphemeBreak::SpacingMark),
+    (0x1927, 0x1928, GraphemeBreak::Extend), (0x1929, 0x192B, GraphemeBreak::SpacingMark),
+    (0x1930, 0x1931, GraphemeBreak::SpacingMark), (0x1932, 0x1932, GraphemeBreak::Extend),
+    (0x1933, 0x1938, GraphemeBreak::SpacingMark), (0x1939, 0x193B, GraphemeBreak::Extend),
+    (0x1A17, 0x1A18, GraphemeBreak::Extend), (0x1A19, 0x1A1A, GraphemeBreak::SpacingMark),
+    (0x1A1B, 0x1A1B, GraphemeBreak::Extend), (0x1A55, 0x1A55, GraphemeBreak::SpacingMark),
+    (0x1A56, 0x1A56, GraphemeBreak::Extend), (0x1A57, 0x1A57, GraphemeBreak::SpacingMark),
+    (0x1A58, 0x1A5E, GraphemeBreak::Extend), (0x1A60, 0x1A60, GraphemeBreak::Extend),
+    (0x1A62, 0x1A62, GraphemeBreak::Extend), (0x1A65, 0x1A6C, GraphemeBreak::Extend),
+    (0x1A6D, 0x1A72, GraphemeBreak::SpacingMark), (0x1A73, 0x1A7C, GraphemeBreak::Extend),
+    (0x1A7F, 0x1A7F, GraphemeBreak::Extend), (0x1AB0, 0x1ACE, GraphemeBreak::Extend),
+    (0x1B00, 0x1B03, GraphemeBreak::Extend), (0x1B04, 0x1B04, GraphemeBreak::SpacingMark),
+    (0x1B34, 0x1B3A, GraphemeBreak::Extend), (0x1B3B, 0x1B3B, GraphemeBreak::SpacingMark),
+    (0x1B3C, 0x1B3C, GraphemeBreak::Extend), (0x1B3D, 0x1B41, GraphemeBreak::SpacingMark),
+    (0x1B42, 0x1B42, GraphemeBreak::Extend), (0x1B43, 0x1B44, GraphemeBreak::SpacingMark),
+    (0x1B6B, 0x1B73, GraphemeBreak::Extend), (0x1B80, 0x1B81, GraphemeBreak::Extend),
+    (0x1B82, 0x1B82, GraphemeBreak::SpacingMark), (0x1BA1, 0x1BA1, GraphemeBreak::SpacingMark),
+    (0x1BA2, 0x1BA5, GraphemeBreak::Extend), (0x1BA6, 0x1BA7, GraphemeBreak::SpacingMark),
+    (0x1BA8, 0x1BA9, GraphemeBreak::Extend), (0x1BAA, 0x1BAA, GraphemeBreak::SpacingMark),
+    (0x1BAB, 0x1BAD, GraphemeBreak::Extend), (0x1BE6, 0x1BE6, GraphemeBreak::Extend),
+    (0x1BE7, 0x1BE7, GraphemeBreak::SpacingMark), (0x1BE8, 0x1BE9, GraphemeBreak::Extend),
+    (0x1BEA, 0x1BEC, GraphemeBreak::SpacingMark), (0x1BED, 0x1BED, GraphemeBreak::Extend),
+    (0x1BEE, 0x1BEE, GraphemeBreak::SpacingMark), (0x1BEF, 0x1BF1, GraphemeBreak::Extend),
+    (0x1BF2, 0x1BF3, GraphemeBreak::SpacingMark), (0x1C24, 0x1C2B, GraphemeBreak::SpacingMark),
+    (0x1C2C, 0x1C33, GraphemeBreak::Extend), (0x1C34, 0x1C35, GraphemeBreak::SpacingMark),
+    (0x1C36, 0x1C37, GraphemeBreak::Extend), (0x1CD0, 0x1CD2, GraphemeBreak::Extend),
+    (0x1CD4, 0x1CE0, GraphemeBreak::Extend), (0x1CE1, 0x1CE1, GraphemeBreak::SpacingMark),
+    (0x1CE2, 0x1CE8, GraphemeBreak::Extend), (0x1CED, 0x1CED, GraphemeBreak::Extend),
+    (0x1CF4, 0x1CF4, GraphemeBreak::Extend), (0x1CF7, 0x1CF7, GraphemeBreak::SpacingMark),
+    (0x1CF8, 0x1CF9, GraphemeBreak::Extend), (0x1DC0, 0x1DFF, GraphemeBreak::Extend),
+    (0x200B, 0x200B, GraphemeBreak::Control), (0x200C, 0x200C, GraphemeBreak::Extend),
+    (0x200D, 0x200D, GraphemeBreak::Zwj), (0x200E, 0x200F, GraphemeBreak::Control),
+    (0x2028, 0x202E, GraphemeBreak::Control), (0x2060, 0x206F, GraphemeBreak::Control),
+    (0x20D0, 0x20F0, GraphemeBreak::Extend), (0x2CEF, 0x2CF1, GraphemeBreak::Extend),
+    (0x2D7F, 0x2D7F, GraphemeBreak::Extend), (0x2DE0, 0x2DFF, GraphemeBreak::Extend),
+    (0x302A, 0x302F, GraphemeBreak::Extend), (0x3099, 0x309A, GraphemeBreak::Extend),
+    (0xA66F, 0xA672, GraphemeBreak::Extend), (0xA674, 0xA67D, GraphemeBreak::Extend),
+    (0xA69E, 0xA69F, GraphemeBreak::Extend), (0xA6F0, 0xA6F1, GraphemeBreak::Extend),
+    (0xA802, 0xA802, GraphemeBreak::Extend), (0xA806, 0xA806, GraphemeBreak::Extend),
+    (0xA80B, 0xA80B, GraphemeBreak::Extend), (0xA823, 0xA824, GraphemeBreak::SpacingMark),
+    (0xA825, 0xA826, GraphemeBreak::Extend), (0xA827, 0xA827, GraphemeBreak::SpacingMark),
+    (0xA82C, 0xA82C, GraphemeBreak::Extend), (0xA880, 0xA881, GraphemeBreak::SpacingMark),
+    (0xA8B4, 0xA8C3, GraphemeBreak::SpacingMark), (0xA8C4, 0xA8C5, GraphemeBreak::Extend),
+    (0xA8E0, 0xA8F1, GraphemeBreak::Extend), (0xA8FF, 0xA8FF, GraphemeBreak::Extend),
+    (0xA926, 0xA92D, GraphemeBreak::Extend), (0xA947, 0xA951, GraphemeBreak::Extend),
+    (0xA952, 0xA953, GraphemeBreak::SpacingMark), (0xA960, 0xA97C, GraphemeBreak::L),
+    (0xA980, 0xA982, GraphemeBreak::Extend), (0xA983, 0xA983, GraphemeBreak::SpacingMark),
+    (0xA9B3, 0xA9B3, GraphemeBreak::Extend), (0xA9B4, 0xA9B5, GraphemeBreak::SpacingMark),
+    (0xA9B6, 0xA9B9, GraphemeBreak::Extend), (0xA9BA, 0xA9BB, GraphemeBreak::SpacingMark),
+    (0xA9BC, 0xA9BD, GraphemeBreak::Extend), (0xA9BE, 0xA9C0, GraphemeBreak::SpacingMark),
+    (0xA9E5, 0xA9E5, GraphemeBreak::Extend), (0xAA29, 0xAA2E, GraphemeBreak::Extend),
+    (0xAA2F, 0xAA30, GraphemeBreak::SpacingMark), (0xAA31, 0xAA32, GraphemeBreak::Extend),
+    (0xAA33, 0xAA34, GraphemeBreak::SpacingMark), (0xAA35, 0xAA36, GraphemeBreak::Extend),
+    (0xAA43, 0xAA43, GraphemeBreak::Extend), (0xAA4C, 0xAA4C, GraphemeBreak::Extend),
+    (0xAA4D, 0xAA4D, GraphemeBreak::SpacingMark), (0xAA7C, 0xAA7C, GraphemeBreak::Extend),
+    (0xAAB0, 0xAAB0, GraphemeBreak::Extend), (0xAAB2, 0xAAB4, GraphemeBreak::Extend),
+    (0xAAB7, 0xAAB8, GraphemeBreak::Extend), (0xAABE, 0xAABF, GraphemeBreak::Extend),
+    (0xAAC1, 0xAAC1, GraphemeBreak::Extend), (0xAAEB, 0xAAEB, GraphemeBreak::SpacingMark),
+    (0xAAEC, 0xAAED, GraphemeBreak::Extend), (0xAAEE, 0xAAEF, GraphemeBreak::SpacingMark),
+    (0xAAF5, 0xAAF5, GraphemeBreak::SpacingMark), (0xAAF6, 0xAAF6, GraphemeBreak::Extend),
+    (0xABE3, 0xABE4, GraphemeBreak::SpacingMark), (0xABE5, 0xABE5, GraphemeBreak::Extend),
+    (0xABE6, 0xABE7, GraphemeBreak::SpacingMark), (0xABE8, 0xABE8, GraphemeBreak::Extend),
+    (0xABE9, 0xABEA, GraphemeBreak::SpacingMark), (0xABEC, 0xABEC, GraphemeBreak::SpacingMark),
+    (0xABED, 0xABED, GraphemeBreak::Extend), (0xD7B0, 0xD7C6, GraphemeBreak::V),
+    (0xD7CB, 0xD7FB, GraphemeBreak::T), (0xFB1E, 0xFB1E, GraphemeBreak::Extend),
+    (0xFE00, 0xFE0F, GraphemeBreak::Extend), (0xFE20, 0xFE2F, GraphemeBreak::Extend),
+    (0xFEFF, 0xFEFF, GraphemeBreak::Control), (0xFF9E, 0xFF9F, GraphemeBreak::Extend),
+    (0xFFF0, 0xFFFB, GraphemeBreak::Control), (0x101FD, 0x101FD, GraphemeBreak::Extend),
+    (0x102E0, 0x102E0, GraphemeBreak::Extend), (0x10376, 0x1037A, GraphemeBreak::Extend),
+    (0x10A01, 0x10A03, GraphemeBreak::Extend), (0x10A05, 0x10A06, GraphemeBreak::Extend),
+    (0x10A0C, 0x10A0F, GraphemeBreak::Extend), (0x10A38, 0x10A3A, GraphemeBreak::Extend),
+    (0x10A3F, 0x10A3F, GraphemeBreak::Extend), (0x10AE5, 0x10AE6, GraphemeBreak::Extend),
+    (0x10D24, 0x10D27, GraphemeBreak::Extend), (0x10EAB, 0x10EAC, GraphemeBreak::Extend),
+    (0x10F46, 0x10F50, GraphemeBreak::Extend), (0x10F82, 0x10F85, GraphemeBreak::Extend),
+    (0x11000, 0x11000, GraphemeBreak::SpacingMark), (0x11001, 0x11001, GraphemeBreak::Extend),
+    (0x11002, 0x11002, GraphemeBreak::SpacingMark), (0x11038, 0x11046, GraphemeBreak::Extend),
+    (0x11070, 0x11070, GraphemeBreak::Extend), (0x11073, 0x11074, GraphemeBreak::Extend),
+    (0x1107F, 0x11081, GraphemeBreak::Extend), (0x11082, 0x11082, GraphemeBreak::SpacingMark),
+    (0x110B0, 0x110B2, GraphemeBreak::SpacingMark), (0x110B3, 0x110B6, GraphemeBreak::Extend),
+    (0x110B7, 0x110B8, GraphemeBreak::SpacingMark), (0x110B9, 0x110BA, GraphemeBreak::Extend),
+    (0x110BD, 0x110BD, GraphemeBreak::Prepend), (0x110C2, 0x110C2, GraphemeBreak::Extend),
+    (0x110CD, 0x110CD, GraphemeBreak::Prepend), (0x11100, 0x11102, GraphemeBreak::Extend),
+    (0x11127, 0x1112B, GraphemeBreak::Extend), (0x1112C, 0x1112C, GraphemeBreak::SpacingMark),
+    (0x1112D, 0x11134, GraphemeBreak::Extend), (0x11145, 0x11146, GraphemeBreak::SpacingMark),
+    (0x11173, 0x11173, GraphemeBreak::Extend), (0x11180, 0x11181, GraphemeBreak::Extend),
+    (0x11182, 0x11182, GraphemeBreak::SpacingMark), (0x111B3, 0x111B5, GraphemeBreak::SpacingMark),
+    (0x111B6, 0x111BE, GraphemeBreak::Extend), (0x111BF, 0x111C0, GraphemeBreak::SpacingMark),
+    (0x111C2, 0x111C3, GraphemeBreak::Prepend), (0x111C9, 0x111CC, GraphemeBreak::Extend),
+    (0x111CE, 0x111CE, GraphemeBreak::SpacingMark), (0x111CF, 0x111CF, GraphemeBreak::Extend),
+    (0x1122C, 0x1122E, GraphemeBreak::SpacingMark), (0x1122F, 0x11231, GraphemeBreak::Extend),
+    (0x11232, 0x11233, GraphemeBreak::SpacingMark), (0x11234, 0x11234, GraphemeBreak::Extend),
+    (0x11235, 0x11235, GraphemeBreak::SpacingMark), (0x11236, 0x11237, GraphemeBreak::Extend),
+    (0x1123E, 0x1123E, GraphemeBreak::Extend), (0x112DF, 0x112DF, GraphemeBreak::Extend),
+    (0x112E0, 0x112E2, GraphemeBreak::SpacingMark), (0x112E3, 0x112EA, GraphemeBreak::Extend),
+    (0x11300, 0x11301, GraphemeBreak::Extend), (0x11302, 0x11303, GraphemeBreak::SpacingMark),
+    (0x1133B, 0x1133C, GraphemeBreak::Extend), (0x1133E, 0x1133E, GraphemeBreak::Extend),
+    (0x1133F, 0x1133F, GraphemeBreak::SpacingMark), (0x11340, 0x11340, GraphemeBreak::Extend),
+    (0x11341, 0x11344, GraphemeBreak::SpacingMark), (0x11347, 0x11348, GraphemeBreak::SpacingMark),
+    (0x1134B, 0x1134D, GraphemeBreak::SpacingMark), (0x11357, 0x11357, GraphemeBreak::Extend),
+    (0x11362, 0x11363, GraphemeBreak::SpacingMark), (0x11366, 0x1136C, GraphemeBreak::Extend),
+    (0x11370, 0x11374, GraphemeBreak::Extend), (0x11435, 0x11437, GraphemeBreak::SpacingMark),
+    (0x11438, 0x1143F, GraphemeBreak::Extend), (0x11440, 0x11441, GraphemeBreak::SpacingMark),
+    (0x11442, 0x11444, GraphemeBreak::Extend), (0x11445, 0x11445, GraphemeBreak::SpacingMark),
+    (0x11446, 0x11446, GraphemeBreak::Extend), (0x1145E, 0x1145E, GraphemeBreak::Extend),
+    (0x114B0, 0x114B0, GraphemeBreak::Extend), (0x114B1, 0x114B2, GraphemeBreak::SpacingMark),
+    (0x114B3, 0x114B8, GraphemeBreak::Extend), (0x114B9, 0x114B9, GraphemeBreak::SpacingMark),
+    (0x114BA, 0x114BA, GraphemeBreak::Extend), (0x114BB, 0x114BC, GraphemeBreak::SpacingMark),
+    (0x114BD, 0x114BD, GraphemeBreak::Extend), (0x114BE, 0x114BE, GraphemeBreak::SpacingMark),
+    (0x114BF, 0x114C0, GraphemeBreak::Extend), (0x114C1, 0x114C1, GraphemeBreak::SpacingMark),
+    (0x114C2, 0x114C3, GraphemeBreak::Extend), (0x115AF, 0x115AF, GraphemeBreak::Extend),
+    (0x115B0, 0x115B1, GraphemeBreak::SpacingMark), (0x115B2, 0x115B5, GraphemeBreak::Extend),
+    (0x115B8, 0x115BB, GraphemeBreak::SpacingMark), (0x115BC, 0x115BD, GraphemeBreak::Extend),
+    (0x115BE, 0x115BE, GraphemeBreak::SpacingMark), (0x115BF, 0x115C0, GraphemeBreak::Extend),
+    (0x115DC, 0x115DD, GraphemeBreak::Extend), (0x11630, 0x11632, GraphemeBreak::SpacingMark),
+    (0x11633, 0x1163A, GraphemeBreak::Extend), (0x1163B, 0x1163C, GraphemeBreak::SpacingMark),
+    (0x1163D, 0x1163D, GraphemeBreak::Extend), (0x1163E, 0x1163E, GraphemeBreak::SpacingMark),
+    (0x1163F, 0x11640, GraphemeBreak::Extend), (0x116AB, 0x116AB, GraphemeBreak::Extend),
+    (0x116AC, 0x116AC, GraphemeBreak::SpacingMark), (0x116AD, 0x116AD, GraphemeBreak::Extend),
+    (0x116AE, 0x116AF, GraphemeBreak::SpacingMark), (0x116B0, 0x116B5, GraphemeBreak::Extend),
+    (0x116B6, 0x116B6, GraphemeBreak::SpacingMark), (0x116B7, 0x116B7, GraphemeBreak::Extend),
+    (0x1171D, 0x1171F, GraphemeBreak::Extend), (0x11722, 0x11725, GraphemeBreak::Extend),
+    (0x11726, 0x11726, GraphemeBreak::SpacingMark), (0x11727, 0x1172B, GraphemeBreak::Extend),
+    (0x1182C, 0x1182E, GraphemeBreak::SpacingMark), (0x1182F, 0x11837, GraphemeBreak::Extend),
+    (0x11838, 0x11838, GraphemeBreak::SpacingMark), (0x11839, 0x1183A, GraphemeBreak::Extend),
+    (0x11930, 0x11930, GraphemeBreak::Extend), (0x11931, 0x11935, GraphemeBreak::SpacingMark),
+    (0x11937, 0x11938, GraphemeBreak::SpacingMark), (0x1193B, 0x1193C, GraphemeBreak::Extend),
+    (0x1193D, 0x1193D, GraphemeBreak::SpacingMark), (0x1193E, 0x1193E, GraphemeBreak::Extend),
+    (0x1193F, 0x1193F, GraphemeBreak::Prepend), (0x11940, 0x11940, GraphemeBreak::SpacingMark),
+    (0x11941, 0x11941, GraphemeBreak::Prepend), (0x11942, 0x11942, GraphemeBreak::SpacingMark),
+    (0x11943, 0x11943, GraphemeBreak::Extend), (0x119D1, 0x119D3, GraphemeBreak::SpacingMark),
+    (0x119D4, 0x119D7, GraphemeBreak::Extend), (0x119DA, 0x119DB, GraphemeBreak::Extend),
+    (0x119DC, 0x119DF, GraphemeBreak::SpacingMark), (0x119E0, 0x119E0, GraphemeBreak::Extend),
+    (0x119E4, 0x119E4, GraphemeBreak::SpacingMark), (0x11A01, 0x11A0A, GraphemeBreak::Extend),
+    (0x11A33, 0x11A38, GraphemeBreak::Extend), (0x11A39, 0x11A39, GraphemeBreak::SpacingMark),
+    (0x11A3A, 0x11A3A, GraphemeBreak::Prepend), (0x11A3B, 0x11A3E, GraphemeBreak::Extend),
+    (0x11A47, 0x11A47, GraphemeBreak::Extend), (0x11A51, 0x11A56, GraphemeBreak::Extend),
+    (0x11A57, 0x11A58, GraphemeBreak::SpacingMark), (0x11A59, 0x11A5B, GraphemeBreak::Extend),
+    (0x11A84, 0x11A89, GraphemeBreak::Prepend), (0x11A8A, 0x11A96, GraphemeBreak::Extend),
+    (0x11A97, 0x11A97, GraphemeBreak::SpacingMark), (0x11A98, 0x11A99, GraphemeBreak::Extend),
+    (0x11C2F, 0x11C2F, GraphemeBreak::SpacingMark), (0x11C30, 0x11C36, GraphemeBreak::Extend),
+    (0x11C38, 0x11C3D, GraphemeBreak::Extend), (0x11C3E, 0x11C3E, GraphemeBreak::SpacingMark),
+    (0x11C3F, 0x11C3F, GraphemeBreak::Extend), (0x11C92, 0x11CA7, GraphemeBreak::Extend),
+    (0x11CA9, 0x11CA9, GraphemeBreak::SpacingMark), (0x11CAA, 0x11CB0, GraphemeBreak::Extend),
+    (0x11CB1, 0x11CB1, GraphemeBreak::SpacingMark), (0x11CB2, 0x11CB3, GraphemeBreak::Extend),
+    (0x11CB4, 0x11CB4, GraphemeBreak::SpacingMark), (0x11CB5, 0x11CB6, GraphemeBreak::Extend),
+    (0x11D31, 0x11D36, GraphemeBreak::Extend), (0x11D3A, 0x11D3A, GraphemeBreak::Extend),
+    (0x11D3C, 0x11D3D, GraphemeBreak::Extend), (0x11D3F, 0x11D45, GraphemeBreak::Extend),
+    (0x11D46, 0x11D46, GraphemeBreak::Prepend), (0x11D47, 0x11D47, GraphemeBreak::Extend),
+    (0x11D8A, 0x11D8E, GraphemeBreak::SpacingMark), (0x11D90, 0x11D91, GraphemeBreak::Extend),
+    (0x11D93, 0x11D94, GraphemeBreak::SpacingMark), (0x11D95, 0x11D95, GraphemeBreak::Extend),
+    (0x11D96, 0x11D96, GraphemeBreak::SpacingMark), (0x11D97, 0x11D97, GraphemeBreak::Extend),
+    (0x11EF3, 0x11EF4, GraphemeBreak::Extend), (0x11EF5, 0x11EF6, GraphemeBreak::SpacingMark),
+    (0x13430, 0x13438, GraphemeBreak::Control), (0x16AF0, 0x16AF4, GraphemeBreak::Extend),
+    (0x16B30, 0x16B36, GraphemeBreak::Extend), (0x16F4F, 0x16F4F, GraphemeBreak::Extend),
+    (0x16F51, 0x16F87, GraphemeBreak::SpacingMark), (0x16F8F, 0x16F92, GraphemeBreak::Extend),
+    (0x16FE4, 0x16FE4, GraphemeBreak::Extend), (0x16FF0, 0x16FF1, GraphemeBreak::SpacingMark),
+    (0x1BC9D, 0x1BC9E, GraphemeBreak::Extend), (0x1BCA0, 0x1BCA3, GraphemeBreak::Control),
+    (0x1CF00, 0x1CF2D, GraphemeBreak::Extend), (0x1CF30, 0x1CF46, GraphemeBreak::Extend),
+    (0x1D165, 0x1D165, GraphemeBreak::Extend), (0x1D166, 0x1D166, GraphemeBreak::SpacingMark),
+    (0x1D167, 0x1D169, GraphemeBreak::Extend), (0x1D16D, 0x1D16D, GraphemeBreak::SpacingMark),
+    (0x1D16E, 0x1D172, GraphemeBreak::Extend), (0x1D173, 0x1D17A, GraphemeBreak::Control),
+    (0x1D17B, 0x1D182, GraphemeBreak::Extend), (0x1D185, 0x1D18B, GraphemeBreak::Extend),
+    (0x1D1AA, 0x1D1AD, GraphemeBreak::Extend), (0x1D242, 0x1D244, GraphemeBreak::Extend),
+    (0x1DA00, 0x1DA36, GraphemeBreak::Extend), (0x1DA3B, 0x1DA6C, GraphemeBreak::Extend),
+    (0x1DA75, 0x1DA75, GraphemeBreak::Extend), (0x1DA84, 0x1DA84, GraphemeBreak::Extend),
+    (0x1DA9B, 0x1DA9F, GraphemeBreak::Extend), (0x1DAA1, 0x1DAAF, GraphemeBreak::Extend),
+    (0x1E000, 0x1E006, GraphemeBreak::Extend), (0x1E008, 0x1E018, GraphemeBreak::Extend),
+    (0x1E01B, 0x1E021, GraphemeBreak::Extend), (0x1E023, 0x1E024, GraphemeBreak::Extend),
+    (0x1E026, 0x1E02A, GraphemeBreak::Extend), (0x1E130, 0x1E136, GraphemeBreak::Extend),
+    (0x1E2AE, 0x1E2AE, GraphemeBreak::Extend), (0x1E2EC, 0x1E2EF, GraphemeBreak::Extend),
+    (0x1E8D0, 0x1E8D6, GraphemeBreak::Extend), (0x1E944, 0x1E94A, GraphemeBreak::Extend),
+    (0x1F1E6, 0x1F1FF, GraphemeBreak::RegionalIndicator), (0x1F3FB, 0x1F3FF, GraphemeBreak::Extend),
+    (0xE0000, 0xE001F, GraphemeBreak::Control), (0xE0020, 0xE007F, GraphemeBreak::Extend),
+    (0xE0080, 0xE00FF, GraphemeBreak::Control), (0xE0100, 0xE01EF, GraphemeBreak::Extend),
+    (0xE01F0, 0xE0FFF, GraphemeBreak::Control),
+];
+
+/// Extended_Pictographic code point ranges.
+pub(crate) const EXTENDED_PICTOGRAPHIC: [(u32, u32); 78] = [
+    (0xA9, 0xA9), (0xAE, 0xAE), (0x203C, 0x203C), (0x2049, 0x2049), (0x2122, 0x2122),
+    (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA), (0x231A, 0x231B), (0x2328, 0x2328),
+    (0x2388, 0x2388), (0x23CF, 0x23CF), (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB), (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x2605),
+    (0x2607, 0x2612), (0x2614, 0x2685), (0x2690, 0x2705), (0x2708, 0x2712), (0x2714, 0x2714),
+    (0x2716, 0x2716), (0x271D, 0x271D), (0x2721, 0x2721), (0x2728, 0x2728), (0x2733, 0x2734),
+    (0x2744, 0x2744), (0x2747, 0x2747), (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755),
+    (0x2757, 0x2757), (0x2763, 0x2767), (0x2795, 0x2797), (0x27A1, 0x27A1), (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF), (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D), (0x3297, 0x3297), (0x3299, 0x3299),
+    (0x1F000, 0x1F0FF), (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F), (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F1AD, 0x1F1E5),
+    (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F), (0x1F232, 0x1F23A),
+    (0x1F23C, 0x1F23F), (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D), (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF), (0x1F80C, 0x1F80F),
+    (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F), (0x1F888, 0x1F88F), (0x1F8AE, 0x1F8FF),
+    (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF), (0x1FC00, 0x1FFFD),
+];
+
+/// Bidi classes; unlisted code points are `L`.
+pub(crate) const BIDI_CLASS: [(u32, u32, BidiClass); 712] = [
+    (0x0, 0x8, BN), (0x9, 0x9, S), (0xA, 0xA, B), (0xB, 0xB, S), (0xC, 0xC, WS), (0xD, 0xD, B),
+    (0xE, 0x1B, BN), (0x1C, 0x1E, B), (0x1F, 0x1F, S), (0x20, 0x20, WS), (0x21, 0x22, ON),
+    (0x23, 0x25, ET), (0x26, 0x2A, ON), (0x2B, 0x2B, ES), (0x2C, 0x2C, CS), (0x2D, 0x2D, ES),
+    (0x2E, 0x2F, CS), (0x30, 0x39, EN), (0x3A, 0x3A, CS), (0x3B, 0x40, ON), (0x5B, 0x60, ON),
+    (0x7B, 0x7E, ON), (0x7F, 0x84, BN), (0x85, 0x85, B), (0x86, 0x9F, BN), (0xA0, 0xA0, CS),
+    (0xA1, 0xA1, ON), (0xA2, 0xA5, ET), (0xA6, 0xA9, ON), (0xAB, 0xAC, ON), (0xAD, 0xAD, BN),
+    (0xAE, 0xAF, ON), (0xB0, 0xB1, ET), (0xB2, 0xB3, EN), (0xB4, 0xB4, ON), (0xB6, 0xB8, ON),
+    (0xB9, 0xB9, EN), (0xBB, 0xBF, ON), (0xD7, 0xD7, ON), (0xF7, 0xF7, ON), (0x2B9, 0x2BA, ON),
+    (0x2C2, 0x2CF, ON), (0x2D2, 0x2DF, ON), (0x2E5, 0x2ED, ON), (0x2EF, 0x2FF, ON),
+    (0x300, 0x36F, NSM), (0x374, 0x375, ON), (0x37E, 0x37E, ON), (0x384, 0x385, ON),
+    (0x387, 0x387, ON), (0x3F6, 0x3F6, ON), (0x483, 0x489, NSM), (0x58A, 0x58A, ON),
+    (0x58D, 0x58E, ON), (0x58F, 0x58F, ET), (0x590, 0x590, R), (0x591, 0x5BD, NSM),
+    (0x5BE, 0x5BE, R), (0x5BF, 0x5BF, NSM), (0x5C0, 0x5C0, R), (0x5C1, 0x5C2, NSM),
+    (0x5C3, 0x5C3, R), (0x5C4, 0x5C5, NSM), (0x5C6, 0x5C6, R), (0x5C7, 0x5C7, NSM),
+    (0x5C8, 0x5FF, R), (0x600, 0x605, AN), (0x606, 0x607, ON), (0x608, 0x608, AL),
+    (0x609, 0x60A, ET), (0x60B, 0x60B, AL), (0x60C, 0x60C, CS), (0x60D, 0x60D, AL),
+    (0x60E, 0x60F, ON), (0x610, 0x61A, NSM), (0x61B, 0x64A, AL), (0x64B, 0x65F, NSM),
+    (0x660, 0x669, AN), (0x66A, 0x66A, ET), (0x66B, 0x66C, AN), (0x66D, 0x66F, AL),
+    (0x670, 0x670, NSM), (0x671, 0x6D5, AL), (0x6D6, 0x6DC, NSM), (0x6DD, 0x6DD, AN),
+    (0x6DE, 0x6DE, ON), (0x6DF, 0x6E4, NSM), (0x6E5, 0x6E6, AL), (0x6E7, 0x6E8, NSM),
+    (0x6E9, 0x6E9, ON), (0x6EA, 0x6ED, NSM), (0x6EE, 0x6EF, AL), (0x6F0, 0x6F9, EN),
+    (0x6FA, 0x710, AL), (0x711, 0x711, NSM), (0x712, 0x72F, AL), (0x730, 0x74A, NSM),
+    (0x74B, 0x7A5, AL), (0x7A6, 0x7B0, NSM), (0x7B1, 0x7BF, AL), (0x7C0, 0x7EA, R),
+    (0x7EB, 0x7F3, NSM), (0x7F4, 0x7F5, R), (0x7F6, 0x7F9, ON), (0x7FA, 0x7FC, R),
+    (0x7FD, 0x7FD, NSM), (0x7FE, 0x815, R), (0x816, 0x819, NSM), (0x81A, 0x81A, R),
+    (0x81B, 0x823, NSM), (0x824, 0x824, R), (0x825, 0x827, NSM), (0x828, 0x828, R),
+    (0x829, 0x82D, NSM), (0x82E, 0x858, R), (0x859, 0x85B, NSM), (0x85C, 0x85F, R),
+    (0x860, 0x88F, AL), (0x890, 0x891, AN), (0x892, 0x897, AL), (0x898, 0x89F, NSM),
+    (0x8A0, 0x8C9, AL), (0x8CA, 0x8E1, NSM), (0x8E2, 0x8E2, AN), (0x8E3, 0x902, NSM),
+    (0x93A, 0x93A, NSM), (0x93C, 0x93C, NSM), (0x941, 0x948, NSM), (0x94D, 0x94D, NSM),
+    (0x951, 0x957, NSM), (0x962, 0x963, NSM), (0x981, 0x981, NSM), (0x9BC, 0x9BC, NSM),
+    (0x9C1, 0x9C4, NSM), (0x9CD, 0x9CD, NSM), (0x9E2, 0x9E3, NSM), (0x9F2, 0x9F3, ET),
+    (0x9FB, 0x9FB, ET), (0x9FE, 0x9FE, NSM), (0xA01, 0xA02, NSM), (0xA3C, 0xA3C, NSM),
+    (0xA41, 0xA42, NSM), (0xA47, 0xA48, NSM), (0xA4B, 0xA4D, NSM), (0xA51, 0xA51, NSM),
+    (0xA70, 0xA71, NSM), (0xA75, 0xA75, NSM), (0xA81, 0xA82, NSM), (0xABC, 0xABC, NSM),
+    (0xAC1, 0xAC5, NSM), (0xAC7, 0xAC8, NSM), (0xACD, 0xACD, NSM), (0xAE2, 0xAE3, NSM),
+    (0xAF1, 0xAF1, ET), (0xAFA, 0xAFF, NSM), (0xB01, 0xB01, NSM), (0xB3C, 0xB3C, NSM),
+    (0xB3F, 0xB3F, NSM), (0xB41, 0xB44, NSM), (0xB4D, 0xB4D, NSM), (0xB55, 0xB56, NSM),
+    (0xB62, 0xB63, NSM), (0xB82, 0xB82, NSM), (0xBC0, 0xBC0, NSM), (0xBCD, 0xBCD, NSM),
+    (0xBF3, 0xBF8, ON), (0xBF9, 0xBF9, ET), (0xBFA, 0xBFA, ON), (0xC00, 0xC00, NSM),
+    (0xC04, 0xC04, NSM), (0xC3C, 0xC3C, NSM), (0xC3E, 0xC40, NSM), (0xC46, 0xC48, NSM),
+    (0xC4A, 0xC4D, NSM), (0xC55, 0xC56, NSM), (0xC62, 0xC63, NSM), (0xC78, 0xC7E, ON),
+    (0xC81, 0xC81, NSM), (0xCBC, 0xCBC, NSM), (0xCCC, 0xCCD, NSM), (0xCE2, 0xCE3, NSM),
+    (0xD00, 0xD01, NSM), (0xD3B, 0xD3C, NSM), (0xD41, 0xD44, NSM), (0xD4D, 0xD4D, NSM),
+    (0xD62, 0xD63, NSM), (0xD81, 0xD81, NSM), (0xDCA, 0xDCA, NSM), (0xDD2, 0xDD4, NSM),
+    (0xDD6, 0xDD6, NSM), (0xE31, 0xE31, NSM), (0xE34, 0xE3A, NSM), (0xE3F, 0xE3F, ET),
+    (0xE47, 0xE4E, NSM), (0xEB1, 0xEB1, NSM), (0xEB4, 0xEBC, NSM), (0xEC8, 0xECD, NSM),
+    (0xF18, 0xF19, NSM), (0xF35, 0xF35, NSM), (0xF37, 0xF37, NSM), (0xF39, 0xF39, NSM),
+    (0xF3A, 0xF3D, ON), (0xF71, 0xF7E, NSM), (0xF80, 0xF84, NSM), (0xF86, 0xF87, NSM),
+    (0xF8D, 0xF97, NSM), (0xF99, 0xFBC, NSM), (0xFC6, 0xFC6, NSM), (0x102D, 0x1030, NSM),
+    (0x1032, 0x1037, NSM), (0x1039, 0x103A, NSM), (0x103D, 0x103E, NSM), (0x1058, 0x1059, NSM),
+    (0x105E, 0x1060, NSM), (0x1071, 0x1074, NSM), (0x1082, 0x1082, NSM), (0x1085, 0x1086, NSM),
+    (0x108D, 0x108D, NSM), (0x109D, 0x109D, NSM), (0x135D, 0x135F, NSM), (0x1390, 0x1399, ON),
+    (0x1400, 0x1400, ON), (0x1680, 0x1680, WS), (0x169B, 0x169C, ON), (0x1712, 0x1714, NSM),
+    (0x1732, 0x1733, NSM), (0x1752, 0x1753, NSM), (0x1772, 0x1773, NSM), (0x17B4, 0x17B5, NSM),
+    (0x17B7, 0x17BD, NSM), (0x17C6, 0x17C6, NSM), (0x17C9, 0x17D3, NSM), (0x17DB, 0x17DB, ET),
+    (0x17DD, 0x17DD, NSM), (0x17F0, 0x17F9, ON), (0x1800, 0x180A, ON), (0x180B, 0x180D, NSM),
+    (0x180E, 0x180E, BN), (0x180F, 0x180F, NSM), (0x1885, 0x1886, NSM), (0x18A9, 0x18A9, NSM),
+    (0x1920, 0x1922, NSM), (0x1927, 0x1928, NSM), (0x1932, 0x1932, NSM), (0x1939, 0x193B, NSM),
+    (0x1940, 0x1940, ON), (0x1944, 0x1945, ON), (0x19DE, 0x19FF, ON), (0x1A17, 0x1A18, NSM),
+    (0x1A1B, 0x1A1B, NSM), (0x1A56, 0x1A56, NSM), (0x1A58, 0x1A5E, NSM), (0x1A60, 0x1A60, NSM),
+    (0x1A62, 0x1A62, NSM), (0x1A65, 0x1A6C, NSM), (0x1A73, 0x1A7C, NSM), (0x1A7F, 0x1A7F, NSM),
+    (0x1AB0, 0x1ACE, NSM), (0x1B00, 0x1B03, NSM), (0x1B34, 0x1B34, NSM), (0x1B36, 0x1B3A, NSM),
+    (0x1B3C, 0x1B3C, NSM), (0x1B42, 0x1B42, NSM), (0x1B6B, 0x1B73, NSM), (0x1B80, 0x1B81, NSM),
+    (0x1BA2, 0x1BA5, NSM), (0x1BA8, 0x1BA9, NSM), (0x1BAB, 0x1BAD, NSM), (0x1BE6, 0x1BE6, NSM),
+    (0x1BE8, 0x1BE9, NSM), (0x1BED, 0x1BED, NSM), (0x1BEF, 0x1BF1, NSM), (0x1C2C, 0x1C33, NSM),
+    (0x1C36, 0x1C37, NSM), (0x1CD0, 0x1CD2, NSM), (0x1CD4, 0x1CE0, NSM), (0x1CE2, 0x1CE8, NSM),
+    (0x1CED, 0x1CED, NSM), (0x1CF4, 0x1CF4, NSM), (0x1CF8, 0x1CF9, NSM), (0x1DC0, 0x1DFF, NSM),
+    (0x1FBD, 0x1FBD, ON), (0x1FBF, 0x1FC1, ON), (0x1FCD, 0x1FCF, ON), (0x1FDD, 0x1FDF, ON),
+    (0x1FED, 0x1FEF, ON), (0x1FFD, 0x1FFE, ON), (0x2000, 0x200A, WS), (0x200B, 0x200D, BN),
+    (0x200F, 0x200F, R), (0x2010, 0x2027, ON), (0x2028, 0x2028, WS), (0x2029, 0x2029, B),
+    (0x202A, 0x202A, LRE), (0x202B, 0x202B, RLE), (0x202C, 0x202C, PDF), (0x202D, 0x202D, LRO),
+    (0x202E, 0x202E, RLO), (0x202F, 0x202F, CS), (0x2030, 0x2034, ET), (0x2035, 0x2043, ON),
+    (0x2044, 0x2044, CS), (0x2045, 0x205E, ON), (0x205F, 0x205F, WS), (0x2060, 0x2065, BN),
+    (0x2066, 0x2066, LRI), (0x2067, 0x2067, RLI), (0x2068, 0x2068, FSI), (0x2069, 0x2069, PDI),
+    (0x206A, 0x206F, BN), (0x2070, 0x2070, EN), (0x2074, 0x2079, EN), (0x207A, 0x207B, ES),
+    (0x207C, 0x207E, ON), (0x2080, 0x2089, EN), (0x208A, 0x208B, ES), (0x208C, 0x208E, ON),
+    (0x20A0, 0x20CF, ET), (0x20D0, 0x20F0, NSM), (0x2100, 0x2101, ON), (0x2103, 0x2106, ON),
+    (0x2108, 0x2109, ON), (0x2114, 0x2114, ON), (0x2116, 0x2118, ON), (0x211E, 0x2123, ON),
+    (0x2125, 0x2125, ON), (0x2127, 0x2127, ON), (0x2129, 0x2129, ON), (0x212E, 0x212E, ET),
+    (0x213A, 0x213B, ON), (0x2140, 0x2144, ON), (0x214A, 0x214D, ON), (0x2150, 0x215F, ON),
+    (0x2189, 0x218B, ON), (0x2190, 0x2211, ON), (0x2212, 0x2212, ES), (0x2213, 0x2213, ET),
+    (0x2214, 0x2335, ON), (0x237B, 0x2394, ON), (0x2396, 0x2426, ON), (0x2440, 0x244A, ON),
+    (0x2460, 0x2487, ON), (0x2488, 0x249B, EN), (0x24EA, 0x26AB, ON), (0x26AD, 0x27FF, ON),
+    (0x2900, 0x2B73, ON), (0x2B76, 0x2B95, ON), (0x2B97, 0x2BFF, ON), (0x2CE5, 0x2CEA, ON),
+    (0x2CEF, 0x2CF1, NSM), (0x2CF9, 0x2CFF, ON), (0x2D7F, 0x2D7F, NSM), (0x2DE0, 0x2DFF, NSM),
+    (0x2E00, 0x2E5D, ON), (0x2E80, 0x2E99, ON), (0x2E9B, 0x2EF3, ON), (0x2F00, 0x2FD5, ON),
+    (0x2FF0, 0x2FFB, ON), (0x3000, 0x3000, WS), (0x3001, 0x3004, ON), (0x3008, 0x3020, ON),
+    (0x302A, 0x302D, NSM), (0x3030, 0x3030, ON), (0x3036, 0x3037, ON), (0x303D, 0x303F, ON),
+    (0x3099, 0x309A, NSM), (0x309B, 0x309C, ON), (0x30A0, 0x30A0, ON), (0x30FB, 0x30FB, ON),
+    (0x31C0, 0x31E3, ON), (0x321D, 0x321E, ON), (0x3250, 0x325F, ON), (0x327C, 0x327E, ON),
+    (0x32B1, 0x32BF, ON), (0x32CC, 0x32CF, ON), (0x3377, 0x337A, ON), (0x33DE, 0x33DF, ON),
+    (0x33FF, 0x33FF, ON), (0x4DC0, 0x4DFF, ON), (0xA490, 0xA4C6, ON), (0xA60D, 0xA60F, ON),
+    (0xA66F, 0xA672, NSM), (0xA673, 0xA673, ON), (0xA674, 0xA67D, NSM), (0xA67E, 0xA67F, ON),
+    (0xA69E, 0xA69F, NSM), (0xA6F0, 0xA6F1, NSM), (0xA700, 0xA721, ON), (0xA788, 0xA788, ON),
+    (0xA802, 0xA802, NSM), (0xA806, 0xA806, NSM), (0xA80B, 0xA80B, NSM), (0xA825, 0xA826, NSM),
+    (0xA828, 0xA82B, ON), (0xA82C, 0xA82C, NSM), (0xA838, 0xA839, ET), (0xA874, 0xA877, ON),
+    (0xA8C4, 0xA8C5, NSM), (0xA8E0, 0xA8F1, NSM), (0xA8FF, 0xA8FF, NSM), (0xA926, 0xA92D, NSM),
+    (0xA947, 0xA951, NSM), (0xA980, 0xA982, NSM), (0xA9B3, 0xA9B3, NSM), (0xA9B6, 0xA9B9, NSM),
+    (0xA9BC, 0xA9BD, NSM), (0xA9E5, 0xA9E5, NSM), (0xAA29, 0xAA2E, NSM), (0xAA31, 0xAA32, NSM),
+    (0xAA35, 0xAA36, NSM), (0xAA43, 0xAA43, NSM), (0xAA4C, 0xAA4C, NSM), (0xAA7C, 0xAA7C, NSM),
+    (0xAAB0, 0xAAB0, NSM), (0xAAB2, 0xAAB4, NSM), (0xAAB7, 0xAAB8, NSM), (0xAABE, 0xAABF, NSM),
+    (0xAAC1, 0xAAC1, NSM), (0xAAEC, 0xAAED, NSM), (0xAAF6, 0xAAF6, NSM), (0xAB6A, 0xAB6B, ON),
+    (0xABE5, 0xABE5, NSM), (0xABE8, 0xABE8, NSM), (0xABED, 0xABED, NSM), (0xFB1D, 0xFB1D, R),
+    (0xFB1E, 0xFB1E, NSM), (0xFB1F, 0xFB28, R), (0xFB29, 0xFB29, ES), (0xFB2A, 0xFB4F, R),
+    (0xFB50, 0xFD3D, AL), (0xFD3E, 0xFD4F, ON), (0xFD50, 0xFDCE, AL), (0xFDCF, 0xFDCF, ON),
+    (0xFDD0, 0xFDEF, BN), (0xFDF0, 0xFDFC, AL), (0xFDFD, 0xFDFF, ON), (0xFE00, 0xFE0F, NSM),
+    (0xFE10, 0xFE19, ON), (0xFE20, 0xFE2F, NSM), (0xFE30, 0xFE4F, ON), (0xFE50, 0xFE50, CS),
+    (0xFE51, 0xFE51, ON), (0xFE52, 0xFE52, CS), (0xFE54, 0xFE54, ON), (0xFE55, 0xFE55, CS),
+    (0xFE56, 0xFE5E, ON), (0xFE5F, 0xFE5F, ET), (0xFE60, 0xFE61, ON), (0xFE62, 0xFE63, ES),
+    (0xFE64, 0xFE66, ON), (0xFE68, 0xFE68, ON), (0xFE69, 0xFE6A, ET), (0xFE6B, 0xFE6B, ON),
+    (0xFE70, 0xFEFE, AL), (0xFEFF, 0xFEFF, BN), (0xFF01, 0xFF02, ON), (0xFF03, 0xFF05, ET),
+    (0xFF06, 0xFF0A, ON), (0xFF0B, 0xFF0B, ES), (0xFF0C, 0xFF0C, CS), (0xFF0D, 0xFF0D, ES),
+    (0xFF0E, 0xFF0F, CS), (0xFF10, 0xFF19, EN), (0xFF1A, 0xFF1A, CS), (0xFF1B, 0xFF20, ON),
+    (0xFF3B, 0xFF40, ON), (0xFF5B, 0xFF65, ON), (0xFFE0, 0xFFE1, ET), (0xFFE2, 0xFFE4, ON),
+    (0xFFE5, 0xFFE6, ET), (0xFFE8, 0xFFEE, ON), (0xFFF0, 0xFFF8, BN), (0xFFF9, 0xFFFD, ON),
+    (0xFFFE, 0xFFFF, BN), (0x10101, 0x10101, ON), (0x10140, 0x1018C, ON), (0x10190, 0x1019C, ON),
+    (0x101A0, 0x101A0, ON), (0x101FD, 0x101FD, NSM), (0x102E0, 0x102E0, NSM),
+    (0x102E1, 0x102FB, EN), (0x10376, 0x1037A, NSM), (0x10800, 0x1091E, R), (0x1091F, 0x1091F, ON),
+    (0x10920, 0x10A00, R), (0x10A01, 0x10A03, NSM), (0x10A04, 0x10A04, R), (0x10A05, 0x10A06, NSM),
+    (0x10A07, 0x10A0B, R), (0x10A0C, 0x10A0F, NSM), (0x10A10, 0x10A37, R), (0x10A38, 0x10A3A, NSM),
+    (0x10A3B, 0x10A3E, R), (0x10A3F, 0x10A3F, NSM), (0x10A40, 0x10AE4, R), (0x10AE5, 0x10AE6, NSM),
+    (0x10AE7, 0x10B38, R), (0x10B39, 0x10B3F, ON), (0x10B40, 0x10CFF, R), (0x10D00, 0x10D23, AL),
+    (0x10D24, 0x10D27, NSM), (0x10D28, 0x10D2F, AL), (0x10D30, 0x10D39, AN), (0x10D3A, 0x10D3F, AL),
+    (0x10D40, 0x10E5F, R), (0x10E60, 0x10E7E, AN), (0x10E7F, 0x10EAA, R), (0x10EAB, 0x10EAC, NSM),
+    (0x10EAD, 0x10F2F, R), (0x10F30, 0x10F45, AL), (0x10F46, 0x10F50, NSM), (0x10F51, 0x10F6F, AL),
+    (0x10F70, 0x10F81, R), (0x10F82, 0x10F85, NSM), (0x10F86, 0x10FFF, R), (0x11001, 0x11001, NSM),
+    (0x11038, 0x11046, NSM), (0x11052, 0x11065, ON), (0x11070, 0x11070, NSM),
+    (0x11073, 0x11074, NSM), (0x1107F, 0x11081, NSM), (0x110B3, 0x110B6, NSM),
+    (0x110B9, 0x110BA, NSM), (0x110C2, 0x110C2, NSM), (0x11100, 0x11102, NSM),
+    (0x11127, 0x1112B, NSM), (0x1112D, 0x11134, NSM), (0x11173, 0x11173, NSM),
+    (0x11180, 0x11181, NSM), (0x111B6, 0x111BE, NSM), (0x111C9, 0x111CC, NSM),
+    (0x111CF, 0x111CF, NSM), (0x1122F, 0x11231, NSM), (0x11234, 0x11234, NSM),
+    (0x11236, 0x11237, NSM), (0x1123E, 0x1123E, NSM), (0x112DF, 0x112DF, NSM),
+    (0x112E3, 0x112EA, NSM), (0x11300, 0x11301, NSM), (0x1133B, 0x1133C, NSM),
+    (0x11340, 0x11340, NSM), (0x11366, 0x1136C, NSM), (0x11370, 0x11374, NSM),
+    (0x11438, 0x1143F, NSM), (0x11442, 0x11444, NSM), (0x11446, 0x11446, NSM),
+    (0x1145E, 0x1145E, NSM), (0x114B3, 0x114B8, NSM), (0x114BA, 0x114BA, NSM),
+    (0x114BF, 0x114C0, NSM), (0x114C2, 0x114C3, NSM), (0x115B2, 0x115B5, NSM),
+    (0x115BC, 0x115BD, NSM), (0x115BF, 0x115C0, NSM), (0x115DC, 0x115DD, NSM),
+    (0x11633, 0x1163A, NSM), (0x1163D, 0x1163D, NSM), (0x1163F, 0x11640, NSM),
+    (0x11660, 0x1166C, ON), (0x116AB, 0x116AB, NSM), (0x116AD, 0x116AD, NSM),
+    (0x116B0, 0x116B5, NSM), (0x116B7, 0x116B7, NSM), (0x1171D, 0x1171F, NSM),
+    (0x11722, 0x11725, NSM), (0x11727, 0x1172B, NSM), (0x1182F, 0x11837, NSM),
+    (0x11839, 0x1183A, NSM), (0x1193B, 0x1193C, NSM), (0x1193E, 0x1193E, NSM),
+    (0x11943, 0x11943, NSM), (0x119D4, 0x119D7, NSM), (0x119DA, 0x119DB, NSM),
+    (0x119E0, 0x119E0, NSM), (0x11A01, 0x11A06, NSM), (0x11A09, 0x11A0A, NSM),
+    (0x11A33, 0x11A38, NSM), (0x11A3B, 0x11A3E, NSM), (0x11A47, 0x11A47, NSM),
+    (0x11A51, 0x11A56, NSM), (0x11A59, 0x11A5B, NSM), (0x11A8A, 0x11A96, NSM),
+    (0x11A98, 0x11A99, NSM), (0x11C30, 0x11C36, NSM), (0x11C38, 0x11C3D, NSM),
+    (0x11C92, 0x11CA7, NSM), (0x11CAA, 0x11CB0, NSM), (0x11CB2, 0x11CB3, NSM),
+    (0x11CB5, 0x11CB6, NSM), (0x11D31, 0x11D36, NSM), (0x11D3A, 0x11D3A, NSM),
+    (0x11D3C, 0x11D3D, NSM), (0x11D3F, 0x11D45, NSM), (0x11D47, 0x11D47, NSM),
+    (0x11D90, 0x11D91, NSM), (0x11D95, 0x11D95, NSM), (0x11D97, 0x11D97, NSM),
+    (0x11EF3, 0x11EF4, NSM), (0x11FD5, 0x11FDC, ON), (0x11FDD, 0x11FE0, ET), (0x11FE1, 0x11FF1, ON),
+    (0x16AF0, 0x16AF4, NSM), (0x16B30, 0x16B36, NSM), (0x16F4F, 0x16F4F, NSM),
+    (0x16F8F, 0x16F92, NSM), (0x16FE2, 0x16FE2, ON), (0x16FE4, 0x16FE4, NSM),
+    (0x1BC9D, 0x1BC9E, NSM), (0x1BCA0, 0x1BCA3, BN), (0x1CF00, 0x1CF2D, NSM),
+    (0x1CF30, 0x1CF46, NSM), (0x1D167, 0x1D169, NSM), (0x1D173, 0x1D17A, BN),
+    (0x1D17B, 0x1D182, NSM), (0x1D185, 0x1D18B, NSM), (0x1D1AA, 0x1D1AD, NSM),
+    (0x1D1E9, 0x1D1EA, ON), (0x1D200, 0x1D241, ON), (0x1D242, 0x1D244, NSM), (0x1D245, 0x1D245, ON),
+    (0x1D300, 0x1D356, ON), (0x1D6DB, 0x1D6DB, ON), (0x1D715, 0x1D715, ON), (0x1D74F, 0x1D74F, ON),
+    (0x1D789, 0x1D789, ON), (0x1D7C3, 0x1D7C3, ON), (0x1D7CE, 0x1D7FF, EN), (0x1DA00, 0x1DA36, NSM),
+    (0x1DA3B, 0x1DA6C, NSM), (0x1DA75, 0x1DA75, NSM), (0x1DA84, 0x1DA84, NSM),
+    (0x1DA9B, 0x1DA9F, NSM), (0x1DAA1, 0x1DAAF, NSM), (0x1E000, 0x1E006, NSM),
+    (0x1E008, 0x1E018, NSM), (0x1E01B, 0x1E021, NSM), (0x1E023, 0x1E024, NSM),
+    (0x1E026, 0x1E02A, NSM), (0x1E130, 0x1E136, NSM), (0x1E2AE, 0x1E2AE, NSM),
+    (0x1E2EC, 0x1E2EF, NSM), (0x1E2FF, 0x1E2FF, ET), (0x1E800, 0x1E8CF, R), (0x1E8D0, 0x1E8D6, NSM),
+    (0x1E8D7, 0x1E943, R), (0x1E944, 0x1E94A, NSM), (0x1E94B, 0x1EC6F, R), (0x1EC70, 0x1ECBF, AL),
+    (0x1ECC0, 0x1ECFF, R), (0x1ED00, 0x1ED4F, AL), (0x1ED50, 0x1EDFF, R), (0x1EE00, 0x1EEEF, AL),
+    (0x1EEF0, 0x1EEF1, ON), (0x1EEF2, 0x1EEFF, AL), (0x1EF00, 0x1EFFF, R), (0x1F000, 0x1F02B, ON),
+    (0x1F030, 0x1F093, ON), (0x1F0A0, 0x1F0AE, ON), (0x1F0B1, 0x1F0BF, ON), (0x1F0C1, 0x1F0CF, ON),
+    (0x1F0D1, 0x1F0F5, ON), (0x1F100, 0x1F10A, EN), (0x1F10B, 0x1F10F, ON), (0x1F12F, 0x1F12F, ON),
+    (0x1F16A, 0x1F16F, ON), (0x1F1AD, 0x1F1AD, ON), (0x1F260, 0x1F265, ON), (0x1F300, 0x1F6D7, ON),
+    (0x1F6DD, 0x1F6EC, ON), (0x1F6F0, 0x1F6FC, ON), (0x1F700, 0x1F773, ON), (0x1F780, 0x1F7D8, ON),
+    (0x1F7E0, 0x1F7EB, ON), (0x1F7F0, 0x1F7F0, ON), (0x1F800, 0x1F80B, ON), (0x1F810, 0x1F847, ON),
+    (0x1F850, 0x1F859, ON), (0x1F860, 0x1F887, ON), (0x1F890, 0x1F8AD, ON), (0x1F8B0, 0x1F8B1, ON),
+    (0x1F900, 0x1FA53, ON), (0x1FA60, 0x1FA6D, ON), (0x1FA70, 0x1FA74, ON), (0x1FA78, 0x1FA7C, ON),
+    (0x1FA80, 0x1FA86, ON), (0x1FA90, 0x1FAAC, ON), (0x1FAB0, 0x1FABA, ON), (0x1FAC0, 0x1FAC5, ON),
+    (0x1FAD0, 0x1FAD9, ON), (0x1FAE0, 0x1FAE7, ON), (0x1FAF0, 0x1FAF6, ON), (0x1FB00, 0x1FB92, ON),
+    (0x1FB94, 0x1FBCA, ON), (0x1FBF0, 0x1FBF9, EN), (0x1FFFE, 0x1FFFF, BN), (0x2FFFE, 0x2FFFF, BN),
+    (0x3FFFE, 0x3FFFF, BN), (0x4FFFE, 0x4FFFF, BN), (0x5FFFE, 0x5FFFF, BN), (0x6FFFE, 0x6FFFF, BN),
+    (0x7FFFE, 0x7FFFF, BN), (0x8FFFE, 0x8FFFF, BN), (0x9FFFE, 0x9FFFF, BN), (0xAFFFE, 0xAFFFF, BN),
+    (0xBFFFE, 0xBFFFF, BN), (0xCFFFE, 0xCFFFF, BN), (0xDFFFE, 0xE00FF, BN), (0xE0100, 0xE01EF, NSM),
+    (0xE01F0, 0xE0FFF, BN), (0xEFFFE, 0xEFFFF, BN), (0xFFFFE, 0xFFFFF, BN),
+    (0x10FFFE, 0x10FFFF, BN),
+];
+
+/// Opening brackets and their closing partners.
+pub(crate) const BRACKET_PAIRS: [(u32, u32); 64] = [
+    (0x28, 0x29), (0x5B, 0x5D), (0x7B, 0x7D), (0xF3A, 0xF3B), (0xF3C, 0xF3D), (0x169B, 0x169C),
+    (0x2045, 0x2046), (0x207D, 0x207E), (0x208D, 0x208E), (0x2308, 0x2309), (0x230A, 0x230B),
+    (0x2329, 0x232A), (0x2768, 0x2769), (0x276A, 0x276B), (0x276C, 0x276D), (0x276E, 0x276F),
+    (0x2770, 0x2771), (0x2772, 0x2773), (0x2774, 0x2775), (0x27C5, 0x27C6), (0x27E6, 0x27E7),
+    (0x27E8, 0x27E9), (0x27EA, 0x27EB), (0x27EC, 0x27ED), (0x27EE, 0x27EF), (0x2983, 0x2984),
+    (0x2985, 0x2986), (0x2987, 0x2988), (0x2989, 0x298A), (0x298B, 0x298C), (0x298D, 0x2990),
+    (0x298F, 0x298E), (0x2991, 0x2992), (0x2993, 0x2994), (0x2995, 0x2996), (0x2997, 0x2998),
+    (0x29D8, 0x29D9), (0x29DA, 0x29DB), (0x29FC, 0x29FD), (0x2E22, 0x2E23), (0x2E24, 0x2E25),
+    (0x2E26, 0x2E27), (0x2E28, 0x2E29), (0x2E55, 0x2E56), (0x2E57, 0x2E58), (0x2E59, 0x2E5A),
+    (0x2E5B, 0x2E5C), (0x3008, 0x3009), (0x300A, 0x300B), (0x300C, 0x300D), (0x300E, 0x300F),
+    (0x3010, 0x3011), (0x3014, 0x3015), (0x3016, 0x3017), (0x3018, 0x3019), (0x301A, 0x301B),
+    (0xFE59, 0xFE5A), (0xFE5B, 0xFE5C), (0xFE5D, 0xFE5E), (0xFF08, 0xFF09), (0xFF3B, 0xFF3D),
+    (0xFF5B, 0xFF5D), (0xFF5F, 0xFF60), (0xFF62, 0xFF63),
+];
+
+/// Characters displayed as a mirrored glyph in right-to-left text.
+pub(crate) const MIRRORED: [(u32, u32); 428] = [
+    (0x28, 0x29), (0x29, 0x28), (0x3C, 0x3E), (0x3E, 0x3C), (0x5B, 0x5D), (0x5D, 0x5B),
+    (0x7B, 0x7D), (0x7D, 0x7B), (0xAB, 0xBB), (0xBB, 0xAB), (0xF3A, 0xF3B), (0xF3B, 0xF3A),
+    (0xF3C, 0xF3D), (0xF3D, 0xF3C), (0x169B, 0x169C), (0x169C, 0x169B), (0x2039, 0x203A),
+    (0x203A, 0x2039), (0x2045, 0x2046), (0x2046, 0x2045), (0x207D, 0x207E), (0x207E, 0x207D),
+    (0x208D, 0x208E), (0x208E, 0x208D), (0x2208, 0x220B), (0x2209, 0x220C), (0x220A, 0x220D),
+    (0x220B, 0x2208), (0x220C, 0x2209), (0x220D, 0x220A), (0x2215, 0x29F5), (0x221F, 0x2BFE),
+    (0x2220, 0x29A3), (0x2221, 0x299B), (0x2222, 0x29A0), (0x2224, 0x2AEE), (0x223C, 0x223D),
+    (0x223D, 0x223C), (0x2243, 0x22CD), (0x2245, 0x224C), (0x224C, 0x2245), (0x2252, 0x2253),
+    (0x2253, 0x2252), (0x2254, 0x2255), (0x2255, 0x2254), (0x2264, 0x2265), (0x2265, 0x2264),
+    (0x2266, 0x2267), (0x2267, 0x2266), (0x2268, 0x2269), (0x2269, 0x2268), (0x226A, 0x226B),
+    (0x226B, 0x226A), (0x226E, 0x226F), (0x226F, 0x226E), (0x2270, 0x2271), (0x2271, 0x2270),
+    (0x2272, 0x2273), (0x2273, 0x2272), (0x2274, 0x2275), (0x2275, 0x2274), (0x2276, 0x2277),
+    (0x2277, 0x2276), (0x2278, 0x2279), (0x2279, 0x2278), (0x227A, 0x227B), (0x227B, 0x227A),
+    (0x227C, 0x227D), (0x227D, 0x227C), (0x227E, 0x227F), (0x227F, 0x227E), (0x2280, 0x2281),
+    (0x2281, 0x2280), (0x2282, 0x2283), (0x2283, 0x2282), (0x2284, 0x2285), (0x2285, 0x2284),
+    (0x2286, 0x2287), (0x2287, 0x2286), (0x2288, 0x2289), (0x2289, 0x2288), (0x228A, 0x228B),
+    (0x228B, 0x228A), (0x228F, 0x2290), (0x2290, 0x228F), (0x2291, 0x2292), (0x2292, 0x2291),
+    (0x2298, 0x29B8), (0x22A2, 0x22A3), (0x22A3, 0x22A2), (0x22A6, 0x2ADE), (0x22A8, 0x2AE4),
+    (0x22A9, 0x2AE3), (0x22AB, 0x2AE5), (0x22B0, 0x22B1), (0x22B1, 0x22B0), (0x22B2, 0x22B3),
+    (0x22B3, 0x22B2), (0x22B4, 0x22B5), (0x22B5, 0x22B4), (0x22B6, 0x22B7), (0x22B7, 0x22B6),
+    (0x22B8, 0x27DC), (0x22C9, 0x22CA), (0x22CA, 0x22C9), (0x22CB, 0x22CC), (0x22CC, 0x22CB),
+    (0x22CD, 0x2243), (0x22D0, 0x22D1), (0x22D1, 0x22D0), (0x22D6, 0x22D7), (0x22D7, 0x22D6),
+    (0x22D8, 0x22D9), (0x22D9, 0x22D8), (0x22DA, 0x22DB), (0x22DB, 0x22DA), (0x22DC, 0x22DD),
+    (0x22DD, 0x22DC), (0x22DE, 0x22DF), (0x22DF, 0x22DE), (0x22E0, 0x22E1), (0x22E1, 0x22E0),
+    (0x22E2, 0x22E3), (0x22E3, 0x22E2), (0x22E4, 0x22E5), (0x22E5, 0x22E4), (0x22E6, 0x22E7),
+    (0x22E7, 0x22E6), (0x22E8, 0x22E9), (0x22E9, 0x22E8), (0x22EA, 0x22EB), (0x22EB, 0x22EA),
+    (0x22EC, 0x22ED), (0x22ED, 0x22EC), (0x22F0, 0x22F1), (0x22F1, 0x22F0), (0x22F2, 0x22FA),
+    (0x22F3, 0x22FB), (0x22F4, 0x22FC), (0x22F6, 0x22FD), (0x22F7, 0x22FE), (0x22FA, 0x22F2),
+    (0x22FB, 0x22F3), (0x22FC, 0x22F4), (0x22FD, 0x22F6), (0x22FE, 0x22F7), (0x2308, 0x2309),
+    (0x2309, 0x2308), (0x230A, 0x230B), (0x230B, 0x230A), (0x2329, 0x232A), (0x232A, 0x2329),
+    (0x2768, 0x2769), (0x2769, 0x2768), (0x276A, 0x276B), (0x276B, 0x276A), (0x276C, 0x276D),
+    (0x276D, 0x276C), (0x276E, 0x276F), (0x276F, 0x276E), (0x2770, 0x2771), (0x2771, 0x2770),
+    (0x2772, 0x2773), (0x2773, 0x2772), (0x2774, 0x2775), (0x2775, 0x2774), (0x27C3, 0x27C4),
+    (0x27C4, 0x27C3), (0x27C5, 0x27C6), (0x27C6, 0x27C5), (0x27C8, 0x27C9), (0x27C9, 0x27C8),
+    (0x27CB, 0x27CD), (0x27CD, 0x27CB), (0x27D5, 0x27D6), (0x27D6, 0x27D5), (0x27DC, 0x22B8),
+    (0x27DD, 0x27DE), (0x27DE, 0x27DD), (0x27E2, 0x27E3), (0x27E3, 0x27E2), (0x27E4, 0x27E5),
+    (0x27E5, 0x27E4), (0x27E6, 0x27E7), (0x27E7, 0x27E6), (0x27E8, 0x27E9), (0x27E9, 0x27E8),
+    (0x27EA, 0x27EB), (0x27EB, 0x27EA), (0x27EC, 0x27ED), (0x27ED, 0x27EC), (0x27EE, 0x27EF),
+    (0x27EF, 0x27EE), (0x2983, 0x2984), (0x2984, 0x2983), (0x2985, 0x2986), (0x2986, 0x2985),
+    (0x2987, 0x2988), (0x2988, 0x2987), (0x2989, 0x298A), (0x298A, 0x2989), (0x298B, 0x298C),
+    (0x298C, 0x298B), (0x298D, 0x2990), (0x298E, 0x298F), (0x298F, 0x298E), (0x2990, 0x298D),
+    (0x2991, 0x2992), (0x2992, 0x2991), (0x2993, 0x2994), (0x2994, 0x2993), (0x2995, 0x2996),
+    (0x2996, 0x2995), (0x2997, 0x2998), (0x2998, 0x2997), (0x299B, 0x2221), (0x29A0, 0x2222),
+    (0x29A3, 0x2220), (0x29A4, 0x29A5), (0x29A5, 0x29A4), (0x29A8, 0x29A9), (0x29A9, 0x29A8),
+    (0x29AA, 0x29AB), (0x29AB, 0x29AA), (0x29AC, 0x29AD), (0x29AD, 0x29AC), (0x29AE, 0x29AF),
+    (0x29AF, 0x29AE), (0x29B8, 0x2298), (0x29C0, 0x29C1), (0x29C1, 0x29C0), (0x29C4, 0x29C5),
+    (0x29C5, 0x29C4), (0x29CF, 0x29D0), (0x29D0, 0x29CF), (0x29D1, 0x29D2), (0x29D2, 0x29D1),
+    (0x29D4, 0x29D5), (0x29D5, 0x29D4), (0x29D8, 0x29D9), (0x29D9, 0x29D8), (0x29DA, 0x29DB),
+    (0x29DB, 0x29DA), (0x29E8, 0x29E9), (0x29E9, 0x29E8), (0x29F5, 0x2215), (0x29F8, 0x29F9),
+    (0x29F9, 0x29F8), (0x29FC, 0x29FD), (0x29FD, 0x29FC), (0x2A2B, 0x2A2C), (0x2A2C, 0x2A2B),
+    (0x2A2D, 0x2A2E), (0x2A2E, 0x2A2D), (0x2A34, 0x2A35), (0x2A35, 0x2A34), (0x2A3C, 0x2A3D),
+    (0x2A3D, 0x2A3C), (0x2A64, 0x2A65), (0x2A65, 0x2A64), (0x2A79, 0x2A7A), (0x2A7A, 0x2A79),
+    (0x2A7B, 0x2A7C), (0x2A7C, 0x2A7B), (0x2A7D, 0x2A7E), (0x2A7E, 0x2A7D), (0x2A7F, 0x2A80),
+    (0x2A80, 0x2A7F), (0x2A81, 0x2A82), (0x2A82, 0x2A81), (0x2A83, 0x2A84), (0x2A84, 0x2A83),
+    (0x2A85, 0x2A86), (0x2A86, 0x2A85), (0x2A87, 0x2A88), (0x2A88, 0x2A87), (0x2A89, 0x2A8A),
+    (0x2A8A, 0x2A89), (0x2A8B, 0x2A8C), (0x2A8C, 0x2A8B), (0x2A8D, 0x2A8E), (0x2A8E, 0x2A8D),
+    (0x2A8F, 0x2A90), (0x2A90, 0x2A8F), (0x2A91, 0x2A92), (0x2A92, 0x2A91), (0x2A93, 0x2A94),
+    (0x2A94, 0x2A93), (0x2A95, 0x2A96), (0x2A96, 0x2A95), (0x2A97, 0x2A98), (0x2A98, 0x2A97),
+    (0x2A99, 0x2A9A), (0x2A9A, 0x2A99), (0x2A9B, 0x2A9C), (0x2A9C, 0x2A9B), (0x2A9D, 0x2A9E),
+    (0x2A9E, 0x2A9D), (0x2A9F, 0x2AA0), (0x2AA0, 0x2A9F), (0x2AA1, 0x2AA2), (0x2AA2, 0x2AA1),
+    (0x2AA6, 0x2AA7), (0x2AA7, 0x2AA6), (0x2AA8, 0x2AA9), (0x2AA9, 0x2AA8), (0x2AAA, 0x2AAB),
+    (0x2AAB, 0x2AAA), (0x2AAC, 0x2AAD), (0x2AAD, 0x2AAC), (0x2AAF, 0x2AB0), (0x2AB0, 0x2AAF),
+    (0x2AB1, 0x2AB2), (0x2AB2, 0x2AB1), (0x2AB3, 0x2AB4), (0x2AB4, 0x2AB3), (0x2AB5, 0x2AB6),
+    (0x2AB6, 0x2AB5), (0x2AB7, 0x2AB8), (0x2AB8, 0x2AB7), (0x2AB9, 0x2ABA), (0x2ABA, 0x2AB9),
+    (0x2ABB, 0x2ABC), (0x2ABC, 0x2ABB), (0x2ABD, 0x2ABE), (0x2ABE, 0x2ABD), (0x2ABF, 0x2AC0),
+    (0x2AC0, 0x2ABF), (0x2AC1, 0x2AC2), (0x2AC2, 0x2AC1), (0x2AC3, 0x2AC4), (0x2AC4, 0x2AC3),
+    (0x2AC5, 0x2AC6), (0x2AC6, 0x2AC5), (0x2AC7, 0x2AC8), (0x2AC8, 0x2AC7), (0x2AC9, 0x2ACA),
+    (0x2ACA, 0x2AC9), (0x2ACB, 0x2ACC), (0x2ACC, 0x2ACB), (0x2ACD, 0x2ACE), (0x2ACE, 0x2ACD),
+    (0x2ACF, 0x2AD0), (0x2AD0, 0x2ACF), (0x2AD1, 0x2AD2), (0x2AD2, 0x2AD1), (0x2AD3, 0x2AD4),
+    (0x2AD4, 0x2AD3), (0x2AD5, 0x2AD6), (0x2AD6, 0x2AD5), (0x2ADE, 0x22A6), (0x2AE3, 0x22A9),
+    (0x2AE4, 0x22A8), (0x2AE5, 0x22AB), (0x2AEC, 0x2AED), (0x2AED, 0x2AEC), (0x2AEE, 0x2224),
+    (0x2AF7, 0x2AF8), (0x2AF8, 0x2AF7), (0x2AF9, 0x2AFA), (0x2AFA, 0x2AF9), (0x2BFE, 0x221F),
+    (0x2E02, 0x2E03), (0x2E03, 0x2E02), (0x2E04, 0x2E05), (0x2E05, 0x2E04), (0x2E09, 0x2E0A),
+    (0x2E0A, 0x2E09), (0x2E0C, 0x2E0D), (0x2E0D, 0x2E0C), (0x2E1C, 0x2E1D), (0x2E1D, 0x2E1C),
+    (0x2E20, 0x2E21), (0x2E21, 0x2E20), (0x2E22, 0x2E23), (0x2E23, 0x2E22), (0x2E24, 0x2E25),
+    (0x2E25, 0x2E24), (0x2E26, 0x2E27), (0x2E27, 0x2E26), (0x2E28, 0x2E29), (0x2E29, 0x2E28),
+    (0x2E55, 0x2E56), (0x2E56, 0x2E55), (0x2E57, 0x2E58), (0x2E58, 0x2E57), (0x2E59, 0x2E5A),
+    (0x2E5A, 0x2E59), (0x2E5B, 0x2E5C), (0x2E5C, 0x2E5B), (0x3008, 0x3009), (0x3009, 0x3008),
+    (0x300A, 0x300B), (0x300B, 0x300A), (0x300C, 0x300D), (0x300D, 0x300C), (0x300E, 0x300F),
+    (0x300F, 0x300E), (0x3010, 0x3011), (0x3011, 0x3010), (0x3014, 0x3015), (0x3015, 0x3014),
+    (0x3016, 0x3017), (0x3017, 0x3016), (0x3018, 0x3019), (0x3019, 0x3018), (0x301A, 0x301B),
+    (0x301B, 0x301A), (0xFE59, 0xFE5A), (0xFE5A, 0xFE59), (0xFE5B, 0xFE5C), (0xFE5C, 0xFE5B),
+    (0xFE5D, 0xFE5E), (0xFE5E, 0xFE5D), (0xFE64, 0xFE65), (0xFE65, 0xFE64), (0xFF08, 0xFF09),
+    (0xFF09, 0xFF08), (0xFF1C, 0xFF1E), (0xFF1E, 0xFF1C), (0xFF3B, 0xFF3D), (0xFF3D, 0xFF3B),
+    (0xFF5B, 0xFF5D), (0xFF5D, 0xFF5B), (0xFF5F, 0xFF60), (0xFF60, 0xFF5F), (0xFF62, 0xFF63),
+    (0xFF63, 0xFF62),
+];
@@ -35,13 +35,17 @@ pub mod platform;
 pub mod drawing;
 pub mod widgets;
 pub mod layout;
+pub mod recording;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 // Public API re-exports
 pub use application::{Application, ApplicationBuilder};
 pub use error::{Error, Result};
 pub use events::{Event, EventHandler, EventLoop};
 pub use window::{Window, WindowBuilder, WindowId};
-pub use drawing::{Canvas, Color, Point, Size, Rect};
+pub use recording::{FrameRecorder, RecordingFormat};
+pub use drawing::{Canvas, Color, Point, Size, Rect, Transform, Path, FillRule, CornerRadii, StrokeStyle, Paint, LinearGradient, RadialGradient, Image, ImageFilter, NinePatch, Insets, Font, FontId, FontDatabase, FontQuery, TextMetrics, TextLayout, LayoutOptions, TextDirection, RichText, TextStyle, DisplayList, RetainedRenderer, BlendMode, ColorSpace, Shadow, RenderTarget, SvgImage, SvgDocument, PdfDocument};
 pub use widgets::{Widget, Button, TextInput, Label};
 pub use layout::{Layout, LayoutManager, FlexLayout};
 pub use platform::{MouseButton, Key, KeyModifiers};
@@ -3,7 +3,8 @@
 //! This module contains cross-platform utilities and helper functions
 //! that are used by all platform-specific implementations.
 
-use crate::{Error, Result};
+use crate::drawing::DrawStats;
+use crate::{Color, Error, Result};
 use std::time::{Duration, Instant};
 
 /// Platform-agnostic timer for frame rate control and animations
@@ -116,32 +117,7 @@ impl ColorUtils {
     
     /// Parse a hex color string (e.g., "#FF0000" or "FF0000")
     pub fn parse_hex(hex: &str) -> Result<(f32, f32, f32, f32)> {
-        let hex = hex.trim_start_matches('#');
-        
-        let (r, g, b, a) = match hex.len() {
-            6 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                (r, g, b, 255)
-            }
-            8 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let a = u8::from_str_radix(&hex[6..8], 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                (r, g, b, a)
-            }
-            3 => {
-                let r = u8::from_str_radix(&hex[0..1].repeat(2), 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let g = u8::from_str_radix(&hex[1..2].repeat(2), 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                let b = u8::from_str_radix(&hex[2..3].repeat(2), 16).map_err(|_| Error::framework("Invalid hex color"))?;
-                (r, g, b, 255)
-            }
-            _ => return Err(Error::framework("Invalid hex color length")),
-        };
-        
-        Ok(Self::rgba_to_float(r, g, b, a))
+        Color::from_hex(hex).map(Color::to_tuple).map_err(|_| Error::framework("Invalid hex color"))
     }
 }
 
@@ -223,8 +199,10 @@ impl KeyboardUtils {
 #[derive(Debug)]
 pub struct PerformanceMonitor {
     frame_times: Vec<Duration>,
+    draw_stats: Vec<DrawStats>,
     max_samples: usize,
     current_index: usize,
+    draw_index: usize,
 }
 
 impl PerformanceMonitor {
@@ -232,19 +210,22 @@ impl PerformanceMonitor {
     pub fn new(max_samples: usize) -> Self {
         Self {
             frame_times: Vec::with_capacity(max_samples),
+            draw_stats: Vec::with_capacity(max_samples),
             max_samples,
             current_index: 0,
+            draw_index: 0,
         }
     }
     
     /// Record a frame time
     pub fn record_frame_time(&mut self, frame_time: Duration) {
-        if self.frame_times.len() < self.max_samples {
-            self.frame_times.push(frame_time);
-        } else {
-            self.frame_times[self.current_index] = frame_time;
-            self.current_index = (self.current_index + 1) % self.max_samples;
-        }
+        push_sample(&mut self.frame_times, &mut self.current_index, self.max_samples, frame_time);
+    }
+    
+    /// Record a frame time together with the drawing calls made during the frame
+    pub fn record_frame(&mut self, frame_time: Duration, draws: DrawStats) {
+        self.record_frame_time(frame_time);
+        push_sample(&mut self.draw_stats, &mut self.draw_index, self.max_samples, draws);
     }
     
     /// Get the average frame time
@@ -277,10 +258,48 @@ impl PerformanceMonitor {
         self.frame_times.iter().max().copied()
     }
     
+    /// Get the frame time that the given fraction (0.0-1.0) of frames stay within,
+    /// such as 0.95 for the 95th percentile
+    pub fn percentile_frame_time(&self, fraction: f64) -> Option<Duration> {
+        let mut sorted = self.frame_times.clone();
+        sorted.sort_unstable();
+        let rank = (fraction.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+    
+    /// Get the average number of drawing calls made by canvases per frame
+    pub fn average_draw_calls(&self) -> f64 {
+        self.average_draws(|stats| stats.draw_calls)
+    }
+    
+    /// Get the average number of calls that reached the backend per frame after batching
+    pub fn average_submissions(&self) -> f64 {
+        self.average_draws(|stats| stats.submissions)
+    }
+    
     /// Clear all recorded samples
     pub fn clear(&mut self) {
         self.frame_times.clear();
+        self.draw_stats.clear();
         self.current_index = 0;
+        self.draw_index = 0;
+    }
+    
+    fn average_draws(&self, count: impl Fn(&DrawStats) -> u64) -> f64 {
+        if self.draw_stats.is_empty() {
+            return 0.0;
+        }
+        self.draw_stats.iter().map(count).sum::<u64>() as f64 / self.draw_stats.len() as f64
+    }
+}
+
+/// Add a sample to a ring buffer of at most `max_samples` entries
+fn push_sample<T>(samples: &mut Vec<T>, index: &mut usize, max_samples: usize, sample: T) {
+    if samples.len() < max_samples {
+        samples.push(sample);
+    } else if max_samples > 0 {
+        samples[*index] = sample;
+        *index = (*index + 1) % max_samples;
     }
 }
 
@@ -320,6 +339,8 @@ mod tests {
         
         let color = ColorUtils::parse_hex("#F00").unwrap();
         assert_eq!(color, (1.0, 0.0, 0.0, 1.0));
+        
+        assert!(matches!(ColorUtils::parse_hex("#12345"), Err(Error::Framework(_))));
     }
 
     #[test]
@@ -342,4 +363,18 @@ mod tests {
         let avg = monitor.average_frame_time();
         assert!(avg.as_millis() >= 15 && avg.as_millis() <= 17);
     }
+
+    #[test]
+    fn test_frame_percentiles_and_draws() {
+        let mut monitor = PerformanceMonitor::new(4);
+        for (millis, draw_calls) in [(10, 300), (12, 200), (30, 400), (11, 100), (13, 300)] {
+            monitor.record_frame(Duration::from_millis(millis), DrawStats { draw_calls, submissions: 4 });
+        }
+        // The oldest sample has been replaced
+        assert_eq!(monitor.percentile_frame_time(0.5), Some(Duration::from_millis(12)));
+        assert_eq!(monitor.percentile_frame_time(0.95), Some(Duration::from_millis(30)));
+        assert_eq!(monitor.average_draw_calls(), 250.0);
+        assert_eq!(monitor.average_submissions(), 4.0);
+        assert_eq!(PerformanceMonitor::new(0).percentile_frame_time(0.5), None);
+    }
 }
\ No newline at end of file
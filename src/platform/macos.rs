@@ -24,6 +24,7 @@ use objc::{
 };
 
 use crate::{Error, Result};
+use crate::drawing::{Path, PathElement, FillRule, Point, Rect, Clip, Transform, Image, ImageFilter, BlendMode, StrokeStyle, TextMetrics, LineCap, LineJoin, raster, effects};
 use super::{
     PlatformBackend, WindowHandle, WindowParams, PlatformEvent, PlatformHandle,
     DrawingContext, MouseButton, Key, KeyModifiers,
@@ -32,7 +33,7 @@ use std::collections::HashMap;
 use std::ptr;
 use std::sync::{Arc, Mutex, Once};
 use std::ffi::{CString, CStr};
-use std::os::raw::{c_void, c_int, c_uint, c_ulong};
+use std::os::raw::{c_void, c_int, c_uint, c_long, c_ulong};
 
 static INIT: Once = Once::new();
 static mut NSAPP_INITIALIZED: bool = false;
@@ -79,6 +80,7 @@ struct MacOSDrawingContext {
     width: u32,
     height: u32,
     graphics_context: Option<id>,
+    clip: Option<Clip>,
 }
 
 unsafe impl Send for MacOSDrawingContext {}
@@ -303,6 +305,7 @@ impl PlatformBackend for MacOSBackend {
                 width: window.width,
                 height: window.height,
                 graphics_context: None,
+                clip: None,
             }))
         } else {
             Err(Error::window("Window not found"))
@@ -476,6 +479,72 @@ struct MacOSWindowHandle {
     nswindow: SafeNSWindow,
 }
 
+impl MacOSDrawingContext {
+    /// Intersect the focused view's clip with the current clip region.
+    unsafe fn apply_clip(&self) {
+        if let Some(ref clip) = self.clip {
+            let bounds = clip.bounds();
+            let rect = NSMakeRect(bounds.x as f64, bounds.y as f64, bounds.width as f64, bounds.height as f64);
+            let _: () = msg_send![class!(NSBezierPath), clipRect: rect];
+            
+            for (path, fill_rule) in clip.paths() {
+                let bezier = bezier_path(path);
+                let winding_rule: c_ulong = match fill_rule {
+                    FillRule::NonZero => 0,
+                    FillRule::EvenOdd => 1,
+                };
+                let _: () = msg_send![bezier, setWindingRule: winding_rule];
+                let _: () = msg_send![bezier, addClip];
+            }
+        }
+    }
+    
+    /// Read back a device-space region of the content view, let `draw`
+    /// modify it in software, then draw it back over the region
+    unsafe fn composite<F: FnOnce(&mut Image, (i32, i32))>(&mut self, region: Rect, draw: F) -> Result<()> {
+        let Some((mut image, (x, y))) = self.read_region(region.expand(1.0))? else {
+            return Ok(());
+        };
+        draw(&mut image, (x, y));
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        self.draw_image(&image, src, Transform::translate(x as f32, y as f32), ImageFilter::Nearest)
+    }
+    
+    /// Read the whole pixels of a device-space region of the content view,
+    /// with the device position of its top-left pixel
+    unsafe fn read_region(&self, region: Rect) -> Result<Option<(Image, (i32, i32))>> {
+        let window = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+        let region = match region.intersection(window) {
+            Some(region) => region,
+            None => return Ok(None),
+        };
+        let x = region.x.floor() as i32;
+        let y = region.y.floor() as i32;
+        let width = (region.right().ceil() as i32 - x).max(0) as u32;
+        let height = (region.bottom().ceil() as i32 - y).max(0) as u32;
+        if width == 0 || height == 0 {
+            return Ok(None);
+        }
+        
+        let nswindow = self.nswindow.as_id();
+        let content_view: id = msg_send![nswindow, contentView];
+        if content_view == nil {
+            return Err(Error::drawing("Window has no content view"));
+        }
+        let _: () = msg_send![content_view, lockFocus];
+        let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+        let rect = NSMakeRect(x as f64, y as f64, width as f64, height as f64);
+        let bitmap: id = msg_send![bitmap, initWithFocusedViewRect: rect];
+        let _: () = msg_send![content_view, unlockFocus];
+        if bitmap == nil {
+            return Err(Error::drawing("Failed to read window pixels"));
+        }
+        let image = image_from_bitmap(bitmap, width, height);
+        let _: () = msg_send![bitmap, release];
+        Ok(Some((image?, (x, y))))
+    }
+}
+
 impl DrawingContext for MacOSDrawingContext {
     fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
         unsafe {
@@ -505,6 +574,7 @@ impl DrawingContext for MacOSDrawingContext {
             
             if content_view != nil {
                 let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
                 
                 let ns_color = NSColor::colorWithCalibratedRed_green_blue_alpha_(
                     nil,
@@ -525,13 +595,14 @@ impl DrawingContext for MacOSDrawingContext {
         Ok(())
     }
     
-    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke_width: f32) -> Result<()> {
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
         unsafe {
             let nswindow = self.nswindow.as_id();
             let content_view: id = msg_send![nswindow, contentView];
             
             if content_view != nil {
                 let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
                 
                 let ns_color = NSColor::colorWithCalibratedRed_green_blue_alpha_(
                     nil,
@@ -544,7 +615,7 @@ impl DrawingContext for MacOSDrawingContext {
                 
                 let rect = NSMakeRect(x as f64, y as f64, width as f64, height as f64);
                 let path = NSBezierPath::bezierPathWithRect_(nil, rect);
-                let _: () = msg_send![path, setLineWidth: stroke_width as f64];
+                apply_stroke_style(path, stroke);
                 let _: () = msg_send![path, stroke];
                 
                 let _: () = msg_send![content_view, unlockFocus];
@@ -560,9 +631,118 @@ impl DrawingContext for MacOSDrawingContext {
             
             if content_view != nil {
                 let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
+                draw_string(text, NSMakePoint(x as f64, y as f64), color);
+                let _: () = msg_send![content_view, unlockFocus];
+            }
+        }
+        Ok(())
+    }
+    
+    fn measure_text(&self, text: &str) -> Option<TextMetrics> {
+        unsafe {
+            let font = NSFont::systemFontOfSize_(nil, 14.0);
+            let attributes = NSDictionary::dictionaryWithObjects_forKeys_(
+                nil,
+                NSArray::arrayWithObjects_(nil, &[font]),
+                NSArray::arrayWithObjects_(nil, &[NSString::alloc(nil).init_str("NSFont")]),
+            );
+            let width = |prefix: &str| {
+                let string = NSString::alloc(nil).init_str(prefix);
+                let size: NSSize = msg_send![string, sizeWithAttributes: attributes];
+                let _: () = msg_send![string, release];
+                size.width as f32
+            };
+            
+            // Measuring growing prefixes keeps the kerning between characters
+            let mut previous = 0.0;
+            let advances: Vec<f32> = text
+                .char_indices()
+                .map(|(index, ch)| {
+                    let right = width(&text[..index + ch.len_utf8()]);
+                    let advance = right - previous;
+                    previous = right;
+                    advance
+                })
+                .collect();
+            let ascender: f64 = msg_send![font, ascender];
+            let descender: f64 = msg_send![font, descender];
+            Some(TextMetrics::from_advances(text, advances, ascender as f32, -descender as f32))
+        }
+    }
+    
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        unsafe {
+            let nswindow = self.nswindow.as_id();
+            let content_view: id = msg_send![nswindow, contentView];
+            
+            if content_view != nil {
+                let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
+                
+                let _: () = msg_send![class!(NSGraphicsContext), saveGraphicsState];
+                concat_transform(transform);
+                draw_string(text, NSMakePoint(0.0, 0.0), color);
+                let _: () = msg_send![class!(NSGraphicsContext), restoreGraphicsState];
+                
+                let _: () = msg_send![content_view, unlockFocus];
+            }
+        }
+        Ok(())
+    }
+    
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        unsafe {
+            let nswindow = self.nswindow.as_id();
+            let content_view: id = msg_send![nswindow, contentView];
+            
+            if content_view != nil {
+                let bitmap = bitmap_rep(image);
+                if bitmap == nil {
+                    return Err(Error::drawing("Failed to create bitmap"));
+                }
+                
+                let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
+                
+                let _: () = msg_send![class!(NSGraphicsContext), saveGraphicsState];
+                let context: id = msg_send![class!(NSGraphicsContext), currentContext];
+                let interpolation: c_ulong = match filter {
+                    ImageFilter::Nearest => 1,
+                    ImageFilter::Bilinear => 3,
+                };
+                let _: () = msg_send![context, setImageInterpolation: interpolation];
+                concat_transform(transform);
+                
+                // Source rectangles of image reps are measured from the bottom
+                let dst = NSMakeRect(src.x as f64, src.y as f64, src.width as f64, src.height as f64);
+                let from = NSMakeRect(src.x as f64, (image.height() as f32 - src.bottom()) as f64, src.width as f64, src.height as f64);
+                let _: BOOL = msg_send![bitmap,
+                    drawInRect: dst
+                    fromRect: from
+                    operation: 2 as c_ulong
+                    fraction: 1.0f64
+                    respectFlipped: YES
+                    hints: nil
+                ];
+                
+                let _: () = msg_send![class!(NSGraphicsContext), restoreGraphicsState];
+                let _: () = msg_send![content_view, unlockFocus];
+                let _: () = msg_send![bitmap, release];
+            }
+        }
+        Ok(())
+    }
+    
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        unsafe {
+            let nswindow = self.nswindow.as_id();
+            let content_view: id = msg_send![nswindow, contentView];
+            
+            if content_view != nil {
+                let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
                 
-                let ns_string = NSString::alloc(nil).init_str(text);
-                let font = NSFont::systemFontOfSize_(nil, 14.0);
                 let ns_color = NSColor::colorWithCalibratedRed_green_blue_alpha_(
                     nil,
                     color.0 as f64,
@@ -570,19 +750,43 @@ impl DrawingContext for MacOSDrawingContext {
                     color.2 as f64,
                     color.3 as f64,
                 );
+                let _: () = msg_send![ns_color, set];
                 
-                let point = NSMakePoint(x as f64, y as f64);
-                let _: () = msg_send![ns_string,
-                    drawAtPoint: point
-                    withAttributes: NSDictionary::dictionaryWithObjects_forKeys_(
-                        nil,
-                        NSArray::arrayWithObjects_(nil, &[font, ns_color]),
-                        NSArray::arrayWithObjects_(nil, &[
-                            NSString::alloc(nil).init_str("NSFont"),
-                            NSString::alloc(nil).init_str("NSForegroundColor")
-                        ])
-                    )
-                ];
+                let bezier = bezier_path(path);
+                let winding_rule: c_ulong = match fill_rule {
+                    FillRule::NonZero => 0,
+                    FillRule::EvenOdd => 1,
+                };
+                let _: () = msg_send![bezier, setWindingRule: winding_rule];
+                let _: () = msg_send![bezier, fill];
+                
+                let _: () = msg_send![content_view, unlockFocus];
+            }
+        }
+        Ok(())
+    }
+    
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        unsafe {
+            let nswindow = self.nswindow.as_id();
+            let content_view: id = msg_send![nswindow, contentView];
+            
+            if content_view != nil {
+                let _: () = msg_send![content_view, lockFocus];
+                self.apply_clip();
+                
+                let ns_color = NSColor::colorWithCalibratedRed_green_blue_alpha_(
+                    nil,
+                    color.0 as f64,
+                    color.1 as f64,
+                    color.2 as f64,
+                    color.3 as f64,
+                );
+                let _: () = msg_send![ns_color, set];
+                
+                let bezier = bezier_path(path);
+                apply_stroke_style(bezier, stroke);
+                let _: () = msg_send![bezier, stroke];
                 
                 let _: () = msg_send![content_view, unlockFocus];
             }
@@ -590,6 +794,35 @@ impl DrawingContext for MacOSDrawingContext {
         Ok(())
     }
     
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        unsafe {
+            self.composite(region, |target, origin| {
+                effects::blur_region(target, origin, region, radius);
+            })
+        }
+    }
+    
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let layer_origin = (origin.x as i32, origin.y as i32);
+        let region = Rect::new(origin.x, origin.y, layer.width() as f32, layer.height() as f32);
+        unsafe {
+            self.composite(region, |target, target_origin| {
+                raster::composite_layer(target, target_origin, layer, layer_origin, opacity, blend_mode);
+            })
+        }
+    }
+    
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        self.clip = clip.cloned();
+        Ok(())
+    }
+    
+    fn read_pixels(&mut self) -> Result<Image> {
+        let window = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+        let region = unsafe { self.read_region(window)? };
+        Ok(region.map_or_else(|| Image::new(self.width, self.height), |(image, _)| image))
+    }
+    
     fn present(&mut self) -> Result<()> {
         unsafe {
             let nswindow = self.nswindow.as_id();
@@ -608,6 +841,196 @@ impl DrawingContext for MacOSDrawingContext {
     }
 }
 
+/// Matrix layout of `NSAffineTransform`.
+#[repr(C)]
+struct NSAffineTransformStruct {
+    m11: f64,
+    m12: f64,
+    m21: f64,
+    m22: f64,
+    t_x: f64,
+    t_y: f64,
+}
+
+unsafe impl objc::Encode for NSAffineTransformStruct {
+    fn encode() -> objc::Encoding {
+        unsafe { objc::Encoding::from_str("{_NSAffineTransformStruct=dddddd}") }
+    }
+}
+
+/// Concatenate an affine transform onto the current graphics state.
+unsafe fn concat_transform(transform: Transform) {
+    let matrix = NSAffineTransformStruct {
+        m11: transform.a as f64,
+        m12: transform.b as f64,
+        m21: transform.c as f64,
+        m22: transform.d as f64,
+        t_x: transform.tx as f64,
+        t_y: transform.ty as f64,
+    };
+    let affine: id = msg_send![class!(NSAffineTransform), transform];
+    let _: () = msg_send![affine, setTransformStruct: matrix];
+    let _: () = msg_send![affine, concat];
+}
+
+/// Create a bitmap image rep holding a copy of an image. The caller releases it.
+unsafe fn bitmap_rep(image: &Image) -> id {
+    let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+    let color_space = NSString::alloc(nil).init_str("NSDeviceRGBColorSpace");
+    let bitmap: id = msg_send![bitmap,
+        initWithBitmapDataPlanes: ptr::null_mut::<*mut u8>()
+        pixelsWide: image.width() as c_long
+        pixelsHigh: image.height() as c_long
+        bitsPerSample: 8 as c_long
+        samplesPerPixel: 4 as c_long
+        hasAlpha: YES
+        isPlanar: NO
+        colorSpaceName: color_space
+        bitmapFormat: 2 as c_ulong
+        bytesPerRow: (image.width() * 4) as c_long
+        bitsPerPixel: 32 as c_long
+    ];
+    if bitmap != nil {
+        // Format 2 is non-premultiplied alpha, matching `Image`
+        let data: *mut u8 = msg_send![bitmap, bitmapData];
+        ptr::copy_nonoverlapping(image.pixels().as_ptr(), data, image.pixels().len());
+    }
+    bitmap
+}
+
+/// Copy a 32-bit bitmap into a `width` × `height` image, sampling one
+/// backing pixel per point on high-resolution displays.
+unsafe fn image_from_bitmap(bitmap: id, width: u32, height: u32) -> Result<Image> {
+    let bits_per_pixel: c_long = msg_send![bitmap, bitsPerPixel];
+    let planar: BOOL = msg_send![bitmap, isPlanar];
+    let data: *const u8 = msg_send![bitmap, bitmapData];
+    if bits_per_pixel != 32 || planar == YES || data.is_null() {
+        return Err(Error::drawing("Unsupported window pixel format"));
+    }
+    let has_alpha: BOOL = msg_send![bitmap, hasAlpha];
+    let pixels_wide: c_long = msg_send![bitmap, pixelsWide];
+    let pixels_high: c_long = msg_send![bitmap, pixelsHigh];
+    let bytes_per_row: c_long = msg_send![bitmap, bytesPerRow];
+    // Format bit 0 puts alpha first, bit 1 leaves the color unpremultiplied
+    let format: c_ulong = msg_send![bitmap, bitmapFormat];
+    let alpha_first = format & 1 != 0;
+    let premultiplied = format & 2 == 0;
+    
+    let mut image = Image::new(width, height);
+    for (index, pixel) in image.pixels_mut().chunks_exact_mut(4).enumerate() {
+        let x = (index as u32 % width) as c_long * pixels_wide / width as c_long;
+        let y = (index as u32 / width) as c_long * pixels_high / height as c_long;
+        let offset = (y * bytes_per_row + x * 4) as usize;
+        let source = std::slice::from_raw_parts(data.add(offset), 4);
+        let mut rgba = if alpha_first {
+            [source[1], source[2], source[3], source[0]]
+        } else {
+            [source[0], source[1], source[2], source[3]]
+        };
+        if has_alpha == NO {
+            rgba[3] = 255;
+        } else if premultiplied && rgba[3] > 0 && rgba[3] < 255 {
+            for channel in &mut rgba[..3] {
+                *channel = (*channel as u32 * 255 / rgba[3] as u32).min(255) as u8;
+            }
+        }
+        pixel.copy_from_slice(&rgba);
+    }
+    Ok(image)
+}
+
+/// Draw a string with the system font into the focused view.
+unsafe fn draw_string(text: &str, point: NSPoint, color: (f32, f32, f32, f32)) {
+    let ns_string = NSString::alloc(nil).init_str(text);
+    let font = NSFont::systemFontOfSize_(nil, 14.0);
+    let ns_color = NSColor::colorWithCalibratedRed_green_blue_alpha_(
+        nil,
+        color.0 as f64,
+        color.1 as f64,
+        color.2 as f64,
+        color.3 as f64,
+    );
+    
+    let _: () = msg_send![ns_string,
+        drawAtPoint: point
+        withAttributes: NSDictionary::dictionaryWithObjects_forKeys_(
+            nil,
+            NSArray::arrayWithObjects_(nil, &[font, ns_color]),
+            NSArray::arrayWithObjects_(nil, &[
+                NSString::alloc(nil).init_str("NSFont"),
+                NSString::alloc(nil).init_str("NSForegroundColor")
+            ])
+        )
+    ];
+}
+
+/// Apply width, caps, joins and dashes to an NSBezierPath.
+unsafe fn apply_stroke_style(bezier: id, stroke: &StrokeStyle) {
+    let cap: c_ulong = match stroke.cap {
+        LineCap::Butt => 0,
+        LineCap::Round => 1,
+        LineCap::Square => 2,
+    };
+    let join: c_ulong = match stroke.join {
+        LineJoin::Miter => 0,
+        LineJoin::Round => 1,
+        LineJoin::Bevel => 2,
+    };
+    
+    let _: () = msg_send![bezier, setLineWidth: stroke.width as f64];
+    let _: () = msg_send![bezier, setLineCapStyle: cap];
+    let _: () = msg_send![bezier, setLineJoinStyle: join];
+    let _: () = msg_send![bezier, setMiterLimit: stroke.miter_limit as f64];
+    
+    let dashes: Vec<f64> = stroke.dash_pattern().iter().map(|d| *d as f64).collect();
+    if !dashes.is_empty() {
+        let _: () = msg_send![bezier,
+            setLineDash: dashes.as_ptr()
+            count: dashes.len() as c_long
+            phase: stroke.dash_offset as f64
+        ];
+    }
+}
+
+/// Build an autoreleased NSBezierPath from a framework path.
+unsafe fn bezier_path(path: &Path) -> id {
+    let ns_point = |p: Point| NSMakePoint(p.x as f64, p.y as f64);
+    let bezier: id = msg_send![class!(NSBezierPath), bezierPath];
+    let mut start = Point::zero();
+    let mut current = Point::zero();
+    
+    for element in path.elements() {
+        match *element {
+            PathElement::MoveTo(p) => {
+                let _: () = msg_send![bezier, moveToPoint: ns_point(p)];
+                start = p;
+                current = p;
+            }
+            PathElement::LineTo(p) => {
+                let _: () = msg_send![bezier, lineToPoint: ns_point(p)];
+                current = p;
+            }
+            PathElement::QuadTo(c, p) => {
+                // Raise the quadratic to an equivalent cubic
+                let c1 = Point::new(current.x + (c.x - current.x) * 2.0 / 3.0, current.y + (c.y - current.y) * 2.0 / 3.0);
+                let c2 = Point::new(p.x + (c.x - p.x) * 2.0 / 3.0, p.y + (c.y - p.y) * 2.0 / 3.0);
+                let _: () = msg_send![bezier, curveToPoint: ns_point(p) controlPoint1: ns_point(c1) controlPoint2: ns_point(c2)];
+                current = p;
+            }
+            PathElement::CubicTo(c1, c2, p) => {
+                let _: () = msg_send![bezier, curveToPoint: ns_point(p) controlPoint1: ns_point(c1) controlPoint2: ns_point(c2)];
+                current = p;
+            }
+            PathElement::Close => {
+                let _: () = msg_send![bezier, closePath];
+                current = start;
+            }
+        }
+    }
+    
+    bezier
+}
+
 // Helper functions for key mapping
 fn keycode_to_key(keycode: u16) -> Key {
     match keycode {
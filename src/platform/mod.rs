@@ -5,6 +5,8 @@
 //! processing, and system integration in a cross-platform manner.
 
 use crate::Result;
+use crate::drawing::raster;
+use crate::drawing::{Path, FillRule, CornerRadii, Rect, Point, StrokeStyle, Paint, Clip, Transform, Image, ImageFilter, BlendMode, Font, GlyphPosition, TextMetrics};
 use std::any::Any;
 use std::sync::{Arc, Mutex};
 
@@ -255,6 +257,9 @@ impl Default for KeyModifiers {
     }
 }
 
+/// Glyph advance assumed when a backend cannot measure its font.
+const FALLBACK_GLYPH_ADVANCE: f32 = 8.0;
+
 /// Drawing context trait for platform-specific rendering
 pub trait DrawingContext: Send + Sync {
     /// Clear the drawing surface with the given color
@@ -264,18 +269,188 @@ pub trait DrawingContext: Send + Sync {
     fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()>;
     
     /// Draw a rectangle outline
-    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), width_: f32) -> Result<()>;
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()>;
     
     /// Draw text
     fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()>;
     
+    /// Draw text whose baseline origin is mapped to device space by `transform`
+    ///
+    /// The default places each glyph at its transformed origin without
+    /// scaling or rotating the glyph itself.
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        let positions: Vec<f32> = match self.measure_text(text) {
+            Some(metrics) => metrics.glyphs.iter().map(|glyph| glyph.x).collect(),
+            None => (0..text.chars().count()).map(|i| i as f32 * FALLBACK_GLYPH_ADVANCE).collect(),
+        };
+        let mut buffer = [0u8; 4];
+        for (ch, x) in text.chars().zip(positions) {
+            let origin = transform.transform_point(Point::new(x, 0.0));
+            self.draw_text(ch.encode_utf8(&mut buffer), origin.x, origin.y, color)?;
+        }
+        Ok(())
+    }
+    
+    /// Measure text as `draw_text` draws it, in device pixels
+    ///
+    /// Backends that cannot measure their text font return `None`.
+    fn measure_text(&self, _text: &str) -> Option<TextMetrics> {
+        None
+    }
+    
+    /// Draw glyphs of a loaded font, positioned relative to a baseline origin
+    /// that is mapped to device space by `transform`
+    ///
+    /// Backends that embed fonts draw the glyphs and return `true`; the
+    /// default returns `false` and the canvas rasterizes them instead.
+    fn draw_glyph_run(&mut self, _font: &Font, _glyphs: &[GlyphPosition], _size: f32, _transform: Transform, _color: (f32, f32, f32, f32)) -> Result<bool> {
+        Ok(false)
+    }
+    
+    /// Draw the `src` region of an image whose pixel space is mapped to device space by `transform`
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()>;
+    
+    /// Composite a layer image whose top-left pixel sits at device `origin`
+    ///
+    /// Backends that cannot read back what is already drawn only support
+    /// normal blending; the default scales the layer's alpha by `opacity`
+    /// and draws it as an image for every blend mode.
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let _ = blend_mode;
+        let mut faded = layer.clone();
+        if opacity < 1.0 {
+            for pixel in faded.pixels_mut().chunks_exact_mut(4) {
+                pixel[3] = (pixel[3] as f32 * opacity.max(0.0)).round() as u8;
+            }
+        }
+        let src = Rect::new(0.0, 0.0, layer.width() as f32, layer.height() as f32);
+        self.draw_image(&faded, src, Transform::translate(origin.x, origin.y), ImageFilter::Nearest)
+    }
+    
+    /// Blur what is already drawn inside a device-space region
+    ///
+    /// Backends that cannot read back what is drawn leave the region as is.
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        let _ = (region, radius);
+        Ok(())
+    }
+    
+    /// Fill a path given in device coordinates
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()>;
+    
+    /// Stroke a path given in device coordinates
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()>;
+    
+    /// Fill a path with a gradient or pattern paint given in device coordinates
+    ///
+    /// The default rasterizes the paint in software over the visible part of
+    /// the path and draws the result with [`draw_image`](Self::draw_image).
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        if let Some(color) = paint.as_solid() {
+            return self.fill_path(path, color.to_tuple(), fill_rule);
+        }
+        draw_rasterized(self, path.bounds(), |image, origin| {
+            raster::fill_path(image, origin, path, paint, fill_rule);
+        })
+    }
+    
+    /// Stroke a path with a gradient or pattern paint given in device coordinates
+    ///
+    /// The default rasterizes the paint in software like
+    /// [`fill_path_paint`](Self::fill_path_paint).
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        if let Some(color) = paint.as_solid() {
+            return self.stroke_path(path, color.to_tuple(), stroke);
+        }
+        let region = path.bounds().expand(stroke.width.max(1.0) * stroke.miter_limit);
+        draw_rasterized(self, region, |image, origin| {
+            raster::stroke_path(image, origin, path, paint, stroke);
+        })
+    }
+    
+    /// Draw a filled rectangle with rounded corners
+    fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32)) -> Result<()> {
+        let path = Path::rounded_rect(rect, radii);
+        self.fill_path(&path, color, FillRule::NonZero)
+    }
+    
+    /// Draw a rounded rectangle outline
+    fn stroke_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let path = Path::rounded_rect(rect, radii);
+        self.stroke_path(&path, color, stroke)
+    }
+    
+    /// Draw a filled ellipse
+    fn fill_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        let path = Path::ellipse(Point::new(cx, cy), radius_x, radius_y);
+        self.fill_path(&path, color, FillRule::NonZero)
+    }
+    
+    /// Draw an ellipse outline
+    fn stroke_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        let path = Path::ellipse(Point::new(cx, cy), radius_x, radius_y);
+        self.stroke_path(&path, color, stroke)
+    }
+    
+    /// Limit subsequent drawing to a device-space clip region, or remove the clip
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()>;
+    
     /// Present/flush the drawing operations
     fn present(&mut self) -> Result<()>;
     
+    /// Read back the whole drawing surface
+    ///
+    /// Backends that cannot read back what is drawn return an error.
+    fn read_pixels(&mut self) -> Result<Image> {
+        Err(crate::Error::drawing("Reading back pixels is not supported by this backend"))
+    }
+    
+    /// Present only the given device-space regions
+    ///
+    /// The default presents the whole surface.
+    fn present_rects(&mut self, _rects: &[Rect]) -> Result<()> {
+        self.present()
+    }
+    
+    /// Whether the canvas should merge consecutive text and rectangle draws
+    /// into one image before they reach this context
+    ///
+    /// Worth it for backends where every call is a request to a display server.
+    fn supports_batching(&self) -> bool {
+        false
+    }
+    
     /// Get the size of the drawing surface
     fn size(&self) -> (u32, u32);
 }
 
+/// Rasterize into a transparent image covering the visible part of a
+/// device-space region, then draw it with the context's `draw_image`.
+fn draw_rasterized<C, F>(context: &mut C, region: Rect, draw: F) -> Result<()>
+where
+    C: DrawingContext + ?Sized,
+    F: FnOnce(&mut Image, (i32, i32)),
+{
+    let (width, height) = context.size();
+    let surface = Rect::new(0.0, 0.0, width as f32, height as f32);
+    let region = match region.expand(1.0).intersection(surface) {
+        Some(region) => region,
+        None => return Ok(()),
+    };
+    let x = region.x.floor() as i32;
+    let y = region.y.floor() as i32;
+    let width = (region.right().ceil() as i32 - x).max(0) as u32;
+    let height = (region.bottom().ceil() as i32 - y).max(0) as u32;
+    if width == 0 || height == 0 {
+        return Ok(());
+    }
+    
+    let mut image = Image::new(width, height);
+    draw(&mut image, (x, y));
+    let src = Rect::new(0.0, 0.0, width as f32, height as f32);
+    context.draw_image(&image, src, Transform::translate(x as f32, y as f32), ImageFilter::Nearest)
+}
+
 /// Factory function to create platform-specific backend
 pub fn create_platform_backend() -> Result<Box<dyn PlatformBackend>> {
     #[cfg(windows)]
@@ -362,4 +537,80 @@ pub fn get_platform_capabilities() -> PlatformCapabilities {
             metal_support: true,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing::surface::ImageContext;
+    use crate::drawing::{Color, LinearGradient};
+
+    /// Context that only implements the required methods, like the native backends.
+    struct Minimal(ImageContext);
+
+    impl DrawingContext for Minimal {
+        fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.clear(color)
+        }
+
+        fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.fill_rect(x, y, width, height, color)
+        }
+
+        fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.0.stroke_rect(x, y, width, height, color, stroke)
+        }
+
+        fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+            self.0.draw_text(text, x, y, color)
+        }
+
+        fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+            self.0.draw_image(image, src, transform, filter)
+        }
+
+        fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+            self.0.fill_path(path, color, fill_rule)
+        }
+
+        fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+            self.0.stroke_path(path, color, stroke)
+        }
+
+        fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+            self.0.set_clip(clip)
+        }
+
+        fn present(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn size(&self) -> (u32, u32) {
+            self.0.size()
+        }
+    }
+
+    #[test]
+    fn test_default_paint_fallback() {
+        let gradient = Paint::LinearGradient(
+            LinearGradient::new(Point::new(0.0, 0.0), Point::new(20.0, 0.0))
+                .stop(0.0, Color::RED)
+                .stop(1.0, Color::BLUE),
+        );
+        let path = Path::rect(Rect::new(-5.0, 2.0, 30.0, 12.0));
+        let stroke = StrokeStyle::new(2.0);
+
+        let expected = Arc::new(Mutex::new(Image::new(16, 16)));
+        let mut software = ImageContext::new(expected.clone(), (0, 0), (16, 16));
+        software.fill_path_paint(&path, &gradient, FillRule::NonZero).unwrap();
+        software.stroke_path_paint(&path, &gradient, &stroke).unwrap();
+
+        let actual = Arc::new(Mutex::new(Image::new(16, 16)));
+        let mut minimal = Minimal(ImageContext::new(actual.clone(), (0, 0), (16, 16)));
+        minimal.fill_path_paint(&path, &gradient, FillRule::NonZero).unwrap();
+        minimal.stroke_path_paint(&path, &gradient, &stroke).unwrap();
+
+        let (expected, actual) = (expected.lock().unwrap(), actual.lock().unwrap());
+        assert_ne!(actual.pixel(1, 8), actual.pixel(14, 8));
+        assert_eq!(actual.pixels(), expected.pixels());
+    }
+}
@@ -12,6 +12,10 @@ use x11::{
         XCreateSimpleWindow, XSelectInput, XMapWindow, XUnmapWindow, XDestroyWindow,
         XNextEvent, XPending, XStoreName, XMoveResizeWindow, XGetWindowAttributes,
         XClearWindow, XFillRectangle, XDrawRectangle, XDrawString, XFlush,
+        XFillPolygon, XDrawLines, XFillArc, XDrawArc, XSetFillRule, XSetLineAttributes, XSetDashes, XPoint,
+        XGetImage, XPutImage, XGetPixel, XPutPixel, XDestroyImage, XImage, XAllPlanes, ZPixmap,
+        XCreateRegion, XDestroyRegion, XPolygonRegion, XIntersectRegion, XUnionRectWithRegion,
+        XSetRegion, XSetClipMask, XRectangle,
         ExposureMask, KeyPressMask, KeyReleaseMask, ButtonPressMask, ButtonReleaseMask,
         PointerMotionMask, StructureNotifyMask, FocusChangeMask, EnterWindowMask, 
         LeaveWindowMask, SubstructureNotifyMask,
@@ -26,12 +30,14 @@ use x11::{
 };
 
 use crate::{Error, Result};
+use crate::drawing::{Path, FillRule, Point, Rect, Color, Image, ImageFilter, Paint, Clip, Transform, BlendMode, StrokeStyle, TextMetrics, LineCap, LineJoin, raster, effects, path::DEFAULT_TOLERANCE};
 use super::{
     PlatformBackend, WindowHandle, WindowParams, PlatformEvent, PlatformHandle,
     DrawingContext, MouseButton, Key, KeyModifiers,
 };
 use std::collections::HashMap;
 use std::ptr;
+use std::sync::{Arc, Mutex};
 use std::ffi::{CString, CStr};
 use std::os::raw::{c_char, c_int, c_uint, c_ulong};
 
@@ -42,8 +48,13 @@ pub struct UnixBackend {
     next_window_id: u64,
     event_queue: Vec<PlatformEvent>,
     wm_delete_window: c_ulong,
+    display_open: DisplayOpen,
 }
 
+/// Whether the X connection is still open, shared with drawing contexts
+/// that can outlive it. It is locked while the connection is closed.
+type DisplayOpen = Arc<Mutex<bool>>;
+
 unsafe impl Send for UnixBackend {}
 unsafe impl Sync for UnixBackend {}
 
@@ -61,11 +72,24 @@ struct UnixDrawingContext {
     width: u32,
     height: u32,
     gc: c_ulong, // Graphics Context
+    display_open: DisplayOpen,
 }
 
 unsafe impl Send for UnixDrawingContext {}
 unsafe impl Sync for UnixDrawingContext {}
 
+impl Drop for UnixDrawingContext {
+    fn drop(&mut self) {
+        // A canvas kept past the backend's cleanup must not touch the closed display
+        let open = self.display_open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *open {
+            unsafe {
+                xlib::XFreeGC(self.display, self.gc as *mut xlib::_XGC);
+            }
+        }
+    }
+}
+
 impl UnixBackend {
     pub fn new() -> Result<Self> {
         unsafe {
@@ -91,6 +115,7 @@ impl UnixBackend {
                 next_window_id: 1,
                 event_queue: Vec::new(),
                 wm_delete_window,
+                display_open: Arc::new(Mutex::new(true)),
             })
         }
     }
@@ -291,8 +316,12 @@ impl PlatformBackend for UnixBackend {
     fn get_drawing_context(&self, handle: &WindowHandle) -> Result<Box<dyn DrawingContext>> {
         if let Some(window) = self.windows.get(&handle.id) {
             unsafe {
-                let screen = xlib::XDefaultScreen(self.display);
-                let gc = xlib::XDefaultGC(self.display, screen);
+                // Each context owns its GC so clip and line state never leak
+                // into other windows; windows keep it until they are resized
+                let gc = xlib::XCreateGC(self.display, window.xwindow, 0, ptr::null_mut());
+                if gc.is_null() {
+                    return Err(Error::drawing("Failed to create graphics context"));
+                }
                 
                 Ok(Box::new(UnixDrawingContext {
                     display: self.display,
@@ -300,6 +329,7 @@ impl PlatformBackend for UnixBackend {
                     width: window.width,
                     height: window.height,
                     gc: gc as c_ulong,
+                    display_open: Arc::clone(&self.display_open),
                 }))
             }
         } else {
@@ -317,8 +347,10 @@ impl PlatformBackend for UnixBackend {
             
             // Close X display
             if !self.display.is_null() {
+                let mut open = self.display_open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
                 xlib::XCloseDisplay(self.display);
                 self.display = ptr::null_mut();
+                *open = false;
             }
         }
         Ok(())
@@ -505,6 +537,256 @@ struct UnixWindowHandle {
     xwindow: XWindow,
 }
 
+impl UnixDrawingContext {
+    /// Allocate the given color and make it the GC foreground.
+    unsafe fn set_foreground(&self, color: (f32, f32, f32, f32)) -> xlib::GC {
+        let screen = xlib::XDefaultScreen(self.display);
+        let colormap = xlib::XDefaultColormap(self.display, screen);
+        let gc = self.gc as *mut xlib::_XGC;
+        
+        let mut xcolor: xlib::XColor = std::mem::zeroed();
+        xcolor.red = (color.0 * 65535.0) as u16;
+        xcolor.green = (color.1 * 65535.0) as u16;
+        xcolor.blue = (color.2 * 65535.0) as u16;
+        
+        xlib::XAllocColor(self.display, colormap, &mut xcolor);
+        xlib::XSetForeground(self.display, gc, xcolor.pixel);
+        gc
+    }
+    
+    /// Configure the GC line attributes from a stroke style.
+    ///
+    /// X11 uses a fixed miter limit, so `miter_limit` is not honoured here.
+    unsafe fn set_stroke_style(&self, gc: xlib::GC, stroke: &StrokeStyle) {
+        let pattern = stroke.dash_pattern();
+        let line_style = if pattern.is_empty() { xlib::LineSolid } else { xlib::LineOnOffDash };
+        let cap_style = match stroke.cap {
+            LineCap::Butt => xlib::CapButt,
+            LineCap::Round => xlib::CapRound,
+            LineCap::Square => xlib::CapProjecting,
+        };
+        let join_style = match stroke.join {
+            LineJoin::Miter => xlib::JoinMiter,
+            LineJoin::Round => xlib::JoinRound,
+            LineJoin::Bevel => xlib::JoinBevel,
+        };
+        
+        XSetLineAttributes(
+            self.display,
+            gc,
+            stroke.width.round().max(0.0) as c_uint,
+            line_style,
+            cap_style,
+            join_style,
+        );
+        
+        if !pattern.is_empty() {
+            let dashes: Vec<c_char> = pattern
+                .iter()
+                .map(|d| d.round().clamp(1.0, 255.0) as u8 as c_char)
+                .collect();
+            XSetDashes(
+                self.display,
+                gc,
+                stroke.dash_offset.round() as c_int,
+                dashes.as_ptr(),
+                dashes.len() as c_int,
+            );
+        }
+    }
+    
+    /// Render text with the context's font into a coverage mask.
+    ///
+    /// The mask origin is the start of the baseline, matching `XDrawString`.
+    unsafe fn text_mask(&self, text: &str) -> Result<Option<raster::Mask>> {
+        let gc = self.gc as *mut xlib::_XGC;
+        let text_cstr = CString::new(text).map_err(|_| Error::drawing("Text contains a null byte"))?;
+        let font = xlib::XQueryFont(self.display, xlib::XGContextFromGC(gc));
+        if font.is_null() {
+            return Err(Error::drawing("Failed to query the text font"));
+        }
+        let width = xlib::XTextWidth(font, text_cstr.as_ptr(), text.len() as c_int);
+        let (ascent, descent) = ((*font).ascent, (*font).descent);
+        xlib::XFreeFontInfo(ptr::null_mut(), font, 1);
+        let height = ascent + descent;
+        if width <= 0 || height <= 0 {
+            return Ok(None);
+        }
+        
+        // Draw white text on black so any channel can be read as coverage
+        let screen = xlib::XDefaultScreen(self.display);
+        let depth = xlib::XDefaultDepth(self.display, screen) as c_uint;
+        let pixmap = xlib::XCreatePixmap(self.display, self.xwindow, width as c_uint, height as c_uint, depth);
+        let pixmap_gc = xlib::XCreateGC(self.display, pixmap, 0, ptr::null_mut());
+        xlib::XCopyGC(self.display, gc, xlib::GCFont as c_ulong, pixmap_gc);
+        xlib::XSetForeground(self.display, pixmap_gc, xlib::XBlackPixel(self.display, screen));
+        XFillRectangle(self.display, pixmap, pixmap_gc, 0, 0, width as c_uint, height as c_uint);
+        xlib::XSetForeground(self.display, pixmap_gc, xlib::XWhitePixel(self.display, screen));
+        XDrawString(self.display, pixmap, pixmap_gc, 0, ascent, text_cstr.as_ptr(), text.len() as c_int);
+        
+        let ximage = XGetImage(self.display, pixmap, 0, 0, width as c_uint, height as c_uint, XAllPlanes(), ZPixmap);
+        xlib::XFreeGC(self.display, pixmap_gc);
+        xlib::XFreePixmap(self.display, pixmap);
+        if ximage.is_null() {
+            return Err(Error::drawing("Failed to read text pixels"));
+        }
+        
+        let mut image = Image::new(width as u32, height as u32);
+        read_ximage(ximage, &mut image);
+        XDestroyImage(ximage);
+        
+        Ok(Some(raster::Mask {
+            x: 0,
+            y: -ascent,
+            width: width as usize,
+            height: height as usize,
+            coverage: image.pixels().chunks_exact(4).map(|p| p[1] as f32 / 255.0).collect(),
+        }))
+    }
+    
+    /// Upload part of an opaque image straight to the window.
+    unsafe fn put_image(&mut self, image: &Image, src: Rect, x: c_int, y: c_int) -> Result<()> {
+        let (src_x, src_y) = (src.x as usize, src.y as usize);
+        let (width, height) = (src.width as c_uint, src.height as c_uint);
+        let screen = xlib::XDefaultScreen(self.display);
+        let visual = xlib::XDefaultVisual(self.display, screen);
+        let depth = xlib::XDefaultDepth(self.display, screen) as c_uint;
+        let ximage = xlib::XCreateImage(self.display, visual, depth, ZPixmap, 0, ptr::null_mut(), width, height, 32, 0);
+        if ximage.is_null() {
+            return Err(Error::drawing("Failed to create image"));
+        }
+        
+        // The pixel buffer stays owned by Rust and is detached before destroying the image
+        let mut data = vec![0 as c_char; (*ximage).bytes_per_line as usize * height as usize];
+        (*ximage).data = data.as_mut_ptr();
+        let (red, green, blue) = ((*ximage).red_mask, (*ximage).green_mask, (*ximage).blue_mask);
+        let pixels = image.pixels();
+        for row in 0..height as usize {
+            for column in 0..width as usize {
+                let i = ((src_y + row) * image.width() as usize + src_x + column) * 4;
+                let value = channel_to_pixel(pixels[i], red) | channel_to_pixel(pixels[i + 1], green) | channel_to_pixel(pixels[i + 2], blue);
+                XPutPixel(ximage, column as c_int, row as c_int, value);
+            }
+        }
+        
+        let gc = self.gc as *mut xlib::_XGC;
+        XPutImage(self.display, self.xwindow, gc, ximage, 0, 0, x, y, width, height);
+        (*ximage).data = ptr::null_mut();
+        XDestroyImage(ximage);
+        XFlush(self.display);
+        Ok(())
+    }
+    
+    /// Draw into a region of the window in software.
+    ///
+    /// The region is read back with XGetImage, handed to `draw` as an image
+    /// whose top-left pixel sits at the given device origin, and written back
+    /// with XPutImage.
+    unsafe fn composite<F: FnOnce(&mut Image, (i32, i32))>(&mut self, region: Rect, draw: F) -> Result<()> {
+        let window = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+        let region = match region.expand(1.0).intersection(window) {
+            Some(region) => region,
+            None => return Ok(()),
+        };
+        let x = region.x.floor() as c_int;
+        let y = region.y.floor() as c_int;
+        let width = (region.right().ceil() as c_int - x).max(0) as c_uint;
+        let height = (region.bottom().ceil() as c_int - y).max(0) as c_uint;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        
+        let ximage = XGetImage(self.display, self.xwindow, x, y, width, height, XAllPlanes(), ZPixmap);
+        if ximage.is_null() {
+            return Err(Error::drawing("Failed to read window pixels"));
+        }
+        
+        let mut image = Image::new(width, height);
+        read_ximage(ximage, &mut image);
+        draw(&mut image, (x, y));
+        write_ximage(ximage, &image);
+        
+        let gc = self.gc as *mut xlib::_XGC;
+        XPutImage(self.display, self.xwindow, gc, ximage, 0, 0, x, y, width, height);
+        XDestroyImage(ximage);
+        XFlush(self.display);
+        Ok(())
+    }
+}
+
+/// Extract a color channel from a pixel value as 0..255.
+fn channel_from_pixel(pixel: c_ulong, mask: c_ulong) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = mask >> shift;
+    (((pixel & mask) >> shift) * 255 / max) as u8
+}
+
+/// Pack a 0..255 channel value into a pixel value.
+fn channel_to_pixel(value: u8, mask: c_ulong) -> c_ulong {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = mask >> shift;
+    ((value as c_ulong * max + 127) / 255) << shift
+}
+
+/// Copy the pixels of an XImage into an opaque image.
+unsafe fn read_ximage(ximage: *mut XImage, image: &mut Image) {
+    let (red, green, blue) = ((*ximage).red_mask, (*ximage).green_mask, (*ximage).blue_mask);
+    let width = image.width() as usize;
+    for (i, pixel) in image.pixels_mut().chunks_exact_mut(4).enumerate() {
+        let value = XGetPixel(ximage, (i % width) as c_int, (i / width) as c_int);
+        pixel[0] = channel_from_pixel(value, red);
+        pixel[1] = channel_from_pixel(value, green);
+        pixel[2] = channel_from_pixel(value, blue);
+        pixel[3] = 255;
+    }
+}
+
+/// Copy the pixels of an image back into an XImage.
+unsafe fn write_ximage(ximage: *mut XImage, image: &Image) {
+    let (red, green, blue) = ((*ximage).red_mask, (*ximage).green_mask, (*ximage).blue_mask);
+    let width = image.width() as usize;
+    for (i, pixel) in image.pixels().chunks_exact(4).enumerate() {
+        let value = channel_to_pixel(pixel[0], red) | channel_to_pixel(pixel[1], green) | channel_to_pixel(pixel[2], blue);
+        XPutPixel(ximage, (i % width) as c_int, (i / width) as c_int, value);
+    }
+}
+
+/// Flatten a path into a single X11 polygon.
+///
+/// Each contour is closed and followed by a return to the first contour's
+/// start point, so the connecting edges are traversed once in each direction
+/// and cancel out under both fill rules.
+fn polygon_points(path: &Path) -> Vec<XPoint> {
+    let mut points: Vec<XPoint> = Vec::new();
+    let mut anchor = None;
+    for contour in path.flatten(DEFAULT_TOLERANCE) {
+        if contour.points.len() < 3 {
+            continue;
+        }
+        points.extend(contour.points.iter().map(|p| to_xpoint(*p)));
+        points.push(to_xpoint(contour.points[0]));
+        match anchor {
+            Some(anchor) => points.push(anchor),
+            None => anchor = Some(to_xpoint(contour.points[0])),
+        }
+    }
+    points
+}
+
+/// Convert a point to X11 coordinates.
+fn to_xpoint(point: Point) -> XPoint {
+    XPoint {
+        x: point.x.round() as i16,
+        y: point.y.round() as i16,
+    }
+}
+
 impl DrawingContext for UnixDrawingContext {
     fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
         unsafe {
@@ -553,19 +835,10 @@ impl DrawingContext for UnixDrawingContext {
         Ok(())
     }
     
-    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), _stroke_width: f32) -> Result<()> {
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
         unsafe {
-            let screen = xlib::XDefaultScreen(self.display);
-            let colormap = xlib::XDefaultColormap(self.display, screen);
-            let gc = self.gc as *mut xlib::_XGC;
-            
-            let mut xcolor: xlib::XColor = std::mem::zeroed();
-            xcolor.red = (color.0 * 65535.0) as u16;
-            xcolor.green = (color.1 * 65535.0) as u16;
-            xcolor.blue = (color.2 * 65535.0) as u16;
-            
-            xlib::XAllocColor(self.display, colormap, &mut xcolor);
-            xlib::XSetForeground(self.display, gc, xcolor.pixel);
+            let gc = self.set_foreground(color);
+            self.set_stroke_style(gc, stroke);
             
             XDrawRectangle(
                 self.display,
@@ -610,6 +883,232 @@ impl DrawingContext for UnixDrawingContext {
         Ok(())
     }
     
+    fn measure_text(&self, text: &str) -> Option<TextMetrics> {
+        unsafe {
+            let gc = self.gc as *mut xlib::_XGC;
+            let font = xlib::XQueryFont(self.display, xlib::XGContextFromGC(gc));
+            if font.is_null() {
+                return None;
+            }
+            // The core font draws each byte of the text as one glyph
+            let advances: Vec<f32> = text
+                .char_indices()
+                .map(|(index, ch)| {
+                    let bytes = &text.as_bytes()[index..index + ch.len_utf8()];
+                    xlib::XTextWidth(font, bytes.as_ptr() as *const c_char, bytes.len() as c_int) as f32
+                })
+                .collect();
+            let (ascent, descent) = ((*font).ascent as f32, (*font).descent as f32);
+            xlib::XFreeFontInfo(ptr::null_mut(), font, 1);
+            Some(TextMetrics::from_advances(text, advances, ascent, descent))
+        }
+    }
+    
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        let mask = match unsafe { self.text_mask(text)? } {
+            Some(mask) => mask,
+            None => return Ok(()),
+        };
+        let local = Rect::new(mask.x as f32, mask.y as f32, mask.width as f32, mask.height as f32);
+        let paint = Paint::Solid(Color::rgba(color.0, color.1, color.2, color.3));
+        unsafe {
+            self.composite(transform.transform_rect(local), |image, origin| {
+                raster::composite_transformed_mask(image, origin, &mask, transform, &paint);
+            })
+        }
+    }
+    
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        // Opaque images drawn 1:1 at whole pixels need no sampling or blending
+        let whole = |value: f32| value.fract() == 0.0;
+        if transform.is_translation()
+            && [src.x, src.y, src.width, src.height, transform.tx, transform.ty].iter().all(|v| whole(*v))
+            && image.is_opaque()
+        {
+            let origin = transform.transform_point(Point::new(src.x, src.y));
+            return unsafe { self.put_image(image, src, origin.x as c_int, origin.y as c_int) };
+        }
+        
+        unsafe {
+            self.composite(transform.transform_rect(src), |target, origin| {
+                raster::draw_image(target, origin, image, src, transform, filter);
+            })
+        }
+    }
+    
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        unsafe {
+            self.composite(region, |target, origin| {
+                effects::blur_region(target, origin, region, radius);
+            })
+        }
+    }
+    
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let layer_origin = (origin.x as i32, origin.y as i32);
+        let region = Rect::new(origin.x, origin.y, layer.width() as f32, layer.height() as f32);
+        unsafe {
+            self.composite(region, |target, target_origin| {
+                raster::composite_layer(target, target_origin, layer, layer_origin, opacity, blend_mode);
+            })
+        }
+    }
+    
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        let mut points = polygon_points(path);
+        if points.is_empty() {
+            return Ok(());
+        }
+        
+        unsafe {
+            let gc = self.set_foreground(color);
+            let rule = match fill_rule {
+                FillRule::NonZero => xlib::WindingRule,
+                FillRule::EvenOdd => xlib::EvenOddRule,
+            };
+            XSetFillRule(self.display, gc, rule);
+            XFillPolygon(
+                self.display,
+                self.xwindow,
+                gc,
+                points.as_mut_ptr(),
+                points.len() as c_int,
+                xlib::Complex,
+                xlib::CoordModeOrigin,
+            );
+            XFlush(self.display);
+        }
+        Ok(())
+    }
+    
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        unsafe {
+            let gc = self.set_foreground(color);
+            self.set_stroke_style(gc, stroke);
+            
+            for contour in path.flatten(DEFAULT_TOLERANCE) {
+                let mut points: Vec<XPoint> = contour.points.iter().map(|p| to_xpoint(*p)).collect();
+                if contour.closed {
+                    points.push(to_xpoint(contour.points[0]));
+                }
+                XDrawLines(
+                    self.display,
+                    self.xwindow,
+                    gc,
+                    points.as_mut_ptr(),
+                    points.len() as c_int,
+                    xlib::CoordModeOrigin,
+                );
+            }
+            XFlush(self.display);
+        }
+        Ok(())
+    }
+    
+    fn fill_path_paint(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) -> Result<()> {
+        if let Some(color) = paint.as_solid() {
+            return self.fill_path(path, color.to_tuple(), fill_rule);
+        }
+        unsafe {
+            self.composite(path.bounds(), |image, origin| {
+                raster::fill_path(image, origin, path, paint, fill_rule);
+            })
+        }
+    }
+    
+    fn stroke_path_paint(&mut self, path: &Path, paint: &Paint, stroke: &StrokeStyle) -> Result<()> {
+        if let Some(color) = paint.as_solid() {
+            return self.stroke_path(path, color.to_tuple(), stroke);
+        }
+        let region = path.bounds().expand(stroke.width.max(1.0) * stroke.miter_limit);
+        unsafe {
+            self.composite(region, |image, origin| {
+                raster::stroke_path(image, origin, path, paint, stroke);
+            })
+        }
+    }
+    
+    fn fill_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
+        unsafe {
+            let gc = self.set_foreground(color);
+            XFillArc(
+                self.display,
+                self.xwindow,
+                gc,
+                (cx - radius_x).round() as c_int,
+                (cy - radius_y).round() as c_int,
+                (radius_x * 2.0).round().max(0.0) as c_uint,
+                (radius_y * 2.0).round().max(0.0) as c_uint,
+                0,
+                360 * 64,
+            );
+            XFlush(self.display);
+        }
+        Ok(())
+    }
+    
+    fn stroke_ellipse(&mut self, cx: f32, cy: f32, radius_x: f32, radius_y: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        unsafe {
+            let gc = self.set_foreground(color);
+            self.set_stroke_style(gc, stroke);
+            XDrawArc(
+                self.display,
+                self.xwindow,
+                gc,
+                (cx - radius_x).round() as c_int,
+                (cy - radius_y).round() as c_int,
+                (radius_x * 2.0).round().max(0.0) as c_uint,
+                (radius_y * 2.0).round().max(0.0) as c_uint,
+                0,
+                360 * 64,
+            );
+            XFlush(self.display);
+        }
+        Ok(())
+    }
+    
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        unsafe {
+            let gc = self.gc as *mut xlib::_XGC;
+            let clip = match clip {
+                Some(clip) => clip,
+                None => {
+                    XSetClipMask(self.display, gc, 0);
+                    return Ok(());
+                }
+            };
+            
+            let bounds = clip.bounds();
+            let left = bounds.x.round().max(i16::MIN as f32) as i32;
+            let top = bounds.y.round().max(i16::MIN as f32) as i32;
+            let right = bounds.right().round().min(i16::MAX as f32) as i32;
+            let bottom = bounds.bottom().round().min(i16::MAX as f32) as i32;
+            let mut rect = XRectangle {
+                x: left as i16,
+                y: top as i16,
+                width: (right - left).max(0) as u16,
+                height: (bottom - top).max(0) as u16,
+            };
+            
+            let region = XCreateRegion();
+            XUnionRectWithRegion(&mut rect, region, region);
+            for (path, fill_rule) in clip.paths() {
+                let mut points = polygon_points(path);
+                let rule = match fill_rule {
+                    FillRule::NonZero => xlib::WindingRule,
+                    FillRule::EvenOdd => xlib::EvenOddRule,
+                };
+                let path_region = XPolygonRegion(points.as_mut_ptr(), points.len() as c_int, rule);
+                XIntersectRegion(region, path_region, region);
+                XDestroyRegion(path_region);
+            }
+            
+            XSetRegion(self.display, gc, region);
+            XDestroyRegion(region);
+        }
+        Ok(())
+    }
+    
     fn present(&mut self) -> Result<()> {
         unsafe {
             XFlush(self.display);
@@ -617,6 +1116,23 @@ impl DrawingContext for UnixDrawingContext {
         Ok(())
     }
     
+    fn read_pixels(&mut self) -> Result<Image> {
+        unsafe {
+            let ximage = XGetImage(self.display, self.xwindow, 0, 0, self.width, self.height, XAllPlanes(), ZPixmap);
+            if ximage.is_null() {
+                return Err(Error::drawing("Failed to read window pixels"));
+            }
+            let mut image = Image::new(self.width, self.height);
+            read_ximage(ximage, &mut image);
+            XDestroyImage(ximage);
+            Ok(image)
+        }
+    }
+    
+    fn supports_batching(&self) -> bool {
+        true
+    }
+    
     fn size(&self) -> (u32, u32) {
         (self.width, self.height)
     }
@@ -5,9 +5,10 @@
 
 #[cfg(windows)]
 use winapi::{
+    ctypes::c_void,
     shared::{
         minwindef::{UINT, WPARAM, LPARAM, LRESULT, ATOM},
-        windef::{HWND, RECT, HBRUSH},
+        windef::{HWND, HDC, RECT, HBRUSH, HPEN, POINT, SIZE, COLORREF},
     },
     um::{
         winuser::{
@@ -25,12 +26,24 @@ use winapi::{
             VK_DELETE, VK_INSERT, VK_HOME, VK_END, VK_PRIOR, VK_NEXT, VK_LEFT, VK_RIGHT,
             VK_UP, VK_DOWN, VK_SHIFT, VK_CONTROL, VK_MENU, VK_LWIN, VK_RWIN,
         },
-        wingdi::{CreateSolidBrush, RGB, SetTextColor, SetBkMode, TextOutW, TRANSPARENT},
+        wingdi::{
+            CreateSolidBrush, ExtCreatePen, SelectObject, DeleteObject, GetStockObject,
+            SetPolyFillMode, SetMiterLimit, PolyPolygon, Polyline, RGB, SetTextColor, SetBkMode,
+            TextOutW, LOGBRUSH, TRANSPARENT, NULL_PEN, BS_SOLID, PS_GEOMETRIC, PS_SOLID,
+            PS_USERSTYLE, PS_ENDCAP_FLAT, PS_ENDCAP_ROUND, PS_ENDCAP_SQUARE, PS_JOIN_MITER,
+            PS_JOIN_ROUND, PS_JOIN_BEVEL, ALTERNATE, WINDING,
+            CreateRectRgn, CreatePolyPolygonRgn, CombineRgn, SelectClipRgn, RGN_AND,
+            SetGraphicsMode, SetWorldTransform, ModifyWorldTransform, XFORM, GM_ADVANCED, GM_COMPATIBLE, MWT_IDENTITY,
+            CreateCompatibleDC, CreateDIBSection, DeleteDC, BitBlt, GdiFlush, SetDIBitsToDevice,
+            BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+            GetTextExtentExPointW, GetTextMetricsW, TEXTMETRICW,
+        },
         libloaderapi::GetModuleHandleW,
     },
 };
 
 use crate::{Error, Result};
+use crate::drawing::{Path, FillRule, Point, Rect, Clip, Transform, Image, ImageFilter, BlendMode, raster, effects, StrokeStyle, TextMetrics, LineCap, LineJoin, path::DEFAULT_TOLERANCE};
 use super::{
     PlatformBackend, WindowHandle, WindowParams, PlatformEvent,
     DrawingContext, MouseButton, Key, KeyModifiers,
@@ -78,10 +91,22 @@ struct WindowsWindow {
 
 struct WindowsDrawingContext {
     hwnd: SafeHWND,
+    /// Device context of the window, kept until the context is dropped.
+    hdc: usize,
     width: u32,
     height: u32,
+    clip: Option<Clip>,
+    /// Solid brushes by color.
+    brushes: HashMap<COLORREF, usize>,
+    /// Pens by style.
+    pens: HashMap<PenStyle, usize>,
+    /// Text color currently set in the device context.
+    text_color: Option<COLORREF>,
 }
 
+/// Number of brushes or pens cached before the cache is emptied.
+const MAX_CACHED_OBJECTS: usize = 64;
+
 impl WindowsBackend {
     pub fn new() -> Result<Self> {
         Self::initialize_window_class()?;
@@ -310,11 +335,7 @@ impl PlatformBackend for WindowsBackend {
     fn get_drawing_context(&self, handle: &WindowHandle) -> Result<Box<dyn DrawingContext>> {
         if let Some(window) = self.windows.get(&handle.id) {
             let (width, height) = self.get_window_size(handle)?;
-            Ok(Box::new(WindowsDrawingContext {
-                hwnd: window.hwnd,
-                width,
-                height,
-            }))
+            Ok(Box::new(unsafe { WindowsDrawingContext::new(window.hwnd, width, height)? }))
         } else {
             Err(Error::window("Window not found"))
         }
@@ -529,20 +550,210 @@ unsafe fn push_event(event: PlatformEvent) {
     }
 }
 
+impl WindowsDrawingContext {
+    /// Create a context drawing to a window through a device context it
+    /// keeps, with its clip, text color and GDI objects, until dropped.
+    unsafe fn new(hwnd: SafeHWND, width: u32, height: u32) -> Result<Self> {
+        let hdc = GetDC(hwnd.as_hwnd());
+        if hdc.is_null() {
+            return Err(Error::drawing("Failed to get device context"));
+        }
+        SetBkMode(hdc, TRANSPARENT as i32);
+        Ok(Self {
+            hwnd,
+            hdc: hdc as usize,
+            width,
+            height,
+            clip: None,
+            brushes: HashMap::new(),
+            pens: HashMap::new(),
+            text_color: None,
+        })
+    }
+    
+    fn hdc(&self) -> HDC {
+        self.hdc as HDC
+    }
+    
+    /// Get a solid brush of `color`, creating it on first use.
+    unsafe fn brush(&mut self, color: (f32, f32, f32, f32)) -> HBRUSH {
+        let key = colorref(color);
+        if let Some(&brush) = self.brushes.get(&key) {
+            return brush as HBRUSH;
+        }
+        if self.brushes.len() >= MAX_CACHED_OBJECTS {
+            for (_, brush) in self.brushes.drain() {
+                DeleteObject(brush as *mut _);
+            }
+        }
+        let brush = CreateSolidBrush(key);
+        self.brushes.insert(key, brush as usize);
+        brush
+    }
+    
+    /// Get a pen matching a stroke style, creating it on first use.
+    unsafe fn pen(&mut self, stroke: &StrokeStyle, color: (f32, f32, f32, f32)) -> HPEN {
+        let style = PenStyle::new(stroke, color);
+        if let Some(&pen) = self.pens.get(&style) {
+            return pen as HPEN;
+        }
+        if self.pens.len() >= MAX_CACHED_OBJECTS {
+            for (_, pen) in self.pens.drain() {
+                DeleteObject(pen as *mut _);
+            }
+        }
+        let pen = create_pen(&style);
+        self.pens.insert(style, pen as usize);
+        pen
+    }
+    
+    /// Set the text color, unless the device context already has it.
+    unsafe fn set_text_color(&mut self, color: (f32, f32, f32, f32)) {
+        let color = colorref(color);
+        if self.text_color != Some(color) {
+            SetTextColor(self.hdc(), color);
+            self.text_color = Some(color);
+        }
+    }
+    
+    /// Select the current clip region into the device context.
+    unsafe fn select_clip(&self) {
+        let clip = match self.clip {
+            Some(ref clip) => clip,
+            None => {
+                SelectClipRgn(self.hdc(), ptr::null_mut());
+                return;
+            }
+        };
+        
+        let bounds = clip.bounds();
+        let region = CreateRectRgn(
+            bounds.x.round() as i32,
+            bounds.y.round() as i32,
+            bounds.right().round() as i32,
+            bounds.bottom().round() as i32,
+        );
+        for (path, fill_rule) in clip.paths() {
+            let mut points = Vec::new();
+            let mut counts = Vec::new();
+            for contour in path.flatten(DEFAULT_TOLERANCE) {
+                if contour.points.len() >= 3 {
+                    counts.push(contour.points.len() as i32);
+                    points.extend(contour.points.iter().map(|p| to_point(*p)));
+                }
+            }
+            let mode = match fill_rule {
+                FillRule::NonZero => WINDING,
+                FillRule::EvenOdd => ALTERNATE,
+            };
+            let path_region = CreatePolyPolygonRgn(points.as_ptr(), counts.as_ptr(), counts.len() as i32, mode);
+            CombineRgn(region, region, path_region, RGN_AND);
+            DeleteObject(path_region as *mut _);
+        }
+        
+        SelectClipRgn(self.hdc(), region);
+        DeleteObject(region as *mut _);
+    }
+    
+    /// Draw into a region of the window in software.
+    ///
+    /// The region is copied into a DIB section, handed to `draw` as an image
+    /// whose top-left pixel sits at the given device origin, and copied back.
+    unsafe fn composite<F: FnOnce(&mut Image, (i32, i32))>(&self, region: Rect, draw: F) -> Result<()> {
+        let window = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+        let region = match region.expand(1.0).intersection(window) {
+            Some(region) => region,
+            None => return Ok(()),
+        };
+        let x = region.x.floor() as i32;
+        let y = region.y.floor() as i32;
+        let width = (region.right().ceil() as i32 - x).max(0);
+        let height = (region.bottom().ceil() as i32 - y).max(0);
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        
+        let hdc = self.hdc();
+        let memory_dc = CreateCompatibleDC(hdc);
+        if memory_dc.is_null() {
+            return Err(Error::drawing("Failed to create memory device context"));
+        }
+        let info = bitmap_info(width, height);
+        let mut bits: *mut c_void = ptr::null_mut();
+        let bitmap = CreateDIBSection(hdc, &info, DIB_RGB_COLORS, &mut bits, ptr::null_mut(), 0);
+        if bitmap.is_null() || bits.is_null() {
+            DeleteDC(memory_dc);
+            return Err(Error::drawing("Failed to create bitmap"));
+        }
+        let previous = SelectObject(memory_dc, bitmap as *mut _);
+        BitBlt(memory_dc, 0, 0, width, height, hdc, x, y, SRCCOPY);
+        GdiFlush();
+        
+        let data = std::slice::from_raw_parts_mut(bits as *mut u8, width as usize * height as usize * 4);
+        let mut image = Image::new(width as u32, height as u32);
+        for (pixel, bgra) in image.pixels_mut().chunks_exact_mut(4).zip(data.chunks_exact(4)) {
+            pixel.copy_from_slice(&[bgra[2], bgra[1], bgra[0], 255]);
+        }
+        draw(&mut image, (x, y));
+        for (bgra, pixel) in data.chunks_exact_mut(4).zip(image.pixels().chunks_exact(4)) {
+            bgra.copy_from_slice(&[pixel[2], pixel[1], pixel[0], 0]);
+        }
+        BitBlt(hdc, x, y, width, height, memory_dc, 0, 0, SRCCOPY);
+        
+        SelectObject(memory_dc, previous);
+        DeleteObject(bitmap as *mut _);
+        DeleteDC(memory_dc);
+        Ok(())
+    }
+    
+    /// Upload part of an opaque image straight to the window.
+    unsafe fn put_image(&self, image: &Image, src: Rect, x: i32, y: i32) {
+        let (src_x, src_y) = (src.x as usize, src.y as usize);
+        let (width, height) = (src.width as usize, src.height as usize);
+        let pixels = image.pixels();
+        let mut bgra = Vec::with_capacity(width * height * 4);
+        for row in 0..height {
+            let start = ((src_y + row) * image.width() as usize + src_x) * 4;
+            for pixel in pixels[start..start + width * 4].chunks_exact(4) {
+                bgra.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 0]);
+            }
+        }
+        
+        let info = bitmap_info(width as i32, height as i32);
+        SetDIBitsToDevice(
+            self.hdc(),
+            x,
+            y,
+            width as u32,
+            height as u32,
+            0,
+            0,
+            0,
+            height as u32,
+            bgra.as_ptr() as *const c_void,
+            &info,
+            DIB_RGB_COLORS,
+        );
+    }
+}
+
+impl Drop for WindowsDrawingContext {
+    fn drop(&mut self) {
+        unsafe {
+            for (_, brush) in self.brushes.drain() {
+                DeleteObject(brush as *mut _);
+            }
+            for (_, pen) in self.pens.drain() {
+                DeleteObject(pen as *mut _);
+            }
+            ReleaseDC(self.hwnd.as_hwnd(), self.hdc());
+        }
+    }
+}
+
 impl DrawingContext for WindowsDrawingContext {
     fn clear(&mut self, color: (f32, f32, f32, f32)) -> Result<()> {
         unsafe {
-            let hdc = GetDC(self.hwnd.as_hwnd());
-            if hdc.is_null() {
-                return Err(Error::drawing("Failed to get device context"));
-            }
-            
-            let brush = CreateSolidBrush(RGB(
-                (color.0 * 255.0) as u8,
-                (color.1 * 255.0) as u8,
-                (color.2 * 255.0) as u8,
-            ));
-            
             let rect = RECT {
                 left: 0,
                 top: 0,
@@ -550,26 +761,17 @@ impl DrawingContext for WindowsDrawingContext {
                 bottom: self.height as i32,
             };
             
-            FillRect(hdc, &rect, brush);
-            winapi::um::wingdi::DeleteObject(brush as *mut _);
-            ReleaseDC(self.hwnd.as_hwnd(), hdc);
+            // Clearing covers the whole window, whatever the clip
+            SelectClipRgn(self.hdc(), ptr::null_mut());
+            let brush = self.brush(color);
+            FillRect(self.hdc(), &rect, brush);
+            self.select_clip();
         }
         Ok(())
     }
     
     fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<()> {
         unsafe {
-            let hdc = GetDC(self.hwnd.as_hwnd());
-            if hdc.is_null() {
-                return Err(Error::drawing("Failed to get device context"));
-            }
-            
-            let brush = CreateSolidBrush(RGB(
-                (color.0 * 255.0) as u8,
-                (color.1 * 255.0) as u8,
-                (color.2 * 255.0) as u8,
-            ));
-            
             let rect = RECT {
                 left: x as i32,
                 top: y as i32,
@@ -577,47 +779,192 @@ impl DrawingContext for WindowsDrawingContext {
                 bottom: (y + height) as i32,
             };
             
-            FillRect(hdc, &rect, brush);
-            winapi::um::wingdi::DeleteObject(brush as *mut _);
-            ReleaseDC(self.hwnd.as_hwnd(), hdc);
+            let brush = self.brush(color);
+            FillRect(self.hdc(), &rect, brush);
         }
         Ok(())
     }
     
-    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke_width: f32) -> Result<()> {
-        // Simple implementation using fill_rect for now
-        let sw = stroke_width;
-        self.fill_rect(x, y, width, sw, color)?; // Top
-        self.fill_rect(x, y + height - sw, width, sw, color)?; // Bottom
-        self.fill_rect(x, y, sw, height, color)?; // Left
-        self.fill_rect(x + width - sw, y, sw, height, color)?; // Right
-        Ok(())
+    fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        self.stroke_path(&Path::rect(Rect::new(x, y, width, height)), color, stroke)
     }
     
     fn draw_text(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32, f32)) -> Result<()> {
         unsafe {
-            let hdc = GetDC(self.hwnd.as_hwnd());
-            if hdc.is_null() {
-                return Err(Error::drawing("Failed to get device context"));
-            }
-            
             let text_wide = wide_string(text);
-            SetTextColor(hdc, RGB(
-                (color.0 * 255.0) as u8,
-                (color.1 * 255.0) as u8,
-                (color.2 * 255.0) as u8,
-            ));
-            SetBkMode(hdc, TRANSPARENT as i32);
-            
+            self.set_text_color(color);
             TextOutW(
-                hdc,
+                self.hdc(),
                 x as i32,
                 y as i32,
                 text_wide.as_ptr(),
                 text_wide.len() as i32 - 1, // -1 for null terminator
             );
+        }
+        Ok(())
+    }
+    
+    fn measure_text(&self, text: &str) -> Option<TextMetrics> {
+        unsafe {
+            let units: Vec<u16> = text.encode_utf16().collect();
+            let mut extents = vec![0i32; units.len()];
+            let mut size: SIZE = std::mem::zeroed();
+            let mut font: TEXTMETRICW = std::mem::zeroed();
+            let measured = GetTextExtentExPointW(
+                self.hdc(),
+                units.as_ptr(),
+                units.len() as i32,
+                0,
+                ptr::null_mut(),
+                extents.as_mut_ptr(),
+                &mut size,
+            ) != 0
+                && GetTextMetricsW(self.hdc(), &mut font) != 0;
+            if !measured {
+                return None;
+            }
+            
+            // Extents are cumulative per UTF-16 unit; a character ends with its last unit
+            let mut end = 0;
+            let mut previous = 0;
+            let advances: Vec<f32> = text
+                .chars()
+                .map(|ch| {
+                    end += ch.len_utf16();
+                    let advance = extents[end - 1] - previous;
+                    previous = extents[end - 1];
+                    advance as f32
+                })
+                .collect();
+            Some(TextMetrics::from_advances(text, advances, font.tmAscent as f32, font.tmDescent as f32))
+        }
+    }
+    
+    fn draw_text_transformed(&mut self, text: &str, transform: Transform, color: (f32, f32, f32, f32)) -> Result<()> {
+        unsafe {
+            let hdc = self.hdc();
+            
+            // The clip region stays in device units; only drawing is transformed
+            let xform = XFORM {
+                eM11: transform.a,
+                eM12: transform.b,
+                eM21: transform.c,
+                eM22: transform.d,
+                eDx: transform.tx,
+                eDy: transform.ty,
+            };
+            SetGraphicsMode(hdc, GM_ADVANCED as i32);
+            SetWorldTransform(hdc, &xform);
             
-            ReleaseDC(self.hwnd.as_hwnd(), hdc);
+            let text_wide = wide_string(text);
+            self.set_text_color(color);
+            TextOutW(hdc, 0, 0, text_wide.as_ptr(), text_wide.len() as i32 - 1);
+            
+            // The device context is kept, so later calls must not inherit the transform
+            ModifyWorldTransform(hdc, ptr::null(), MWT_IDENTITY);
+            SetGraphicsMode(hdc, GM_COMPATIBLE as i32);
+        }
+        Ok(())
+    }
+    
+    fn draw_image(&mut self, image: &Image, src: Rect, transform: Transform, filter: ImageFilter) -> Result<()> {
+        unsafe {
+            // Opaque images drawn 1:1 at whole pixels need no sampling or blending
+            let whole = |value: f32| value.fract() == 0.0;
+            if transform.is_translation()
+                && [src.x, src.y, src.width, src.height, transform.tx, transform.ty].iter().all(|v| whole(*v))
+                && image.is_opaque()
+            {
+                let origin = transform.transform_point(Point::new(src.x, src.y));
+                self.put_image(image, src, origin.x as i32, origin.y as i32);
+                return Ok(());
+            }
+            
+            self.composite(transform.transform_rect(src), |target, origin| {
+                raster::draw_image(target, origin, image, src, transform, filter);
+            })
+        }
+    }
+    
+    fn blur_region(&mut self, region: Rect, radius: f32) -> Result<()> {
+        unsafe {
+            self.composite(region, |target, origin| {
+                effects::blur_region(target, origin, region, radius);
+            })
+        }
+    }
+    
+    fn draw_layer(&mut self, layer: &Image, origin: Point, opacity: f32, blend_mode: BlendMode) -> Result<()> {
+        let layer_origin = (origin.x as i32, origin.y as i32);
+        let region = Rect::new(origin.x, origin.y, layer.width() as f32, layer.height() as f32);
+        unsafe {
+            self.composite(region, |target, target_origin| {
+                raster::composite_layer(target, target_origin, layer, layer_origin, opacity, blend_mode);
+            })
+        }
+    }
+    
+    fn fill_path(&mut self, path: &Path, color: (f32, f32, f32, f32), fill_rule: FillRule) -> Result<()> {
+        let mut points = Vec::new();
+        let mut counts = Vec::new();
+        for contour in path.flatten(DEFAULT_TOLERANCE) {
+            if contour.points.len() < 3 {
+                continue;
+            }
+            counts.push(contour.points.len() as i32);
+            points.extend(contour.points.iter().map(|p| to_point(*p)));
+        }
+        if counts.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            let brush = self.brush(color);
+            let hdc = self.hdc();
+            let old_brush = SelectObject(hdc, brush as *mut _);
+            let old_pen = SelectObject(hdc, GetStockObject(NULL_PEN as i32));
+            SetPolyFillMode(hdc, match fill_rule {
+                FillRule::NonZero => WINDING,
+                FillRule::EvenOdd => ALTERNATE,
+            });
+
+            PolyPolygon(hdc, points.as_ptr(), counts.as_ptr(), counts.len() as u32);
+
+            // Cached objects are deselected so they can be deleted at any time
+            SelectObject(hdc, old_pen);
+            SelectObject(hdc, old_brush);
+        }
+        Ok(())
+    }
+    
+    fn stroke_path(&mut self, path: &Path, color: (f32, f32, f32, f32), stroke: &StrokeStyle) -> Result<()> {
+        unsafe {
+            let pen = self.pen(stroke, color);
+            let hdc = self.hdc();
+            let old_pen = SelectObject(hdc, pen as *mut _);
+            SetMiterLimit(hdc, stroke.miter_limit, ptr::null_mut());
+
+            for contour in path.flatten(DEFAULT_TOLERANCE) {
+                let mut points: Vec<POINT> = contour.points.iter().map(|p| to_point(*p)).collect();
+                if contour.closed {
+                    if let Some(first) = points.first().copied() {
+                        points.push(first);
+                    }
+                }
+                if points.len() > 1 {
+                    Polyline(hdc, points.as_ptr(), points.len() as i32);
+                }
+            }
+
+            SelectObject(hdc, old_pen);
+        }
+        Ok(())
+    }
+    
+    fn set_clip(&mut self, clip: Option<&Clip>) -> Result<()> {
+        if self.clip.as_ref() != clip {
+            self.clip = clip.cloned();
+            unsafe { self.select_clip() };
         }
         Ok(())
     }
@@ -630,11 +977,101 @@ impl DrawingContext for WindowsDrawingContext {
         Ok(())
     }
     
+    fn read_pixels(&mut self) -> Result<Image> {
+        unsafe {
+            // Compositing without drawing copies the pixels out and back unchanged
+            let mut captured = None;
+            let window = Rect::new(0.0, 0.0, self.width as f32, self.height as f32);
+            SelectClipRgn(self.hdc(), ptr::null_mut());
+            let result = self.composite(window, |image, _| captured = Some(image.clone()));
+            self.select_clip();
+            result?;
+            Ok(captured.unwrap_or_else(|| Image::new(self.width, self.height)))
+        }
+    }
+    
     fn size(&self) -> (u32, u32) {
         (self.width, self.height)
     }
 }
 
+/// Parameters of a GDI pen, identifying it in the cache.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PenStyle {
+    color: COLORREF,
+    width: u32,
+    flags: u32,
+    dashes: Vec<u32>,
+}
+
+impl PenStyle {
+    /// Describe a geometric pen matching a stroke style.
+    fn new(stroke: &StrokeStyle, color: (f32, f32, f32, f32)) -> Self {
+        let cap = match stroke.cap {
+            LineCap::Butt => PS_ENDCAP_FLAT,
+            LineCap::Round => PS_ENDCAP_ROUND,
+            LineCap::Square => PS_ENDCAP_SQUARE,
+        };
+        let join = match stroke.join {
+            LineJoin::Miter => PS_JOIN_MITER,
+            LineJoin::Round => PS_JOIN_ROUND,
+            LineJoin::Bevel => PS_JOIN_BEVEL,
+        };
+        let dashes: Vec<u32> = stroke.dash_pattern().iter().map(|d| d.round().max(1.0) as u32).collect();
+        let style = if dashes.is_empty() { PS_SOLID } else { PS_USERSTYLE };
+        Self {
+            color: colorref(color),
+            width: stroke.width.round().max(1.0) as u32,
+            flags: PS_GEOMETRIC | style | cap | join,
+            dashes,
+        }
+    }
+}
+
+/// Create a geometric pen.
+unsafe fn create_pen(style: &PenStyle) -> HPEN {
+    let brush = LOGBRUSH {
+        lbStyle: BS_SOLID,
+        lbColor: style.color,
+        lbHatch: 0,
+    };
+    ExtCreatePen(
+        style.flags,
+        style.width,
+        &brush,
+        style.dashes.len() as u32,
+        if style.dashes.is_empty() { ptr::null() } else { style.dashes.as_ptr() },
+    )
+}
+
+/// Convert a color to a GDI color, dropping alpha.
+fn colorref(color: (f32, f32, f32, f32)) -> COLORREF {
+    RGB(
+        (color.0 * 255.0) as u8,
+        (color.1 * 255.0) as u8,
+        (color.2 * 255.0) as u8,
+    )
+}
+
+/// Describe a top-down 32-bit BGRA bitmap.
+fn bitmap_info(width: i32, height: i32) -> BITMAPINFO {
+    let mut info: BITMAPINFO = unsafe { std::mem::zeroed() };
+    info.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+    info.bmiHeader.biWidth = width;
+    info.bmiHeader.biHeight = -height;
+    info.bmiHeader.biPlanes = 1;
+    info.bmiHeader.biBitCount = 32;
+    info.bmiHeader.biCompression = BI_RGB;
+    info
+}
+
+fn to_point(point: Point) -> POINT {
+    POINT {
+        x: point.x.round() as i32,
+        y: point.y.round() as i32,
+    }
+}
+
 // Helper function to convert Rust strings to wide strings for Windows API
 fn wide_string(s: &str) -> Vec<u16> {
     OsStr::new(s).encode_wide().chain(once(0)).collect()
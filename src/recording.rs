@@ -0,0 +1,218 @@
+//! Recording window frames as animations.
+//!
+//! A [`FrameRecorder`] reads back a window after each frame of
+//! [`Application::run`](crate::Application::run) and encodes the captured
+//! frames, with the time each one was on screen, as an animated PNG or GIF.
+//!
+//! ```rust,no_run
+//! use rx::{Application, FrameRecorder, WindowBuilder};
+//!
+//! fn main() -> rx::Result<()> {
+//!     let app = Application::new()?;
+//!     let window = WindowBuilder::new().size(320, 240).build(&app)?;
+//!     app.record(FrameRecorder::new(&window, 120).save_to("demo.gif"));
+//!     app.run()
+//! }
+//! ```
+
+use crate::drawing::codec::{apng, gif, AnimationFrame};
+use crate::{Error, Image, Result, Window};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How long the last frame is shown when no other frame timing is known.
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Animation file formats a recording can be encoded as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// Animated PNG, lossless.
+    Apng,
+    /// GIF, with up to 256 colors per frame.
+    Gif,
+}
+
+impl RecordingFormat {
+    /// Pick a format from a file extension: `.gif` for GIF, `.png` or `.apng` for APNG.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "gif" => Some(RecordingFormat::Gif),
+            "png" | "apng" => Some(RecordingFormat::Apng),
+            _ => None,
+        }
+    }
+}
+
+struct Recording {
+    window: Option<Window>,
+    frame_limit: usize,
+    frames: Vec<(Image, Instant)>,
+    output: Option<PathBuf>,
+}
+
+/// Captures a fixed number of frames of a window.
+///
+/// Clones share the same recording, so a recorder handed to
+/// [`Application::record`](crate::Application::record) can still be encoded
+/// by the caller once it is finished.
+#[derive(Clone)]
+pub struct FrameRecorder {
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl FrameRecorder {
+    /// Record `frame_count` frames of `window`.
+    pub fn new(window: &Window, frame_count: usize) -> Self {
+        Self::with_window(Some(window.clone()), frame_count)
+    }
+
+    fn with_window(window: Option<Window>, frame_count: usize) -> Self {
+        Self {
+            recording: Arc::new(Mutex::new(Recording {
+                window,
+                frame_limit: frame_count,
+                frames: Vec::new(),
+                output: None,
+            })),
+        }
+    }
+
+    /// Write the animation to `path` once every frame is captured, in the
+    /// format given by its extension.
+    pub fn save_to(self, path: impl Into<PathBuf>) -> Self {
+        self.lock().output = Some(path.into());
+        self
+    }
+
+    /// Get the number of frames captured so far.
+    pub fn frame_count(&self) -> usize {
+        self.lock().frames.len()
+    }
+
+    /// Check if every requested frame has been captured.
+    pub fn is_finished(&self) -> bool {
+        let recording = self.lock();
+        recording.frames.len() >= recording.frame_limit
+    }
+
+    /// Capture the window's current contents as the next frame.
+    ///
+    /// Returns `true` once the recording is finished, after saving it if an
+    /// output path was given.
+    pub fn capture_frame(&self) -> Result<bool> {
+        if self.is_finished() {
+            return Ok(true);
+        }
+        let window = self.lock().window.clone().ok_or_else(|| Error::invalid_operation("Recorder has no window"))?;
+        self.push_frame(window.capture()?, Instant::now())
+    }
+
+    /// Add a captured frame, saving the recording once it is complete.
+    fn push_frame(&self, image: Image, time: Instant) -> Result<bool> {
+        let output = {
+            let mut recording = self.lock();
+            recording.frames.push((image, time));
+            if recording.frames.len() < recording.frame_limit {
+                return Ok(false);
+            }
+            recording.output.clone()
+        };
+        if let Some(path) = output {
+            self.save(path)?;
+        }
+        Ok(true)
+    }
+
+    /// Encode the frames captured so far as an animated PNG.
+    pub fn to_apng(&self) -> Vec<u8> {
+        apng::encode(&self.animation_frames())
+    }
+
+    /// Encode the frames captured so far as a GIF.
+    pub fn to_gif(&self) -> Vec<u8> {
+        gif::encode(&self.animation_frames())
+    }
+
+    /// Encode the frames captured so far and write them to `path`, in the
+    /// format given by its extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let data = match RecordingFormat::from_path(path) {
+            Some(RecordingFormat::Apng) => self.to_apng(),
+            Some(RecordingFormat::Gif) => self.to_gif(),
+            None => return Err(Error::resource(format!("Unsupported recording format: {}", path.display()))),
+        };
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Pair each frame with the time until the next one was captured.
+    ///
+    /// The last frame is shown as long as the one before it.
+    fn animation_frames(&self) -> Vec<AnimationFrame> {
+        let recording = self.lock();
+        let mut last_delay = DEFAULT_FRAME_DELAY;
+        let mut frames: Vec<AnimationFrame> = recording
+            .frames
+            .windows(2)
+            .map(|pair| {
+                last_delay = pair[1].1.duration_since(pair[0].1);
+                AnimationFrame { image: pair[0].0.clone(), delay: last_delay }
+            })
+            .collect();
+        if let Some((image, _)) = recording.frames.last() {
+            frames.push(AnimationFrame { image: image.clone(), delay: last_delay });
+        }
+        frames
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Recording> {
+        self.recording.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl std::fmt::Debug for FrameRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let recording = self.lock();
+        f.debug_struct("FrameRecorder")
+            .field("frames", &recording.frames.len())
+            .field("frame_limit", &recording.frame_limit)
+            .field("output", &recording.output)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn test_frame_timing() {
+        let path = std::env::temp_dir().join(format!("rx-recording-{}.png", std::process::id()));
+        let recorder = FrameRecorder::with_window(None, 3).save_to(&path);
+        let start = Instant::now();
+        assert!(!recorder.push_frame(Image::filled(2, 2, Color::RED), start).unwrap());
+        assert!(!recorder.push_frame(Image::filled(2, 2, Color::GREEN), start + Duration::from_millis(30)).unwrap());
+        assert!(!path.exists());
+        assert!(recorder.push_frame(Image::filled(2, 2, Color::BLUE), start + Duration::from_millis(80)).unwrap());
+        assert!(recorder.is_finished());
+        assert!(recorder.capture_frame().unwrap());
+        assert_eq!(recorder.frame_count(), 3);
+
+        let delays: Vec<u128> = recorder.animation_frames().iter().map(|frame| frame.delay.as_millis()).collect();
+        assert_eq!(delays, [30, 50, 50]);
+
+        // Saved as an animated PNG once finished
+        let saved = std::fs::read(&path).unwrap();
+        assert_eq!(saved, recorder.to_apng());
+        assert_eq!(Image::from_png(&saved).unwrap().pixel(0, 0), Color::RED);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(recorder.to_gif().starts_with(b"GIF89a"));
+        assert!(recorder.save("recording.webm").is_err());
+        assert_eq!(RecordingFormat::from_path("clip.GIF"), Some(RecordingFormat::Gif));
+    }
+}
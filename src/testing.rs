@@ -0,0 +1,273 @@
+//! Golden image testing.
+//!
+//! [`assert_render_matches`] renders a widget or drawing function with the
+//! software rasterizer and compares the result against a PNG stored with the
+//! tests. When a render differs, the actual image and a diff highlighting the
+//! changed pixels are written next to the golden as `<name>.actual.png` and
+//! `<name>.diff.png`.
+//!
+//! Set `RX_UPDATE_GOLDENS=1` to write the current renders as the new goldens
+//! instead, so styling changes show up as reviewable image diffs.
+//!
+//! Text in the system font, which widgets fall back to, is drawn with the
+//! first sans-serif face installed on the host, so goldens containing it
+//! only match on hosts with the same fonts, and rendering it fails where
+//! none is installed. Give widgets a font loaded with [`Font::from_bytes`]
+//! for goldens that match everywhere.
+//!
+//! [`Font::from_bytes`]: crate::Font::from_bytes
+//!
+//! The module is only built with the `testing` feature, so enable it on the
+//! dev-dependency of crates that use it from their tests.
+//!
+//! ```rust,no_run
+//! use rx::{Button, Rect, Widget};
+//! use rx::testing::assert_render_matches;
+//!
+//! let mut button = Button::new(1, "OK".to_string());
+//! button.set_bounds(Rect::new(0.0, 0.0, 80.0, 24.0));
+//! assert_render_matches(&button, "golden/button.png", 2);
+//! ```
+
+use crate::drawing::RenderTarget;
+use crate::{Canvas, Error, Image, Result, Size, Widget};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that makes golden checks overwrite the goldens.
+pub const UPDATE_GOLDENS_VAR: &str = "RX_UPDATE_GOLDENS";
+
+/// Something that can be rendered into an image for comparison.
+pub trait RenderSource {
+    /// Render into a new image.
+    fn render_image(&self) -> Result<Image>;
+}
+
+impl<W: Widget + ?Sized> RenderSource for W {
+    fn render_image(&self) -> Result<Image> {
+        self.render_to_image()
+    }
+}
+
+/// A drawing function rendered onto a transparent canvas of a fixed size.
+pub struct RenderFn<F> {
+    size: Size,
+    draw: F,
+}
+
+impl<F: Fn(&mut Canvas) -> Result<()>> RenderSource for RenderFn<F> {
+    fn render_image(&self) -> Result<Image> {
+        let mut target = RenderTarget::new(self.size);
+        (self.draw)(target.canvas())?;
+        Ok(target.into_image())
+    }
+}
+
+/// Render `draw` onto a transparent canvas of the given size.
+pub fn render_fn<F: Fn(&mut Canvas) -> Result<()>>(size: Size, draw: F) -> RenderFn<F> {
+    RenderFn { size, draw }
+}
+
+/// How two images differ.
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Pixels with a channel outside the tolerance.
+    pub mismatched: usize,
+    /// Largest difference of any channel, from 0 to 255.
+    pub max_difference: u8,
+    /// Mismatched pixels in red over a faded copy of the expected image.
+    pub diff: Image,
+}
+
+impl ImageDiff {
+    /// Check if every pixel was within the tolerance.
+    pub fn is_match(&self) -> bool {
+        self.mismatched == 0
+    }
+}
+
+/// Compare two images channel by channel, allowing each channel of a pixel
+/// to differ by up to `tolerance`.
+///
+/// Returns `None` if the sizes differ.
+pub fn compare_images(expected: &Image, actual: &Image, tolerance: u8) -> Option<ImageDiff> {
+    if (expected.width(), expected.height()) != (actual.width(), actual.height()) {
+        return None;
+    }
+    let mut diff = Image::new(expected.width(), expected.height());
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    let pixels = expected.pixels().chunks_exact(4).zip(actual.pixels().chunks_exact(4));
+    for ((expected, actual), output) in pixels.zip(diff.pixels_mut().chunks_exact_mut(4)) {
+        let difference = expected.iter().zip(actual).map(|(a, b)| a.abs_diff(*b)).max().unwrap_or(0);
+        max_difference = max_difference.max(difference);
+        if difference > tolerance {
+            mismatched += 1;
+            output.copy_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Faded luminance of the expected pixel, for orientation
+            let luminance = (expected[0] as u32 * 3 + expected[1] as u32 * 6 + expected[2] as u32) / 10;
+            let faded = (255 - (255 - luminance) * expected[3] as u32 / 255 / 4) as u8;
+            output.copy_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+    Some(ImageDiff { mismatched, max_difference, diff })
+}
+
+/// Render `source` and compare it against the golden PNG at `path`,
+/// returning an error describing any mismatch.
+///
+/// Relative paths are resolved against the crate being tested.
+pub fn check_render(source: &(impl RenderSource + ?Sized), path: impl AsRef<Path>, tolerance: u8) -> Result<()> {
+    let update = std::env::var_os(UPDATE_GOLDENS_VAR).is_some_and(|value| !value.is_empty() && value != "0");
+    check_golden(&source.render_image()?, &resolve(path.as_ref()), tolerance, update)
+}
+
+/// Render `source` and panic unless it matches the golden PNG at `path`.
+///
+/// See the [module documentation](self) for the files written on mismatch.
+#[track_caller]
+pub fn assert_render_matches(source: &(impl RenderSource + ?Sized), path: impl AsRef<Path>, tolerance: u8) {
+    if let Err(error) = check_render(source, path, tolerance) {
+        panic!("{}", error);
+    }
+}
+
+fn resolve(path: &Path) -> PathBuf {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(root) if path.is_relative() => Path::new(&root).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn check_golden(actual: &Image, path: &Path, tolerance: u8, update: bool) -> Result<()> {
+    let actual_path = path.with_extension("actual.png");
+    let diff_path = path.with_extension("diff.png");
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if update {
+        std::fs::write(path, actual.to_png())?;
+        return remove_stale(&[&actual_path, &diff_path]);
+    }
+
+    let expected = match std::fs::read(path) {
+        Ok(data) => Image::from_png(&data)?,
+        Err(_) => {
+            std::fs::write(&actual_path, actual.to_png())?;
+            return Err(Error::resource(format!(
+                "Golden image {} is missing; the render was written to {} (set {}=1 to accept it)",
+                path.display(),
+                actual_path.display(),
+                UPDATE_GOLDENS_VAR
+            )));
+        }
+    };
+
+    let message = match compare_images(&expected, actual, tolerance) {
+        Some(diff) if diff.is_match() => return remove_stale(&[&actual_path, &diff_path]),
+        Some(diff) => {
+            std::fs::write(&diff_path, diff.diff.to_png())?;
+            format!(
+                "{} pixels differ by more than {} (up to {}); diff written to {}",
+                diff.mismatched,
+                tolerance,
+                diff.max_difference,
+                diff_path.display()
+            )
+        }
+        None => {
+            // A diff from an earlier mismatch of the same size no longer applies
+            remove_stale(&[&diff_path])?;
+            format!(
+                "size is {}x{} but the golden is {}x{}",
+                actual.width(),
+                actual.height(),
+                expected.width(),
+                expected.height()
+            )
+        }
+    };
+    std::fs::write(&actual_path, actual.to_png())?;
+    Err(Error::drawing(format!(
+        "Render does not match golden image {}: {}; the render was written to {} (set {}=1 to accept it)",
+        path.display(),
+        message,
+        actual_path.display(),
+        UPDATE_GOLDENS_VAR
+    )))
+}
+
+/// Delete renders and diffs left behind by an earlier mismatch.
+fn remove_stale(paths: &[&Path]) -> Result<()> {
+    for stale in paths {
+        if stale.exists() {
+            std::fs::remove_file(stale)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Rect};
+
+    #[test]
+    fn test_compare_images() {
+        let expected = Image::filled(4, 4, Color::rgb(0.5, 0.5, 0.5));
+        let mut actual = expected.clone();
+        actual.pixels_mut()[0] += 3;
+        actual.pixels_mut()[5] -= 1;
+
+        let diff = compare_images(&expected, &actual, 3).unwrap();
+        assert!(diff.is_match());
+        assert_eq!(diff.max_difference, 3);
+
+        let diff = compare_images(&expected, &actual, 2).unwrap();
+        assert_eq!(diff.mismatched, 1);
+        assert_eq!(diff.diff.pixel(0, 0), Color::RED);
+        assert_ne!(diff.diff.pixel(1, 0), Color::RED);
+
+        assert!(compare_images(&expected, &Image::new(2, 2), 255).is_none());
+    }
+
+    #[test]
+    fn test_golden_workflow() {
+        let directory = std::env::temp_dir().join(format!("rx-golden-{}", std::process::id()));
+        let path = directory.join("square.png");
+        let square = |color: Color| {
+            render_fn(Size::new(4.0, 4.0), move |canvas: &mut Canvas| canvas.fill_rect(Rect::new(0.0, 0.0, 2.0, 2.0), color))
+        };
+        let red = square(Color::RED).render_image().unwrap();
+
+        // A missing golden fails and leaves the render behind
+        assert!(check_golden(&red, &path, 0, false).is_err());
+        assert!(directory.join("square.actual.png").exists());
+
+        // Regenerating writes the golden and removes leftovers
+        check_golden(&red, &path, 0, true).unwrap();
+        assert!(!directory.join("square.actual.png").exists());
+        check_golden(&red, &path, 0, false).unwrap();
+        assert_eq!(Image::load(&path).unwrap().pixel(1, 1), Color::RED);
+
+        let blue = square(Color::BLUE).render_image().unwrap();
+        let error = check_golden(&blue, &path, 8, false).unwrap_err().to_string();
+        assert!(error.contains("4 pixels differ"), "{}", error);
+        assert_eq!(Image::load(directory.join("square.diff.png")).unwrap().pixel(0, 0), Color::RED);
+        assert_eq!(Image::load(directory.join("square.actual.png")).unwrap().pixel(0, 0), Color::BLUE);
+
+        // A later match removes the leftovers of the failed check
+        check_golden(&red, &path, 0, false).unwrap();
+        assert!(!directory.join("square.actual.png").exists());
+        assert!(!directory.join("square.diff.png").exists());
+
+        // A size mismatch leaves no diff from an earlier mismatch behind
+        assert!(check_golden(&blue, &path, 0, false).is_err());
+        let large = render_fn(Size::new(6.0, 4.0), |_: &mut Canvas| Ok(())).render_image().unwrap();
+        let error = check_golden(&large, &path, 0, false).unwrap_err().to_string();
+        assert!(error.contains("size is 6x4"), "{}", error);
+        assert!(!directory.join("square.diff.png").exists());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
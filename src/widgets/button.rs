@@ -2,15 +2,28 @@
 
 use super::{Widget, BaseWidget, WidgetId, WidgetManager};
 use crate::{Result, Event, Canvas, Rect, Color, Point, Size};
+use crate::drawing::{CornerRadii, Paint, NinePatch, Shadow, Font, SvgImage};
 use crate::platform::MouseButton;
 use std::any::Any;
 use std::time::Duration;
 
+/// Gap between a button's icon and its text.
+const ICON_SPACING: f32 = 4.0;
+
+/// OKLab lightness change from an accent color to its hover shade.
+const HOVER_LIGHTNESS: f32 = 0.06;
+
+/// OKLab lightness change from an accent color to its pressed shade.
+const PRESSED_LIGHTNESS: f32 = -0.08;
+
 /// Button widget for user interaction.
 pub struct Button {
     base: BaseWidget,
     text: String,
+    icon: Option<SvgImage>,
     text_color: Color,
+    font: Font,
+    font_size: f32,
     normal_color: Color,
     hover_color: Color,
     pressed_color: Color,
@@ -30,7 +43,10 @@ impl Button {
         Self {
             base,
             text,
+            icon: None,
             text_color: Color::BLACK,
+            font: Font::default(),
+            font_size: 14.0,
             normal_color: Color::LIGHT_GRAY,
             hover_color: Color::WHITE,
             pressed_color: Color::GRAY,
@@ -46,7 +62,10 @@ impl Button {
         Self {
             base,
             text: builder.text,
+            icon: builder.icon,
             text_color: builder.text_color,
+            font: builder.font,
+            font_size: builder.font_size,
             normal_color: builder.normal_color,
             hover_color: builder.hover_color,
             pressed_color: builder.pressed_color,
@@ -67,11 +86,41 @@ impl Button {
         &self.text
     }
     
+    /// Set the icon drawn before the text.
+    pub fn set_icon(&mut self, icon: Option<SvgImage>) {
+        self.icon = icon;
+    }
+    
+    /// Get the icon.
+    pub fn icon(&self) -> Option<&SvgImage> {
+        self.icon.as_ref()
+    }
+    
     /// Set the text color.
     pub fn set_text_color(&mut self, color: Color) {
         self.text_color = color;
     }
     
+    /// Set the font.
+    pub fn set_font(&mut self, font: Font) {
+        self.font = font;
+    }
+    
+    /// Get the font.
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+    
+    /// Set the font size.
+    pub fn set_font_size(&mut self, size: f32) {
+        self.font_size = size.max(1.0);
+    }
+    
+    /// Get the font size.
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+    
     /// Set the button colors for different states.
     pub fn set_colors(&mut self, normal: Color, hover: Color, pressed: Color, disabled: Color) {
         self.normal_color = normal;
@@ -98,6 +147,20 @@ impl Button {
         self.is_hovered
     }
     
+    /// Get the side of the square the icon is drawn in.
+    fn icon_size(&self) -> f32 {
+        self.font_size
+    }
+    
+    /// Get the width of the icon and text together.
+    fn content_width(&self, text_width: f32) -> f32 {
+        match (&self.icon, self.text.is_empty()) {
+            (Some(_), true) => self.icon_size(),
+            (Some(_), false) => self.icon_size() + ICON_SPACING + text_width,
+            (None, _) => text_width,
+        }
+    }
+    
     /// Get the current background color based on state.
     fn current_background_color(&self) -> Color {
         if !self.base.is_enabled() {
@@ -127,13 +190,12 @@ impl Widget for Button {
     
     fn preferred_size(&self) -> Size {
         // Calculate preferred size based on text
-        // For now, use a simple heuristic
-        let text_width = self.text.len() as f32 * 8.0; // Assume ~8 pixels per character
-        let text_height = 16.0; // Assume 16 pixel font height
+        let text_size = self.font.measure(&self.text, self.font_size).size();
+        let height = if self.icon.is_some() { text_size.height.max(self.icon_size()) } else { text_size.height };
         
         Size::new(
-            text_width + 20.0, // Add padding
-            text_height + 10.0, // Add padding
+            self.content_width(text_size.width) + 20.0, // Add padding
+            height + 10.0, // Add padding
         )
     }
     
@@ -213,20 +275,48 @@ impl Widget for Button {
             return Ok(());
         }
         
-        // Draw background with current state color
-        canvas.fill_rect(self.bounds(), self.current_background_color())?;
+        // Draw background with current state color, or the background paint
+        // with a light or dark tint for hover and press feedback. Nine-patch
+        // skins carry their own shape, so they are drawn untinted.
+        if let Some(patch) = self.base.nine_patch() {
+            canvas.draw_nine_patch(&patch.image, patch.insets, self.bounds(), patch.mode)?;
+        } else {
+            match self.base.background_paint() {
+                Some(paint) if self.base.is_enabled() => {
+                    canvas.fill_rounded_rect(self.bounds(), self.base.corner_radius(), paint)?;
+                    let tint = if self.is_pressed {
+                        Some(Color::BLACK.with_alpha(0.15))
+                    } else if self.is_hovered {
+                        Some(Color::WHITE.with_alpha(0.15))
+                    } else {
+                        None
+                    };
+                    if let Some(tint) = tint {
+                        canvas.fill_rounded_rect(self.bounds(), self.base.corner_radius(), tint)?;
+                    }
+                }
+                _ => canvas.fill_rounded_rect(self.bounds(), self.base.corner_radius(), self.current_background_color())?,
+            }
+        }
         
         // Draw border
         if let Some(border_color) = self.base.border_color() {
-            canvas.stroke_rect(self.bounds(), border_color, self.base.border_width())?;
+            canvas.stroke_rounded_rect(self.bounds(), self.base.corner_radius(), border_color, self.base.border_width())?;
         }
         
-        // Draw text centered
+        // Draw the icon and text centered together
         let bounds = self.bounds();
-        let text_x = bounds.x + bounds.width / 2.0 - (self.text.len() as f32 * 4.0); // Rough centering
-        let text_y = bounds.y + bounds.height / 2.0 + 8.0; // Rough vertical centering
+        let metrics = canvas.measure_text(&self.text, &self.font, self.font_size);
+        let mut text_x = bounds.x + (bounds.width - self.content_width(metrics.width)) / 2.0;
+        if let Some(ref icon) = self.icon {
+            let size = self.icon_size();
+            let icon_rect = Rect::new(text_x, bounds.y + (bounds.height - size) / 2.0, size, size);
+            canvas.draw_svg(icon, icon_rect)?;
+            text_x += size + ICON_SPACING;
+        }
+        let text_y = bounds.y + (bounds.height + metrics.ascent - metrics.descent) / 2.0;
         
-        canvas.draw_text(&self.text, Point::new(text_x, text_y), self.text_color)?;
+        canvas.draw_text(&self.text, Point::new(text_x, text_y), &self.font, self.font_size, self.text_color)?;
         
         Ok(())
     }
@@ -249,8 +339,15 @@ pub struct ButtonBuilder {
     background_color: Option<Color>,
     border_color: Option<Color>,
     border_width: f32,
+    corner_radius: CornerRadii,
+    background_paint: Option<Paint>,
+    nine_patch: Option<NinePatch>,
+    shadow: Option<Shadow>,
     text: String,
+    icon: Option<SvgImage>,
     text_color: Color,
+    font: Font,
+    font_size: f32,
     normal_color: Color,
     hover_color: Color,
     pressed_color: Color,
@@ -269,8 +366,15 @@ impl ButtonBuilder {
             background_color: None,
             border_color: None,
             border_width: 0.0,
+            corner_radius: CornerRadii::default(),
+            background_paint: None,
+            nine_patch: None,
+            shadow: None,
             text: "Button".to_string(),
+            icon: None,
             text_color: Color::BLACK,
+            font: Font::default(),
+            font_size: 14.0,
             normal_color: Color::LIGHT_GRAY,
             hover_color: Color::WHITE,
             pressed_color: Color::GRAY,
@@ -285,12 +389,30 @@ impl ButtonBuilder {
         self
     }
     
+    /// Set the icon drawn before the text.
+    pub fn icon(mut self, icon: SvgImage) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+    
     /// Set the text color.
     pub fn text_color(mut self, color: Color) -> Self {
         self.text_color = color;
         self
     }
     
+    /// Set the font.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+    
+    /// Set the font size.
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size.max(1.0);
+        self
+    }
+    
     /// Set the button colors for different states.
     pub fn colors(mut self, normal: Color, hover: Color, pressed: Color, disabled: Color) -> Self {
         self.normal_color = normal;
@@ -324,6 +446,19 @@ impl ButtonBuilder {
         self
     }
     
+    /// Derive all state colors and the text color from one base color.
+    ///
+    /// Hover and pressed are perceptually lighter and darker shades, disabled
+    /// is faded towards gray and the text is black or white, whichever reads better.
+    pub fn accent_color(mut self, color: Color) -> Self {
+        self.normal_color = color;
+        self.hover_color = color.adjust_lightness(HOVER_LIGHTNESS);
+        self.pressed_color = color.adjust_lightness(PRESSED_LIGHTNESS);
+        self.disabled_color = color.mix(Color::GRAY, 0.6);
+        self.text_color = color.contrasting_text();
+        self
+    }
+    
     /// Set the click callback.
     pub fn on_click<F>(mut self, callback: F) -> Self
     where
@@ -374,6 +509,45 @@ mod tests {
         // Test would continue if we had a proper widget system
     }
 
+    #[test]
+    fn test_button_background_paint() {
+        let mut manager = WidgetManager::new();
+        let gradient = crate::drawing::LinearGradient::new(Point::new(0.0, 0.0), Point::new(0.0, 30.0))
+            .stop(0.0, Color::WHITE)
+            .stop(1.0, Color::LIGHT_GRAY);
+        
+        let button = ButtonBuilder::new()
+            .text("Gradient")
+            .background_paint(gradient.clone())
+            .corner_radius(4.0)
+            .build(&mut manager);
+        
+        assert_eq!(button.base.background_paint(), Some(&Paint::from(gradient)));
+        assert_eq!(button.base.corner_radius(), CornerRadii::uniform(4.0));
+    }
+    
+    #[test]
+    fn test_button_measures_text() {
+        let mut button = Button::new(1, "AAA".to_string());
+        button.set_font(Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap());
+        button.set_font_size(10.0);
+        
+        // Three 6-pixel glyphs and a 10-pixel line, plus padding
+        assert_eq!(button.preferred_size(), Size::new(38.0, 20.0));
+    }
+    
+    #[test]
+    fn test_button_nine_patch() {
+        let mut manager = WidgetManager::new();
+        let patch = NinePatch::new(crate::drawing::Image::new(12, 12), 4.0);
+        
+        let button = ButtonBuilder::new()
+            .nine_patch(patch.clone())
+            .build(&mut manager);
+        
+        assert_eq!(button.base.nine_patch(), Some(&patch));
+    }
+
     #[test]
     fn test_button_preferred_size() {
         let button = Button::new(1, "Test".to_string());
@@ -383,4 +557,42 @@ mod tests {
         assert!(size.width > 20.0); // At least padding
         assert!(size.height > 10.0); // At least padding
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_button_render_to_image() {
+        let mut button = Button::new(1, "".to_string());
+        button.set_bounds(Rect::new(50.0, 40.0, 30.0, 12.0));
+        let image = button.render_to_image().unwrap();
+        assert_eq!((image.width(), image.height()), (30, 12));
+        for (x, y) in [(3, 3), (26, 8)] {
+            let pixel = image.pixel(x, y);
+            assert!((pixel.r - Color::LIGHT_GRAY.r).abs() < 0.01 && pixel.a == 1.0);
+        }
+    }
+
+    #[test]
+    fn test_button_icon() {
+        let icon = SvgImage::parse(r#"<svg viewBox="0 0 8 8"><rect width="8" height="8" fill="red"/></svg>"#).unwrap();
+        let mut button = Button::new(1, "".to_string());
+        let plain_width = button.preferred_size().width;
+        button.set_icon(Some(icon));
+        assert_eq!(button.preferred_size().width, plain_width + 14.0);
+
+        // An icon without text is centered
+        button.set_bounds(Rect::new(0.0, 0.0, 30.0, 30.0));
+        let image = button.render_to_image().unwrap();
+        assert_eq!(image.pixel(15, 15), Color::RED);
+        assert!((image.pixel(5, 15).r - Color::LIGHT_GRAY.r).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_button_accent_color() {
+        let accent = Color::rgb(0.1, 0.3, 0.7);
+        let builder = ButtonBuilder::new().accent_color(accent);
+        assert_eq!(builder.normal_color, accent);
+        assert!(builder.hover_color.to_oklab().l > accent.to_oklab().l);
+        assert!(builder.pressed_color.to_oklab().l < accent.to_oklab().l);
+        assert_eq!(builder.text_color, Color::WHITE);
+        assert!(builder.text_color.meets_contrast(accent, crate::drawing::color::CONTRAST_AA));
+    }
+}
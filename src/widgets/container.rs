@@ -2,6 +2,7 @@
 
 use super::{Widget, BaseWidget, WidgetId, WidgetManager};
 use crate::{Result, Event, Canvas, Rect, Color, Size};
+use crate::drawing::{CornerRadii, Paint, Path, FillRule, NinePatch, Shadow, BlendMode};
 use std::any::Any;
 use std::time::Duration;
 
@@ -103,6 +104,8 @@ pub struct Container {
     padding: Padding,
     spacing: f32,
     clip_children: bool,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl Container {
@@ -119,6 +122,8 @@ impl Container {
             padding: Padding::default(),
             spacing: 0.0,
             clip_children: false,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -133,6 +138,8 @@ impl Container {
             padding: builder.padding,
             spacing: builder.spacing,
             clip_children: builder.clip_children,
+            opacity: builder.opacity,
+            blend_mode: builder.blend_mode,
         }
     }
     
@@ -244,6 +251,26 @@ impl Container {
         self.clip_children
     }
     
+    /// Set the opacity of the container and its children as a group.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+    
+    /// Get the group opacity.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+    
+    /// Set how the container is blended with what is drawn beneath it.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+    
+    /// Get the blend mode.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+    
     /// Get the content area (bounds minus padding).
     pub fn content_area(&self) -> Rect {
         let bounds = self.bounds();
@@ -528,28 +555,28 @@ impl Widget for Container {
         Ok(())
     }
     
+    fn is_animated(&self) -> bool {
+        self.children.iter().any(|child| child.is_animated())
+    }
+    
     fn render(&self, canvas: &mut Canvas) -> Result<()> {
-        if !self.is_visible() {
+        if !self.is_visible() || self.opacity <= 0.0 {
             return Ok(());
         }
         
-        // Render base (background and border)
-        self.base.render_base(canvas)?;
-        
-        // Set up clipping if enabled
-        if self.clip_children {
-            // TODO: Implement clipping in canvas
-            // For now, we'll just render without clipping
-        }
-        
-        // Render all children
-        for child in &self.children {
-            if child.is_visible() {
-                child.render(canvas)?;
-            }
+        // Translucent or blended containers are drawn into a layer covering
+        // their bounds and border, and composited as one group
+        if self.opacity < 1.0 || self.blend_mode != BlendMode::Normal {
+            canvas.save();
+            canvas.clip_rect(self.bounds().expand(self.base.border_width()));
+            canvas.push_layer(self.opacity, self.blend_mode);
+            let result = self.render_content(canvas);
+            let composited = canvas.pop_layer();
+            canvas.restore();
+            return result.and(composited);
         }
         
-        Ok(())
+        self.render_content(canvas)
     }
     
     fn as_any(&self) -> &dyn Any {
@@ -561,6 +588,37 @@ impl Widget for Container {
     }
 }
 
+impl Container {
+    /// Render the background, border and children.
+    fn render_content(&self, canvas: &mut Canvas) -> Result<()> {
+        // Render base (background and border)
+        self.base.render_base(canvas)?;
+        
+        // Set up clipping if enabled
+        if self.clip_children {
+            canvas.save();
+            let radii = self.base.corner_radius();
+            if radii.is_zero() {
+                canvas.clip_rect(self.bounds());
+            } else {
+                canvas.clip_path(&Path::rounded_rect(self.bounds(), radii), FillRule::NonZero);
+            }
+        }
+        
+        // Render all children
+        let result = self.children
+            .iter()
+            .filter(|child| child.is_visible())
+            .try_for_each(|child| child.render(canvas));
+        
+        if self.clip_children {
+            canvas.restore();
+        }
+        
+        result
+    }
+}
+
 /// Builder for creating container widgets.
 pub struct ContainerBuilder {
     id: Option<WidgetId>,
@@ -570,6 +628,10 @@ pub struct ContainerBuilder {
     background_color: Option<Color>,
     border_color: Option<Color>,
     border_width: f32,
+    corner_radius: CornerRadii,
+    background_paint: Option<Paint>,
+    nine_patch: Option<NinePatch>,
+    shadow: Option<Shadow>,
     children: Vec<Box<dyn Widget>>,
     layout_direction: LayoutDirection,
     main_axis_alignment: Alignment,
@@ -577,6 +639,8 @@ pub struct ContainerBuilder {
     padding: Padding,
     spacing: f32,
     clip_children: bool,
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl ContainerBuilder {
@@ -590,6 +654,10 @@ impl ContainerBuilder {
             background_color: None,
             border_color: None,
             border_width: 0.0,
+            corner_radius: CornerRadii::default(),
+            background_paint: None,
+            nine_patch: None,
+            shadow: None,
             children: Vec::new(),
             layout_direction: LayoutDirection::None,
             main_axis_alignment: Alignment::Start,
@@ -597,6 +665,8 @@ impl ContainerBuilder {
             padding: Padding::default(),
             spacing: 0.0,
             clip_children: false,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
     
@@ -653,6 +723,18 @@ impl ContainerBuilder {
         self.clip_children = clip;
         self
     }
+    
+    /// Set the group opacity.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+    
+    /// Set the blend mode.
+    pub fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
 }
 
 impl Default for ContainerBuilder {
@@ -725,4 +807,41 @@ mod tests {
         
         // Test would continue if we had a proper widget system
     }
+
+    #[test]
+    fn test_container_nine_patch() {
+        let mut manager = WidgetManager::new();
+        let patch = NinePatch::new(crate::drawing::Image::new(16, 16), 6.0)
+            .mode(crate::drawing::PatchMode::Tile);
+        
+        let container = ContainerBuilder::new()
+            .background_color(Color::WHITE)
+            .nine_patch(patch.clone())
+            .build(&mut manager);
+        
+        assert_eq!(container.base.nine_patch(), Some(&patch));
+    }
+
+    #[test]
+    fn test_translucent_container_draws_a_layer() {
+        let mut manager = WidgetManager::new();
+        let container = ContainerBuilder::new()
+            .bounds(Rect::new(10.0, 10.0, 50.0, 20.0))
+            .background_color(Color::RED)
+            .opacity(0.5)
+            .build(&mut manager);
+        assert_eq!(container.opacity(), 0.5);
+        
+        let list = crate::drawing::DisplayList::record((100, 100), |canvas| container.render(canvas)).unwrap();
+        assert_eq!(list.items().len(), 1);
+        match &list.items()[0].command {
+            crate::drawing::DisplayCommand::Layer(image, origin, opacity, blend_mode) => {
+                assert_eq!((image.width(), image.height()), (50, 20));
+                assert_eq!(*origin, crate::Point::new(10.0, 10.0));
+                assert_eq!((*opacity, *blend_mode), (0.5, BlendMode::Normal));
+                assert_eq!(image.pixel(5, 5), Color::RED);
+            }
+            other => panic!("expected a layer, got {:?}", other),
+        }
+    }
 }
\ No newline at end of file
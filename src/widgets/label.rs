@@ -2,6 +2,7 @@
 
 use super::{Widget, BaseWidget, WidgetId, WidgetManager};
 use crate::{Result, Event, Canvas, Rect, Color, Point, Size};
+use crate::drawing::{CornerRadii, Paint, NinePatch, Shadow, Font, TextLayout, LayoutOptions, RichText};
 use std::any::Any;
 use std::time::Duration;
 
@@ -37,7 +38,9 @@ impl Default for VerticalAlign {
 pub struct Label {
     base: BaseWidget,
     text: String,
+    rich_text: Option<RichText>,
     text_color: Color,
+    font: Font,
     font_size: f32,
     text_align: TextAlign,
     vertical_align: VerticalAlign,
@@ -53,7 +56,9 @@ impl Label {
         Self {
             base,
             text,
+            rich_text: None,
             text_color: Color::BLACK,
+            font: Font::default(),
             font_size: 14.0,
             text_align: TextAlign::Left,
             vertical_align: VerticalAlign::Top,
@@ -66,8 +71,10 @@ impl Label {
     pub fn from_base(base: BaseWidget, builder: LabelBuilder) -> Self {
         Self {
             base,
-            text: builder.text,
+            text: builder.rich_text.as_ref().map_or(builder.text, |rich| rich.text().to_string()),
+            rich_text: builder.rich_text,
             text_color: builder.text_color,
+            font: builder.font,
             font_size: builder.font_size,
             text_align: builder.text_align,
             vertical_align: builder.vertical_align,
@@ -76,16 +83,34 @@ impl Label {
         }
     }
     
-    /// Set the label text.
+    /// Set the label text, clearing any rich text.
     pub fn set_text<S: Into<String>>(&mut self, text: S) {
         self.text = text.into();
+        self.rich_text = None;
     }
     
-    /// Get the label text.
+    /// Get the label text, without styling.
     pub fn text(&self) -> &str {
         &self.text
     }
     
+    /// Set styled text. Spans without a color or size use the label's.
+    pub fn set_rich_text(&mut self, rich_text: RichText) {
+        self.text = rich_text.text().to_string();
+        self.rich_text = Some(rich_text);
+    }
+    
+    /// Get the styled text, if set.
+    pub fn rich_text(&self) -> Option<&RichText> {
+        self.rich_text.as_ref()
+    }
+    
+    /// Set styled text from markup such as `"<b>Done</b> in <color=#080>2s</color>"`.
+    pub fn set_markup(&mut self, markup: &str) -> Result<()> {
+        self.set_rich_text(RichText::parse(markup)?);
+        Ok(())
+    }
+    
     /// Set the text color.
     pub fn set_text_color(&mut self, color: Color) {
         self.text_color = color;
@@ -96,6 +121,16 @@ impl Label {
         self.text_color
     }
     
+    /// Set the font.
+    pub fn set_font(&mut self, font: Font) {
+        self.font = font;
+    }
+    
+    /// Get the font.
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+    
     /// Set the font size.
     pub fn set_font_size(&mut self, size: f32) {
         self.font_size = size.max(1.0);
@@ -146,93 +181,51 @@ impl Label {
         self.multiline
     }
     
-    /// Calculate the text position based on alignment.
-    fn calculate_text_position(&self, text_size: Size) -> Point {
+    /// Calculate the horizontal start of a line of text based on alignment.
+    fn aligned_x(&self, width: f32) -> f32 {
         let bounds = self.bounds();
         
-        let x = match self.text_align {
+        match self.text_align {
             TextAlign::Left => bounds.x + 2.0, // Small padding
-            TextAlign::Center => bounds.x + (bounds.width - text_size.width) / 2.0,
-            TextAlign::Right => bounds.x + bounds.width - text_size.width - 2.0, // Small padding
-        };
-        
-        let y = match self.vertical_align {
-            VerticalAlign::Top => bounds.y + self.font_size + 2.0, // Small padding + baseline
-            VerticalAlign::Middle => bounds.y + (bounds.height + self.font_size) / 2.0,
-            VerticalAlign::Bottom => bounds.y + bounds.height - 2.0, // Small padding
-        };
-        
-        Point::new(x, y)
+            TextAlign::Center => bounds.x + (bounds.width - width) / 2.0,
+            TextAlign::Right => bounds.x + bounds.width - width - 2.0, // Small padding
+        }
     }
     
-    /// Estimate text size (rough approximation).
-    fn estimate_text_size(&self, text: &str) -> Size {
-        let char_width = self.font_size * 0.6; // Rough approximation
-        let line_height = self.font_size * 1.2; // Line height with spacing
-        
+    /// Lay out text in the label's font, wrapping at `max_width` when word wrap is on.
+    ///
+    /// Single-line labels show newlines as spaces.
+    fn layout_text(&self, text: &RichText, max_width: Option<f32>) -> TextLayout {
+        let mut options = LayoutOptions::new();
+        if let Some(width) = max_width.filter(|&width| self.word_wrap && width > 0.0) {
+            options = options.max_width(width);
+        }
         if self.multiline {
-            let lines: Vec<&str> = text.split('\n').collect();
-            let max_line_width = lines.iter()
-                .map(|line| line.len() as f32 * char_width)
-                .fold(0.0, f32::max);
-            
-            Size::new(max_line_width, lines.len() as f32 * line_height)
+            TextLayout::rich(text, &self.font, self.font_size, options)
         } else {
-            Size::new(text.len() as f32 * char_width, line_height)
+            TextLayout::rich(&text.single_line(), &self.font, self.font_size, options)
         }
     }
     
+    /// The label's text with its styling.
+    fn content(&self) -> RichText {
+        self.rich_text.clone().unwrap_or_else(|| RichText::plain(self.text.as_str()))
+    }
+    
+    /// Measure the size of text in the label's font.
+    #[cfg(test)]
+    fn estimate_text_size(&self, text: &str) -> Size {
+        self.layout_text(&RichText::plain(text), None).size()
+    }
+    
     /// Wrap text to fit within the given width.
+    #[cfg(test)]
     fn wrap_text(&self, text: &str, max_width: f32) -> Vec<String> {
-        if !self.word_wrap {
-            if self.multiline {
-                return text.split('\n').map(|s| s.to_string()).collect();
-            } else {
-                return vec![text.to_string()];
-            }
-        }
-        
-        let char_width = self.font_size * 0.6; // Rough approximation
-        let max_chars_per_line = (max_width / char_width) as usize;
-        
-        if max_chars_per_line == 0 {
-            return vec![text.to_string()];
-        }
-        
-        let mut lines = Vec::new();
-        let text_lines = if self.multiline {
-            text.split('\n').collect()
-        } else {
-            vec![text]
-        };
-        
-        for line in text_lines {
-            if line.len() <= max_chars_per_line {
-                lines.push(line.to_string());
-            } else {
-                // Simple word wrapping
-                let words: Vec<&str> = line.split_whitespace().collect();
-                let mut current_line = String::new();
-                
-                for word in words {
-                    if current_line.is_empty() {
-                        current_line = word.to_string();
-                    } else if current_line.len() + 1 + word.len() <= max_chars_per_line {
-                        current_line.push(' ');
-                        current_line.push_str(word);
-                    } else {
-                        lines.push(current_line);
-                        current_line = word.to_string();
-                    }
-                }
-                
-                if !current_line.is_empty() {
-                    lines.push(current_line);
-                }
-            }
-        }
-        
-        lines
+        let layout = self.layout_text(&RichText::plain(text), Some(max_width));
+        layout.lines()
+            .iter()
+            .map(|line| layout.text()[line.range.clone()].to_string())
+            .collect()
     }
 }
 
@@ -250,7 +243,7 @@ impl Widget for Label {
     }
     
     fn preferred_size(&self) -> Size {
-        let text_size = self.estimate_text_size(&self.text);
+        let text_size = self.layout_text(&self.content(), None).size();
         Size::new(text_size.width + 4.0, text_size.height + 4.0) // Add padding
     }
     
@@ -291,35 +284,20 @@ impl Widget for Label {
         // Render text
         if !self.text.is_empty() {
             let bounds = self.bounds();
-            let wrapped_lines = self.wrap_text(&self.text, bounds.width - 4.0); // Account for padding
-            let line_height = self.font_size * 1.2;
+            let layout = self.layout_text(&self.content(), Some(bounds.width - 4.0)); // Account for padding
+            let top = match self.vertical_align {
+                VerticalAlign::Top => bounds.y + 2.0, // Small padding
+                VerticalAlign::Middle => bounds.y + (bounds.height - layout.height()) / 2.0,
+                VerticalAlign::Bottom => bounds.y + bounds.height - layout.height() - 2.0, // Small padding
+            };
             
-            for (i, line) in wrapped_lines.iter().enumerate() {
-                if line.is_empty() {
+            for line in layout.lines() {
+                if line.runs.is_empty() {
                     continue;
                 }
                 
-                let line_size = self.estimate_text_size(line);
-                let mut line_pos = self.calculate_text_position(line_size);
-                
-                // Adjust for multiple lines
-                if wrapped_lines.len() > 1 {
-                    match self.vertical_align {
-                        VerticalAlign::Top => {
-                            line_pos.y += i as f32 * line_height;
-                        }
-                        VerticalAlign::Middle => {
-                            let total_height = wrapped_lines.len() as f32 * line_height;
-                            line_pos.y = bounds.y + (bounds.height - total_height) / 2.0 + (i as f32 + 1.0) * line_height;
-                        }
-                        VerticalAlign::Bottom => {
-                            let total_height = wrapped_lines.len() as f32 * line_height;
-                            line_pos.y = bounds.y + bounds.height - total_height + (i as f32 + 1.0) * line_height;
-                        }
-                    }
-                }
-                
-                canvas.draw_text(line, line_pos, self.text_color)?;
+                let position = Point::new(self.aligned_x(line.width), top + line.baseline);
+                canvas.draw_text_line(line, position, self.text_color)?;
             }
         }
         
@@ -344,8 +322,14 @@ pub struct LabelBuilder {
     background_color: Option<Color>,
     border_color: Option<Color>,
     border_width: f32,
+    corner_radius: CornerRadii,
+    background_paint: Option<Paint>,
+    nine_patch: Option<NinePatch>,
+    shadow: Option<Shadow>,
     text: String,
+    rich_text: Option<RichText>,
     text_color: Color,
+    font: Font,
     font_size: f32,
     text_align: TextAlign,
     vertical_align: VerticalAlign,
@@ -364,8 +348,14 @@ impl LabelBuilder {
             background_color: None,
             border_color: None,
             border_width: 0.0,
+            corner_radius: CornerRadii::default(),
+            background_paint: None,
+            nine_patch: None,
+            shadow: None,
             text: "Label".to_string(),
+            rich_text: None,
             text_color: Color::BLACK,
+            font: Font::default(),
             font_size: 14.0,
             text_align: TextAlign::Left,
             vertical_align: VerticalAlign::Top,
@@ -380,12 +370,24 @@ impl LabelBuilder {
         self
     }
     
+    /// Set styled text, which replaces the plain text.
+    pub fn rich_text(mut self, rich_text: RichText) -> Self {
+        self.rich_text = Some(rich_text);
+        self
+    }
+    
     /// Set the text color.
     pub fn text_color(mut self, color: Color) -> Self {
         self.text_color = color;
         self
     }
     
+    /// Set the font.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+    
     /// Set the font size.
     pub fn font_size(mut self, size: f32) -> Self {
         self.font_size = size.max(1.0);
@@ -484,6 +486,20 @@ mod tests {
         assert!(lines.len() > 1);
     }
 
+    #[test]
+    fn test_wrapping_uses_font_metrics() {
+        let font = Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap();
+        let mut label = Label::new(1, "AAA AAA".to_string());
+        label.set_font(font);
+        label.set_font_size(10.0);
+        label.set_word_wrap(true);
+        
+        // Each 'A' advances 6 pixels and the space 5, so the text is 41 wide
+        assert_eq!(label.estimate_text_size("AAA AAA").width, 41.0);
+        assert_eq!(label.wrap_text("AAA AAA", 41.0), vec!["AAA AAA"]);
+        assert_eq!(label.wrap_text("AAA AAA", 30.0), vec!["AAA", "AAA"]);
+    }
+
     #[test]
     fn test_multiline_text() {
         let label = Label::new(1, "Line 1\nLine 2\nLine 3".to_string());
@@ -493,4 +509,20 @@ mod tests {
         let single_line_size = label.estimate_text_size("Line 1");
         assert!(size.height > single_line_size.height);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rich_text() {
+        let mut label = Label::new(1, "plain".to_string());
+        label.set_markup("<b>Saved</b> <size=28>big</size>").unwrap();
+        assert_eq!(label.text(), "Saved big");
+        assert!(label.rich_text().unwrap().style_at(0).is_bold());
+        
+        // The larger span makes the line taller than plain text
+        let plain = Label::new(2, "Saved big".to_string());
+        assert!(label.preferred_size().height > plain.preferred_size().height);
+        
+        assert!(label.set_markup("<b>unclosed").is_err());
+        label.set_text("plain again");
+        assert!(label.rich_text().is_none());
+    }
+}
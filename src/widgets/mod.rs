@@ -4,7 +4,9 @@
 //! and a flexible architecture for creating custom widgets.
 
 use crate::{Result, Event, Canvas, Rect, Color, Point, Size};
+use crate::drawing::{CornerRadii, Paint, NinePatch, Shadow, Path, Image, RenderTarget, DisplayList, Damage, RetainedRenderer};
 use std::any::Any;
+use std::collections::HashMap;
 
 pub mod button;
 pub mod text_input;
@@ -54,6 +56,24 @@ pub trait Widget: Send + Sync {
     /// Render the widget to the canvas.
     fn render(&self, canvas: &mut Canvas) -> Result<()>;
     
+    /// Render the widget into an image the size of its bounds, for
+    /// thumbnails, drag images and snapshots.
+    fn render_to_image(&self) -> Result<Image> {
+        let bounds = self.bounds();
+        let mut target = RenderTarget::new(bounds.size());
+        target.canvas().translate(-bounds.x, -bounds.y);
+        self.render(target.canvas())?;
+        Ok(target.into_image())
+    }
+    
+    /// Check if the widget changes its appearance in `update`, like a blinking caret.
+    ///
+    /// Animated widgets are re-recorded every frame; others keep their cached
+    /// display list until they are invalidated.
+    fn is_animated(&self) -> bool {
+        false
+    }
+    
     /// Get the widget as Any for downcasting.
     fn as_any(&self) -> &dyn Any;
     
@@ -77,6 +97,10 @@ pub struct BaseWidget {
     background_color: Option<Color>,
     border_color: Option<Color>,
     border_width: f32,
+    corner_radius: CornerRadii,
+    background_paint: Option<Paint>,
+    nine_patch: Option<NinePatch>,
+    shadow: Option<Shadow>,
 }
 
 impl BaseWidget {
@@ -91,6 +115,10 @@ impl BaseWidget {
             background_color: None,
             border_color: None,
             border_width: 0.0,
+            corner_radius: CornerRadii::default(),
+            background_paint: None,
+            nine_patch: None,
+            shadow: None,
         }
     }
     
@@ -149,6 +177,36 @@ impl BaseWidget {
         self.background_color
     }
     
+    /// Set a gradient or pattern background, drawn instead of the background color.
+    pub fn set_background_paint(&mut self, paint: Option<Paint>) {
+        self.background_paint = paint;
+    }
+    
+    /// Get the background paint.
+    pub fn background_paint(&self) -> Option<&Paint> {
+        self.background_paint.as_ref()
+    }
+    
+    /// Set a nine-patch image background, drawn instead of the background color or paint.
+    pub fn set_nine_patch(&mut self, nine_patch: Option<NinePatch>) {
+        self.nine_patch = nine_patch;
+    }
+    
+    /// Get the nine-patch background.
+    pub fn nine_patch(&self) -> Option<&NinePatch> {
+        self.nine_patch.as_ref()
+    }
+    
+    /// Set a drop shadow drawn under the background, for elevated popups and cards.
+    pub fn set_shadow(&mut self, shadow: Option<Shadow>) {
+        self.shadow = shadow;
+    }
+    
+    /// Get the drop shadow.
+    pub fn shadow(&self) -> Option<Shadow> {
+        self.shadow
+    }
+    
     /// Set the border color and width.
     pub fn set_border(&mut self, color: Option<Color>, width: f32) {
         self.border_color = color;
@@ -165,6 +223,16 @@ impl BaseWidget {
         self.border_width
     }
     
+    /// Set the corner radius used for the background and border.
+    pub fn set_corner_radius(&mut self, radius: impl Into<CornerRadii>) {
+        self.corner_radius = radius.into();
+    }
+    
+    /// Get the corner radius.
+    pub fn corner_radius(&self) -> CornerRadii {
+        self.corner_radius
+    }
+    
     /// Check if a point is inside the widget.
     pub fn contains_point(&self, point: Point) -> bool {
         self.bounds.contains_point(point)
@@ -176,15 +244,25 @@ impl BaseWidget {
             return Ok(());
         }
         
+        // Draw shadow beneath everything else
+        if let Some(shadow) = self.shadow {
+            let shape = Path::rounded_rect(self.bounds, self.corner_radius);
+            canvas.draw_shadow(shape, shadow.offset, shadow.blur_radius, shadow.spread, shadow.color)?;
+        }
+        
         // Draw background
-        if let Some(bg_color) = self.background_color {
-            canvas.fill_rect(self.bounds, bg_color)?;
+        if let Some(ref patch) = self.nine_patch {
+            canvas.draw_nine_patch(&patch.image, patch.insets, self.bounds, patch.mode)?;
+        } else if let Some(ref paint) = self.background_paint {
+            canvas.fill_rounded_rect(self.bounds, self.corner_radius, paint)?;
+        } else if let Some(bg_color) = self.background_color {
+            canvas.fill_rounded_rect(self.bounds, self.corner_radius, bg_color)?;
         }
         
         // Draw border
         if let Some(border_color) = self.border_color {
             if self.border_width > 0.0 {
-                canvas.stroke_rect(self.bounds, border_color, self.border_width)?;
+                canvas.stroke_rounded_rect(self.bounds, self.corner_radius, border_color, self.border_width)?;
             }
         }
         
@@ -198,6 +276,8 @@ pub struct WidgetManager {
     next_id: WidgetId,
     focused_widget: Option<WidgetId>,
     hovered_widget: Option<WidgetId>,
+    display_lists: HashMap<WidgetId, DisplayList>,
+    renderer: RetainedRenderer,
 }
 
 impl WidgetManager {
@@ -208,6 +288,8 @@ impl WidgetManager {
             next_id: 1,
             focused_widget: None,
             hovered_widget: None,
+            display_lists: HashMap::new(),
+            renderer: RetainedRenderer::default(),
         }
     }
     
@@ -226,6 +308,7 @@ impl WidgetManager {
     /// Remove a widget by ID.
     pub fn remove_widget(&mut self, widget_id: WidgetId) -> Option<Box<dyn Widget>> {
         if let Some(index) = self.widgets.iter().position(|w| w.id() == widget_id) {
+            self.display_lists.remove(&widget_id);
             Some(self.widgets.remove(index))
         } else {
             None
@@ -239,6 +322,7 @@ impl WidgetManager {
     
     /// Get a mutable widget by ID.
     pub fn get_widget_mut(&mut self, widget_id: WidgetId) -> Option<&mut Box<dyn Widget>> {
+        self.invalidate(widget_id);
         self.widgets.iter_mut().find(|w| w.id() == widget_id)
     }
 
@@ -247,6 +331,7 @@ impl WidgetManager {
     where
         F: FnOnce(&mut dyn Widget) -> R,
     {
+        self.invalidate(widget_id);
         self.widgets
             .iter_mut()
             .find(|w| w.id() == widget_id)
@@ -264,6 +349,9 @@ impl WidgetManager {
     }
     
     /// Handle an event and dispatch it to the appropriate widgets.
+    ///
+    /// Widgets ask to be repainted by consuming the event, so only the
+    /// consumer and widgets whose focus or hover changed are invalidated.
     pub fn handle_event(&mut self, event: &Event) -> Result<bool> {
         // Handle mouse events for focus and hover
         match event {
@@ -275,38 +363,48 @@ impl WidgetManager {
                 let point = Point::new(*x as f32, *y as f32);
                 self.update_hover(point)?;
             }
+            Event::MouseLeft { window_id: _ } => {
+                self.set_hovered_widget(None);
+            }
             _ => {}
         }
         
         // First, try to handle the event with the focused widget
+        let mut consumer = None;
         if let Some(focused_id) = self.focused_widget {
-            if let Some(result) = self.with_widget_mut(focused_id, |widget| {
-                widget.handle_event(event)
-            }) {
-                if let Ok(consumed) = result {
-                    if consumed {
-                        return Ok(true); // Event consumed
-                    }
+            if let Some(widget) = self.widgets.iter_mut().find(|w| w.id() == focused_id) {
+                if let Ok(true) = widget.handle_event(event) {
+                    consumer = Some(focused_id);
                 }
             }
         }
         
         // Then, try all other widgets in reverse order (top to bottom)
-        for widget in self.widgets.iter_mut().rev() {
-            if Some(widget.id()) != self.focused_widget {
-                if widget.handle_event(event)? {
-                    return Ok(true); // Event consumed
+        if consumer.is_none() {
+            for widget in self.widgets.iter_mut().rev() {
+                if Some(widget.id()) != self.focused_widget && widget.handle_event(event)? {
+                    consumer = Some(widget.id());
+                    break;
                 }
             }
         }
         
-        Ok(false) // Event not consumed
+        match consumer {
+            Some(widget_id) => {
+                self.invalidate(widget_id);
+                Ok(true) // Event consumed
+            }
+            None => Ok(false), // Event not consumed
+        }
     }
     
     /// Update all widgets.
     pub fn update(&mut self, delta_time: std::time::Duration) -> Result<()> {
         for widget in &mut self.widgets {
             widget.update(delta_time)?;
+            if widget.is_animated() {
+                self.display_lists.remove(&widget.id());
+            }
         }
         Ok(())
     }
@@ -321,8 +419,44 @@ impl WidgetManager {
         Ok(())
     }
     
+    /// Repaint only what changed since the last paint and present it.
+    ///
+    /// Each visible widget is recorded into a display list that is reused
+    /// until the widget is invalidated. Returns the repainted regions.
+    pub fn paint(&mut self, canvas: &mut Canvas) -> Result<Damage> {
+        let mut layers = Vec::with_capacity(self.widgets.len());
+        for widget in self.widgets.iter().filter(|widget| widget.is_visible()) {
+            let list = match self.display_lists.get(&widget.id()) {
+                Some(list) => list.clone(),
+                None => {
+                    let list = DisplayList::record_for(canvas, |canvas| widget.render(canvas))?;
+                    self.display_lists.insert(widget.id(), list.clone());
+                    list
+                }
+            };
+            layers.push((widget.id(), list));
+        }
+        self.renderer.paint(canvas, layers)
+    }
+    
+    /// Drop the cached display list of a widget so it is recorded again on the next paint.
+    pub fn invalidate(&mut self, widget_id: WidgetId) {
+        self.display_lists.remove(&widget_id);
+    }
+    
+    /// Drop every cached display list.
+    pub fn invalidate_all(&mut self) {
+        self.display_lists.clear();
+    }
+    
+    /// Set the color painted behind the widgets.
+    pub fn set_background_color(&mut self, color: Color) {
+        self.renderer.set_background(color);
+    }
+    
     /// Clear all widgets.
     pub fn clear(&mut self) {
+        self.display_lists.clear();
         self.widgets.clear();
         self.focused_widget = None;
         self.hovered_widget = None;
@@ -330,6 +464,10 @@ impl WidgetManager {
     
     /// Set the focused widget.
     pub fn set_focused_widget(&mut self, widget_id: Option<WidgetId>) {
+        if widget_id == self.focused_widget {
+            return;
+        }
+        
         // Unfocus the previously focused widget
         if let Some(old_focused) = self.focused_widget {
             self.with_widget_mut(old_focused, |_widget| {
@@ -379,15 +517,26 @@ impl WidgetManager {
         self.set_focused_widget(widget_at_point);
         
         // Update hover
-        self.hovered_widget = widget_at_point;
+        self.set_hovered_widget(widget_at_point);
         
         Ok(())
     }
     
     fn update_hover(&mut self, point: Point) -> Result<()> {
-        self.hovered_widget = self.widget_at_point(point);
+        let widget_at_point = self.widget_at_point(point);
+        self.set_hovered_widget(widget_at_point);
         Ok(())
     }
+    
+    /// Change the hovered widget, invalidating the widgets that gained or lost hover.
+    fn set_hovered_widget(&mut self, widget_id: Option<WidgetId>) {
+        if widget_id != self.hovered_widget {
+            for changed in [self.hovered_widget, widget_id].into_iter().flatten() {
+                self.invalidate(changed);
+            }
+            self.hovered_widget = widget_id;
+        }
+    }
 }
 
 impl Default for WidgetManager {
@@ -457,6 +606,26 @@ macro_rules! widget_builder {
                 self.border_width = width;
                 self
             }
+            
+            pub fn corner_radius(mut self, radius: impl Into<$crate::drawing::CornerRadii>) -> Self {
+                self.corner_radius = radius.into();
+                self
+            }
+            
+            pub fn background_paint(mut self, paint: impl Into<$crate::drawing::Paint>) -> Self {
+                self.background_paint = Some(paint.into());
+                self
+            }
+            
+            pub fn nine_patch(mut self, nine_patch: $crate::drawing::NinePatch) -> Self {
+                self.nine_patch = Some(nine_patch);
+                self
+            }
+            
+            pub fn shadow(mut self, shadow: $crate::drawing::Shadow) -> Self {
+                self.shadow = Some(shadow);
+                self
+            }
         }
         
         impl $builder {
@@ -470,6 +639,10 @@ macro_rules! widget_builder {
                 base.set_enabled(self.enabled);
                 base.set_background_color(self.background_color);
                 base.set_border(self.border_color, self.border_width);
+                base.set_corner_radius(self.corner_radius);
+                base.set_background_paint(self.background_paint.clone());
+                base.set_nine_patch(self.nine_patch.clone());
+                base.set_shadow(self.shadow);
                 
                 $widget::from_base(base, self)
             }
@@ -494,6 +667,15 @@ mod tests {
         
         widget.set_bounds(Rect::new(10.0, 20.0, 100.0, 50.0));
         assert_eq!(widget.bounds(), Rect::new(10.0, 20.0, 100.0, 50.0));
+        
+        assert!(widget.corner_radius().is_zero());
+        widget.set_corner_radius(6.0);
+        assert_eq!(widget.corner_radius(), CornerRadii::uniform(6.0));
+        
+        assert_eq!(widget.shadow(), None);
+        let shadow = Shadow::new(Point::new(0.0, 2.0), 6.0, Color::BLACK.with_alpha(0.3));
+        widget.set_shadow(Some(shadow));
+        assert_eq!(widget.shadow(), Some(shadow));
     }
 
     #[test]
@@ -507,4 +689,37 @@ mod tests {
         let next_id = manager.next_id();
         assert_eq!(next_id, 2);
     }
+
+    #[test]
+    fn test_event_invalidation() {
+        let mut manager = WidgetManager::new();
+        for (id, x) in [(1, 0.0), (2, 50.0)] {
+            let mut button = Button::new(id, "OK".to_string());
+            button.set_bounds(Rect::new(x, 0.0, 40.0, 20.0));
+            manager.add_widget(Box::new(button));
+        }
+        let mut target = RenderTarget::new(Size::new(100.0, 20.0));
+        let cached = |manager: &WidgetManager| [1, 2].map(|id| manager.display_lists.contains_key(&id));
+        let moved = |x: f64| Event::MouseMoved { window_id: 0, x, y: 10.0 };
+        manager.paint(target.canvas()).unwrap();
+        assert_eq!(cached(&manager), [true, true]);
+
+        // Hovering a button repaints only that button
+        assert!(manager.handle_event(&moved(10.0)).unwrap());
+        assert_eq!(cached(&manager), [false, true]);
+        manager.paint(target.canvas()).unwrap();
+
+        // Events that change nothing keep every display list
+        assert!(!manager.handle_event(&moved(12.0)).unwrap());
+        assert_eq!(cached(&manager), [true, true]);
+
+        // Moving across repaints the button that lost hover and the one that gained it
+        manager.handle_event(&moved(60.0)).unwrap();
+        assert_eq!(cached(&manager), [false, false]);
+        manager.paint(target.canvas()).unwrap();
+
+        // Leaving the window repaints the hovered button
+        manager.handle_event(&Event::MouseLeft { window_id: 0 }).unwrap();
+        assert_eq!(cached(&manager), [true, false]);
+    }
 }
\ No newline at end of file
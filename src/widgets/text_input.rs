@@ -2,6 +2,8 @@
 
 use super::{Widget, BaseWidget, WidgetId, WidgetManager};
 use crate::{Result, Event, Canvas, Rect, Color, Point, Size};
+use crate::drawing::{CornerRadii, Paint, NinePatch, Shadow, Font, TextLayout, LayoutOptions};
+use crate::drawing::text::{next_grapheme_boundary, previous_grapheme_boundary};
 use crate::platform::{MouseButton, Key};
 use std::any::Any;
 use std::time::Duration;
@@ -15,6 +17,7 @@ pub struct TextInput {
     placeholder_color: Color,
     selection_color: Color,
     cursor_color: Color,
+    font: Font,
     font_size: f32,
     cursor_position: usize,
     selection_start: Option<usize>,
@@ -46,6 +49,7 @@ impl TextInput {
             placeholder_color: Color::GRAY,
             selection_color: Color::rgba(0.0, 0.5, 1.0, 0.3),
             cursor_color: Color::BLACK,
+            font: Font::default(),
             font_size: 14.0,
             cursor_position: 0,
             selection_start: None,
@@ -73,6 +77,7 @@ impl TextInput {
             placeholder_color: builder.placeholder_color,
             selection_color: builder.selection_color,
             cursor_color: builder.cursor_color,
+            font: builder.font,
             font_size: builder.font_size,
             cursor_position: 0,
             selection_start: None,
@@ -163,6 +168,26 @@ impl TextInput {
         self.password_char = char;
     }
     
+    /// Set the font.
+    pub fn set_font(&mut self, font: Font) {
+        self.font = font;
+    }
+    
+    /// Get the font.
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+    
+    /// Set the font size.
+    pub fn set_font_size(&mut self, size: f32) {
+        self.font_size = size.max(1.0);
+    }
+    
+    /// Get the font size.
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+    
     /// Set read-only mode.
     pub fn set_read_only(&mut self, read_only: bool) {
         self.read_only = read_only;
@@ -256,8 +281,9 @@ impl TextInput {
         if self.has_selection() {
             self.delete_selection();
         } else if self.cursor_position > 0 {
-            self.cursor_position -= 1;
-            self.text.remove(self.cursor_position);
+            let start = previous_grapheme_boundary(&self.text, self.cursor_position);
+            self.text.replace_range(start..self.cursor_position, "");
+            self.cursor_position = start;
             
             // Trigger callback
             if let Some(ref callback) = self.on_text_changed {
@@ -275,7 +301,8 @@ impl TextInput {
         if self.has_selection() {
             self.delete_selection();
         } else if self.cursor_position < self.text.len() {
-            self.text.remove(self.cursor_position);
+            let end = next_grapheme_boundary(&self.text, self.cursor_position);
+            self.text.replace_range(self.cursor_position..end, "");
             
             // Trigger callback
             if let Some(ref callback) = self.on_text_changed {
@@ -314,9 +341,7 @@ impl TextInput {
             self.clear_selection();
         }
         
-        if self.cursor_position > 0 {
-            self.cursor_position -= 1;
-        }
+        self.cursor_position = previous_grapheme_boundary(&self.text, self.cursor_position);
         
         if extend_selection {
             self.selection_end = Some(self.cursor_position);
@@ -333,9 +358,7 @@ impl TextInput {
             self.clear_selection();
         }
         
-        if self.cursor_position < self.text.len() {
-            self.cursor_position += 1;
-        }
+        self.cursor_position = next_grapheme_boundary(&self.text, self.cursor_position);
         
         if extend_selection {
             self.selection_end = Some(self.cursor_position);
@@ -373,17 +396,45 @@ impl TextInput {
     /// Get the display text (with password masking if enabled).
     fn display_text(&self) -> String {
         if self.is_password && !self.text.is_empty() {
-            self.password_char.to_string().repeat(self.text.len())
+            self.password_char.to_string().repeat(self.text.chars().count())
         } else {
             self.text.clone()
         }
     }
     
+    /// Lay out the displayed text.
+    fn text_layout(&self) -> TextLayout {
+        TextLayout::new(&self.display_text(), &self.font, self.font_size, LayoutOptions::new())
+    }
+    
+    /// Convert a byte index in the text to one in the displayed text.
+    fn display_index(&self, index: usize) -> usize {
+        if self.is_password {
+            let chars = self.text.char_indices().take_while(|&(i, _)| i < index).count();
+            chars * self.password_char.len_utf8()
+        } else {
+            index
+        }
+    }
+    
+    /// Convert a byte index in the displayed text back to one in the text.
+    fn text_index(&self, display_index: usize) -> usize {
+        if self.is_password {
+            let chars = display_index / self.password_char.len_utf8();
+            self.text.char_indices().nth(chars).map_or(self.text.len(), |(i, _)| i)
+        } else {
+            display_index
+        }
+    }
+    
+    /// Calculate the x position of a caret before the byte `index`.
+    fn caret_x(&self, index: usize) -> f32 {
+        self.bounds().x + 5.0 + self.text_layout().caret_position(self.display_index(index)).x // 5px padding
+    }
+    
     /// Calculate cursor x position for rendering.
     fn cursor_x_position(&self) -> f32 {
-        let bounds = self.bounds();
-        let char_width = self.font_size * 0.6; // Rough approximation
-        bounds.x + 5.0 + (self.cursor_position as f32 * char_width) // 5px padding
+        self.caret_x(self.cursor_position)
     }
 }
 
@@ -401,9 +452,8 @@ impl Widget for TextInput {
     }
     
     fn preferred_size(&self) -> Size {
-        let char_width = self.font_size * 0.6;
-        let min_width = char_width * 10.0; // Minimum 10 characters wide
-        let height = self.font_size * 1.5; // Font size + padding
+        let min_width = self.font.measure("0000000000", self.font_size).width; // Minimum 10 characters wide
+        let height = self.font.line_height(self.font_size);
         
         Size::new(min_width + 10.0, height + 10.0) // Add padding
     }
@@ -437,10 +487,9 @@ impl Widget for TextInput {
                         self.focus();
                         
                         // Calculate cursor position from mouse click
-                        let char_width = self.font_size * 0.6;
                         let relative_x = point.x - self.bounds().x - 5.0; // Account for padding
-                        let char_pos = (relative_x / char_width).round() as usize;
-                        self.cursor_position = char_pos.min(self.text.len());
+                        let index = self.text_layout().index_at(Point::new(relative_x, 0.0));
+                        self.cursor_position = self.text_index(index);
                         self.clear_selection();
                         
                         return Ok(true);
@@ -522,6 +571,11 @@ impl Widget for TextInput {
         Ok(())
     }
     
+    fn is_animated(&self) -> bool {
+        // The caret blinks while focused
+        self.is_focused
+    }
+    
     fn render(&self, canvas: &mut Canvas) -> Result<()> {
         if !self.is_visible() {
             return Ok(());
@@ -535,7 +589,7 @@ impl Widget for TextInput {
         // Render focus indicator
         if self.is_focused {
             let focus_color = Color::rgba(0.0, 0.5, 1.0, 0.5);
-            canvas.stroke_rect(bounds, focus_color, 2.0)?;
+            canvas.stroke_rounded_rect(bounds, self.base.corner_radius(), focus_color, 2.0)?;
         }
         
         // Render text or placeholder
@@ -553,19 +607,22 @@ impl Widget for TextInput {
         };
         
         if !text_to_show.is_empty() {
-            let text_pos = Point::new(bounds.x + 5.0, bounds.y + bounds.height / 2.0 + self.font_size / 2.0);
-            canvas.draw_text(text_to_show, text_pos, text_color)?;
+            let ascent = self.font.ascent(self.font_size);
+            let descent = self.font.descent(self.font_size);
+            let top = bounds.y + (bounds.height - ascent - descent) / 2.0;
+            let layout = TextLayout::new(text_to_show, &self.font, self.font_size, LayoutOptions::new());
+            canvas.draw_layout(&layout, Point::new(bounds.x + 5.0, top), text_color)?;
         }
         
-        // Render selection
+        // Render selection, which may be split into several pieces by mixed-direction text
         if self.has_selection() {
             if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
                 let (start, end) = if start <= end { (start, end) } else { (end, start) };
-                let char_width = self.font_size * 0.6;
-                let selection_x = bounds.x + 5.0 + (start as f32 * char_width);
-                let selection_width = (end - start) as f32 * char_width;
-                let selection_rect = Rect::new(selection_x, bounds.y + 2.0, selection_width, bounds.height - 4.0);
-                canvas.fill_rect(selection_rect, self.selection_color)?;
+                let range = self.display_index(start)..self.display_index(end);
+                for rect in self.text_layout().selection_rects(range) {
+                    let selection_rect = Rect::new(bounds.x + 5.0 + rect.x, bounds.y + 2.0, rect.width, bounds.height - 4.0);
+                    canvas.fill_rect(selection_rect, self.selection_color)?;
+                }
             }
         }
         
@@ -597,12 +654,17 @@ pub struct TextInputBuilder {
     background_color: Option<Color>,
     border_color: Option<Color>,
     border_width: f32,
+    corner_radius: CornerRadii,
+    background_paint: Option<Paint>,
+    nine_patch: Option<NinePatch>,
+    shadow: Option<Shadow>,
     text: String,
     placeholder: String,
     text_color: Color,
     placeholder_color: Color,
     selection_color: Color,
     cursor_color: Color,
+    font: Font,
     font_size: f32,
     max_length: Option<usize>,
     is_password: bool,
@@ -623,12 +685,17 @@ impl TextInputBuilder {
             background_color: Some(Color::WHITE),
             border_color: Some(Color::GRAY),
             border_width: 1.0,
+            corner_radius: CornerRadii::default(),
+            background_paint: None,
+            nine_patch: None,
+            shadow: None,
             text: String::new(),
             placeholder: String::new(),
             text_color: Color::BLACK,
             placeholder_color: Color::GRAY,
             selection_color: Color::rgba(0.0, 0.5, 1.0, 0.3),
             cursor_color: Color::BLACK,
+            font: Font::default(),
             font_size: 14.0,
             max_length: None,
             is_password: false,
@@ -663,6 +730,12 @@ impl TextInputBuilder {
         self
     }
     
+    /// Set the font.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+    
     /// Set the font size.
     pub fn font_size(mut self, size: f32) -> Self {
         self.font_size = size.max(1.0);
@@ -800,4 +873,73 @@ mod tests {
         
         assert_eq!(input.text(), "Hello");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_caret_uses_font_metrics() {
+        let mut input = TextInput::new(1);
+        input.set_font(Font::from_bytes(crate::drawing::font::ttf::testing::test_font()).unwrap());
+        input.set_font_size(10.0);
+        input.set_bounds(Rect::new(0.0, 0.0, 100.0, 30.0));
+        input.set_text("AB");
+        
+        // 'A' advances 6 and kerns 0.8 closer to 'B', after 5px of padding
+        assert!((input.caret_x(1) - 10.2).abs() < 1e-4);
+        assert_eq!(input.text_index(input.text_layout().index_at(Point::new(7.0, 0.0))), 1);
+        assert_eq!(input.text_index(input.text_layout().index_at(Point::new(50.0, 0.0))), 2);
+    }
+
+    #[test]
+    fn test_password_caret_mapping() {
+        let mut input = TextInput::new(1);
+        input.set_password(true);
+        input.set_text("éA");
+        
+        // Each character shows as one three-byte bullet
+        assert_eq!(input.display_text(), "••");
+        assert_eq!(input.display_index(2), 3);
+        assert_eq!(input.text_index(3), 2);
+        assert_eq!(input.text_index(6), 3);
+    }
+
+    #[test]
+    fn test_editing_moves_by_grapheme() {
+        let mut input = TextInput::new(1);
+        input.set_text("ae\u{301}b");
+        input.cursor_position = 4;
+        
+        input.move_cursor_left(false);
+        assert_eq!(input.cursor_position, 1);
+        input.move_cursor_right(false);
+        assert_eq!(input.cursor_position, 4);
+        
+        input.delete_char();
+        assert_eq!(input.text(), "ab");
+        assert_eq!(input.cursor_position, 1);
+        
+        input.set_text("\u{1F1EB}\u{1F1F7}!");
+        input.cursor_position = 0;
+        input.delete_char_forward();
+        assert_eq!(input.text(), "!");
+    }
+
+    #[test]
+    fn test_blinking_caret_repaints_only_caret() {
+        let mut manager = WidgetManager::new();
+        let mut input = TextInput::new(manager.next_id());
+        input.set_bounds(Rect::new(20.0, 20.0, 200.0, 30.0));
+        input.focus();
+        let label = crate::widgets::Label::new(manager.next_id(), "Title".to_string());
+        manager.add_widget(Box::new(label));
+        manager.add_widget(Box::new(input));
+        
+        let mut canvas = crate::drawing::display_list::test_canvas((1920, 1080));
+        assert_eq!(manager.paint(&mut canvas).unwrap().area(), 1920.0 * 1080.0);
+        assert!(manager.paint(&mut canvas).unwrap().is_empty());
+        
+        manager.update(Duration::from_millis(600)).unwrap();
+        let damage = manager.paint(&mut canvas).unwrap();
+        assert_eq!(damage.rects().len(), 1);
+        let caret = damage.rects()[0];
+        assert!(caret.width <= 4.0 && caret.height <= 30.0, "{:?}", caret);
+    }
+}
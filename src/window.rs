@@ -3,9 +3,12 @@
 //! This module provides window creation, management, and rendering capabilities
 //! in a cross-platform manner.
 
-use crate::{Error, Result, Event, Canvas};
-use crate::platform::{PlatformBackend, WindowHandle, WindowParams};
-use std::sync::{Arc, Mutex, Weak};
+use crate::{Error, Result, Event, Canvas, Image};
+use crate::drawing::batch::{DrawCounters, DrawStats};
+use crate::drawing::ContextOwner;
+use crate::platform::{DrawingContext, PlatformBackend, WindowHandle, WindowParams};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
 use std::time::Duration;
 
 /// Unique identifier for windows.
@@ -21,6 +24,7 @@ pub struct Window {
     handle: WindowHandle,
     properties: WindowProperties,
     backend: Weak<Mutex<Box<dyn PlatformBackend>>>,
+    surface: Arc<WindowSurface>,
 }
 
 /// Window properties that can be queried and modified.
@@ -161,6 +165,7 @@ impl WindowBuilder {
             handle,
             properties,
             backend: Arc::downgrade(&backend),
+            surface: Arc::new(WindowSurface::new()),
         };
         
         // Register the window with the application
@@ -228,6 +233,7 @@ impl Window {
         if let Some(backend) = self.backend.upgrade() {
             let mut backend_lock = backend.lock().map_err(|_| Error::window("Failed to lock backend"))?;
             backend_lock.set_window_size(&self.handle, width, height)?;
+            self.surface.invalidate();
             self.properties.width = width;
             self.properties.height = height;
         }
@@ -301,14 +307,43 @@ impl Window {
     }
     
     /// Get a drawing canvas for this window.
+    ///
+    /// The canvas borrows the window's drawing context, which is kept
+    /// between frames and only recreated after the window is resized. A
+    /// canvas made while another is still alive gets a context of its own.
     pub fn canvas(&self) -> Result<Canvas> {
-        if let Some(backend) = self.backend.upgrade() {
-            let backend_lock = backend.lock().map_err(|_| Error::window("Failed to lock backend"))?;
-            let context = backend_lock.get_drawing_context(&self.handle)?;
-            Ok(Canvas::new(context))
-        } else {
-            Err(Error::window("Backend no longer available"))
-        }
+        let context = self.surface.lend(|| self.create_context())?;
+        let owner: Arc<dyn ContextOwner> = Arc::clone(&self.surface) as Arc<dyn ContextOwner>;
+        Ok(Canvas::lent(context, owner, Arc::clone(&self.surface.draw_counters)))
+    }
+    
+    /// Get the drawing calls made to this window's canvases since the last
+    /// call, and reset them.
+    pub fn take_draw_stats(&self) -> DrawStats {
+        self.surface.draw_counters.take()
+    }
+    
+    /// Capture the current contents of the window.
+    pub fn capture(&self) -> Result<Image> {
+        self.surface.with_context(|| self.create_context(), |context| context.read_pixels())
+    }
+    
+    /// Capture the current contents of the window and write them to a PNG file.
+    pub fn screenshot(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        std::fs::write(path, self.capture()?.to_png())?;
+        Ok(())
+    }
+    
+    /// Ask the backend for a new drawing context for the window.
+    fn create_context(&self) -> Result<Box<dyn DrawingContext>> {
+        let backend = self.backend.upgrade().ok_or_else(|| Error::window("Backend no longer available"))?;
+        let backend_lock = backend.lock().map_err(|_| Error::window("Failed to lock backend"))?;
+        backend_lock.get_drawing_context(&self.handle)
+    }
+    
+    /// Drop the window's drawing context and the platform resources it holds.
+    pub(crate) fn release_surface(&self) {
+        self.surface.invalidate();
     }
     
     /// Handle an event for this window.
@@ -316,6 +351,8 @@ impl Window {
         // Update window properties based on events
         match event {
             Event::WindowResized { window_id, width: _width, height: _height } if *window_id == self.id() => {
+                // The drawing context still has the old size
+                self.surface.invalidate();
                 // Note: We can't modify self here because this method takes &self
                 // In a real implementation, we'd need to use interior mutability
                 // or handle this differently
@@ -343,16 +380,15 @@ impl Window {
     /// Render the window (called every frame).
     pub(crate) fn render(&self) -> Result<()> {
         // Basic rendering - just present the current frame
-        if let Some(backend) = self.backend.upgrade() {
-            let backend_lock = backend.lock().map_err(|_| Error::window("Failed to lock backend"))?;
-            let mut context = backend_lock.get_drawing_context(&self.handle)?;
-            context.present()?;
+        if self.backend.strong_count() > 0 {
+            self.surface.with_context(|| self.create_context(), |context| context.present())?;
         }
         Ok(())
     }
     
     /// Close the window.
     pub fn close(&self) -> Result<()> {
+        self.release_surface();
         if let Some(backend) = self.backend.upgrade() {
             let mut backend_lock = backend.lock().map_err(|_| Error::window("Failed to lock backend"))?;
             backend_lock.destroy_window(&self.handle)?;
@@ -361,6 +397,112 @@ impl Window {
     }
 }
 
+/// Long-lived drawing context of a window.
+///
+/// The context and the platform resources it holds are created on first
+/// use and kept until the window is resized or closed. Canvases borrow it
+/// for their lifetime, so drawing a frame neither creates a context nor
+/// locks anything per call.
+struct WindowSurface {
+    slot: Mutex<SurfaceSlot>,
+    draw_counters: Arc<DrawCounters>,
+}
+
+/// The context of a window surface and whether it is lent out.
+#[derive(Default)]
+struct SurfaceSlot {
+    /// The context, while no canvas is drawing with it.
+    context: Option<Box<dyn DrawingContext>>,
+    /// Address of the context lent to a canvas, if any.
+    lent: Option<usize>,
+}
+
+impl WindowSurface {
+    fn new() -> Self {
+        Self {
+            slot: Mutex::default(),
+            draw_counters: Arc::default(),
+        }
+    }
+    
+    fn slot(&self) -> MutexGuard<'_, SurfaceSlot> {
+        self.slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    
+    /// Wrap a context from the backend for drawing to the surface.
+    fn wrap(&self, context: Box<dyn DrawingContext>) -> Box<dyn DrawingContext> {
+        Canvas::batched(context, &self.draw_counters)
+    }
+    
+    /// Take the context for a canvas, creating it with `create` if there is none.
+    ///
+    /// While the context is lent, further canvases get one of their own
+    /// that is dropped with them.
+    fn lend(&self, create: impl FnOnce() -> Result<Box<dyn DrawingContext>>) -> Result<Box<dyn DrawingContext>> {
+        let mut slot = self.slot();
+        let context = match slot.context.take() {
+            Some(context) => context,
+            None => self.wrap(create()?),
+        };
+        if slot.lent.is_none() {
+            slot.lent = Some(address(context.as_ref()));
+        }
+        Ok(context)
+    }
+    
+    /// Run `action` on the context, creating it with `create` if there is none.
+    fn with_context<T>(
+        &self,
+        create: impl FnOnce() -> Result<Box<dyn DrawingContext>>,
+        action: impl FnOnce(&mut dyn DrawingContext) -> Result<T>,
+    ) -> Result<T> {
+        let mut slot = self.slot();
+        if let Some(context) = slot.context.as_mut() {
+            return action(context.as_mut());
+        }
+        let mut context = self.wrap(create()?);
+        if slot.lent.is_some() {
+            // A canvas is drawing with the surface's context
+            return action(context.as_mut());
+        }
+        action(slot.context.insert(context).as_mut())
+    }
+    
+    /// Drop the context so the next frame creates a fresh one.
+    ///
+    /// A context lent out at the time is dropped when its canvas is.
+    fn invalidate(&self) {
+        let mut slot = self.slot();
+        slot.context = None;
+        slot.lent = None;
+    }
+}
+
+impl ContextOwner for WindowSurface {
+    fn restore(&self, mut context: Box<dyn DrawingContext>) {
+        // Pending draws are submitted and the canvas's clip removed
+        let _ = context.set_clip(None);
+        let mut slot = self.slot();
+        if slot.lent.is_some() && slot.lent == Some(address(context.as_ref())) {
+            slot.lent = None;
+            slot.context = Some(context);
+        }
+    }
+}
+
+impl fmt::Debug for WindowSurface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let slot = self.slot();
+        let created = slot.context.is_some() || slot.lent.is_some();
+        f.debug_struct("WindowSurface").field("created", &created).finish()
+    }
+}
+
+/// Identity of a boxed context, to recognize it when it is handed back.
+fn address(context: &dyn DrawingContext) -> usize {
+    context as *const dyn DrawingContext as *const () as usize
+}
+
 /// Window manager for handling multiple windows.
 pub struct WindowManager {
     windows: Vec<Window>,
@@ -512,4 +654,56 @@ mod tests {
         manager.clear();
         assert_eq!(manager.window_count(), 0);
     }
+
+    #[test]
+    fn test_window_surface_reuse() {
+        use crate::drawing::surface::ImageContext;
+        use crate::drawing::Rect;
+        use crate::Color;
+
+        let image = Arc::new(Mutex::new(Image::new(4, 4)));
+        let created = std::cell::Cell::new(0);
+        let surface = Arc::new(WindowSurface::new());
+        let create = || -> Result<Box<dyn DrawingContext>> {
+            created.set(created.get() + 1);
+            Ok(Box::new(ImageContext::new(Arc::clone(&image), (0, 0), (4, 4))))
+        };
+        let canvas = || {
+            let context = surface.lend(create).unwrap();
+            Canvas::lent(context, Arc::clone(&surface) as Arc<dyn ContextOwner>, Arc::default())
+        };
+
+        // Canvases borrow one context across frames
+        for _ in 0..3 {
+            canvas().fill_rect(Rect::new(0.0, 0.0, 2.0, 2.0), Color::RED).unwrap();
+        }
+        assert_eq!(created.get(), 1);
+        assert_eq!(image.lock().unwrap().pixel(1, 1), Color::RED);
+
+        // A clip left by one canvas does not carry over to the next
+        let mut clipped = canvas();
+        clipped.clip_rect(Rect::new(0.0, 0.0, 1.0, 1.0));
+        clipped.fill_rect(Rect::new(0.0, 0.0, 1.0, 1.0), Color::GREEN).unwrap();
+        drop(clipped);
+        canvas().fill_rect(Rect::new(3.0, 3.0, 1.0, 1.0), Color::BLUE).unwrap();
+        assert_eq!(image.lock().unwrap().pixel(3, 3), Color::BLUE);
+        assert_eq!(created.get(), 1);
+
+        // A second canvas drawing at the same time gets its own context
+        let first = canvas();
+        drop(canvas());
+        assert_eq!(created.get(), 2);
+        drop(first);
+        drop(canvas());
+        assert_eq!(created.get(), 2);
+
+        // Resizing recreates it, and a canvas alive at the time does not bring the old one back
+        let stale = canvas();
+        surface.invalidate();
+        drop(stale);
+        drop(canvas());
+        assert_eq!(created.get(), 3);
+        surface.with_context(create, |context| context.present()).unwrap();
+        assert_eq!(created.get(), 3);
+    }
 }
\ No newline at end of file